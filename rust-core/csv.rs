@@ -0,0 +1,339 @@
+use crate::*;
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::mem::take;
+
+/// Header names recognized as the longitude column, compared case insensitively
+const LON_COLUMNS: [&str; 5] = ["lon", "lng", "long", "longitude", "x"];
+/// Header names recognized as the latitude column, compared case insensitively
+const LAT_COLUMNS: [&str; 3] = ["lat", "latitude", "y"];
+
+/// How to read and write delimited point data
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CsvOptions {
+    /// The field delimiter, `,` for CSV and `\t` for TSV
+    pub delimiter: char,
+    /// The longitude column. If None, the first of `lon`, `lng`, `long`, `longitude` or `x`
+    /// is used when reading, and `lon` when writing
+    pub lon_column: Option<String>,
+    /// The latitude column. If None, the first of `lat`, `latitude` or `y` is used when
+    /// reading, and `lat` when writing
+    pub lat_column: Option<String>,
+}
+impl Default for CsvOptions {
+    fn default() -> Self {
+        CsvOptions { delimiter: ',', lon_column: None, lat_column: None }
+    }
+}
+impl CsvOptions {
+    /// Options for tab separated values
+    pub fn tsv() -> Self {
+        CsvOptions { delimiter: '\t', ..Default::default() }
+    }
+}
+
+/// Split the input into records, each with the line it starts on. Quoted fields may hold
+/// delimiters, newlines and doubled quotes. Blank lines are skipped
+fn records(csv: &str, delimiter: char) -> Result<Vec<(usize, Vec<String>)>, S2JsonError> {
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = String::new();
+    let (mut line, mut start) = (1, 1);
+    let mut chars = csv.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if field.is_empty() => {
+                let quote_line = line;
+                loop {
+                    match chars.next() {
+                        None => {
+                            return Err(S2JsonError::invalid_csv(
+                                quote_line,
+                                "unterminated quoted field",
+                            ));
+                        }
+                        Some('"') if chars.peek() == Some(&'"') => {
+                            chars.next();
+                            field.push('"');
+                        }
+                        Some('"') => break,
+                        Some(c) => {
+                            if c == '\n' {
+                                line += 1;
+                            }
+                            field.push(c);
+                        }
+                    }
+                }
+            }
+            '\r' if chars.peek() == Some(&'\n') => {}
+            '\n' => {
+                record.push(take(&mut field));
+                if record.len() > 1 || !record[0].is_empty() {
+                    records.push((start, take(&mut record)));
+                }
+                record.clear();
+                line += 1;
+                start = line;
+            }
+            c if c == delimiter => record.push(take(&mut field)),
+            c => field.push(c),
+        }
+    }
+    if !field.is_empty() || !record.is_empty() {
+        record.push(field);
+        records.push((start, record));
+    }
+    Ok(records)
+}
+
+/// The narrowest shape a cell's text fits. Empty cells are null
+fn cell_shape(cell: &str) -> PrimitiveShape {
+    let cell = cell.trim();
+    if cell.is_empty() {
+        PrimitiveShape::Null
+    } else if cell == "true" || cell == "false" {
+        PrimitiveShape::Bool
+    } else if cell.parse::<u64>().is_ok() {
+        PrimitiveShape::U64
+    } else if cell.parse::<i64>().is_ok() {
+        PrimitiveShape::I64
+    } else if cell.parse::<f64>().is_ok_and(f64::is_finite) {
+        PrimitiveShape::F64
+    } else {
+        PrimitiveShape::String
+    }
+}
+
+/// Read a cell as its column's shape
+fn cell_value(cell: &str, shape: &PrimitiveShape) -> PrimitiveValue {
+    let trimmed = cell.trim();
+    if trimmed.is_empty() {
+        return PrimitiveValue::Null;
+    }
+    let value = match shape {
+        PrimitiveShape::U64 => trimmed.parse::<u64>().ok().map(Into::into),
+        PrimitiveShape::I64 => trimmed.parse::<i64>().ok().map(Into::into),
+        PrimitiveShape::F32 => trimmed.parse::<f32>().ok().map(Into::into),
+        PrimitiveShape::F64 => trimmed.parse::<f64>().ok().map(Into::into),
+        PrimitiveShape::Bool => trimmed.parse::<bool>().ok().map(Into::into),
+        PrimitiveShape::String | PrimitiveShape::Null => None,
+    };
+    value.unwrap_or_else(|| cell.into())
+}
+
+/// Find a column by its configured name, or by the first matching known name
+fn find_column(
+    header: &[String],
+    configured: &Option<String>,
+    known: &[&str],
+    what: &str,
+) -> Result<usize, S2JsonError> {
+    let found = match configured {
+        Some(name) => header.iter().position(|column| column.trim() == name),
+        None => known.iter().find_map(|known| {
+            header.iter().position(|column| column.trim().eq_ignore_ascii_case(known))
+        }),
+    };
+    found.ok_or_else(|| S2JsonError::invalid_csv(1, format!("no {what} column")))
+}
+
+/// The points of a file: each row's longitude, latitude and properties, and the shape of all the
+/// properties
+type CsvRows = (Shape, Vec<(f64, f64, Properties)>);
+
+fn read_rows(csv: &str, options: &CsvOptions) -> Result<CsvRows, S2JsonError> {
+    let mut records = records(csv, options.delimiter)?.into_iter();
+    let Some((_, header)) = records.next() else { return Ok((Shape::new(), Vec::new())) };
+    let lon = find_column(&header, &options.lon_column, &LON_COLUMNS, "longitude")?;
+    let lat = find_column(&header, &options.lat_column, &LAT_COLUMNS, "latitude")?;
+    let rows: Vec<(usize, Vec<String>)> = records.collect();
+    if let Some((line, row)) = rows.iter().find(|(_, row)| row.len() != header.len()) {
+        return Err(S2JsonError::invalid_csv(
+            *line,
+            format!("expected {} fields, found {}", header.len(), row.len()),
+        ));
+    }
+
+    // infer each column's shape first, so every row reads its cells the same way
+    let mut shapes = Vec::with_capacity(header.len());
+    for column in 0..header.len() {
+        let mut shape = PrimitiveShape::Null;
+        let mut nullable = false;
+        for (_, row) in &rows {
+            let cell_shape = cell_shape(&row[column]);
+            nullable |= cell_shape == PrimitiveShape::Null;
+            shape.try_merge(&cell_shape, ShapeMergePolicy::Widen)?;
+        }
+        shapes.push((shape, nullable));
+    }
+
+    let mut points = Vec::with_capacity(rows.len());
+    for (line, row) in &rows {
+        let coordinate = |column: usize| {
+            row[column].trim().parse::<f64>().map_err(|_| {
+                S2JsonError::invalid_csv(*line, format!("invalid coordinate \"{}\"", row[column]))
+            })
+        };
+        let (x, y) = (coordinate(lon)?, coordinate(lat)?);
+        let mut properties = Properties::new();
+        for (column, (name, (shape, _))) in header.iter().zip(&shapes).enumerate() {
+            if column != lon && column != lat {
+                properties
+                    .insert(name.clone(), ValueType::Primitive(cell_value(&row[column], shape)));
+            }
+        }
+        points.push((x, y, properties));
+    }
+
+    let mut shape = Shape::new();
+    for (column, (name, (primitive, nullable))) in header.iter().zip(shapes).enumerate() {
+        if column != lon && column != lat {
+            let shape_type = ShapeType::Primitive(primitive.clone());
+            let shape_type = if nullable && primitive != PrimitiveShape::Null {
+                shape_type.nullable()
+            } else {
+                shape_type
+            };
+            shape.insert(name.clone(), shape_type);
+        }
+    }
+    Ok((shape, points))
+}
+
+/// Read delimited rows into point [`Feature`]s, along with the [`Shape`] of their properties.
+///
+/// The first record is the header. Every column but the coordinates becomes a property. A
+/// column's shape is the narrowest that fits all its cells: numbers widen as in
+/// [`PrimitiveShape::get_highest_order_number`], `true` and `false` are booleans, and a column
+/// mixing other kinds is a string. Empty cells are null and make the column nullable
+pub fn read_csv(csv: &str, options: &CsvOptions) -> Result<(Shape, Vec<Feature>), S2JsonError> {
+    let (shape, rows) = read_rows(csv, options)?;
+    let features = rows
+        .into_iter()
+        .map(|(x, y, properties)| {
+            let geometry = Geometry::Point(PointGeometry {
+                _type: GeometryType::Point,
+                coordinates: Point(x, y),
+                ..Default::default()
+            });
+            Feature::new(None, properties, geometry, None)
+        })
+        .collect();
+    Ok((shape, features))
+}
+
+/// Read delimited rows into point [`VectorFeature`]s. See [`read_csv`]
+pub fn read_csv_vector(
+    csv: &str,
+    options: &CsvOptions,
+) -> Result<(Shape, Vec<VectorFeature>), S2JsonError> {
+    let (shape, rows) = read_rows(csv, options)?;
+    let features = rows
+        .into_iter()
+        .map(|(x, y, properties)| {
+            let geometry = VectorGeometry::new_point(VectorPoint::from_xy(x, y), None);
+            VectorFeature::new_wm(None, properties, geometry, None)
+        })
+        .collect();
+    Ok((shape, features))
+}
+
+/// Quote a field if it holds the delimiter, a quote or a line break
+fn write_field(field: &str, delimiter: char, out: &mut String) {
+    if field.contains([delimiter, '"', '\n', '\r']) {
+        out.push('"');
+        out.push_str(&field.replace('"', "\"\""));
+        out.push('"');
+    } else {
+        out.push_str(field);
+    }
+}
+
+fn field_text(value: &ValueType) -> String {
+    match value {
+        ValueType::Primitive(PrimitiveValue::Null) => String::new(),
+        ValueType::Primitive(PrimitiveValue::String(string)) => string.clone(),
+        ValueType::Primitive(PrimitiveValue::U64(value)) => value.to_string(),
+        ValueType::Primitive(PrimitiveValue::I64(value)) => value.to_string(),
+        ValueType::Primitive(PrimitiveValue::F32(value)) => value.to_string(),
+        ValueType::Primitive(PrimitiveValue::F64(value)) => value.to_string(),
+        ValueType::Primitive(PrimitiveValue::Bool(value)) => value.to_string(),
+        // arrays and nested values are kept as JSON
+        value => serde_json::to_string(value).unwrap_or_default(),
+    }
+}
+
+fn not_a_point(found: GeometryType) -> S2JsonError {
+    S2JsonError::invalid_type("point geometry", &format!("{found:?}"))
+}
+
+/// Write point features as delimited rows, the inverse of [`read_csv`].
+///
+/// The header holds the coordinate columns followed by every property key found in the
+/// features, in sorted order. A multi point writes a row for each of its points, and a
+/// property missing from a feature is left empty. Other geometries are an error
+pub fn write_csv<M, D: Clone + Default>(
+    features: &[Feature<M, Properties, D>],
+    options: &CsvOptions,
+) -> Result<String, S2JsonError> {
+    let delimiter = options.delimiter;
+    let mut keys: Vec<&String> = Vec::new();
+    for feature in features {
+        for key in feature.properties.keys() {
+            if !keys.contains(&key) {
+                keys.push(key);
+            }
+        }
+    }
+    keys.sort();
+
+    let mut out = String::new();
+    let lon = options.lon_column.as_deref().unwrap_or("lon");
+    let lat = options.lat_column.as_deref().unwrap_or("lat");
+    for (i, column) in [lon, lat].into_iter().chain(keys.iter().map(|key| key.as_str())).enumerate()
+    {
+        if i > 0 {
+            out.push(delimiter);
+        }
+        write_field(column, delimiter, &mut out);
+    }
+    out.push('\n');
+
+    for feature in features {
+        let points: Vec<(f64, f64)> = match &feature.geometry {
+            Geometry::Point(point) => Vec::from([(point.coordinates.0, point.coordinates.1)]),
+            Geometry::Point3D(point) => Vec::from([(point.coordinates.0, point.coordinates.1)]),
+            Geometry::MultiPoint(points) => {
+                points.coordinates.iter().map(|point| (point.0, point.1)).collect()
+            }
+            Geometry::MultiPoint3D(points) => {
+                points.coordinates.iter().map(|point| (point.0, point.1)).collect()
+            }
+            Geometry::LineString(g) => return Err(not_a_point(g._type)),
+            Geometry::MultiLineString(g) => return Err(not_a_point(g._type)),
+            Geometry::Polygon(g) => return Err(not_a_point(g._type)),
+            Geometry::MultiPolygon(g) => return Err(not_a_point(g._type)),
+            Geometry::LineString3D(g) => return Err(not_a_point(g._type)),
+            Geometry::MultiLineString3D(g) => return Err(not_a_point(g._type)),
+            Geometry::Polygon3D(g) => return Err(not_a_point(g._type)),
+            Geometry::MultiPolygon3D(g) => return Err(not_a_point(g._type)),
+        };
+        for (x, y) in points {
+            out.push_str(&x.to_string());
+            out.push(delimiter);
+            out.push_str(&y.to_string());
+            for key in &keys {
+                out.push(delimiter);
+                if let Some(value) = feature.properties.get(*key) {
+                    write_field(&field_text(value), delimiter, &mut out);
+                }
+            }
+            out.push('\n');
+        }
+    }
+    Ok(out)
+}
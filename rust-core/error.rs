@@ -0,0 +1,228 @@
+use crate::ShapeType;
+use alloc::string::String;
+use core::fmt;
+
+/// # S2JSON Error
+///
+/// ## Description
+/// The crate wide error type. Returned by fallible parsing and conversion tooling instead of
+/// panicking, so that malformed input can be handled gracefully.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum S2JsonError {
+    /// A type string (e.g. a geometry or feature `type`) was not recognized
+    InvalidType {
+        /// The kind of type that was being parsed, e.g. "GeometryType"
+        expected: &'static str,
+        /// The string that was found
+        found: String,
+    },
+    /// A vector geometry was converted into a primitive geometry of the wrong dimension
+    DimensionMismatch {
+        /// True if the target geometry is 3D
+        expected_3d: bool,
+    },
+    /// Two shapes could not be merged, or a set of values did not share a single shape
+    ShapeMismatch {
+        /// The key path of the conflict, e.g. `a.b[]` or `[2]`. Empty at the root
+        path: String,
+        /// The shape that was already in place
+        expected: ShapeType,
+        /// The conflicting shape
+        found: ShapeType,
+    },
+    /// Binary data ended before a complete value could be read
+    UnexpectedEof,
+    /// A protobuf message has an unknown wire type, a field of the wrong wire type or is nested
+    /// too deeply
+    InvalidProtobuf {
+        /// The byte offset in the input where decoding failed
+        offset: usize,
+        /// What went wrong at the offset
+        message: String,
+    },
+    /// Well-known text could not be parsed
+    InvalidWkt {
+        /// The byte offset in the input where parsing failed
+        offset: usize,
+        /// What went wrong at the offset
+        message: String,
+    },
+    /// Well-known binary has an unknown byte order or geometry type, or is otherwise malformed
+    InvalidWkb {
+        /// The byte offset in the input where decoding failed
+        offset: usize,
+        /// What went wrong at the offset
+        message: String,
+    },
+    /// An encoded polyline has an invalid character or header, or ends mid value
+    InvalidPolyline {
+        /// The byte offset in the input where decoding failed
+        offset: usize,
+        /// What went wrong at the offset
+        message: String,
+    },
+    /// A TopoJSON topology is not valid JSON or does not follow the specification
+    InvalidTopoJson(String),
+    /// A JSON Schema uses a construct that has no equivalent shape
+    InvalidJsonSchema {
+        /// The key path of the offending schema, e.g. "a.b" or "c[]". Empty at the root
+        path: String,
+        /// What could not be converted
+        message: String,
+    },
+    /// A CSV or TSV file has an unterminated quote, a ragged row or an invalid coordinate
+    InvalidCsv {
+        /// The 1-based line number the record starts on
+        line: usize,
+        /// What went wrong on the line
+        message: String,
+    },
+    /// A GPX or KML document is not well-formed XML or is missing required data
+    InvalidXml {
+        /// The byte offset in the input where reading failed
+        offset: usize,
+        /// What went wrong at the offset
+        message: String,
+    },
+}
+impl S2JsonError {
+    /// Create a new InvalidType error
+    pub fn invalid_type(expected: &'static str, found: &str) -> Self {
+        S2JsonError::InvalidType { expected, found: found.into() }
+    }
+
+    /// Create a new ShapeMismatch error
+    pub fn shape_mismatch(path: &str, expected: ShapeType, found: ShapeType) -> Self {
+        S2JsonError::ShapeMismatch { path: path.into(), expected, found }
+    }
+
+    /// Create a new InvalidProtobuf error
+    pub fn invalid_protobuf(offset: usize, message: impl Into<String>) -> Self {
+        S2JsonError::InvalidProtobuf { offset, message: message.into() }
+    }
+
+    /// Create a new InvalidWkt error
+    pub fn invalid_wkt(offset: usize, message: impl Into<String>) -> Self {
+        S2JsonError::InvalidWkt { offset, message: message.into() }
+    }
+
+    /// Create a new InvalidWkb error
+    pub fn invalid_wkb(offset: usize, message: impl Into<String>) -> Self {
+        S2JsonError::InvalidWkb { offset, message: message.into() }
+    }
+
+    /// Create a new InvalidPolyline error
+    pub fn invalid_polyline(offset: usize, message: impl Into<String>) -> Self {
+        S2JsonError::InvalidPolyline { offset, message: message.into() }
+    }
+
+    /// Create a new InvalidJsonSchema error
+    pub fn invalid_json_schema(path: &str, message: impl Into<String>) -> Self {
+        S2JsonError::InvalidJsonSchema { path: path.into(), message: message.into() }
+    }
+
+    /// Create a new InvalidCsv error
+    pub fn invalid_csv(line: usize, message: impl Into<String>) -> Self {
+        S2JsonError::InvalidCsv { line, message: message.into() }
+    }
+
+    /// Create a new InvalidXml error
+    pub fn invalid_xml(offset: usize, message: impl Into<String>) -> Self {
+        S2JsonError::InvalidXml { offset, message: message.into() }
+    }
+}
+impl fmt::Display for S2JsonError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            S2JsonError::InvalidType { expected, found } => {
+                write!(f, "invalid {expected}: \"{found}\"")
+            }
+            S2JsonError::DimensionMismatch { expected_3d: true } => {
+                write!(f, "expected a 3D vector geometry but found a 2D one")
+            }
+            S2JsonError::DimensionMismatch { expected_3d: false } => {
+                write!(f, "expected a 2D vector geometry but found a 3D one")
+            }
+            S2JsonError::ShapeMismatch { path, expected, found } => {
+                write!(f, "shape mismatch at \"{path}\": expected {expected:?}, found {found:?}")
+            }
+            S2JsonError::UnexpectedEof => write!(f, "unexpected end of data"),
+            S2JsonError::InvalidProtobuf { offset, message } => {
+                write!(f, "invalid protobuf at byte {offset}: {message}")
+            }
+            S2JsonError::InvalidWkt { offset, message } => {
+                write!(f, "invalid WKT at byte {offset}: {message}")
+            }
+            S2JsonError::InvalidWkb { offset, message } => {
+                write!(f, "invalid WKB at byte {offset}: {message}")
+            }
+            S2JsonError::InvalidPolyline { offset, message } => {
+                write!(f, "invalid polyline at byte {offset}: {message}")
+            }
+            S2JsonError::InvalidTopoJson(message) => write!(f, "invalid TopoJSON: {message}"),
+            S2JsonError::InvalidJsonSchema { path, message } => {
+                write!(f, "invalid JSON Schema at \"{path}\": {message}")
+            }
+            S2JsonError::InvalidCsv { line, message } => {
+                write!(f, "invalid CSV at line {line}: {message}")
+            }
+            S2JsonError::InvalidXml { offset, message } => {
+                write!(f, "invalid XML at byte {offset}: {message}")
+            }
+        }
+    }
+}
+impl core::error::Error for S2JsonError {}
+
+/// A single way in which a value does not conform to a shape
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShapeViolation {
+    /// The key path of the violation, e.g. `a.b` or `c[2].d`. Empty at the root
+    pub path: String,
+    /// What is wrong at the path
+    pub kind: ShapeViolationKind,
+}
+/// The kind of [`ShapeViolation`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ShapeViolationKind {
+    /// A key the shape requires is absent
+    MissingKey,
+    /// A key is present that the shape does not describe
+    ExtraKey,
+    /// The value is not of the type the shape describes
+    WrongType {
+        /// The shape at the path
+        expected: ShapeType,
+        /// The shape of the value that was found
+        found: ShapeType,
+    },
+    /// The elements of an array do not share a single shape. The path points at the first element
+    /// that conflicts with the ones before it
+    HeterogeneousArray {
+        /// The shape of the elements before the path
+        expected: ShapeType,
+        /// The shape of the element at the path
+        found: ShapeType,
+    },
+}
+impl ShapeViolation {
+    /// Create a new ShapeViolation
+    pub fn new(path: &str, kind: ShapeViolationKind) -> Self {
+        ShapeViolation { path: path.into(), kind }
+    }
+}
+impl fmt::Display for ShapeViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let path = &self.path;
+        match &self.kind {
+            ShapeViolationKind::MissingKey => write!(f, "missing key \"{path}\""),
+            ShapeViolationKind::ExtraKey => write!(f, "unexpected key \"{path}\""),
+            ShapeViolationKind::WrongType { expected, found } => {
+                write!(f, "wrong type at \"{path}\": expected {expected:?}, found {found:?}")
+            }
+            ShapeViolationKind::HeterogeneousArray { expected, found } => {
+                write!(f, "mixed array at \"{path}\": expected {expected:?}, found {found:?}")
+            }
+        }
+    }
+}
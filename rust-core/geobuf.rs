@@ -0,0 +1,506 @@
+use crate::{
+    impls::proto::{
+        MAX_PRECISION, ProtoCoordinates, Schema, Wire, check_code, check_message, delta_decode,
+        delta_encode, precision_scale, read_f64s, value_type_schema, write_f64s,
+    },
+    mvt::Table,
+    *,
+};
+use alloc::{string::String, vec, vec::Vec};
+use libm::round;
+use pbf::{ProtoRead, Protobuf};
+use serde::{Serialize, de::DeserializeOwned};
+
+/// Largest integer a double stores exactly
+const MAX_SAFE_INTEGER: f64 = 9_007_199_254_740_992.;
+
+/// Find the fewest decimal places that store every value exactly, if any
+fn detect_precision(values: &[f64]) -> Option<u8> {
+    (0..=MAX_PRECISION).find(|&precision| {
+        let scale = precision_scale(precision);
+        values.iter().all(|value| {
+            let quantized = round(value * scale);
+            quantized.abs() < MAX_SAFE_INTEGER && quantized / scale == *value
+        })
+    })
+}
+
+/// A point stored as its raw values
+trait GeobufPoint: Sized {
+    /// The number of values per point
+    const DIM: usize;
+    fn write_values(&self, values: &mut Vec<f64>);
+    fn from_values(values: &[f64]) -> Self;
+}
+impl GeobufPoint for Point {
+    const DIM: usize = 2;
+
+    fn write_values(&self, values: &mut Vec<f64>) {
+        values.extend([self.0, self.1]);
+    }
+
+    fn from_values(values: &[f64]) -> Self {
+        Point(values[0], values[1])
+    }
+}
+impl GeobufPoint for Point3D {
+    const DIM: usize = 3;
+
+    fn write_values(&self, values: &mut Vec<f64>) {
+        values.extend([self.0, self.1, self.2]);
+    }
+
+    fn from_values(values: &[f64]) -> Self {
+        Point3D(values[0], values[1], values[2])
+    }
+}
+
+/// A 2D or 3D bounding box stored as its raw values
+trait GeobufBBox: Sized {
+    fn to_values(&self) -> Vec<f64>;
+    fn from_values(values: &[f64]) -> Self;
+}
+impl GeobufBBox for BBox {
+    fn to_values(&self) -> Vec<f64> {
+        vec![self.left, self.bottom, self.right, self.top]
+    }
+
+    fn from_values(values: &[f64]) -> Self {
+        let value = |i: usize| values.get(i).copied().unwrap_or_default();
+        BBox::new(value(0), value(1), value(2), value(3))
+    }
+}
+impl GeobufBBox for BBox3D {
+    fn to_values(&self) -> Vec<f64> {
+        vec![self.left, self.bottom, self.right, self.top, self.near, self.far]
+    }
+
+    fn from_values(values: &[f64]) -> Self {
+        let value = |i: usize| values.get(i).copied().unwrap_or_default();
+        BBox3D::new(value(0), value(1), value(2), value(3), value(4), value(5))
+    }
+}
+
+// Encoding
+
+/// Write a properties or m-value object with its keys replaced by indices into the key table
+fn write_keyed(pb: &mut Protobuf, tag: u64, value: &MValue, keys: &mut Table<String>) {
+    let mut message = Protobuf::new();
+    let mut indices = vec![];
+    for (key, value) in value.iter() {
+        indices.push(keys.index(key));
+        message.write_message(1, value);
+    }
+    if !indices.is_empty() {
+        message.write_packed_varint(2, &indices);
+    }
+    pb.write_bytes_field(tag, &message.take());
+}
+
+/// Write a geometry. Coordinates are zig-zag delta encoded at the fewest decimal places that keep
+/// them exact, or stored as raw doubles when no precision does
+fn write_base_geometry<
+    G: ProtoCoordinates<Point: GeobufPoint>,
+    B: GeobufBBox,
+    D: MValueCompatible,
+>(
+    pb: &mut Protobuf,
+    geometry: &BaseGeometry<G::Nested<D>, G, B>,
+    keys: &mut Table<String>,
+) {
+    pb.write_varint_field(1, u8::from(geometry._type));
+    let (mut points, mut lengths) = (vec![], vec![]);
+    geometry.coordinates.flatten(&mut points, &mut lengths);
+    let mut values = Vec::with_capacity(points.len() * G::Point::DIM);
+    for point in points {
+        point.write_values(&mut values);
+    }
+    if !lengths.is_empty() {
+        pb.write_packed_varint(3, &lengths);
+    }
+    match detect_precision(&values) {
+        Some(precision) => {
+            pb.write_varint_field(2, precision);
+            pb.write_packed_s_varint(4, &delta_encode(&values, G::Point::DIM, precision));
+        }
+        None => write_f64s(pb, 5, &values),
+    }
+    if let Some(m_values) = &geometry.m_values {
+        let (mut flat, mut m_lengths) = (vec![], vec![]);
+        G::flatten_nested(m_values, &mut flat, &mut m_lengths);
+        // always written so that an m-value list without entries still round-trips
+        pb.write_packed_varint(6, &m_lengths);
+        for m_value in flat {
+            write_keyed(pb, 7, &m_value.clone().into(), keys);
+        }
+    }
+    if let Some(bbox) = &geometry.bbox {
+        write_f64s(pb, 8, &bbox.to_values());
+    }
+}
+
+fn write_geometry<D: MValueCompatible>(
+    pb: &mut Protobuf,
+    geometry: &Geometry<D>,
+    keys: &mut Table<String>,
+) {
+    match geometry {
+        Geometry::Point(g) => write_base_geometry::<Point, BBox, D>(pb, g, keys),
+        Geometry::MultiPoint(g) => write_base_geometry::<MultiPoint, BBox, D>(pb, g, keys),
+        Geometry::LineString(g) => write_base_geometry::<LineString, BBox, D>(pb, g, keys),
+        Geometry::MultiLineString(g) => {
+            write_base_geometry::<MultiLineString, BBox, D>(pb, g, keys)
+        }
+        Geometry::Polygon(g) => write_base_geometry::<Polygon, BBox, D>(pb, g, keys),
+        Geometry::MultiPolygon(g) => write_base_geometry::<MultiPolygon, BBox, D>(pb, g, keys),
+        Geometry::Point3D(g) => write_base_geometry::<Point3D, BBox3D, D>(pb, g, keys),
+        Geometry::MultiPoint3D(g) => write_base_geometry::<MultiPoint3D, BBox3D, D>(pb, g, keys),
+        Geometry::LineString3D(g) => write_base_geometry::<LineString3D, BBox3D, D>(pb, g, keys),
+        Geometry::MultiLineString3D(g) => {
+            write_base_geometry::<MultiLineString3D, BBox3D, D>(pb, g, keys)
+        }
+        Geometry::Polygon3D(g) => write_base_geometry::<Polygon3D, BBox3D, D>(pb, g, keys),
+        Geometry::MultiPolygon3D(g) => {
+            write_base_geometry::<MultiPolygon3D, BBox3D, D>(pb, g, keys)
+        }
+    }
+}
+
+fn write_feature<M: Serialize, P: MValueCompatible, D: MValueCompatible>(
+    pb: &mut Protobuf,
+    feature: &Feature<M, P, D>,
+    keys: &mut Table<String>,
+) {
+    if let Some(id) = feature.id {
+        pb.write_varint_field(1, id);
+    }
+    let mut geometry = Protobuf::new();
+    write_geometry(&mut geometry, &feature.geometry, keys);
+    pb.write_bytes_field(2, &geometry.take());
+    write_keyed(pb, 3, &feature.properties.clone().into(), keys);
+    if let Some(metadata) = &feature.metadata
+        && let Ok(json) = serde_json::to_string(metadata)
+    {
+        pb.write_string_field(4, &json);
+    }
+}
+
+// Decoding
+
+/// A properties or m-value object whose keys are indices into the key table
+#[derive(Default)]
+struct KeyedValue {
+    values: Vec<ValueType>,
+    keys: Vec<u32>,
+}
+impl ProtoRead for KeyedValue {
+    fn read(&mut self, tag: u64, pb: &mut Protobuf) {
+        match tag {
+            1 => {
+                let mut value = ValueType::default();
+                pb.read_message(&mut value);
+                self.values.push(value);
+            }
+            2 => self.keys = pb.read_packed(),
+            _ => {}
+        }
+    }
+}
+impl KeyedValue {
+    fn resolve(self, keys: &[String]) -> MValue {
+        let mut res = MValue::new();
+        for (index, value) in self.keys.into_iter().zip(self.values) {
+            if let Some(key) = keys.get(index as usize) {
+                res.insert(key.clone(), value);
+            }
+        }
+        res
+    }
+}
+
+/// A geometry message before its m-value keys are resolved
+#[derive(Default)]
+struct RawGeometry {
+    geo_type: GeometryType,
+    precision: Option<u8>,
+    lengths: Vec<u64>,
+    deltas: Vec<i64>,
+    values: Vec<f64>,
+    m_lengths: Option<Vec<u64>>,
+    m_values: Vec<KeyedValue>,
+    bbox: Option<Vec<f64>>,
+}
+impl ProtoRead for RawGeometry {
+    fn read(&mut self, tag: u64, pb: &mut Protobuf) {
+        match tag {
+            1 => {
+                if let Ok(geo_type) = pb.read_varint::<u8>().try_into() {
+                    self.geo_type = geo_type;
+                }
+            }
+            2 => self.precision = Some(pb.read_varint()),
+            3 => self.lengths = pb.read_packed(),
+            4 => self.deltas = pb.read_s_packed(),
+            5 => self.values = read_f64s(pb),
+            6 => self.m_lengths = Some(pb.read_packed()),
+            7 => {
+                let mut m_value = KeyedValue::default();
+                pb.read_message(&mut m_value);
+                self.m_values.push(m_value);
+            }
+            8 => self.bbox = Some(read_f64s(pb)),
+            _ => {}
+        }
+    }
+}
+impl RawGeometry {
+    fn build<G: ProtoCoordinates<Point: GeobufPoint>, B: GeobufBBox, D: MValueCompatible>(
+        self,
+        keys: &[String],
+    ) -> BaseGeometry<G::Nested<D>, G, B> {
+        let RawGeometry { geo_type, precision, lengths, deltas, values, m_lengths, m_values, bbox } =
+            self;
+        let values = match precision {
+            Some(precision) => delta_decode(&deltas, G::Point::DIM, precision),
+            None => values,
+        };
+        let mut points = values.chunks_exact(G::Point::DIM).map(G::Point::from_values);
+        let coordinates = G::unflatten(&mut points, &mut lengths.into_iter()).unwrap_or_default();
+        let m_values = m_lengths.and_then(|m_lengths| {
+            let mut m_values = m_values.into_iter().map(|m_value| D::from(m_value.resolve(keys)));
+            G::unflatten_nested(&mut m_values, &mut m_lengths.into_iter())
+        });
+        BaseGeometry {
+            _type: geo_type,
+            coordinates,
+            m_values,
+            bbox: bbox.map(|bbox| B::from_values(&bbox)),
+        }
+    }
+
+    fn into_geometry<D: MValueCompatible>(self, keys: &[String]) -> Geometry<D> {
+        match self.geo_type {
+            GeometryType::Point => Geometry::Point(self.build::<Point, BBox, D>(keys)),
+            GeometryType::MultiPoint => {
+                Geometry::MultiPoint(self.build::<MultiPoint, BBox, D>(keys))
+            }
+            GeometryType::LineString => {
+                Geometry::LineString(self.build::<LineString, BBox, D>(keys))
+            }
+            GeometryType::MultiLineString => {
+                Geometry::MultiLineString(self.build::<MultiLineString, BBox, D>(keys))
+            }
+            GeometryType::Polygon => Geometry::Polygon(self.build::<Polygon, BBox, D>(keys)),
+            GeometryType::MultiPolygon => {
+                Geometry::MultiPolygon(self.build::<MultiPolygon, BBox, D>(keys))
+            }
+            GeometryType::Point3D => Geometry::Point3D(self.build::<Point3D, BBox3D, D>(keys)),
+            GeometryType::MultiPoint3D => {
+                Geometry::MultiPoint3D(self.build::<MultiPoint3D, BBox3D, D>(keys))
+            }
+            GeometryType::LineString3D => {
+                Geometry::LineString3D(self.build::<LineString3D, BBox3D, D>(keys))
+            }
+            GeometryType::MultiLineString3D => {
+                Geometry::MultiLineString3D(self.build::<MultiLineString3D, BBox3D, D>(keys))
+            }
+            GeometryType::Polygon3D => {
+                Geometry::Polygon3D(self.build::<Polygon3D, BBox3D, D>(keys))
+            }
+            GeometryType::MultiPolygon3D => {
+                Geometry::MultiPolygon3D(self.build::<MultiPolygon3D, BBox3D, D>(keys))
+            }
+        }
+    }
+}
+
+/// Schema of a [`KeyedValue`]
+fn keyed_value_schema(tag: u64) -> Option<Wire> {
+    match tag {
+        1 => Some(Wire::Message(value_type_schema)),
+        2 => Some(Wire::Packed),
+        _ => None,
+    }
+}
+
+/// Schema of a [`RawGeometry`]
+fn geometry_schema(tag: u64) -> Option<Wire> {
+    match tag {
+        1 => Some(Wire::Code(|code| check_code::<GeometryType>(code, "GeometryType"))),
+        2 => Some(Wire::Varint),
+        3 | 4 | 6 => Some(Wire::Packed),
+        5 | 8 => Some(Wire::Fixed64s),
+        7 => Some(Wire::Message(keyed_value_schema)),
+        _ => None,
+    }
+}
+
+/// Schema of a collection
+fn collection_schema(tag: u64) -> Option<Wire> {
+    fn feature(tag: u64) -> Option<Wire> {
+        match tag {
+            1 => Some(Wire::Varint),
+            2 => Some(Wire::Message(geometry_schema)),
+            3 => Some(Wire::Message(keyed_value_schema)),
+            4 => Some(Wire::Bytes),
+            _ => None,
+        }
+    }
+    fn attributions(tag: u64) -> Option<Wire> {
+        matches!(tag, 1 | 2).then_some(Wire::Bytes)
+    }
+    let schema: Schema = match tag {
+        1 | 3 => return Some(Wire::Bytes),
+        2 => feature,
+        4 => attributions,
+        5 => return Some(Wire::Fixed64s),
+        _ => return None,
+    };
+    Some(Wire::Message(schema))
+}
+
+/// Reads a feature message against the key table read so far
+struct FeatureReader<'a, M, P: Clone + Default, D: Clone + Default> {
+    keys: &'a [String],
+    feature: Feature<M, P, D>,
+}
+impl<M: DeserializeOwned, P: MValueCompatible, D: MValueCompatible> ProtoRead
+    for FeatureReader<'_, M, P, D>
+{
+    fn read(&mut self, tag: u64, pb: &mut Protobuf) {
+        match tag {
+            1 => self.feature.id = Some(pb.read_varint()),
+            2 => {
+                let mut geometry = RawGeometry::default();
+                pb.read_message(&mut geometry);
+                self.feature.geometry = geometry.into_geometry(self.keys);
+            }
+            3 => {
+                let mut properties = KeyedValue::default();
+                pb.read_message(&mut properties);
+                self.feature.properties = properties.resolve(self.keys).into();
+            }
+            4 => self.feature.metadata = serde_json::from_str(&pb.read_string()).ok(),
+            _ => {}
+        }
+    }
+}
+
+/// Attribution entries, written as alternating name and link fields
+#[derive(Default)]
+struct AttributionsReader {
+    attributions: Attributions,
+    name: String,
+}
+impl ProtoRead for AttributionsReader {
+    fn read(&mut self, tag: u64, pb: &mut Protobuf) {
+        match tag {
+            1 => self.name = pb.read_string(),
+            2 => {
+                let link = pb.read_string();
+                self.attributions.insert(core::mem::take(&mut self.name), link);
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Reads a collection. The key table always precedes the features that use it
+struct CollectionReader<M, P: Clone + Default, D: Clone + Default> {
+    keys: Vec<String>,
+    collection: FeatureCollection<M, P, D>,
+}
+impl<
+    M: DeserializeOwned,
+    P: MValueCompatible + DeserializeOwned,
+    D: MValueCompatible + DeserializeOwned,
+> ProtoRead for CollectionReader<M, P, D>
+{
+    fn read(&mut self, tag: u64, pb: &mut Protobuf) {
+        match tag {
+            1 => self.keys.push(pb.read_string()),
+            2 => {
+                let mut reader = FeatureReader {
+                    keys: &self.keys,
+                    feature: Feature::new(None, P::default(), Geometry::default(), None),
+                };
+                pb.read_message(&mut reader);
+                self.collection.features.push(Features::Feature(reader.feature));
+            }
+            3 => {
+                if let Ok(feature) = serde_json::from_str(&pb.read_string()) {
+                    self.collection.features.push(Features::VectorFeature(feature));
+                }
+            }
+            4 => {
+                let mut reader = AttributionsReader::default();
+                pb.read_message(&mut reader);
+                self.collection.attributions = Some(reader.attributions);
+            }
+            5 => self.collection.bbox = Some(BBox::from_values(&read_f64s(pb))),
+            _ => {}
+        }
+    }
+}
+
+impl<
+    M: Serialize + DeserializeOwned,
+    P: MValueCompatible + Serialize + DeserializeOwned,
+    D: MValueCompatible + Serialize + DeserializeOwned,
+> FeatureCollection<M, P, D>
+{
+    /// Encode the collection into a compact, Geobuf inspired protobuf.
+    ///
+    /// Property and m-value keys are stored once in a shared table. Coordinates are zig-zag delta
+    /// encoded at the fewest decimal places that keep every value of a geometry exact, falling
+    /// back to raw doubles, so the collection round-trips losslessly with
+    /// [`FeatureCollection::from_geobuf`]. Metadata, and the rare [`VectorFeature`] inside a WG
+    /// collection, are embedded as JSON.
+    pub fn to_geobuf(&self) -> Vec<u8> {
+        let mut keys = Table::<String>::new();
+        let mut body = Protobuf::new();
+        for feature in &self.features {
+            match feature {
+                Features::Feature(feature) => {
+                    let mut message = Protobuf::new();
+                    write_feature(&mut message, feature, &mut keys);
+                    body.write_bytes_field(2, &message.take());
+                }
+                Features::VectorFeature(feature) => {
+                    if let Ok(json) = serde_json::to_string(feature) {
+                        body.write_string_field(3, &json);
+                    }
+                }
+            }
+        }
+        if let Some(attributions) = &self.attributions {
+            let mut message = Protobuf::new();
+            for (name, link) in attributions.iter() {
+                message.write_string_field(1, name);
+                message.write_string_field(2, link);
+            }
+            body.write_bytes_field(4, &message.take());
+        }
+        if let Some(bbox) = &self.bbox {
+            write_f64s(&mut body, 5, &bbox.to_values());
+        }
+
+        let mut head = Protobuf::new();
+        for key in &keys.list {
+            head.write_string_field(1, key);
+        }
+        let mut data = head.take();
+        data.extend(body.take());
+        data
+    }
+
+    /// Decode a collection written by [`FeatureCollection::to_geobuf`]. Truncated or malformed
+    /// data and unknown geometry types are errors
+    pub fn from_geobuf(data: &[u8]) -> Result<Self, S2JsonError> {
+        check_message(data, collection_schema)?;
+        let mut reader =
+            CollectionReader { keys: vec![], collection: FeatureCollection::new(None) };
+        Protobuf::from_input(data.to_vec()).read_fields(&mut reader, None);
+        Ok(reader.collection)
+    }
+}
@@ -0,0 +1,303 @@
+use crate::{
+    Axis, BBox, BBox3D, Interpolate, VectorBaseGeometry, VectorGeometry, VectorGeometryType,
+    VectorLineOffset, VectorLineString, VectorPoint, VectorPolygon, VectorPolygonOffset,
+};
+use alloc::vec::Vec;
+use libm::sqrt;
+
+// Lines and rings are walked one segment at a time, keeping the points inside the band and adding
+// a point wherever a segment crosses k1 or k2. Lines start a new piece each time they leave the
+// band, while rings continue along the band's edge so they stay closed. Each piece records how
+// far along the original line it starts, so dashes and labels can continue where they left off.
+
+/// A piece of a line or ring that survived a clip
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClippedLine<M: Clone> {
+    /// The points of the piece
+    pub points: VectorLineString<M>,
+    /// The distance along the original line to the piece's first point
+    pub offset: VectorLineOffset,
+}
+impl<M: Clone> ClippedLine<M> {
+    fn new() -> Self {
+        ClippedLine { points: Vec::new(), offset: 0. }
+    }
+
+    /// Add a point `distance` along the original line, skipping repeats
+    fn push(&mut self, point: VectorPoint<M>, distance: f64) {
+        match self.points.last() {
+            None => self.offset = distance,
+            Some(last) if last.x == point.x && last.y == point.y => return,
+            _ => {}
+        }
+        self.points.push(point);
+    }
+}
+
+fn coordinate<M: Clone>(point: &VectorPoint<M>, axis: Axis) -> f64 {
+    match axis {
+        Axis::X => point.x,
+        Axis::Y => point.y,
+    }
+}
+
+/// The point where segment a-b crosses `k` on the axis, and how far along the segment it lies.
+/// Its z and m-values are interpolated, and it is never simplified away
+fn intersect<M: Clone + Interpolate>(
+    a: &VectorPoint<M>,
+    b: &VectorPoint<M>,
+    axis: Axis,
+    k: f64,
+) -> (VectorPoint<M>, f64) {
+    let t = (k - coordinate(a, axis)) / (coordinate(b, axis) - coordinate(a, axis));
+    let mut point = a.interpolate(b, t);
+    match axis {
+        Axis::X => point.x = k,
+        Axis::Y => point.y = k,
+    }
+    point.t = a.t.or(b.t).map(|_| f64::INFINITY);
+    (point, t)
+}
+
+/// Clip a line or ring to the band k1..k2 of an axis. `offset` is the distance along the line to
+/// its first point, which the offsets of the pieces build on. Rings come back closed, and pieces
+/// too short to be a line or ring are dropped
+pub fn clip_line<M: Clone + Interpolate>(
+    line: &[VectorPoint<M>],
+    offset: VectorLineOffset,
+    axis: Axis,
+    k1: f64,
+    k2: f64,
+    is_ring: bool,
+) -> Vec<ClippedLine<M>> {
+    let mut slices = Vec::new();
+    let mut slice = ClippedLine::new();
+    let mut distance = offset;
+    for pair in line.windows(2) {
+        let (a, b) = (&pair[0], &pair[1]);
+        let (ak, bk) = (coordinate(a, axis), coordinate(b, axis));
+        let (dx, dy) = (b.x - a.x, b.y - a.y);
+        let length = sqrt(dx * dx + dy * dy);
+        let cut = |slice: &mut ClippedLine<M>, k: f64| {
+            let (point, t) = intersect(a, b, axis, k);
+            slice.push(point, distance + length * t);
+        };
+
+        // entering the band, or inside it
+        if ak < k1 {
+            if bk > k1 {
+                cut(&mut slice, k1);
+            }
+        } else if ak > k2 {
+            if bk < k2 {
+                cut(&mut slice, k2);
+            }
+        } else {
+            slice.push(a.clone(), distance);
+        }
+        // leaving the band
+        let mut exited = false;
+        if bk < k1 && ak >= k1 {
+            cut(&mut slice, k1);
+            exited = true;
+        }
+        if bk > k2 && ak <= k2 {
+            cut(&mut slice, k2);
+            exited = true;
+        }
+        if exited && !is_ring {
+            slices.push(core::mem::replace(&mut slice, ClippedLine::new()));
+        }
+        distance += length;
+    }
+    if let Some(last) = line.last() {
+        let k = coordinate(last, axis);
+        if k >= k1 && k <= k2 {
+            slice.push(last.clone(), distance);
+        }
+    }
+    if is_ring
+        && let (Some(first), Some(last)) = (slice.points.first(), slice.points.last())
+        && (first.x != last.x || first.y != last.y)
+    {
+        slice.points.push(first.clone());
+    }
+    slices.push(slice);
+
+    let min_points = if is_ring { 4 } else { 2 };
+    slices.retain(|slice| slice.points.len() >= min_points);
+    slices
+}
+
+/// Clip the rings of a polygon. The polygon is gone if its outer ring is
+fn clip_polygon<M: Clone + Interpolate>(
+    polygon: &VectorPolygon<M>,
+    offsets: Option<&VectorPolygonOffset>,
+    axis: Axis,
+    k1: f64,
+    k2: f64,
+) -> Option<(VectorPolygon<M>, VectorPolygonOffset)> {
+    let mut rings = Vec::new();
+    let mut ring_offsets = Vec::new();
+    for (r, ring) in polygon.iter().enumerate() {
+        let offset = offsets.and_then(|offsets| offsets.get(r)).copied().unwrap_or_default();
+        match clip_line(ring, offset, axis, k1, k2, true).pop() {
+            Some(slice) => {
+                rings.push(slice.points);
+                ring_offsets.push(slice.offset);
+            }
+            None if r == 0 => return None,
+            None => {}
+        }
+    }
+    Some((rings, ring_offsets))
+}
+
+fn in_band<M: Clone>(point: &VectorPoint<M>, axis: Axis, k1: f64, k2: f64) -> bool {
+    let k = coordinate(point, axis);
+    k >= k1 && k <= k2
+}
+
+/// A clipped geometry, keeping the type, dimension and bbox of the original. Its vec_bbox is
+/// measured from the clipped coordinates, and any tessellation is dropped
+fn clipped<G, O>(
+    _type: VectorGeometryType,
+    original: &VectorBaseGeometry<impl Clone, impl Clone>,
+    coordinates: G,
+    offset: Option<O>,
+    vec_bbox: BBox3D,
+) -> VectorBaseGeometry<G, O> {
+    VectorBaseGeometry {
+        _type,
+        is_3d: original.is_3d,
+        coordinates,
+        offset,
+        bbox: original.bbox,
+        vec_bbox: Some(vec_bbox),
+        indices: None,
+        tessellation: None,
+    }
+}
+
+impl<M: Clone + Default + Interpolate> VectorGeometry<M> {
+    /// Clip the geometry to the band `k1..=k2` along an axis, returning None if nothing is left.
+    ///
+    /// Points outside the band are dropped. Lines are cut where they cross the band's edges,
+    /// with the z and m-values of each cut point interpolated from the segment it lies on. A line
+    /// that leaves and re-enters the band becomes a multi line, and each piece's offset records
+    /// its distance along the original line. Polygon rings follow the band's edges so they stay
+    /// closed, and a polygon whose outer ring is clipped away is dropped. The result's vec_bbox
+    /// is updated to the clipped coordinates
+    pub fn clip_axis(&self, axis: Axis, k1: f64, k2: f64) -> Option<VectorGeometry<M>> {
+        let geometry = match self {
+            VectorGeometry::Point(g) => {
+                if !in_band(&g.coordinates, axis, k1, k2) {
+                    return None;
+                }
+                let vec_bbox = BBox3D::from_point(&g.coordinates);
+                VectorGeometry::Point(clipped(
+                    g._type,
+                    g,
+                    g.coordinates.clone(),
+                    g.offset.clone(),
+                    vec_bbox,
+                ))
+            }
+            VectorGeometry::MultiPoint(g) => {
+                let points: Vec<VectorPoint<M>> = g
+                    .coordinates
+                    .iter()
+                    .filter(|point| in_band(point, axis, k1, k2))
+                    .cloned()
+                    .collect();
+                if points.is_empty() {
+                    return None;
+                }
+                let vec_bbox = BBox3D::from_linestring(&points);
+                VectorGeometry::MultiPoint(clipped(g._type, g, points, g.offset, vec_bbox))
+            }
+            VectorGeometry::LineString(g) => {
+                let offset = g.offset.unwrap_or_default();
+                let mut slices = clip_line(&g.coordinates, offset, axis, k1, k2, false);
+                match slices.len() {
+                    0 => return None,
+                    1 => {
+                        let ClippedLine { points, offset } = slices.remove(0);
+                        let vec_bbox = BBox3D::from_linestring(&points);
+                        VectorGeometry::LineString(clipped(
+                            g._type,
+                            g,
+                            points,
+                            Some(offset),
+                            vec_bbox,
+                        ))
+                    }
+                    _ => {
+                        let offsets = slices.iter().map(|slice| slice.offset).collect();
+                        let lines: Vec<_> = slices.into_iter().map(|slice| slice.points).collect();
+                        let vec_bbox = BBox3D::from_multi_linestring(&lines);
+                        VectorGeometry::MultiLineString(clipped(
+                            VectorGeometryType::MultiLineString,
+                            g,
+                            lines,
+                            Some(offsets),
+                            vec_bbox,
+                        ))
+                    }
+                }
+            }
+            VectorGeometry::MultiLineString(g) => {
+                let mut lines = Vec::new();
+                let mut offsets = Vec::new();
+                for (l, line) in g.coordinates.iter().enumerate() {
+                    let offset = g.offset.as_ref().and_then(|o| o.get(l)).copied();
+                    for slice in clip_line(line, offset.unwrap_or_default(), axis, k1, k2, false) {
+                        lines.push(slice.points);
+                        offsets.push(slice.offset);
+                    }
+                }
+                if lines.is_empty() {
+                    return None;
+                }
+                let vec_bbox = BBox3D::from_multi_linestring(&lines);
+                VectorGeometry::MultiLineString(clipped(g._type, g, lines, Some(offsets), vec_bbox))
+            }
+            VectorGeometry::Polygon(g) => {
+                let (polygon, offsets) =
+                    clip_polygon(&g.coordinates, g.offset.as_ref(), axis, k1, k2)?;
+                let vec_bbox = BBox3D::from_polygon(&polygon);
+                VectorGeometry::Polygon(clipped(g._type, g, polygon, Some(offsets), vec_bbox))
+            }
+            VectorGeometry::MultiPolygon(g) => {
+                let mut polygons = Vec::new();
+                let mut offsets = Vec::new();
+                for (p, polygon) in g.coordinates.iter().enumerate() {
+                    let polygon_offsets = g.offset.as_ref().and_then(|o| o.get(p));
+                    if let Some((polygon, polygon_offsets)) =
+                        clip_polygon(polygon, polygon_offsets, axis, k1, k2)
+                    {
+                        polygons.push(polygon);
+                        offsets.push(polygon_offsets);
+                    }
+                }
+                if polygons.is_empty() {
+                    return None;
+                }
+                let vec_bbox = BBox3D::from_multi_polygon(&polygons);
+                VectorGeometry::MultiPolygon(clipped(g._type, g, polygons, Some(offsets), vec_bbox))
+            }
+        };
+        Some(geometry)
+    }
+
+    /// Clip the geometry to a bounding box grown by `buffer` on every side, returning None if
+    /// nothing is left. See [`VectorGeometry::clip_axis`]
+    pub fn clip_bbox(&self, bbox: &BBox, buffer: Option<f64>) -> Option<VectorGeometry<M>> {
+        let buffer = buffer.unwrap_or_default();
+        self.clip_axis(Axis::X, bbox.left - buffer, bbox.right + buffer)?.clip_axis(
+            Axis::Y,
+            bbox.bottom - buffer,
+            bbox.top + buffer,
+        )
+    }
+}
@@ -0,0 +1,306 @@
+use crate::*;
+use alloc::{vec, vec::Vec};
+
+//? Geometry types
+
+impl From<GeometryType> for VectorGeometryType {
+    fn from(t: GeometryType) -> Self {
+        match t {
+            GeometryType::Point | GeometryType::Point3D => VectorGeometryType::Point,
+            GeometryType::MultiPoint | GeometryType::MultiPoint3D => VectorGeometryType::MultiPoint,
+            GeometryType::LineString | GeometryType::LineString3D => VectorGeometryType::LineString,
+            GeometryType::MultiLineString | GeometryType::MultiLineString3D => {
+                VectorGeometryType::MultiLineString
+            }
+            GeometryType::Polygon | GeometryType::Polygon3D => VectorGeometryType::Polygon,
+            GeometryType::MultiPolygon | GeometryType::MultiPolygon3D => {
+                VectorGeometryType::MultiPolygon
+            }
+        }
+    }
+}
+impl GeometryType {
+    /// Get the geometry type of a vector geometry type with the given dimension
+    pub fn from_vector_type(t: VectorGeometryType, is_3d: bool) -> Self {
+        match (t, is_3d) {
+            (VectorGeometryType::Point, false) => GeometryType::Point,
+            (VectorGeometryType::MultiPoint, false) => GeometryType::MultiPoint,
+            (VectorGeometryType::LineString, false) => GeometryType::LineString,
+            (VectorGeometryType::MultiLineString, false) => GeometryType::MultiLineString,
+            (VectorGeometryType::Polygon, false) => GeometryType::Polygon,
+            (VectorGeometryType::MultiPolygon, false) => GeometryType::MultiPolygon,
+            (VectorGeometryType::Point, true) => GeometryType::Point3D,
+            (VectorGeometryType::MultiPoint, true) => GeometryType::MultiPoint3D,
+            (VectorGeometryType::LineString, true) => GeometryType::LineString3D,
+            (VectorGeometryType::MultiLineString, true) => GeometryType::MultiLineString3D,
+            (VectorGeometryType::Polygon, true) => GeometryType::Polygon3D,
+            (VectorGeometryType::MultiPolygon, true) => GeometryType::MultiPolygon3D,
+        }
+    }
+
+    /// Check if the geometry type is 3D
+    pub fn is_3d(&self) -> bool {
+        matches!(
+            self,
+            GeometryType::Point3D
+                | GeometryType::MultiPoint3D
+                | GeometryType::LineString3D
+                | GeometryType::MultiLineString3D
+                | GeometryType::Polygon3D
+                | GeometryType::MultiPolygon3D
+        )
+    }
+}
+
+//? Geometry -> VectorGeometry
+
+/// Build a vector geometry from a primitive geometry, zipping the m-values into the points
+fn to_vector<MV, C, B: Into<BBox3D> + Copy, VC, O: Default>(
+    geometry: &BaseGeometry<MV, C, B>,
+    is_3d: bool,
+    zip: impl Fn(&C, Option<&MV>) -> VC,
+) -> VectorBaseGeometry<VC, O> {
+    VectorBaseGeometry {
+        _type: geometry._type.into(),
+        is_3d,
+        coordinates: zip(&geometry.coordinates, geometry.m_values.as_ref()),
+        bbox: geometry.bbox.map(Into::into),
+        offset: None,
+        vec_bbox: None,
+        indices: None,
+        tessellation: None,
+    }
+}
+
+fn zip_point<P: GetXYZ, M: Clone>(point: &P, m: Option<&M>) -> VectorPoint<M> {
+    VectorPoint::new(point.x(), point.y(), point.z(), m.cloned())
+}
+
+fn zip_line<P: GetXYZ, M: Clone>(line: &[P], m: Option<&Vec<M>>) -> VectorLineString<M> {
+    line.iter().enumerate().map(|(i, p)| zip_point(p, m.and_then(|m| m.get(i)))).collect()
+}
+
+fn zip_lines<P: GetXYZ, M: Clone>(
+    lines: &[Vec<P>],
+    m: Option<&Vec<Vec<M>>>,
+) -> VectorMultiLineString<M> {
+    lines.iter().enumerate().map(|(i, line)| zip_line(line, m.and_then(|m| m.get(i)))).collect()
+}
+
+fn zip_polygons<P: GetXYZ, M: Clone>(
+    polygons: &[Vec<Vec<P>>],
+    m: Option<&Vec<Vec<Vec<M>>>>,
+) -> VectorMultiPolygon<M> {
+    polygons
+        .iter()
+        .enumerate()
+        .map(|(i, polygon)| zip_lines(polygon, m.and_then(|m| m.get(i))))
+        .collect()
+}
+
+//? VectorGeometry -> Geometry
+
+/// Build a primitive geometry from a vector geometry, pulling the m-values out of the points
+fn from_vector<MV, C, B: From<BBox3D>, VC, O>(
+    geometry: &VectorBaseGeometry<VC, O>,
+    split: impl Fn(&VC) -> (C, Option<MV>),
+) -> BaseGeometry<MV, C, B> {
+    let (coordinates, m_values) = split(&geometry.coordinates);
+    BaseGeometry {
+        _type: GeometryType::from_vector_type(geometry._type, geometry.is_3d),
+        coordinates,
+        m_values,
+        bbox: geometry.bbox.map(B::from),
+    }
+}
+
+fn split_point<P: for<'a> From<&'a VectorPoint<M>>, M: Clone>(
+    point: &VectorPoint<M>,
+) -> (P, Option<M>) {
+    (P::from(point), point.m.clone())
+}
+
+/// m-values are only stored if at least one point has one. Missing values are defaulted
+fn split_line<P: for<'a> From<&'a VectorPoint<M>>, M: Clone + Default>(
+    line: &[VectorPoint<M>],
+) -> (Vec<P>, Option<LineStringMValues<M>>) {
+    let coordinates = line.iter().map(P::from).collect();
+    let m_values = if line.iter().any(|p| p.m.is_some()) {
+        Some(line.iter().map(|p| p.m.clone().unwrap_or_default()).collect())
+    } else {
+        None
+    };
+    (coordinates, m_values)
+}
+
+fn split_lines<P: for<'a> From<&'a VectorPoint<M>>, M: Clone + Default>(
+    lines: &[VectorLineString<M>],
+) -> (Vec<Vec<P>>, Option<MultiLineStringMValues<M>>) {
+    let has_m = lines.iter().flatten().any(|p| p.m.is_some());
+    let (coordinates, m_values): (Vec<_>, Vec<_>) = lines.iter().map(|l| split_line(l)).unzip();
+    let m_values = has_m.then(|| {
+        m_values
+            .into_iter()
+            .zip(lines)
+            .map(|(m, line)| m.unwrap_or_else(|| vec![M::default(); line.len()]))
+            .collect()
+    });
+    (coordinates, m_values)
+}
+
+fn split_polygons<P: for<'a> From<&'a VectorPoint<M>>, M: Clone + Default>(
+    polygons: &[VectorPolygon<M>],
+) -> (Vec<Vec<Vec<P>>>, Option<MultiPolygonMValues<M>>) {
+    let has_m = polygons.iter().flatten().flatten().any(|p| p.m.is_some());
+    let (coordinates, m_values): (Vec<_>, Vec<_>) =
+        polygons.iter().map(|polygon| split_lines(polygon)).unzip();
+    let m_values = has_m.then(|| {
+        m_values
+            .into_iter()
+            .zip(polygons)
+            .map(|(m, polygon)| {
+                m.unwrap_or_else(|| {
+                    polygon.iter().map(|line| vec![M::default(); line.len()]).collect()
+                })
+            })
+            .collect()
+    });
+    (coordinates, m_values)
+}
+
+/// Implement `From<&Geometry>` for a vector geometry and `TryFrom<&VectorGeometry>` for the
+/// primitive geometry of the given dimension
+macro_rules! impl_geometry_conversion {
+    ($geometry:ident, $vector:ident, $is_3d:literal, $zip:ident, $split:ident) => {
+        impl<M: Clone + Default> From<&$geometry<M>> for $vector<M> {
+            fn from(geometry: &$geometry<M>) -> Self {
+                to_vector(geometry, $is_3d, |c, m| $zip(c, m))
+            }
+        }
+        impl<M: Clone + Default> TryFrom<&$vector<M>> for $geometry<M> {
+            type Error = S2JsonError;
+
+            fn try_from(geometry: &$vector<M>) -> Result<Self, Self::Error> {
+                if geometry.is_3d != $is_3d {
+                    return Err(S2JsonError::DimensionMismatch { expected_3d: $is_3d });
+                }
+                Ok(from_vector(geometry, |c| $split(c)))
+            }
+        }
+    };
+}
+// NOTE: MultiPoint and LineString (as well as MultiLineString and Polygon) share the same
+// underlying types, so they are covered by the same impls and kept apart by `_type`.
+impl_geometry_conversion!(PointGeometry, VectorPointGeometry, false, zip_point, split_point);
+impl_geometry_conversion!(Point3DGeometry, VectorPointGeometry, true, zip_point, split_point);
+impl_geometry_conversion!(
+    MultiPointGeometry,
+    VectorMultiPointGeometry,
+    false,
+    zip_line,
+    split_line
+);
+impl_geometry_conversion!(
+    MultiPoint3DGeometry,
+    VectorMultiPointGeometry,
+    true,
+    zip_line,
+    split_line
+);
+impl_geometry_conversion!(
+    MultiLineStringGeometry,
+    VectorMultiLineStringGeometry,
+    false,
+    zip_lines,
+    split_lines
+);
+impl_geometry_conversion!(
+    MultiLineString3DGeometry,
+    VectorMultiLineStringGeometry,
+    true,
+    zip_lines,
+    split_lines
+);
+impl_geometry_conversion!(
+    MultiPolygonGeometry,
+    VectorMultiPolygonGeometry,
+    false,
+    zip_polygons,
+    split_polygons
+);
+impl_geometry_conversion!(
+    MultiPolygon3DGeometry,
+    VectorMultiPolygonGeometry,
+    true,
+    zip_polygons,
+    split_polygons
+);
+
+//? Geometry <-> VectorGeometry
+
+impl<M: Clone + Default> From<&Geometry<M>> for VectorGeometry<M> {
+    fn from(geometry: &Geometry<M>) -> Self {
+        match geometry {
+            Geometry::Point(g) => VectorGeometry::Point(g.into()),
+            Geometry::MultiPoint(g) => VectorGeometry::MultiPoint(g.into()),
+            Geometry::LineString(g) => VectorGeometry::LineString(g.into()),
+            Geometry::MultiLineString(g) => VectorGeometry::MultiLineString(g.into()),
+            Geometry::Polygon(g) => VectorGeometry::Polygon(g.into()),
+            Geometry::MultiPolygon(g) => VectorGeometry::MultiPolygon(g.into()),
+            Geometry::Point3D(g) => VectorGeometry::Point(g.into()),
+            Geometry::MultiPoint3D(g) => VectorGeometry::MultiPoint(g.into()),
+            Geometry::LineString3D(g) => VectorGeometry::LineString(g.into()),
+            Geometry::MultiLineString3D(g) => VectorGeometry::MultiLineString(g.into()),
+            Geometry::Polygon3D(g) => VectorGeometry::Polygon(g.into()),
+            Geometry::MultiPolygon3D(g) => VectorGeometry::MultiPolygon(g.into()),
+        }
+    }
+}
+impl<M: Clone + Default> From<Geometry<M>> for VectorGeometry<M> {
+    fn from(geometry: Geometry<M>) -> Self {
+        (&geometry).into()
+    }
+}
+impl<M: Clone + Default> From<&VectorGeometry<M>> for Geometry<M> {
+    fn from(geometry: &VectorGeometry<M>) -> Self {
+        match geometry {
+            VectorGeometry::Point(g) if g.is_3d => {
+                Geometry::Point3D(from_vector(g, |c| split_point(c)))
+            }
+            VectorGeometry::Point(g) => Geometry::Point(from_vector(g, |c| split_point(c))),
+            VectorGeometry::MultiPoint(g) if g.is_3d => {
+                Geometry::MultiPoint3D(from_vector(g, |c| split_line(c)))
+            }
+            VectorGeometry::MultiPoint(g) => {
+                Geometry::MultiPoint(from_vector(g, |c| split_line(c)))
+            }
+            VectorGeometry::LineString(g) if g.is_3d => {
+                Geometry::LineString3D(from_vector(g, |c| split_line(c)))
+            }
+            VectorGeometry::LineString(g) => {
+                Geometry::LineString(from_vector(g, |c| split_line(c)))
+            }
+            VectorGeometry::MultiLineString(g) if g.is_3d => {
+                Geometry::MultiLineString3D(from_vector(g, |c| split_lines(c)))
+            }
+            VectorGeometry::MultiLineString(g) => {
+                Geometry::MultiLineString(from_vector(g, |c| split_lines(c)))
+            }
+            VectorGeometry::Polygon(g) if g.is_3d => {
+                Geometry::Polygon3D(from_vector(g, |c| split_lines(c)))
+            }
+            VectorGeometry::Polygon(g) => Geometry::Polygon(from_vector(g, |c| split_lines(c))),
+            VectorGeometry::MultiPolygon(g) if g.is_3d => {
+                Geometry::MultiPolygon3D(from_vector(g, |c| split_polygons(c)))
+            }
+            VectorGeometry::MultiPolygon(g) => {
+                Geometry::MultiPolygon(from_vector(g, |c| split_polygons(c)))
+            }
+        }
+    }
+}
+impl<M: Clone + Default> From<VectorGeometry<M>> for Geometry<M> {
+    fn from(geometry: VectorGeometry<M>) -> Self {
+        (&geometry).into()
+    }
+}
@@ -14,69 +14,55 @@ where
     where
         D: _serde::Deserializer<'de>,
     {
+        use _serde::de::Error;
+
         // 1. Deserialize into an intermediate Value.
         let value = serde_json::Value::deserialize(deserializer)?;
 
-        // 2. Attempt to deserialize from the `value` into each possible variant.
-        // We use serde_json::from_value(value.clone()) to create owned instances
-        // and avoid the lifetime error where a borrow outlives the owned 'value'.
-        if let Ok(geom) = PointGeometry::<M>::deserialize(value.clone()) {
-            return Ok(Geometry::Point(geom));
-        }
-
-        // Attempt to deserialize as MultiPoint then check if it's actually a LineString
-        if let Ok(multipoint) = MultiPointGeometry::<M>::deserialize(value.clone()) {
-            if multipoint._type == GeometryType::LineString
-                && let Ok(linestring) = LineStringGeometry::<M>::deserialize(value.clone())
-            {
-                return Ok(Geometry::LineString(linestring));
-            }
-            // Otherwise, or if LineString parsing failed, we fall back to the valid MultiPoint.
-            return Ok(Geometry::MultiPoint(multipoint));
-        }
-
-        // Attempt as MultiLineString, then check for Polygon
-        if let Ok(multilinestring) = MultiLineStringGeometry::<M>::deserialize(value.clone()) {
-            if multilinestring._type == GeometryType::Polygon
-                && let Ok(polygon) = PolygonGeometry::<M>::deserialize(value.clone())
-            {
-                return Ok(Geometry::Polygon(polygon));
-            }
-            return Ok(Geometry::MultiLineString(multilinestring));
-        }
-
-        if let Ok(geom) = MultiPolygonGeometry::<M>::deserialize(value.clone()) {
-            return Ok(Geometry::MultiPolygon(geom));
-        }
-        if let Ok(geom) = Point3DGeometry::<M>::deserialize(value.clone()) {
-            return Ok(Geometry::Point3D(geom));
-        }
-
-        // Attempt as MultiPoint3D, then check for LineString3D
-        if let Ok(multipoint3d) = MultiPoint3DGeometry::<M>::deserialize(value.clone()) {
-            if multipoint3d._type == GeometryType::LineString3D
-                && let Ok(linestring3d) = LineString3DGeometry::<M>::deserialize(value.clone())
-            {
-                return Ok(Geometry::LineString3D(linestring3d));
-            }
-            return Ok(Geometry::MultiPoint3D(multipoint3d));
-        }
-
-        // Attempt as MultiLineString3D, then check for Polygon3D
-        if let Ok(multilinestring3d) = MultiLineString3DGeometry::<M>::deserialize(value.clone()) {
-            if multilinestring3d._type == GeometryType::Polygon3D
-                && let Ok(polygon3d) = Polygon3DGeometry::<M>::deserialize(value.clone())
-            {
-                return Ok(Geometry::Polygon3D(polygon3d));
-            }
-            return Ok(Geometry::MultiLineString3D(multilinestring3d));
-        }
-
-        if let Ok(geom) = MultiPolygon3DGeometry::<M>::deserialize(value.clone()) {
-            return Ok(Geometry::MultiPolygon3D(geom));
-        }
+        // 2. Dispatch on the `type` tag. Unknown tags are rejected rather than guessed from the
+        // shape of the coordinates, and the coordinates must then match the tagged type.
+        let _type = value
+            .get("type")
+            .and_then(|t| t.as_str())
+            .ok_or_else(|| D::Error::missing_field("type"))?;
+        let geometry = match GeometryType::try_from(_type).map_err(D::Error::custom)? {
+            GeometryType::Point => PointGeometry::<M>::deserialize(value).map(Geometry::Point),
+            GeometryType::MultiPoint => {
+                MultiPointGeometry::<M>::deserialize(value).map(Geometry::MultiPoint)
+            }
+            GeometryType::LineString => {
+                LineStringGeometry::<M>::deserialize(value).map(Geometry::LineString)
+            }
+            GeometryType::MultiLineString => {
+                MultiLineStringGeometry::<M>::deserialize(value).map(Geometry::MultiLineString)
+            }
+            GeometryType::Polygon => {
+                PolygonGeometry::<M>::deserialize(value).map(Geometry::Polygon)
+            }
+            GeometryType::MultiPolygon => {
+                MultiPolygonGeometry::<M>::deserialize(value).map(Geometry::MultiPolygon)
+            }
+            GeometryType::Point3D => {
+                Point3DGeometry::<M>::deserialize(value).map(Geometry::Point3D)
+            }
+            GeometryType::MultiPoint3D => {
+                MultiPoint3DGeometry::<M>::deserialize(value).map(Geometry::MultiPoint3D)
+            }
+            GeometryType::LineString3D => {
+                LineString3DGeometry::<M>::deserialize(value).map(Geometry::LineString3D)
+            }
+            GeometryType::MultiLineString3D => {
+                MultiLineString3DGeometry::<M>::deserialize(value).map(Geometry::MultiLineString3D)
+            }
+            GeometryType::Polygon3D => {
+                Polygon3DGeometry::<M>::deserialize(value).map(Geometry::Polygon3D)
+            }
+            GeometryType::MultiPolygon3D => {
+                MultiPolygon3DGeometry::<M>::deserialize(value).map(Geometry::MultiPolygon3D)
+            }
+        };
 
-        Err(_serde::de::Error::custom("data did not match any variant of untagged enum Geometry"))
+        geometry.map_err(D::Error::custom)
     }
 }
 
@@ -92,39 +78,35 @@ where
     where
         D: _serde::Deserializer<'de>,
     {
-        let value = serde_json::Value::deserialize(deserializer)?;
-
-        if let Ok(geom) = VectorPointGeometry::<M>::deserialize(value.clone()) {
-            return Ok(VectorGeometry::Point(geom));
-        }
-
-        // Attempt to deserialize as MultiPoint, then check for LineString
-        if let Ok(multipoint) = VectorMultiPointGeometry::<M>::deserialize(value.clone()) {
-            if multipoint._type == VectorGeometryType::LineString
-                && let Ok(linestring) = VectorLineStringGeometry::<M>::deserialize(value.clone())
-            {
-                return Ok(VectorGeometry::LineString(linestring));
-            }
-            return Ok(VectorGeometry::MultiPoint(multipoint));
-        }
+        use _serde::de::Error;
 
-        // Attempt to deserialize as MultiLineString, then check for Polygon
-        if let Ok(multilinestring) = VectorMultiLineStringGeometry::<M>::deserialize(value.clone())
-        {
-            if multilinestring._type == VectorGeometryType::Polygon
-                && let Ok(polygon) = VectorPolygonGeometry::<M>::deserialize(value.clone())
-            {
-                return Ok(VectorGeometry::Polygon(polygon));
-            }
-            return Ok(VectorGeometry::MultiLineString(multilinestring));
-        }
+        let value = serde_json::Value::deserialize(deserializer)?;
 
-        if let Ok(geom) = VectorMultiPolygonGeometry::<M>::deserialize(value.clone()) {
-            return Ok(VectorGeometry::MultiPolygon(geom));
-        }
+        let _type = value
+            .get("type")
+            .and_then(|t| t.as_str())
+            .ok_or_else(|| D::Error::missing_field("type"))?;
+        let geometry = match VectorGeometryType::try_from(_type).map_err(D::Error::custom)? {
+            VectorGeometryType::Point => {
+                VectorPointGeometry::<M>::deserialize(value).map(VectorGeometry::Point)
+            }
+            VectorGeometryType::MultiPoint => {
+                VectorMultiPointGeometry::<M>::deserialize(value).map(VectorGeometry::MultiPoint)
+            }
+            VectorGeometryType::LineString => {
+                VectorLineStringGeometry::<M>::deserialize(value).map(VectorGeometry::LineString)
+            }
+            VectorGeometryType::MultiLineString => {
+                VectorMultiLineStringGeometry::<M>::deserialize(value)
+                    .map(VectorGeometry::MultiLineString)
+            }
+            VectorGeometryType::Polygon => {
+                VectorPolygonGeometry::<M>::deserialize(value).map(VectorGeometry::Polygon)
+            }
+            VectorGeometryType::MultiPolygon => VectorMultiPolygonGeometry::<M>::deserialize(value)
+                .map(VectorGeometry::MultiPolygon),
+        };
 
-        Err(_serde::de::Error::custom(
-            "data did not match any variant of untagged enum VectorGeometry",
-        ))
+        geometry.map_err(D::Error::custom)
     }
 }
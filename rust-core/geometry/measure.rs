@@ -0,0 +1,330 @@
+use crate::{Geometry, GetXY, VectorGeometry};
+use alloc::vec::Vec;
+use core::f64::consts::{FRAC_PI_4, PI};
+use libm::{asin, atan, atan2, cos, fabs, log, remainder, sin, sqrt, tan};
+
+/// The mean radius of the earth in meters (IUGG)
+pub const EARTH_RADIUS: f64 = 6_371_008.8;
+/// The semi-major axis of the WGS84 ellipsoid in meters
+pub const WGS84_SEMI_MAJOR_AXIS: f64 = 6_378_137.;
+/// The flattening of the WGS84 ellipsoid
+pub const WGS84_FLATTENING: f64 = 1. / 298.257_223_563;
+
+/// How the shape of the earth is modeled when measuring lon-lat geometry
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum EarthModel {
+    /// A sphere of [`EARTH_RADIUS`]. Distances follow great circles (haversine) and areas are
+    /// spherical excesses
+    #[default]
+    Sphere,
+    /// The WGS84 ellipsoid. Distances follow geodesics (Vincenty) and areas are measured on the
+    /// ellipsoid's equal-area (authalic) sphere
+    Wgs84,
+}
+
+/// The great-circle distance in meters between two lon-lat points on a sphere of
+/// [`EARTH_RADIUS`], using the haversine formula
+pub fn haversine_distance<P: GetXY, Q: GetXY>(a: &P, b: &Q) -> f64 {
+    let (lat1, lat2) = (a.y().to_radians(), b.y().to_radians());
+    let sin_lat = sin((lat2 - lat1) / 2.);
+    let sin_lon = sin((b.x() - a.x()).to_radians() / 2.);
+    let h = sin_lat * sin_lat + cos(lat1) * cos(lat2) * sin_lon * sin_lon;
+    2. * EARTH_RADIUS * asin(sqrt(h.min(1.)))
+}
+
+/// The geodesic distance in meters between two lon-lat points on the WGS84 ellipsoid, using
+/// Vincenty's inverse formula. It is accurate to within a millimeter, but returns None for
+/// nearly antipodal points where its iteration does not converge
+pub fn vincenty_distance<P: GetXY, Q: GetXY>(a: &P, b: &Q) -> Option<f64> {
+    let f = WGS84_FLATTENING;
+    let l = (b.x() - a.x()).to_radians();
+    // reduced latitudes
+    let u1 = atan((1. - f) * tan(a.y().to_radians()));
+    let u2 = atan((1. - f) * tan(b.y().to_radians()));
+    let (sin_u1, cos_u1) = (sin(u1), cos(u1));
+    let (sin_u2, cos_u2) = (sin(u2), cos(u2));
+
+    let mut lambda = l;
+    for _ in 0..200 {
+        let (sin_lambda, cos_lambda) = (sin(lambda), cos(lambda));
+        let cross = cos_u1 * sin_u2 - sin_u1 * cos_u2 * cos_lambda;
+        let sin_sigma = sqrt((cos_u2 * sin_lambda) * (cos_u2 * sin_lambda) + cross * cross);
+        if sin_sigma == 0. {
+            // coincident points
+            return Some(0.);
+        }
+        let cos_sigma = sin_u1 * sin_u2 + cos_u1 * cos_u2 * cos_lambda;
+        let sigma = atan2(sin_sigma, cos_sigma);
+        let sin_alpha = cos_u1 * cos_u2 * sin_lambda / sin_sigma;
+        let cos_sq_alpha = 1. - sin_alpha * sin_alpha;
+        // zero on an equatorial line
+        let cos_2sigma_m =
+            if cos_sq_alpha != 0. { cos_sigma - 2. * sin_u1 * sin_u2 / cos_sq_alpha } else { 0. };
+        let c = f / 16. * cos_sq_alpha * (4. + f * (4. - 3. * cos_sq_alpha));
+        let previous = lambda;
+        lambda = l
+            + (1. - c)
+                * f
+                * sin_alpha
+                * (sigma
+                    + c * sin_sigma
+                        * (cos_2sigma_m
+                            + c * cos_sigma * (-1. + 2. * cos_2sigma_m * cos_2sigma_m)));
+        if fabs(lambda) > PI {
+            return None;
+        }
+        if fabs(lambda - previous) < 1e-12 {
+            return Some(geodesic_length(cos_sq_alpha, sigma, cos_2sigma_m));
+        }
+    }
+    None
+}
+
+/// The length in meters of a WGS84 geodesic that spans `sigma` radians of the auxiliary sphere,
+/// from Vincenty's series. `cos_sq_alpha` is the squared cosine of its azimuth at the equator and
+/// `cos_2sigma_m` the cosine of twice the arc from the equator to its midpoint
+fn geodesic_length(cos_sq_alpha: f64, sigma: f64, cos_2sigma_m: f64) -> f64 {
+    let semi_minor = WGS84_SEMI_MAJOR_AXIS * (1. - WGS84_FLATTENING);
+    let (sin_sigma, cos_sigma) = (sin(sigma), cos(sigma));
+    let a_sq = WGS84_SEMI_MAJOR_AXIS * WGS84_SEMI_MAJOR_AXIS;
+    let u_sq = cos_sq_alpha * (a_sq - semi_minor * semi_minor) / (semi_minor * semi_minor);
+    let big_a = 1. + u_sq / 16_384. * (4_096. + u_sq * (-768. + u_sq * (320. - 175. * u_sq)));
+    let big_b = u_sq / 1_024. * (256. + u_sq * (-128. + u_sq * (74. - 47. * u_sq)));
+    let delta_sigma = big_b
+        * sin_sigma
+        * (cos_2sigma_m
+            + big_b / 4.
+                * (cos_sigma * (-1. + 2. * cos_2sigma_m * cos_2sigma_m)
+                    - big_b / 6.
+                        * cos_2sigma_m
+                        * (-3. + 4. * sin_sigma * sin_sigma)
+                        * (-3. + 4. * cos_2sigma_m * cos_2sigma_m)));
+    semi_minor * big_a * (sigma - delta_sigma)
+}
+
+/// The geodesic distance in meters between two nearly antipodal lon-lat points on the WGS84
+/// ellipsoid, where [`vincenty_distance`] does not converge. As in Karney's method, the azimuth at
+/// the first point is solved for instead of the longitude on the auxiliary sphere: the longitude a
+/// geodesic reaches grows with that azimuth, so it is found by bisection
+fn antipodal_distance<P: GetXY, Q: GetXY>(a: &P, b: &Q) -> f64 {
+    let f = WGS84_FLATTENING;
+    let lon12 = fabs(remainder(b.x() - a.x(), 360.)).to_radians();
+    // put the point furthest from the equator first, and south of it
+    let (mut lat1, mut lat2) = (a.y(), b.y());
+    if fabs(lat1) < fabs(lat2) {
+        (lat1, lat2) = (lat2, lat1);
+    }
+    if lat1 > 0. {
+        (lat1, lat2) = (-lat1, -lat2);
+    }
+    let reduced = |lat: f64| {
+        let beta = atan((1. - f) * tan(lat.to_radians()));
+        (sin(beta), cos(beta))
+    };
+    let ((sin_b1, cos_b1), (sin_b2, cos_b2)) = (reduced(lat1), reduced(lat2));
+    if sin_b1 == 0. && sin_b2 == 0. && lon12 <= (1. - f) * PI {
+        // shorter along the equator than over a pole
+        return WGS84_SEMI_MAJOR_AXIS * lon12;
+    }
+    // an angle in [0, π] from the sine and cosine of a difference that can't be negative
+    let arc = |d: f64| atan2(sin(d).max(0.), cos(d));
+
+    // the geodesic leaving the first point at azimuth `alpha1`, up to where it first crosses the
+    // second latitude heading north: cos²α at the equator, its arc on the auxiliary sphere, the
+    // cosine of twice the arc from the equator to its midpoint, and the longitude it spans
+    let geodesic = |alpha1: f64| {
+        let (sin_a1, cos_a1) = (sin(alpha1), cos(alpha1));
+        let sin_a0 = sin_a1 * cos_b1;
+        let cos_sq_alpha = 1. - sin_a0 * sin_a0;
+        let cos_sq_a2 = if cos_b1 < -sin_b1 {
+            (cos_b2 - cos_b1) * (cos_b1 + cos_b2)
+        } else {
+            (sin_b1 - sin_b2) * (sin_b1 + sin_b2)
+        };
+        let cos_a2 = sqrt((cos_a1 * cos_a1 * cos_b1 * cos_b1 + cos_sq_a2).max(0.)) / cos_b2;
+        let sigma1 = atan2(sin_b1, cos_a1 * cos_b1);
+        let sigma = arc(atan2(sin_b2, cos_a2 * cos_b2) - sigma1);
+        let omega =
+            arc(atan2(sin_a0 * sin_b2, cos_a2 * cos_b2) - atan2(sin_a0 * sin_b1, cos_a1 * cos_b1));
+        let cos_2sigma_m = cos(2. * sigma1 + sigma);
+        let c = f / 16. * cos_sq_alpha * (4. + f * (4. - 3. * cos_sq_alpha));
+        let lon = omega
+            - (1. - c)
+                * f
+                * sin_a0
+                * (sigma
+                    + c * sin(sigma)
+                        * (cos_2sigma_m
+                            + c * cos(sigma) * (-1. + 2. * cos_2sigma_m * cos_2sigma_m)));
+        (cos_sq_alpha, sigma, cos_2sigma_m, lon)
+    };
+    // heading north reaches no longitude and heading south over the pole reaches π. 64 halvings
+    // run past the precision of the azimuth
+    let (mut low, mut high) = (0., PI);
+    for _ in 0..64 {
+        let mid = (low + high) / 2.;
+        if geodesic(mid).3 < lon12 {
+            low = mid;
+        } else {
+            high = mid;
+        }
+    }
+    let (cos_sq_alpha, sigma, cos_2sigma_m, _) = geodesic((low + high) / 2.);
+    geodesic_length(cos_sq_alpha, sigma, cos_2sigma_m)
+}
+
+/// The distance in meters between two lon-lat points. On [`EarthModel::Wgs84`], nearly
+/// antipodal points that Vincenty's formula cannot resolve are measured by solving for the
+/// azimuth of their geodesic instead, so every distance is on the ellipsoid
+pub fn distance<P: GetXY, Q: GetXY>(a: &P, b: &Q, model: EarthModel) -> f64 {
+    match model {
+        EarthModel::Sphere => haversine_distance(a, b),
+        EarthModel::Wgs84 => vincenty_distance(a, b).unwrap_or_else(|| antipodal_distance(a, b)),
+    }
+}
+
+/// The length in meters of a lon-lat line
+pub fn line_length<P: GetXY>(line: &[P], model: EarthModel) -> f64 {
+    line.windows(2).map(|pair| distance(&pair[0], &pair[1], model)).sum()
+}
+
+/// The WGS84 authalic latitude function q(φ), scaled so that q at the pole is q_p
+fn authalic_q(sin_lat: f64) -> f64 {
+    let e_sq = WGS84_FLATTENING * (2. - WGS84_FLATTENING);
+    let e = sqrt(e_sq);
+    let e_sin = e * sin_lat;
+    (1. - e_sq)
+        * (sin_lat / (1. - e_sq * sin_lat * sin_lat) - log((1. - e_sin) / (1. + e_sin)) / (2. * e))
+}
+
+/// The radius of the sphere with the same surface area as the WGS84 ellipsoid
+fn authalic_radius() -> f64 {
+    WGS84_SEMI_MAJOR_AXIS * sqrt(authalic_q(1.) / 2.)
+}
+
+/// Map a geodetic latitude in degrees to its authalic latitude in radians
+fn authalic_latitude(lat: f64) -> f64 {
+    asin((authalic_q(sin(lat.to_radians())) / authalic_q(1.)).clamp(-1., 1.))
+}
+
+/// The area in steradians that a lon-lat ring encloses on the unit sphere, with its edges taken as
+/// great circles. The ring is assumed to enclose the smaller of the two regions it bounds, and
+/// may be open or closed
+fn ring_excess<P: GetXY>(ring: &[P], latitude: impl Fn(f64) -> f64) -> f64 {
+    if ring.len() < 3 {
+        return 0.;
+    }
+    // sum the signed excesses of the triangles each edge forms with the south pole
+    let vertex = |point: &P| {
+        let lat = latitude(point.y()) / 2. + FRAC_PI_4;
+        (point.x().to_radians(), sin(lat), cos(lat))
+    };
+    let mut sum = 0.;
+    let (mut lon0, mut sin0, mut cos0) = vertex(&ring[ring.len() - 1]);
+    for point in ring {
+        let (lon, sin1, cos1) = vertex(point);
+        let d_lon = lon - lon0;
+        let direction = if d_lon >= 0. { 1. } else { -1. };
+        let k = sin0 * sin1;
+        let u = cos0 * cos1 + k * cos(fabs(d_lon));
+        let v = k * direction * sin(fabs(d_lon));
+        sum += atan2(v, u);
+        (lon0, sin0, cos0) = (lon, sin1, cos1);
+    }
+    let excess = fabs(2. * sum);
+    if excess > 2. * PI { 4. * PI - excess } else { excess }
+}
+
+/// The area in square meters of a lon-lat ring, ignoring its winding
+pub fn ring_area<P: GetXY>(ring: &[P], model: EarthModel) -> f64 {
+    match model {
+        EarthModel::Sphere => ring_excess(ring, f64::to_radians) * EARTH_RADIUS * EARTH_RADIUS,
+        EarthModel::Wgs84 => {
+            let radius = authalic_radius();
+            ring_excess(ring, authalic_latitude) * radius * radius
+        }
+    }
+}
+
+/// The area in square meters of a lon-lat polygon: its outer ring less its holes
+pub fn polygon_area<P: GetXY>(polygon: &[Vec<P>], model: EarthModel) -> f64 {
+    let mut rings = polygon.iter().map(|ring| ring_area(ring, model));
+    let outer = rings.next().unwrap_or_default();
+    (outer - rings.sum::<f64>()).max(0.)
+}
+
+impl<M: Clone + Default> Geometry<M> {
+    /// The length in meters of a lon-lat geometry: the length of its lines, or the perimeter of
+    /// its polygons including their holes. Points have no length
+    pub fn length(&self, model: EarthModel) -> f64 {
+        let lines = |lines: &[Vec<_>]| lines.iter().map(|line| line_length(line, model)).sum();
+        match self {
+            Geometry::Point(_)
+            | Geometry::MultiPoint(_)
+            | Geometry::Point3D(_)
+            | Geometry::MultiPoint3D(_) => 0.,
+            Geometry::LineString(g) => line_length(&g.coordinates, model),
+            Geometry::LineString3D(g) => line_length(&g.coordinates, model),
+            Geometry::MultiLineString(g) => lines(&g.coordinates),
+            Geometry::Polygon(g) => lines(&g.coordinates),
+            Geometry::MultiLineString3D(g) => {
+                g.coordinates.iter().map(|line| line_length(line, model)).sum()
+            }
+            Geometry::Polygon3D(g) => {
+                g.coordinates.iter().map(|line| line_length(line, model)).sum()
+            }
+            Geometry::MultiPolygon(g) => g.coordinates.iter().map(|polygon| lines(polygon)).sum(),
+            Geometry::MultiPolygon3D(g) => {
+                g.coordinates.iter().flatten().map(|line| line_length(line, model)).sum()
+            }
+        }
+    }
+
+    /// The area in square meters of a lon-lat polygon or multi polygon, less its holes. Other
+    /// geometries have no area
+    pub fn area(&self, model: EarthModel) -> f64 {
+        match self {
+            Geometry::Polygon(g) => polygon_area(&g.coordinates, model),
+            Geometry::Polygon3D(g) => polygon_area(&g.coordinates, model),
+            Geometry::MultiPolygon(g) => {
+                g.coordinates.iter().map(|polygon| polygon_area(polygon, model)).sum()
+            }
+            Geometry::MultiPolygon3D(g) => {
+                g.coordinates.iter().map(|polygon| polygon_area(polygon, model)).sum()
+            }
+            _ => 0.,
+        }
+    }
+}
+
+impl<M: Clone + Default> VectorGeometry<M> {
+    /// The length in meters of a lon-lat geometry: the length of its lines, or the perimeter of
+    /// its polygons including their holes. Points have no length
+    pub fn length(&self, model: EarthModel) -> f64 {
+        match self {
+            VectorGeometry::Point(_) | VectorGeometry::MultiPoint(_) => 0.,
+            VectorGeometry::LineString(g) => line_length(&g.coordinates, model),
+            VectorGeometry::MultiLineString(g) => {
+                g.coordinates.iter().map(|line| line_length(line, model)).sum()
+            }
+            VectorGeometry::Polygon(g) => {
+                g.coordinates.iter().map(|ring| line_length(ring, model)).sum()
+            }
+            VectorGeometry::MultiPolygon(g) => {
+                g.coordinates.iter().flatten().map(|ring| line_length(ring, model)).sum()
+            }
+        }
+    }
+
+    /// The area in square meters of a lon-lat polygon or multi polygon, less its holes. Other
+    /// geometries have no area
+    pub fn area(&self, model: EarthModel) -> f64 {
+        match self {
+            VectorGeometry::Polygon(g) => polygon_area(&g.coordinates, model),
+            VectorGeometry::MultiPolygon(g) => {
+                g.coordinates.iter().map(|polygon| polygon_area(polygon, model)).sum()
+            }
+            _ => 0.,
+        }
+    }
+}
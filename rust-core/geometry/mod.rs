@@ -1,11 +1,21 @@
 /// BBox and BBox3D shapes and utilities
 pub mod bbox;
+/// Axis and bounding box clipping of vector geometries
+pub mod clip;
+/// Conversions between primitive and vector geometries
+pub mod conversion;
 /// Impls that we want to hide to make the code more readable
 pub mod impls;
 /// Interpolation tools
 pub mod interpolate;
+/// Geodesic length, area and distance measurements
+pub mod measure;
 /// Primitive geometry types (used by GeoJSON spec)
 pub mod primitive;
+/// Douglas-Peucker and Visvalingam simplification
+pub mod simplify;
+/// Earcut polygon tessellation
+pub mod tessellate;
 /// Vector geometry types (used by the s2json spec for both WGS84 and S2Geometry)
 pub mod vector;
 /// The VectorPoint struct is a powerful tool for 2D and 3D points
@@ -13,9 +23,13 @@ pub mod vector_point;
 
 use crate::Face;
 pub use bbox::*;
+pub use clip::*;
 pub use interpolate::*;
+pub use measure::*;
 pub use primitive::*;
 use serde::{Deserialize, Serialize};
+pub use simplify::*;
+pub use tessellate::*;
 pub use vector::*;
 pub use vector_point::*;
 
@@ -165,3 +179,45 @@ pub struct STPoint<M> {
     /// The m coordinate
     pub m: Option<M>,
 }
+impl<M> GetXY for STPoint<M> {
+    fn x(&self) -> f64 {
+        self.s
+    }
+    fn y(&self) -> f64 {
+        self.t
+    }
+}
+impl<M> GetZ for STPoint<M> {
+    fn z(&self) -> Option<f64> {
+        self.z
+    }
+}
+impl<M> GetM<M> for STPoint<M> {
+    fn m(&self) -> Option<&M> {
+        self.m.as_ref()
+    }
+}
+impl<M> SetXY for STPoint<M> {
+    fn set_xy(&mut self, x: f64, y: f64) {
+        self.s = x;
+        self.t = y;
+    }
+
+    fn set_x(&mut self, x: f64) {
+        self.s = x;
+    }
+
+    fn set_y(&mut self, y: f64) {
+        self.t = y;
+    }
+}
+impl<M> SetZ for STPoint<M> {
+    fn set_z(&mut self, z: f64) {
+        self.z = Some(z);
+    }
+}
+impl<M> SetM<M> for STPoint<M> {
+    fn set_m(&mut self, m: M) {
+        self.m = Some(m);
+    }
+}
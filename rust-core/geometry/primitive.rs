@@ -1,6 +1,6 @@
 use crate::*;
 use alloc::vec::Vec;
-use core::cmp::Ordering;
+use core::{cmp::Ordering, str::FromStr};
 use serde::{Deserialize, Serialize};
 
 /// Definition of a Point. May represent WebMercator Lon-Lat or S2Geometry S-T
@@ -343,22 +343,57 @@ pub enum GeometryType {
     /// 3D MultiPolygon
     MultiPolygon3D,
 }
-impl From<&str> for GeometryType {
-    fn from(s: &str) -> Self {
+impl TryFrom<&str> for GeometryType {
+    type Error = S2JsonError;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
         match s {
-            "Point" => GeometryType::Point,
-            "MultiPoint" => GeometryType::MultiPoint,
-            "LineString" => GeometryType::LineString,
-            "MultiLineString" => GeometryType::MultiLineString,
-            "Polygon" => GeometryType::Polygon,
-            "MultiPolygon" => GeometryType::MultiPolygon,
-            "Point3D" => GeometryType::Point3D,
-            "MultiPoint3D" => GeometryType::MultiPoint3D,
-            "LineString3D" => GeometryType::LineString3D,
-            "MultiLineString3D" => GeometryType::MultiLineString3D,
-            "Polygon3D" => GeometryType::Polygon3D,
-            "MultiPolygon3D" => GeometryType::MultiPolygon3D,
-            _ => panic!("Invalid geometry type: {}", s),
+            "Point" => Ok(GeometryType::Point),
+            "MultiPoint" => Ok(GeometryType::MultiPoint),
+            "LineString" => Ok(GeometryType::LineString),
+            "MultiLineString" => Ok(GeometryType::MultiLineString),
+            "Polygon" => Ok(GeometryType::Polygon),
+            "MultiPolygon" => Ok(GeometryType::MultiPolygon),
+            "Point3D" => Ok(GeometryType::Point3D),
+            "MultiPoint3D" => Ok(GeometryType::MultiPoint3D),
+            "LineString3D" => Ok(GeometryType::LineString3D),
+            "MultiLineString3D" => Ok(GeometryType::MultiLineString3D),
+            "Polygon3D" => Ok(GeometryType::Polygon3D),
+            "MultiPolygon3D" => Ok(GeometryType::MultiPolygon3D),
+            _ => Err(S2JsonError::invalid_type("GeometryType", s)),
+        }
+    }
+}
+impl FromStr for GeometryType {
+    type Err = S2JsonError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.try_into()
+    }
+}
+impl From<GeometryType> for u8 {
+    fn from(geo_type: GeometryType) -> Self {
+        geo_type as u8
+    }
+}
+impl TryFrom<u8> for GeometryType {
+    type Error = S2JsonError;
+
+    fn try_from(geo_type: u8) -> Result<Self, Self::Error> {
+        match geo_type {
+            0 => Ok(GeometryType::Point),
+            1 => Ok(GeometryType::MultiPoint),
+            2 => Ok(GeometryType::LineString),
+            3 => Ok(GeometryType::MultiLineString),
+            4 => Ok(GeometryType::Polygon),
+            5 => Ok(GeometryType::MultiPolygon),
+            6 => Ok(GeometryType::Point3D),
+            7 => Ok(GeometryType::MultiPoint3D),
+            8 => Ok(GeometryType::LineString3D),
+            9 => Ok(GeometryType::MultiLineString3D),
+            10 => Ok(GeometryType::Polygon3D),
+            11 => Ok(GeometryType::MultiPolygon3D),
+            _ => Err(S2JsonError::invalid_type("GeometryType", &format!("{geo_type}"))),
         }
     }
 }
@@ -0,0 +1,213 @@
+use crate::{VectorGeometry, VectorPoint};
+use alloc::{collections::BinaryHeap, vec, vec::Vec};
+use core::cmp::Ordering;
+use libm::{fabs, sqrt};
+
+// Simplification runs in two steps. Building stores the importance of every point in its `t`:
+// the largest tolerance at which the point survives. Filtering then keeps the points whose `t`
+// meets a tolerance, so one build serves every zoom. Importance never exceeds that of the point
+// that made it relevant, so filtering at a tolerance gives the same points as running the
+// algorithm at that tolerance. The endpoints of every line and ring have infinite importance.
+
+/// How the importance of each point is measured when building a simplification
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum SimplifyMethod {
+    /// Ramer-Douglas-Peucker. A point's importance is its distance from the segment between the
+    /// points kept around it, so tolerances are distances
+    #[default]
+    DouglasPeucker,
+    /// Visvalingam-Whyatt. A point's importance is the effective area of the triangle it forms
+    /// with its neighbours when it is removed, so tolerances are areas
+    Visvalingam,
+}
+
+/// The distance from p to the segment a-b
+fn segment_distance<M: Clone>(p: &VectorPoint<M>, a: &VectorPoint<M>, b: &VectorPoint<M>) -> f64 {
+    let (mut x, mut y) = (a.x, a.y);
+    let (dx, dy) = (b.x - x, b.y - y);
+    if dx != 0. || dy != 0. {
+        let t = ((p.x - x) * dx + (p.y - y) * dy) / (dx * dx + dy * dy);
+        if t > 1. {
+            (x, y) = (b.x, b.y);
+        } else if t > 0. {
+            x += dx * t;
+            y += dy * t;
+        }
+    }
+    let (dx, dy) = (p.x - x, p.y - y);
+    sqrt(dx * dx + dy * dy)
+}
+
+/// The area of the triangle a, b, c
+fn triangle_area<M: Clone>(a: &VectorPoint<M>, b: &VectorPoint<M>, c: &VectorPoint<M>) -> f64 {
+    fabs((b.x - a.x) * (c.y - a.y) - (c.x - a.x) * (b.y - a.y)) / 2.
+}
+
+fn build_douglas_peucker<M: Clone>(line: &mut [VectorPoint<M>]) {
+    let mut stack = vec![(0, line.len() - 1, f64::INFINITY)];
+    while let Some((first, last, parent)) = stack.pop() {
+        let mut max_distance = -1.;
+        let mut index = first;
+        for i in first + 1..last {
+            let distance = segment_distance(&line[i], &line[first], &line[last]);
+            if distance > max_distance {
+                max_distance = distance;
+                index = i;
+            }
+        }
+        if index == first {
+            continue;
+        }
+        let t = max_distance.min(parent);
+        line[index].t = Some(t);
+        stack.push((first, index, t));
+        stack.push((index, last, t));
+    }
+}
+
+/// A point queued for removal by its area, smallest first
+#[derive(Debug, PartialEq)]
+struct Candidate {
+    area: f64,
+    index: usize,
+}
+impl Eq for Candidate {}
+impl Ord for Candidate {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.area.total_cmp(&self.area).then(other.index.cmp(&self.index))
+    }
+}
+impl PartialOrd for Candidate {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+fn build_visvalingam<M: Clone>(line: &mut [VectorPoint<M>]) {
+    let len = line.len();
+    let mut prev: Vec<usize> = (0..len).map(|i| i.saturating_sub(1)).collect();
+    let mut next: Vec<usize> = (1..=len).collect();
+    let mut areas = vec![f64::INFINITY; len];
+    let mut queue = BinaryHeap::with_capacity(len);
+    for index in 1..len - 1 {
+        areas[index] = triangle_area(&line[index - 1], &line[index], &line[index + 1]);
+        queue.push(Candidate { area: areas[index], index });
+    }
+
+    let mut max_area: f64 = 0.;
+    while let Some(Candidate { area, index }) = queue.pop() {
+        // skip entries made stale by a neighbour's removal
+        if area != areas[index] || line[index].t.is_some() {
+            continue;
+        }
+        max_area = max_area.max(area);
+        line[index].t = Some(max_area);
+        let (p, n) = (prev[index], next[index]);
+        next[p] = n;
+        prev[n] = p;
+        for neighbour in [p, n] {
+            if neighbour != 0 && neighbour != len - 1 {
+                let area =
+                    triangle_area(&line[prev[neighbour]], &line[neighbour], &line[next[neighbour]]);
+                areas[neighbour] = area;
+                queue.push(Candidate { area, index: neighbour });
+            }
+        }
+    }
+}
+
+/// Store the importance of every point of a line or ring in its `t`
+fn build_line<M: Clone>(line: &mut [VectorPoint<M>], method: SimplifyMethod) {
+    for point in line.iter_mut() {
+        point.t = None;
+    }
+    let Some(last) = line.len().checked_sub(1) else { return };
+    line[0].t = Some(f64::INFINITY);
+    line[last].t = Some(f64::INFINITY);
+    if line.len() < 3 {
+        return;
+    }
+    match method {
+        SimplifyMethod::DouglasPeucker => build_douglas_peucker(line),
+        SimplifyMethod::Visvalingam => build_visvalingam(line),
+    }
+}
+
+fn keep<M: Clone>(point: &VectorPoint<M>, tolerance: f64) -> bool {
+    point.t.is_none_or(|t| t >= tolerance)
+}
+
+fn filter_line<M: Clone>(line: &mut Vec<VectorPoint<M>>, tolerance: f64) {
+    line.retain(|point| keep(point, tolerance));
+}
+
+/// Filter a ring, keeping its most important points if too few meet the tolerance so that it
+/// still has three distinct points, and closing it
+fn filter_ring<M: Clone>(ring: &mut Vec<VectorPoint<M>>, tolerance: f64) {
+    let (Some(first), Some(last)) = (ring.first(), ring.last()) else { return };
+    let closed = ring.len() > 1 && first.x == last.x && first.y == last.y;
+    let body = if closed { ring.len() - 1 } else { ring.len() };
+    if body < 3 {
+        return;
+    }
+
+    let mut kept: Vec<usize> = (0..body).filter(|&i| keep(&ring[i], tolerance)).collect();
+    if kept.len() < 3 {
+        let mut dropped: Vec<usize> = (0..body).filter(|i| !kept.contains(i)).collect();
+        dropped.sort_by(|&a, &b| {
+            let importance = |i: usize| ring[i].t.unwrap_or(f64::INFINITY);
+            importance(b).total_cmp(&importance(a))
+        });
+        kept.extend(dropped.into_iter().take(3 - kept.len()));
+        kept.sort_unstable();
+    }
+
+    let closing = if closed { ring[body].clone() } else { ring[0].clone() };
+    let mut simplified: Vec<VectorPoint<M>> = kept.into_iter().map(|i| ring[i].clone()).collect();
+    simplified.push(closing);
+    *ring = simplified;
+}
+
+impl<M: Clone + Default> VectorGeometry<M> {
+    /// Measure the importance of every point of the geometry with the given method, storing it in
+    /// each point's `t` so that [`VectorGeometry::simplify`] can filter the geometry at any
+    /// tolerance. Points, and the endpoints of every line and ring, are always kept
+    pub fn build_simplification(&mut self, method: SimplifyMethod) {
+        match self {
+            VectorGeometry::Point(g) => g.coordinates.t = Some(f64::INFINITY),
+            VectorGeometry::MultiPoint(g) => {
+                g.coordinates.iter_mut().for_each(|point| point.t = Some(f64::INFINITY))
+            }
+            VectorGeometry::LineString(g) => build_line(&mut g.coordinates, method),
+            VectorGeometry::MultiLineString(g) => {
+                g.coordinates.iter_mut().for_each(|line| build_line(line, method))
+            }
+            VectorGeometry::Polygon(g) => {
+                g.coordinates.iter_mut().for_each(|ring| build_line(ring, method))
+            }
+            VectorGeometry::MultiPolygon(g) => {
+                g.coordinates.iter_mut().flatten().for_each(|ring| build_line(ring, method))
+            }
+        }
+    }
+
+    /// Drop the points whose importance, as measured by [`VectorGeometry::build_simplification`],
+    /// is below the tolerance: a distance for Douglas-Peucker or an area for Visvalingam. Points
+    /// without a measured importance are kept. Polygon rings stay valid: they keep at least three
+    /// distinct points, their most important ones if needed, and are closed
+    pub fn simplify(&mut self, tolerance: f64) {
+        match self {
+            VectorGeometry::Point(_) | VectorGeometry::MultiPoint(_) => {}
+            VectorGeometry::LineString(g) => filter_line(&mut g.coordinates, tolerance),
+            VectorGeometry::MultiLineString(g) => {
+                g.coordinates.iter_mut().for_each(|line| filter_line(line, tolerance))
+            }
+            VectorGeometry::Polygon(g) => {
+                g.coordinates.iter_mut().for_each(|ring| filter_ring(ring, tolerance))
+            }
+            VectorGeometry::MultiPolygon(g) => {
+                g.coordinates.iter_mut().flatten().for_each(|ring| filter_ring(ring, tolerance))
+            }
+        }
+    }
+}
@@ -0,0 +1,868 @@
+use crate::{VectorGeometry, VectorPolygon};
+use alloc::vec::Vec;
+use libm::fabs;
+
+// A port of the mapbox earcut algorithm (ISC license). Rings are circular doubly linked lists of
+// nodes stored in an arena. Ears are clipped until nothing is left, and on failure the remaining
+// ring is cleaned of degenerate points, cured of local self-intersections and finally split in two
+// along a valid diagonal. Holes are bridged into the outer ring before clipping. Larger polygons
+// also link their nodes in z-order to speed up the search for points inside an ear.
+
+/// Polygons with more values than this many points' worth (times the dimension) are indexed on a
+/// z-order curve
+const Z_ORDER_THRESHOLD: usize = 80;
+
+/// A vertex of a ring being clipped
+#[derive(Debug, Clone)]
+struct Node {
+    /// The vertex index in the input data
+    i: usize,
+    x: f64,
+    y: f64,
+    prev: usize,
+    next: usize,
+    /// z-order curve value
+    z: u32,
+    prev_z: Option<usize>,
+    next_z: Option<usize>,
+    /// true if the node is a lone point hole that must not be filtered out
+    steiner: bool,
+}
+
+/// The state of a triangulation
+struct Earcut<'a> {
+    data: &'a [f64],
+    dim: usize,
+    nodes: Vec<Node>,
+    triangles: Vec<usize>,
+    min_x: f64,
+    min_y: f64,
+    /// The scale to the z-order grid, or 0 if the polygon is not indexed
+    inv_size: f64,
+}
+impl<'a> Earcut<'a> {
+    fn new(data: &'a [f64], dim: usize) -> Self {
+        Earcut {
+            data,
+            dim,
+            nodes: Vec::with_capacity(data.len() / dim + 2),
+            triangles: Vec::new(),
+            min_x: 0.,
+            min_y: 0.,
+            inv_size: 0.,
+        }
+    }
+
+    fn x(&self, n: usize) -> f64 {
+        self.nodes[n].x
+    }
+
+    fn y(&self, n: usize) -> f64 {
+        self.nodes[n].y
+    }
+
+    fn next(&self, n: usize) -> usize {
+        self.nodes[n].next
+    }
+
+    fn prev(&self, n: usize) -> usize {
+        self.nodes[n].prev
+    }
+
+    /// Twice the signed area of a triangle of nodes, negative if it turns counter-clockwise
+    fn area(&self, p: usize, q: usize, r: usize) -> f64 {
+        let (p, q, r) = (&self.nodes[p], &self.nodes[q], &self.nodes[r]);
+        (q.y - p.y) * (r.x - q.x) - (q.x - p.x) * (r.y - q.y)
+    }
+
+    fn equals(&self, a: usize, b: usize) -> bool {
+        self.x(a) == self.x(b) && self.y(a) == self.y(b)
+    }
+
+    fn create_node(&mut self, i: usize, x: f64, y: f64) -> usize {
+        let n = self.nodes.len();
+        self.nodes.push(Node {
+            i,
+            x,
+            y,
+            prev: n,
+            next: n,
+            z: 0,
+            prev_z: None,
+            next_z: None,
+            steiner: false,
+        });
+        n
+    }
+
+    /// Create a node after `last`, or a ring of one node
+    fn insert_node(&mut self, i: usize, last: Option<usize>) -> usize {
+        let offset = i * self.dim;
+        let p = self.create_node(i, self.data[offset], self.data[offset + 1]);
+        if let Some(last) = last {
+            let next = self.next(last);
+            self.nodes[p].next = next;
+            self.nodes[p].prev = last;
+            self.nodes[next].prev = p;
+            self.nodes[last].next = p;
+        }
+        p
+    }
+
+    fn remove_node(&mut self, p: usize) {
+        let Node { prev, next, prev_z, next_z, .. } = self.nodes[p];
+        self.nodes[next].prev = prev;
+        self.nodes[prev].next = next;
+        if let Some(prev_z) = prev_z {
+            self.nodes[prev_z].next_z = next_z;
+        }
+        if let Some(next_z) = next_z {
+            self.nodes[next_z].prev_z = prev_z;
+        }
+    }
+
+    /// Twice the signed area of the ring between two vertex offsets, positive if clockwise
+    fn signed_area(&self, start: usize, end: usize) -> f64 {
+        let data = self.data;
+        let mut sum = 0.;
+        let mut j = end - self.dim;
+        let mut i = start;
+        while i < end {
+            sum += (data[j] - data[i]) * (data[i + 1] + data[j + 1]);
+            j = i;
+            i += self.dim;
+        }
+        sum
+    }
+
+    /// Link the ring between two data offsets in the given winding order, dropping a closing
+    /// point that repeats the first
+    fn linked_list(&mut self, start: usize, end: usize, clockwise: bool) -> Option<usize> {
+        if start >= end {
+            return None;
+        }
+        let mut last = None;
+        let dim = self.dim;
+        if clockwise == (self.signed_area(start, end) > 0.) {
+            for offset in (start..end).step_by(dim) {
+                last = Some(self.insert_node(offset / dim, last));
+            }
+        } else {
+            for offset in (start..end).step_by(dim).rev() {
+                last = Some(self.insert_node(offset / dim, last));
+            }
+        }
+        let last = last?;
+        let next = self.next(last);
+        if self.equals(last, next) {
+            self.remove_node(last);
+            return Some(next);
+        }
+        Some(last)
+    }
+
+    /// Remove duplicate and collinear points between `start` and `end`
+    fn filter_points(&mut self, start: usize, end: Option<usize>) -> usize {
+        let mut end = end.unwrap_or(start);
+        let mut p = start;
+        loop {
+            let mut again = false;
+            let (prev, next) = (self.prev(p), self.next(p));
+            if !self.nodes[p].steiner && (self.equals(p, next) || self.area(prev, p, next) == 0.) {
+                self.remove_node(p);
+                p = prev;
+                end = prev;
+                if p == self.next(p) {
+                    break;
+                }
+                again = true;
+            } else {
+                p = next;
+            }
+            if !again && p == end {
+                break;
+            }
+        }
+        end
+    }
+
+    /// Clip ears off the ring, trying harder on each pass if it gets stuck
+    fn earcut_linked(&mut self, ear: usize, pass: u8) {
+        if pass == 0 && self.inv_size != 0. {
+            self.index_curve(ear);
+        }
+        let mut ear = ear;
+        let mut stop = ear;
+        while self.prev(ear) != self.next(ear) {
+            let (prev, next) = (self.prev(ear), self.next(ear));
+            let is_ear =
+                if self.inv_size != 0. { self.is_ear_hashed(ear) } else { self.is_ear(ear) };
+            if is_ear {
+                self.triangles.extend([self.nodes[prev].i, self.nodes[ear].i, self.nodes[next].i]);
+                self.remove_node(ear);
+                // skipping the next vertex leads to less sliver triangles
+                ear = self.next(next);
+                stop = ear;
+                continue;
+            }
+            ear = next;
+            if ear == stop {
+                match pass {
+                    // try filtering points and slicing again
+                    0 => {
+                        let ear = self.filter_points(ear, None);
+                        self.earcut_linked(ear, 1);
+                    }
+                    // cure small local self-intersections
+                    1 => {
+                        let ear = self.filter_points(ear, None);
+                        let ear = self.cure_local_intersections(ear);
+                        self.earcut_linked(ear, 2);
+                    }
+                    // as a last resort, split the polygon in two
+                    _ => self.split_earcut(ear),
+                }
+                break;
+            }
+        }
+    }
+
+    /// The bounds of the triangle of an ear and its neighbours
+    fn ear_bounds(&self, a: usize, b: usize, c: usize) -> (f64, f64, f64, f64) {
+        let (ax, bx, cx) = (self.x(a), self.x(b), self.x(c));
+        let (ay, by, cy) = (self.y(a), self.y(b), self.y(c));
+        (ax.min(bx).min(cx), ay.min(by).min(cy), ax.max(bx).max(cx), ay.max(by).max(cy))
+    }
+
+    /// True if `p` lies within the bounds and triangle of the ear `a`, `b`, `c` and is not reflex
+    fn blocks_ear(
+        &self,
+        p: usize,
+        (a, b, c): (usize, usize, usize),
+        bounds: (f64, f64, f64, f64),
+    ) -> bool {
+        let (x0, y0, x1, y1) = bounds;
+        let (px, py) = (self.x(p), self.y(p));
+        px >= x0
+            && px <= x1
+            && py >= y0
+            && py <= y1
+            && point_in_triangle_except_first(
+                (self.x(a), self.y(a)),
+                (self.x(b), self.y(b)),
+                (self.x(c), self.y(c)),
+                (px, py),
+            )
+            && self.area(self.prev(p), p, self.next(p)) >= 0.
+    }
+
+    /// Check whether a convex vertex is an ear: no other vertex may lie inside its triangle
+    fn is_ear(&self, ear: usize) -> bool {
+        let (a, b, c) = (self.prev(ear), ear, self.next(ear));
+        if self.area(a, b, c) >= 0. {
+            return false;
+        }
+        let bounds = self.ear_bounds(a, b, c);
+        let mut p = self.next(c);
+        while p != a {
+            if self.blocks_ear(p, (a, b, c), bounds) {
+                return false;
+            }
+            p = self.next(p);
+        }
+        true
+    }
+
+    /// [`Self::is_ear`], only visiting the vertices whose z-order falls within the ear's bounds
+    fn is_ear_hashed(&self, ear: usize) -> bool {
+        let (a, b, c) = (self.prev(ear), ear, self.next(ear));
+        if self.area(a, b, c) >= 0. {
+            return false;
+        }
+        let bounds = self.ear_bounds(a, b, c);
+        let (x0, y0, x1, y1) = bounds;
+        let min_z = self.z_order(x0, y0);
+        let max_z = self.z_order(x1, y1);
+        let blocks = |n: usize| n != a && n != c && self.blocks_ear(n, (a, b, c), bounds);
+
+        // look for points inside the triangle in both directions
+        let mut p = self.nodes[ear].prev_z.filter(|&p| self.nodes[p].z >= min_z);
+        let mut n = self.nodes[ear].next_z.filter(|&n| self.nodes[n].z <= max_z);
+        while let (Some(pp), Some(nn)) = (p, n) {
+            if blocks(pp) || blocks(nn) {
+                return false;
+            }
+            p = self.nodes[pp].prev_z.filter(|&p| self.nodes[p].z >= min_z);
+            n = self.nodes[nn].next_z.filter(|&n| self.nodes[n].z <= max_z);
+        }
+        while let Some(pp) = p {
+            if blocks(pp) {
+                return false;
+            }
+            p = self.nodes[pp].prev_z.filter(|&p| self.nodes[p].z >= min_z);
+        }
+        while let Some(nn) = n {
+            if blocks(nn) {
+                return false;
+            }
+            n = self.nodes[nn].next_z.filter(|&n| self.nodes[n].z <= max_z);
+        }
+        true
+    }
+
+    /// Go through the ring and fix small self-intersections by clipping them off
+    fn cure_local_intersections(&mut self, start: usize) -> usize {
+        let mut start = start;
+        let mut p = start;
+        loop {
+            let a = self.prev(p);
+            let p_next = self.next(p);
+            let b = self.next(p_next);
+            if !self.equals(a, b)
+                && self.intersects(a, p, p_next, b)
+                && self.locally_inside(a, b)
+                && self.locally_inside(b, a)
+            {
+                self.triangles.extend([self.nodes[a].i, self.nodes[p].i, self.nodes[b].i]);
+                self.remove_node(p);
+                self.remove_node(p_next);
+                p = b;
+                start = b;
+            }
+            p = self.next(p);
+            if p == start {
+                break;
+            }
+        }
+        self.filter_points(p, None)
+    }
+
+    /// Split the ring along a valid diagonal and triangulate both halves
+    fn split_earcut(&mut self, start: usize) {
+        let mut a = start;
+        loop {
+            let mut b = self.next(self.next(a));
+            while b != self.prev(a) {
+                if self.nodes[a].i != self.nodes[b].i && self.is_valid_diagonal(a, b) {
+                    let c = self.split_polygon(a, b);
+                    let a = self.filter_points(a, Some(self.next(a)));
+                    let c = self.filter_points(c, Some(self.next(c)));
+                    self.earcut_linked(a, 0);
+                    self.earcut_linked(c, 0);
+                    return;
+                }
+                b = self.next(b);
+            }
+            a = self.next(a);
+            if a == start {
+                break;
+            }
+        }
+    }
+
+    /// Link every hole into the outer ring, leftmost hole first
+    fn eliminate_holes(&mut self, hole_indices: &[usize], outer_node: usize) -> usize {
+        let dim = self.dim;
+        let mut queue = Vec::with_capacity(hole_indices.len());
+        for (h, &hole) in hole_indices.iter().enumerate() {
+            let len = self.data.len();
+            let start = hole.saturating_mul(dim).min(len);
+            let end = hole_indices.get(h + 1).map_or(len, |next| next.saturating_mul(dim).min(len));
+            let Some(list) = self.linked_list(start, end, false) else { continue };
+            if list == self.next(list) {
+                self.nodes[list].steiner = true;
+            }
+            queue.push(self.leftmost(list));
+        }
+        queue.sort_by(|&a, &b| self.compare_x_y_slope(a, b));
+
+        let mut outer_node = outer_node;
+        for hole in queue {
+            outer_node = self.eliminate_hole(hole, outer_node);
+        }
+        outer_node
+    }
+
+    fn compare_x_y_slope(&self, a: usize, b: usize) -> core::cmp::Ordering {
+        let mut result = self.x(a) - self.x(b);
+        if result == 0. {
+            result = self.y(a) - self.y(b);
+            if result == 0. {
+                let (an, bn) = (self.next(a), self.next(b));
+                let a_slope = (self.y(an) - self.y(a)) / (self.x(an) - self.x(a));
+                let b_slope = (self.y(bn) - self.y(b)) / (self.x(bn) - self.x(b));
+                result = a_slope - b_slope;
+            }
+        }
+        result.partial_cmp(&0.).unwrap_or(core::cmp::Ordering::Equal)
+    }
+
+    /// Bridge a hole into the outer ring, splitting the ring there
+    fn eliminate_hole(&mut self, hole: usize, outer_node: usize) -> usize {
+        let Some(bridge) = self.find_hole_bridge(hole, outer_node) else { return outer_node };
+        let bridge_reverse = self.split_polygon(bridge, hole);
+        // filter collinear points around the cuts
+        self.filter_points(bridge_reverse, Some(self.next(bridge_reverse)));
+        self.filter_points(bridge, Some(self.next(bridge)))
+    }
+
+    /// Find a vertex of the outer ring that the leftmost vertex of a hole can connect to
+    fn find_hole_bridge(&self, hole: usize, outer_node: usize) -> Option<usize> {
+        let (hx, hy) = (self.x(hole), self.y(hole));
+        let mut p = outer_node;
+        let mut qx = f64::NEG_INFINITY;
+        let mut m = None;
+
+        // find a segment intersected by a ray from the hole's leftmost point to the left; the
+        // segment's endpoint with the lesser x will be a potential connection point. Unless it
+        // intersects at a vertex, in which case the vertex is the connection
+        if self.equals(hole, p) {
+            return Some(p);
+        }
+        loop {
+            let next = self.next(p);
+            if self.equals(hole, next) {
+                return Some(next);
+            } else if hy <= self.y(p) && hy >= self.y(next) && self.y(next) != self.y(p) {
+                let x = self.x(p)
+                    + (hy - self.y(p)) * (self.x(next) - self.x(p)) / (self.y(next) - self.y(p));
+                if x <= hx && x > qx {
+                    qx = x;
+                    let candidate = if self.x(p) < self.x(next) { p } else { next };
+                    if x == hx {
+                        // the hole touches the outer segment; pick the leftmost endpoint
+                        return Some(candidate);
+                    }
+                    m = Some(candidate);
+                }
+            }
+            p = next;
+            if p == outer_node {
+                break;
+            }
+        }
+        let mut m = m?;
+
+        // look for points inside the triangle of the hole point, the segment intersection and
+        // the endpoint. If there are none, the endpoint is the connection. Otherwise pick the
+        // point with the least angle with the ray as the connection
+        let stop = m;
+        let (mx, my) = (self.x(m), self.y(m));
+        let mut tan_min = f64::INFINITY;
+        p = m;
+        loop {
+            let (px, py) = (self.x(p), self.y(p));
+            if hx >= px
+                && px >= mx
+                && hx != px
+                && point_in_triangle(
+                    (if hy < my { hx } else { qx }, hy),
+                    (mx, my),
+                    (if hy < my { qx } else { hx }, hy),
+                    (px, py),
+                )
+            {
+                let tan = fabs(hy - py) / (hx - px);
+                if self.locally_inside(p, hole)
+                    && (tan < tan_min
+                        || (tan == tan_min
+                            && (px > self.x(m)
+                                || (px == self.x(m) && self.sector_contains_sector(m, p)))))
+                {
+                    m = p;
+                    tan_min = tan;
+                }
+            }
+            p = self.next(p);
+            if p == stop {
+                break;
+            }
+        }
+        Some(m)
+    }
+
+    /// Whether the sector in vertex m contains the sector in vertex p in the same coordinates
+    fn sector_contains_sector(&self, m: usize, p: usize) -> bool {
+        self.area(self.prev(m), m, self.prev(p)) < 0.
+            && self.area(self.next(p), m, self.next(m)) < 0.
+    }
+
+    /// Link the ring's nodes in z-order
+    fn index_curve(&mut self, start: usize) {
+        let mut p = start;
+        loop {
+            if self.nodes[p].z == 0 {
+                self.nodes[p].z = self.z_order(self.x(p), self.y(p));
+            }
+            self.nodes[p].prev_z = Some(self.prev(p));
+            self.nodes[p].next_z = Some(self.next(p));
+            p = self.next(p);
+            if p == start {
+                break;
+            }
+        }
+        if let Some(prev_z) = self.nodes[p].prev_z {
+            self.nodes[prev_z].next_z = None;
+        }
+        self.nodes[p].prev_z = None;
+        self.sort_linked(p);
+    }
+
+    /// Simon Tatham's linked list merge sort, on the z links
+    fn sort_linked(&mut self, list: usize) {
+        let mut list = Some(list);
+        let mut in_size = 1;
+        loop {
+            let mut p = list;
+            list = None;
+            let mut tail: Option<usize> = None;
+            let mut num_merges = 0;
+            while let Some(start) = p {
+                num_merges += 1;
+                let mut q = Some(start);
+                let mut p_size = 0;
+                for _ in 0..in_size {
+                    p_size += 1;
+                    q = q.and_then(|q| self.nodes[q].next_z);
+                    if q.is_none() {
+                        break;
+                    }
+                }
+                let mut q_size = in_size;
+                while p_size > 0 || (q_size > 0 && q.is_some()) {
+                    let e = match (p, q) {
+                        (Some(pp), Some(qq))
+                            if p_size != 0
+                                && q_size != 0
+                                && self.nodes[pp].z > self.nodes[qq].z =>
+                        {
+                            q = self.nodes[qq].next_z;
+                            q_size -= 1;
+                            qq
+                        }
+                        (Some(pp), _) if p_size != 0 => {
+                            p = self.nodes[pp].next_z;
+                            p_size -= 1;
+                            pp
+                        }
+                        (_, Some(qq)) => {
+                            q = self.nodes[qq].next_z;
+                            q_size -= 1;
+                            qq
+                        }
+                        _ => break,
+                    };
+                    match tail {
+                        Some(tail) => self.nodes[tail].next_z = Some(e),
+                        None => list = Some(e),
+                    }
+                    self.nodes[e].prev_z = tail;
+                    tail = Some(e);
+                }
+                p = q;
+            }
+            if let Some(tail) = tail {
+                self.nodes[tail].next_z = None;
+            }
+            in_size *= 2;
+            if num_merges <= 1 {
+                break;
+            }
+        }
+    }
+
+    /// The z-order of a point, from coordinates scaled to 15 bits
+    fn z_order(&self, x: f64, y: f64) -> u32 {
+        let spread = |v: f64| {
+            let mut v = v as u32;
+            v = (v | (v << 8)) & 0x00FF00FF;
+            v = (v | (v << 4)) & 0x0F0F0F0F;
+            v = (v | (v << 2)) & 0x33333333;
+            (v | (v << 1)) & 0x55555555
+        };
+        spread((x - self.min_x) * self.inv_size) | (spread((y - self.min_y) * self.inv_size) << 1)
+    }
+
+    /// The leftmost node of a ring
+    fn leftmost(&self, start: usize) -> usize {
+        let mut p = start;
+        let mut leftmost = start;
+        loop {
+            if self.x(p) < self.x(leftmost)
+                || (self.x(p) == self.x(leftmost) && self.y(p) < self.y(leftmost))
+            {
+                leftmost = p;
+            }
+            p = self.next(p);
+            if p == start {
+                break;
+            }
+        }
+        leftmost
+    }
+
+    /// Whether a diagonal between two vertices lies inside the ring without crossing it
+    fn is_valid_diagonal(&self, a: usize, b: usize) -> bool {
+        let (a_prev, a_next) = (self.prev(a), self.next(a));
+        let (b_prev, b_next) = (self.prev(b), self.next(b));
+        self.nodes[a_next].i != self.nodes[b].i
+            && self.nodes[a_prev].i != self.nodes[b].i
+            && !self.intersects_polygon(a, b)
+            && ((self.locally_inside(a, b)
+                && self.locally_inside(b, a)
+                && self.middle_inside(a, b)
+                // no zero-area triangles on either side
+                && (self.area(a_prev, a, b_prev) != 0. || self.area(a, b_prev, b) != 0.))
+                // or a zero-length diagonal between two convex vertices
+                || (self.equals(a, b)
+                    && self.area(a_prev, a, a_next) > 0.
+                    && self.area(b_prev, b, b_next) > 0.))
+    }
+
+    /// Whether segments p1-q1 and p2-q2 intersect
+    fn intersects(&self, p1: usize, q1: usize, p2: usize, q2: usize) -> bool {
+        let o1 = sign(self.area(p1, q1, p2));
+        let o2 = sign(self.area(p1, q1, q2));
+        let o3 = sign(self.area(p2, q2, p1));
+        let o4 = sign(self.area(p2, q2, q1));
+        (o1 != o2 && o3 != o4)
+            || (o1 == 0 && self.on_segment(p1, p2, q1))
+            || (o2 == 0 && self.on_segment(p1, q2, q1))
+            || (o3 == 0 && self.on_segment(p2, p1, q2))
+            || (o4 == 0 && self.on_segment(p2, q1, q2))
+    }
+
+    /// For collinear points p, q and r, whether q lies on segment pr
+    fn on_segment(&self, p: usize, q: usize, r: usize) -> bool {
+        let (px, py, qx, qy, rx, ry) =
+            (self.x(p), self.y(p), self.x(q), self.y(q), self.x(r), self.y(r));
+        qx <= px.max(rx) && qx >= px.min(rx) && qy <= py.max(ry) && qy >= py.min(ry)
+    }
+
+    /// Whether the diagonal a-b crosses any edge of the ring
+    fn intersects_polygon(&self, a: usize, b: usize) -> bool {
+        let (ai, bi) = (self.nodes[a].i, self.nodes[b].i);
+        let mut p = a;
+        loop {
+            let next = self.next(p);
+            let (pi, ni) = (self.nodes[p].i, self.nodes[next].i);
+            if pi != ai && ni != ai && pi != bi && ni != bi && self.intersects(p, next, a, b) {
+                return true;
+            }
+            p = next;
+            if p == a {
+                return false;
+            }
+        }
+    }
+
+    /// Whether the diagonal a-b leaves a into the ring's interior
+    fn locally_inside(&self, a: usize, b: usize) -> bool {
+        let (prev, next) = (self.prev(a), self.next(a));
+        if self.area(prev, a, next) < 0. {
+            self.area(a, b, next) >= 0. && self.area(a, prev, b) >= 0.
+        } else {
+            self.area(a, b, prev) < 0. || self.area(a, next, b) < 0.
+        }
+    }
+
+    /// Whether the middle of the diagonal a-b is inside the ring
+    fn middle_inside(&self, a: usize, b: usize) -> bool {
+        let px = (self.x(a) + self.x(b)) / 2.;
+        let py = (self.y(a) + self.y(b)) / 2.;
+        let mut inside = false;
+        let mut p = a;
+        loop {
+            let next = self.next(p);
+            let (x, y, nx, ny) = (self.x(p), self.y(p), self.x(next), self.y(next));
+            if (y > py) != (ny > py) && ny != y && px < (nx - x) * (py - y) / (ny - y) + x {
+                inside = !inside;
+            }
+            p = next;
+            if p == a {
+                return inside;
+            }
+        }
+    }
+
+    /// Link a to b with a bridge, splitting the ring in two. If a and b are in the same ring the
+    /// result is two rings, and if they are in different rings the result is one. Returns the
+    /// copy of b
+    fn split_polygon(&mut self, a: usize, b: usize) -> usize {
+        let a2 = self.create_node(self.nodes[a].i, self.x(a), self.y(a));
+        let b2 = self.create_node(self.nodes[b].i, self.x(b), self.y(b));
+        let an = self.next(a);
+        let bp = self.prev(b);
+
+        self.nodes[a].next = b;
+        self.nodes[b].prev = a;
+        self.nodes[a2].next = an;
+        self.nodes[an].prev = a2;
+        self.nodes[b2].next = a2;
+        self.nodes[a2].prev = b2;
+        self.nodes[bp].next = b2;
+        self.nodes[b2].prev = bp;
+        b2
+    }
+}
+
+fn sign(value: f64) -> i8 {
+    if value > 0. {
+        1
+    } else if value < 0. {
+        -1
+    } else {
+        0
+    }
+}
+
+/// Whether p lies inside the triangle a, b, c
+fn point_in_triangle(a: (f64, f64), b: (f64, f64), c: (f64, f64), p: (f64, f64)) -> bool {
+    let ((ax, ay), (bx, by), (cx, cy), (px, py)) = (a, b, c, p);
+    (cx - px) * (ay - py) >= (ax - px) * (cy - py)
+        && (ax - px) * (by - py) >= (bx - px) * (ay - py)
+        && (bx - px) * (cy - py) >= (cx - px) * (by - py)
+}
+
+/// Whether p lies inside the triangle a, b, c and is not a
+fn point_in_triangle_except_first(
+    a: (f64, f64),
+    b: (f64, f64),
+    c: (f64, f64),
+    p: (f64, f64),
+) -> bool {
+    a != p && point_in_triangle(a, b, c, p)
+}
+
+/// Triangulate a polygon with the earcut algorithm.
+///
+/// `data` is the flat list of vertex coordinates of the outer ring followed by each hole, `dim`
+/// values per vertex of which the first two are used. `hole_indices` are the vertex indices at
+/// which each hole starts. Returns the vertex indices of the triangles, three per triangle.
+/// Rings may be closed or open and wind either way. Repeated points, collinear runs and rings
+/// that enclose no area are tolerated, and contribute no triangles of their own. A trailing
+/// partial vertex is ignored, as are holes that start past the end of `data`
+pub fn earcut(data: &[f64], hole_indices: &[usize], dim: usize) -> Vec<usize> {
+    let dim = dim.max(2);
+    let data = &data[..data.len() - data.len() % dim];
+    let mut earcut = Earcut::new(data, dim);
+    let outer_len =
+        hole_indices.first().map_or(data.len(), |hole| hole.saturating_mul(dim)).min(data.len());
+    let Some(mut outer_node) = earcut.linked_list(0, outer_len, true) else {
+        return Vec::new();
+    };
+    if earcut.next(outer_node) == earcut.prev(outer_node) {
+        return Vec::new();
+    }
+    if !hole_indices.is_empty() {
+        outer_node = earcut.eliminate_holes(hole_indices, outer_node);
+    }
+
+    // if the shape is not too simple, index it on a z-order curve using its bounds
+    if data.len() > Z_ORDER_THRESHOLD * dim {
+        let (mut min_x, mut min_y) = (f64::INFINITY, f64::INFINITY);
+        let (mut max_x, mut max_y) = (f64::NEG_INFINITY, f64::NEG_INFINITY);
+        for vertex in data[..outer_len].chunks_exact(dim) {
+            min_x = min_x.min(vertex[0]);
+            min_y = min_y.min(vertex[1]);
+            max_x = max_x.max(vertex[0]);
+            max_y = max_y.max(vertex[1]);
+        }
+        let size = (max_x - min_x).max(max_y - min_y);
+        earcut.min_x = min_x;
+        earcut.min_y = min_y;
+        earcut.inv_size = if size != 0. { 32767. / size } else { 0. };
+    }
+
+    earcut.earcut_linked(outer_node, 0);
+    earcut.triangles
+}
+
+/// Triangulate a planar polygon in 3D with the earcut algorithm.
+///
+/// `data` holds x, y and z for each vertex of the outer ring followed by each hole, and
+/// `hole_indices` are the vertex indices at which each hole starts. The rings are projected onto
+/// the axis plane that their normal is closest to, so vertical walls triangulate as well as flat
+/// roofs. Returns the vertex indices of the triangles, three per triangle
+pub fn earcut_3d(data: &[f64], hole_indices: &[usize]) -> Vec<usize> {
+    let outer_len =
+        hole_indices.first().map_or(data.len(), |hole| hole.saturating_mul(3)).min(data.len());
+    let (outer, _) = data[..outer_len].as_chunks::<3>();
+
+    // Newell's method gives the normal of the outer ring, however it winds
+    let (mut nx, mut ny, mut nz) = (0., 0., 0.);
+    for (i, a) in outer.iter().enumerate() {
+        let b = outer[(i + 1) % outer.len()];
+        nx += (a[1] - b[1]) * (a[2] + b[2]);
+        ny += (a[2] - b[2]) * (a[0] + b[0]);
+        nz += (a[0] - b[0]) * (a[1] + b[1]);
+    }
+    let (ax, ay, az) = (fabs(nx), fabs(ny), fabs(nz));
+    // drop the axis the normal points along
+    let (u, v) = if az >= ax && az >= ay {
+        (0, 1)
+    } else if ax >= ay {
+        (1, 2)
+    } else {
+        (2, 0)
+    };
+    let projected: Vec<f64> =
+        data.as_chunks::<3>().0.iter().flat_map(|vertex| [vertex[u], vertex[v]]).collect();
+    earcut(&projected, hole_indices, 2)
+}
+
+/// Flatten the rings of a polygon onto `vertices`, noting where each hole starts
+fn flatten_polygon<M: Clone>(
+    polygon: &VectorPolygon<M>,
+    is_3d: bool,
+    vertices: &mut Vec<f64>,
+) -> Vec<usize> {
+    let dim = if is_3d { 3 } else { 2 };
+    let first = vertices.len() / dim;
+    let mut hole_indices = Vec::new();
+    for (r, ring) in polygon.iter().enumerate() {
+        if r > 0 {
+            hole_indices.push(vertices.len() / dim - first);
+        }
+        for point in ring {
+            vertices.extend([point.x, point.y]);
+            if is_3d {
+                vertices.push(point.z.unwrap_or_default());
+            }
+        }
+    }
+    hole_indices
+}
+
+impl<M: Clone + Default> VectorGeometry<M> {
+    /// Triangulate a polygon or multi polygon, storing its vertices as the tessellation and the
+    /// triangles, three vertex indices each, as its indices (see [`VectorGeometry::set_tess`] and
+    /// [`VectorGeometry::set_indices`]).
+    ///
+    /// The tessellation lists the x and y of every point of every ring in order, along with z if
+    /// the geometry is 3D, so vertex `i` is the `i`th point of the flattened rings. 3D polygons
+    /// are triangulated in the plane of their rings with [`earcut_3d`]. Holes are cut out, and
+    /// degenerate rings, such as those with fewer than three distinct points or no area, add no
+    /// triangles. Other geometries are left untouched
+    pub fn tessellate(&mut self) {
+        let (polygons, is_3d) = match self {
+            VectorGeometry::Polygon(g) => (core::slice::from_ref(&g.coordinates), g.is_3d),
+            VectorGeometry::MultiPolygon(g) => (g.coordinates.as_slice(), g.is_3d),
+            _ => return,
+        };
+        let dim = if is_3d { 3 } else { 2 };
+        let mut vertices = Vec::new();
+        let mut indices = Vec::new();
+        for polygon in polygons {
+            let start = vertices.len();
+            let offset = start / dim;
+            let hole_indices = flatten_polygon(polygon, is_3d, &mut vertices);
+            let data = &vertices[start..];
+            let triangles =
+                if is_3d { earcut_3d(data, &hole_indices) } else { earcut(data, &hole_indices, 2) };
+            indices.extend(triangles.into_iter().map(|i| (offset + i) as u32));
+        }
+        self.set_tess(vertices);
+        self.set_indices(indices);
+    }
+}
@@ -1,5 +1,6 @@
 use crate::*;
-use alloc::vec::Vec;
+use alloc::{format, vec::Vec};
+use core::str::FromStr;
 use serde::{Deserialize, Serialize};
 
 /// Enum to represent specific vector geometry types as strings
@@ -19,16 +20,45 @@ pub enum VectorGeometryType {
     /// MultiPolygon
     MultiPolygon,
 }
-impl From<&str> for VectorGeometryType {
-    fn from(s: &str) -> Self {
+impl TryFrom<&str> for VectorGeometryType {
+    type Error = S2JsonError;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
         match s {
-            "Point" => VectorGeometryType::Point,
-            "MultiPoint" => VectorGeometryType::MultiPoint,
-            "LineString" => VectorGeometryType::LineString,
-            "MultiLineString" => VectorGeometryType::MultiLineString,
-            "Polygon" => VectorGeometryType::Polygon,
-            "MultiPolygon" => VectorGeometryType::MultiPolygon,
-            _ => panic!("Invalid vector geometry type: {}", s),
+            "Point" => Ok(VectorGeometryType::Point),
+            "MultiPoint" => Ok(VectorGeometryType::MultiPoint),
+            "LineString" => Ok(VectorGeometryType::LineString),
+            "MultiLineString" => Ok(VectorGeometryType::MultiLineString),
+            "Polygon" => Ok(VectorGeometryType::Polygon),
+            "MultiPolygon" => Ok(VectorGeometryType::MultiPolygon),
+            _ => Err(S2JsonError::invalid_type("VectorGeometryType", s)),
+        }
+    }
+}
+impl FromStr for VectorGeometryType {
+    type Err = S2JsonError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.try_into()
+    }
+}
+impl From<VectorGeometryType> for u8 {
+    fn from(geo_type: VectorGeometryType) -> Self {
+        geo_type as u8
+    }
+}
+impl TryFrom<u8> for VectorGeometryType {
+    type Error = S2JsonError;
+
+    fn try_from(geo_type: u8) -> Result<Self, Self::Error> {
+        match geo_type {
+            0 => Ok(VectorGeometryType::Point),
+            1 => Ok(VectorGeometryType::MultiPoint),
+            2 => Ok(VectorGeometryType::LineString),
+            3 => Ok(VectorGeometryType::MultiLineString),
+            4 => Ok(VectorGeometryType::Polygon),
+            5 => Ok(VectorGeometryType::MultiPolygon),
+            _ => Err(S2JsonError::invalid_type("VectorGeometryType", &format!("{geo_type}"))),
         }
     }
 }
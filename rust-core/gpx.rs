@@ -0,0 +1,109 @@
+use crate::{
+    xml::{XmlElement, parse_xml, xml_value},
+    *,
+};
+use alloc::{format, vec::Vec};
+
+/// Child elements of a waypoint, route or track that become feature properties
+const GPX_PROPERTY_TAGS: [&str; 5] = ["name", "desc", "cmt", "src", "type"];
+
+fn gpx_properties(element: &XmlElement) -> Properties {
+    let mut properties = Properties::new();
+    for tag in GPX_PROPERTY_TAGS {
+        if let Some(text) = element.child_text(tag) {
+            properties.insert(tag.into(), text.into());
+        }
+    }
+    properties
+}
+
+/// Store the leaves of an element, however deeply nested, by their local names
+fn insert_leaves(element: &XmlElement, m: &mut MValue) {
+    for child in &element.children {
+        if child.is_leaf() {
+            m.insert(child.name.clone(), xml_value(&child.text));
+        } else {
+            insert_leaves(child, m);
+        }
+    }
+}
+
+/// Read a waypoint, route point or track point. Elevation is z, and every other value the point
+/// carries, including its extensions, is an m-value. `skip` lists children read elsewhere
+fn gpx_point<D: MValueCompatible>(
+    point: &XmlElement,
+    skip: &[&str],
+) -> Result<VectorPoint<D>, S2JsonError> {
+    let ordinate = |name: &str| point.attribute(name).and_then(|value| value.trim().parse().ok());
+    let (Some(lon), Some(lat)) = (ordinate("lon"), ordinate("lat")) else {
+        return Err(S2JsonError::invalid_xml(
+            point.offset,
+            format!("<{}> needs numeric lat and lon attributes", point.name),
+        ));
+    };
+    let z = point.child_text("ele").and_then(|ele| ele.parse().ok());
+    let mut m = MValue::new();
+    for child in &point.children {
+        if child.name == "extensions" {
+            insert_leaves(child, &mut m);
+        } else if child.is_leaf() && child.name != "ele" && !skip.contains(&child.name.as_str()) {
+            m.insert(child.name.clone(), xml_value(&child.text));
+        }
+    }
+    Ok(VectorPoint::new(lon, lat, z, (!m.is_empty()).then(|| m.into())))
+}
+
+fn gpx_line<D: MValueCompatible>(
+    element: &XmlElement,
+    point_name: &str,
+) -> Result<VectorLineString<D>, S2JsonError> {
+    element.children_named(point_name).map(|point| gpx_point(point, &[])).collect()
+}
+
+/// Read a GPX document into a [`VectorFeature`] per waypoint, route and track.
+///
+/// Waypoints are points, routes are lines and tracks are lines, or multi lines if they have
+/// several segments. Elevation is read as z. The time and other values of each point, such as
+/// heart rate or cadence from its extensions, become the point's m-values, keyed by their local
+/// element names. Names, descriptions, comments, sources and types become properties. Routes and
+/// tracks without points are skipped
+pub fn read_gpx<D: MValueCompatible>(
+    gpx: &str,
+) -> Result<Vec<VectorFeature<(), Properties, D>>, S2JsonError> {
+    let root = parse_xml(gpx)?;
+    if root.name != "gpx" {
+        return Err(S2JsonError::invalid_xml(
+            root.offset,
+            format!("expected a <gpx> root element, found <{}>", root.name),
+        ));
+    }
+
+    let mut features = Vec::new();
+    for element in &root.children {
+        let geometry = match element.name.as_str() {
+            "wpt" => VectorGeometry::new_point(gpx_point(element, &GPX_PROPERTY_TAGS)?, None),
+            "rte" => {
+                let line = gpx_line(element, "rtept")?;
+                if line.is_empty() {
+                    continue;
+                }
+                VectorGeometry::new_linestring(line, None)
+            }
+            "trk" => {
+                let mut lines = element
+                    .children_named("trkseg")
+                    .map(|segment| gpx_line(segment, "trkpt"))
+                    .collect::<Result<Vec<_>, _>>()?;
+                lines.retain(|line| !line.is_empty());
+                match lines.len() {
+                    0 => continue,
+                    1 => VectorGeometry::new_linestring(lines.remove(0), None),
+                    _ => VectorGeometry::new_multilinestring(lines, None),
+                }
+            }
+            _ => continue,
+        };
+        features.push(VectorFeature::new_wm(None, gpx_properties(element), geometry, None));
+    }
+    Ok(features)
+}
@@ -1,6 +1,14 @@
 /// JSON Specific impl
 pub mod json;
+/// Protobuf encoding of vector features and geometries
+pub mod proto;
+/// JSON Schema generation and reading for shapes
+pub mod schema;
 /// Shape Specific impl
 pub mod shape;
+/// Shape driven binary encoding of values
+pub mod shaped;
+/// Validation of values against shapes
+pub mod validate;
 /// Value Specific impl
 pub mod value;
@@ -0,0 +1,915 @@
+use crate::*;
+use alloc::{format, vec, vec::Vec};
+use libm::{pow, round};
+use pbf::{ProtoRead, ProtoWrite, Protobuf};
+use serde::{Serialize, de::DeserializeOwned};
+
+/// Default number of decimal places kept for vector coordinates. Sub-centimeter in both lon-lat
+/// degrees and S2 st space
+pub const DEFAULT_PRECISION: u8 = 9;
+/// The highest supported precision. Higher precisions are clamped to it
+pub const MAX_PRECISION: u8 = 15;
+
+/// Multiplier that quantizes a coordinate to `precision` decimal places
+pub(crate) fn precision_scale(precision: u8) -> f64 {
+    pow(10., precision.min(MAX_PRECISION) as f64)
+}
+
+/// Quantize the values and delta encode each against the value `stride` places before it, so
+/// that every dimension of a coordinate list is its own delta chain
+pub(crate) fn delta_encode(values: &[f64], stride: usize, precision: u8) -> Vec<i64> {
+    let scale = precision_scale(precision);
+    let quantized: Vec<i64> = values.iter().map(|v| round(v * scale) as i64).collect();
+    quantized
+        .iter()
+        .enumerate()
+        .map(|(i, q)| if i < stride { *q } else { q.wrapping_sub(quantized[i - stride]) })
+        .collect()
+}
+
+/// Inverse of [`delta_encode`]
+pub(crate) fn delta_decode(deltas: &[i64], stride: usize, precision: u8) -> Vec<f64> {
+    let scale = precision_scale(precision);
+    let mut quantized: Vec<i64> = Vec::with_capacity(deltas.len());
+    for (i, delta) in deltas.iter().enumerate() {
+        let prev = if i < stride { 0 } else { quantized[i - stride] };
+        quantized.push(prev.wrapping_add(*delta));
+    }
+    quantized.into_iter().map(|q| q as f64 / scale).collect()
+}
+
+/// Write a list of doubles as packed fixed64 values
+pub(crate) fn write_f64s(pb: &mut Protobuf, tag: u64, values: &[f64]) {
+    let mut packed = Protobuf::new();
+    for value in values {
+        packed.write_fixed(*value);
+    }
+    pb.write_bytes_field(tag, &packed.take());
+}
+
+/// Read a list of doubles written by [`write_f64s`]
+pub(crate) fn read_f64s(pb: &mut Protobuf) -> Vec<f64> {
+    let end = pb.decode_varint() as usize + pb.get_pos();
+    let mut values = vec![];
+    while pb.get_pos() < end {
+        values.push(pb.read_fixed());
+    }
+    values
+}
+
+// Validation
+//
+// The pbf reader trusts its input. It panics on truncated varints, unknown wire types and lengths
+// past the end of the buffer, and reads every known tag with the method its decoder expects. So
+// untrusted bytes are walked against a schema of the expected fields before they are read
+
+/// Messages nested deeper than this are rejected instead of recursed into
+const MAX_MESSAGE_DEPTH: usize = 64;
+
+/// The expected encoding of a known field
+#[derive(Clone, Copy)]
+pub(crate) enum Wire {
+    /// A varint
+    Varint,
+    /// A varint checked by the decoder, like a type code
+    Code(fn(u64) -> Result<(), S2JsonError>),
+    /// A fixed 64 bit value
+    Fixed64,
+    /// Length delimited bytes, like a string
+    Bytes,
+    /// Length delimited varints
+    Packed,
+    /// Length delimited fixed 64 bit values
+    Fixed64s,
+    /// A nested message
+    Message(Schema),
+}
+/// The expected encoding of each tag of a message. Unknown tags (`None`) are skipped as long as
+/// they are well formed
+pub(crate) type Schema = fn(u64) -> Option<Wire>;
+
+/// Check that the top level fields of `data` are well formed and match the schema, so reading
+/// them can't panic
+pub(crate) fn check_message(data: &[u8], schema: Schema) -> Result<(), S2JsonError> {
+    check_fields(data, 0, data.len(), schema, 0)
+}
+
+/// Read a varint that must end before `end`
+fn check_varint(data: &[u8], pos: &mut usize, end: usize) -> Result<u64, S2JsonError> {
+    let mut value = 0;
+    for shift in (0..64).step_by(7) {
+        if *pos >= end {
+            return Err(S2JsonError::UnexpectedEof);
+        }
+        let byte = data[*pos];
+        *pos += 1;
+        value |= ((byte & 0x7f) as u64) << shift;
+        if byte < 0x80 {
+            return Ok(value);
+        }
+    }
+    Err(S2JsonError::invalid_protobuf(*pos, "varint is longer than 10 bytes"))
+}
+
+fn check_fields(
+    data: &[u8],
+    mut pos: usize,
+    end: usize,
+    schema: Schema,
+    depth: usize,
+) -> Result<(), S2JsonError> {
+    if depth > MAX_MESSAGE_DEPTH {
+        return Err(S2JsonError::invalid_protobuf(pos, "messages are nested too deeply"));
+    }
+    while pos < end {
+        let start = pos;
+        let key = check_varint(data, &mut pos, end)?;
+        let (tag, wire) = (key >> 3, key & 0x7);
+        let expected = schema(tag);
+        let mismatch =
+            || S2JsonError::invalid_protobuf(start, format!("field {tag} has wire type {wire}"));
+        match wire {
+            0 => {
+                let value = check_varint(data, &mut pos, end)?;
+                match expected {
+                    None | Some(Wire::Varint) => {}
+                    Some(Wire::Code(check)) => check(value)?,
+                    _ => return Err(mismatch()),
+                }
+            }
+            1 | 5 => {
+                if !matches!((wire, expected), (_, None) | (1, Some(Wire::Fixed64))) {
+                    return Err(mismatch());
+                }
+                let size = if wire == 1 { 8 } else { 4 };
+                if end - pos < size {
+                    return Err(S2JsonError::UnexpectedEof);
+                }
+                pos += size;
+            }
+            2 => {
+                let len = check_varint(data, &mut pos, end)?;
+                if len > (end - pos) as u64 {
+                    return Err(S2JsonError::UnexpectedEof);
+                }
+                let inner_end = pos + len as usize;
+                match expected {
+                    None | Some(Wire::Bytes) => {}
+                    Some(Wire::Packed) => {
+                        let mut inner = pos;
+                        while inner < inner_end {
+                            check_varint(data, &mut inner, inner_end)?;
+                        }
+                    }
+                    Some(Wire::Fixed64s) if len % 8 != 0 => {
+                        return Err(S2JsonError::invalid_protobuf(
+                            pos,
+                            format!("field {tag} is not a list of fixed64 values"),
+                        ));
+                    }
+                    Some(Wire::Fixed64s) => {}
+                    Some(Wire::Message(schema)) => {
+                        check_fields(data, pos, inner_end, schema, depth + 1)?
+                    }
+                    _ => return Err(mismatch()),
+                }
+                pos = inner_end;
+            }
+            7 if expected.is_none() => {}
+            7 => return Err(mismatch()),
+            _ => {
+                return Err(S2JsonError::invalid_protobuf(
+                    start,
+                    format!("unknown wire type {wire}"),
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Check a type code with the `TryFrom<u8>` of the type it decodes to
+pub(crate) fn check_code<T: TryFrom<u8, Error = S2JsonError>>(
+    code: u64,
+    name: &'static str,
+) -> Result<(), S2JsonError> {
+    let code =
+        u8::try_from(code).map_err(|_| S2JsonError::invalid_type(name, &format!("{code}")))?;
+    T::try_from(code).map(|_| ())
+}
+
+/// Schema of a [`Value`]
+pub(crate) fn value_schema(tag: u64) -> Option<Wire> {
+    fn entry(tag: u64) -> Option<Wire> {
+        match tag {
+            1 => Some(Wire::Bytes),
+            2 => Some(Wire::Message(value_type_schema)),
+            _ => None,
+        }
+    }
+    (tag == 1).then_some(Wire::Message(entry))
+}
+pub(crate) fn value_type_schema(tag: u64) -> Option<Wire> {
+    match tag {
+        1 => Some(Wire::Message(primitive_value_schema)),
+        2 => Some(Wire::Message(value_primitive_type_schema)),
+        3 => Some(Wire::Message(value_schema)),
+        4 => Some(Wire::Varint),
+        _ => None,
+    }
+}
+fn value_primitive_type_schema(tag: u64) -> Option<Wire> {
+    fn entry(tag: u64) -> Option<Wire> {
+        match tag {
+            1 => Some(Wire::Bytes),
+            2 => Some(Wire::Message(primitive_value_schema)),
+            _ => None,
+        }
+    }
+    fn value_primitive(tag: u64) -> Option<Wire> {
+        (tag == 1).then_some(Wire::Message(entry))
+    }
+    match tag {
+        1 => Some(Wire::Message(primitive_value_schema)),
+        2 => Some(Wire::Message(value_primitive)),
+        _ => None,
+    }
+}
+fn primitive_value_schema(tag: u64) -> Option<Wire> {
+    match tag {
+        1 => Some(Wire::Bytes),
+        2..=7 => Some(Wire::Varint),
+        _ => None,
+    }
+}
+
+/// Schema of a [`BBox3D`]
+fn bbox_schema(tag: u64) -> Option<Wire> {
+    (1..=6).contains(&tag).then_some(Wire::Fixed64)
+}
+
+/// Schema of a [`VectorGeometry`]
+fn vector_geometry_schema(tag: u64) -> Option<Wire> {
+    fn coordinates(tag: u64) -> Option<Wire> {
+        fn m_value(tag: u64) -> Option<Wire> {
+            (tag == 1).then_some(Wire::Message(value_schema))
+        }
+        match tag {
+            1 | 2 => Some(Wire::Varint),
+            3 | 4 => Some(Wire::Packed),
+            5 => Some(Wire::Message(m_value)),
+            _ => None,
+        }
+    }
+    fn offsets(tag: u64) -> Option<Wire> {
+        match tag {
+            1 => Some(Wire::Varint),
+            2 => Some(Wire::Fixed64s),
+            _ => None,
+        }
+    }
+    fn tessellation(tag: u64) -> Option<Wire> {
+        match tag {
+            1 => Some(Wire::Varint),
+            2 => Some(Wire::Code(|stride| match stride {
+                0 => Err(S2JsonError::invalid_type("tessellation stride", "0")),
+                _ => Ok(()),
+            })),
+            3 => Some(Wire::Packed),
+            _ => None,
+        }
+    }
+    match tag {
+        1 => Some(Wire::Code(|code| check_code::<VectorGeometryType>(code, "VectorGeometryType"))),
+        2 => Some(Wire::Varint),
+        3 => Some(Wire::Message(coordinates)),
+        4 => Some(Wire::Message(offsets)),
+        5 | 6 => Some(Wire::Message(bbox_schema)),
+        7 => Some(Wire::Packed),
+        8 => Some(Wire::Message(tessellation)),
+        _ => None,
+    }
+}
+
+/// Schema of a [`VectorFeature`]
+fn vector_feature_schema(tag: u64) -> Option<Wire> {
+    match tag {
+        1 => Some(Wire::Code(|code| check_code::<VectorFeatureType>(code, "VectorFeatureType"))),
+        2 | 3 => Some(Wire::Varint),
+        4 => Some(Wire::Message(value_schema)),
+        5 => Some(Wire::Message(vector_geometry_schema)),
+        6 => Some(Wire::Bytes),
+        _ => None,
+    }
+}
+
+/// Geometry coordinates that flatten into a point list and a list of ring/line lengths. Values
+/// nested like the coordinates, such as the m-values of a [`BaseGeometry`], flatten the same way
+pub(crate) trait ProtoCoordinates: Sized + Default {
+    /// The point type of the coordinates
+    type Point;
+    /// The coordinates with a `T` in place of every point
+    type Nested<T>;
+    /// Push every point in order, and the length of every list before its points
+    fn flatten<'a>(&'a self, points: &mut Vec<&'a Self::Point>, lengths: &mut Vec<u64>);
+    /// Rebuild the coordinates from the flattened points and lengths. `None` once both run out,
+    /// so lengths from corrupt data can't outgrow the points
+    fn unflatten(
+        points: &mut impl Iterator<Item = Self::Point>,
+        lengths: &mut impl Iterator<Item = u64>,
+    ) -> Option<Self>;
+    /// [`ProtoCoordinates::flatten`] for nested values
+    fn flatten_nested<'a, T>(
+        nested: &'a Self::Nested<T>,
+        values: &mut Vec<&'a T>,
+        lengths: &mut Vec<u64>,
+    );
+    /// [`ProtoCoordinates::unflatten`] for nested values
+    fn unflatten_nested<T>(
+        values: &mut impl Iterator<Item = T>,
+        lengths: &mut impl Iterator<Item = u64>,
+    ) -> Option<Self::Nested<T>>;
+}
+macro_rules! impl_proto_point {
+    ([$($generics:tt)*] $point:ty) => {
+        impl<$($generics)*> ProtoCoordinates for $point {
+            type Point = Self;
+            type Nested<T> = T;
+
+            fn flatten<'a>(&'a self, points: &mut Vec<&'a Self>, _lengths: &mut Vec<u64>) {
+                points.push(self);
+            }
+
+            fn unflatten(
+                points: &mut impl Iterator<Item = Self>,
+                _lengths: &mut impl Iterator<Item = u64>,
+            ) -> Option<Self> {
+                points.next()
+            }
+
+            fn flatten_nested<'a, T>(nested: &'a T, values: &mut Vec<&'a T>, _lengths: &mut Vec<u64>) {
+                values.push(nested);
+            }
+
+            fn unflatten_nested<T>(
+                values: &mut impl Iterator<Item = T>,
+                _lengths: &mut impl Iterator<Item = u64>,
+            ) -> Option<T> {
+                values.next()
+            }
+        }
+    };
+}
+impl_proto_point!([M: Clone + Default] VectorPoint<M>);
+impl_proto_point!([] Point);
+impl_proto_point!([] Point3D);
+impl<E: ProtoCoordinates> ProtoCoordinates for Vec<E> {
+    type Point = E::Point;
+    type Nested<T> = Vec<E::Nested<T>>;
+
+    fn flatten<'a>(&'a self, points: &mut Vec<&'a E::Point>, lengths: &mut Vec<u64>) {
+        lengths.push(self.len() as u64);
+        for elem in self {
+            elem.flatten(points, lengths);
+        }
+    }
+
+    fn unflatten(
+        points: &mut impl Iterator<Item = E::Point>,
+        lengths: &mut impl Iterator<Item = u64>,
+    ) -> Option<Self> {
+        let len = lengths.next()?;
+        Some((0..len).map_while(|_| E::unflatten(points, lengths)).collect())
+    }
+
+    fn flatten_nested<'a, T>(
+        nested: &'a Vec<E::Nested<T>>,
+        values: &mut Vec<&'a T>,
+        lengths: &mut Vec<u64>,
+    ) {
+        lengths.push(nested.len() as u64);
+        for elem in nested {
+            E::flatten_nested(elem, values, lengths);
+        }
+    }
+
+    fn unflatten_nested<T>(
+        values: &mut impl Iterator<Item = T>,
+        lengths: &mut impl Iterator<Item = u64>,
+    ) -> Option<Vec<E::Nested<T>>> {
+        let len = lengths.next()?;
+        Some((0..len).map_while(|_| E::unflatten_nested(values, lengths)).collect())
+    }
+}
+
+/// Geometry offsets that convert to and from [`VectorOffsets`]
+trait ProtoOffset: Sized {
+    fn to_offsets(&self, geo_type: VectorGeometryType) -> VectorOffsets;
+    fn from_offsets(offsets: VectorOffsets) -> Option<Self>;
+}
+impl ProtoOffset for VectorOffsets {
+    fn to_offsets(&self, _geo_type: VectorGeometryType) -> VectorOffsets {
+        self.clone()
+    }
+
+    fn from_offsets(offsets: VectorOffsets) -> Option<Self> {
+        Some(offsets)
+    }
+}
+impl ProtoOffset for VectorLineOffset {
+    fn to_offsets(&self, _geo_type: VectorGeometryType) -> VectorOffsets {
+        VectorOffsets::LineOffset(*self)
+    }
+
+    fn from_offsets(offsets: VectorOffsets) -> Option<Self> {
+        match offsets {
+            VectorOffsets::LineOffset(offset) => Some(offset),
+            _ => None,
+        }
+    }
+}
+impl ProtoOffset for VectorMultiLineOffset {
+    fn to_offsets(&self, geo_type: VectorGeometryType) -> VectorOffsets {
+        match geo_type {
+            VectorGeometryType::Polygon => VectorOffsets::PolygonOffset(self.clone()),
+            _ => VectorOffsets::MultiLineOffset(self.clone()),
+        }
+    }
+
+    fn from_offsets(offsets: VectorOffsets) -> Option<Self> {
+        match offsets {
+            VectorOffsets::MultiLineOffset(offset) | VectorOffsets::PolygonOffset(offset) => {
+                Some(offset)
+            }
+            _ => None,
+        }
+    }
+}
+impl ProtoOffset for VectorMultiPolygonOffset {
+    fn to_offsets(&self, _geo_type: VectorGeometryType) -> VectorOffsets {
+        VectorOffsets::MultiPolygonOffset(self.clone())
+    }
+
+    fn from_offsets(offsets: VectorOffsets) -> Option<Self> {
+        match offsets {
+            VectorOffsets::MultiPolygonOffset(offset) => Some(offset),
+            _ => None,
+        }
+    }
+}
+
+/// An m-value slot of a point being written. Empty when the point has no m-value
+struct MValueRef<'a, M>(Option<&'a M>);
+impl<M: ProtoWrite> ProtoWrite for MValueRef<'_, M> {
+    fn write(&self, pb: &mut Protobuf) {
+        if let Some(m) = self.0 {
+            pb.write_message(1, m);
+        }
+    }
+}
+/// An m-value slot of a point being read
+#[derive(Default)]
+struct MValueSlot<M>(Option<M>);
+impl<M: Default + ProtoRead> ProtoRead for MValueSlot<M> {
+    fn read(&mut self, tag: u64, pb: &mut Protobuf) {
+        if tag == 1 {
+            let mut m = M::default();
+            pb.read_message(&mut m);
+            self.0 = Some(m);
+        }
+    }
+}
+
+/// Flattened points of a geometry. Carries its own precision so it decodes without outside state
+struct CoordinatesRef<'a, M: Clone> {
+    points: Vec<&'a VectorPoint<M>>,
+    lengths: Vec<u64>,
+    precision: u8,
+}
+impl<M: Clone + ProtoWrite> ProtoWrite for CoordinatesRef<'_, M> {
+    fn write(&self, pb: &mut Protobuf) {
+        let has_z = self.points.iter().any(|point| point.z.is_some());
+        let stride = if has_z { 3 } else { 2 };
+        let mut values = Vec::with_capacity(self.points.len() * stride);
+        for point in &self.points {
+            values.push(point.x);
+            values.push(point.y);
+            if has_z {
+                values.push(point.z.unwrap_or_default());
+            }
+        }
+        pb.write_varint_field(1, self.precision);
+        if has_z {
+            pb.write_varint_field(2, true);
+        }
+        if !self.lengths.is_empty() {
+            pb.write_packed_varint(3, &self.lengths);
+        }
+        pb.write_packed_s_varint(4, &delta_encode(&values, stride, self.precision));
+        if self.points.iter().any(|point| point.m.is_some()) {
+            for point in &self.points {
+                pb.write_message(5, &MValueRef(point.m.as_ref()));
+            }
+        }
+    }
+}
+/// Flattened points of a geometry being read
+#[derive(Default)]
+struct Coordinates<M> {
+    precision: u8,
+    has_z: bool,
+    lengths: Vec<u64>,
+    values: Vec<i64>,
+    m_values: Vec<Option<M>>,
+}
+impl<M: Default + ProtoRead> ProtoRead for Coordinates<M> {
+    fn read(&mut self, tag: u64, pb: &mut Protobuf) {
+        match tag {
+            1 => self.precision = pb.read_varint(),
+            2 => self.has_z = pb.read_varint(),
+            3 => self.lengths = pb.read_packed(),
+            4 => self.values = pb.read_s_packed(),
+            5 => {
+                let mut slot = MValueSlot::default();
+                pb.read_message(&mut slot);
+                self.m_values.push(slot.0);
+            }
+            _ => {}
+        }
+    }
+}
+impl<M: Clone> Coordinates<M> {
+    /// Decode into the points and the list lengths
+    fn into_parts(self) -> (Vec<VectorPoint<M>>, Vec<u64>) {
+        let stride = if self.has_z { 3 } else { 2 };
+        let values = delta_decode(&self.values, stride, self.precision);
+        let mut m_values = self.m_values.into_iter();
+        let points = values
+            .chunks_exact(stride)
+            .map(|c| VectorPoint {
+                x: c[0],
+                y: c[1],
+                z: if self.has_z { Some(c[2]) } else { None },
+                m: m_values.next().flatten(),
+                t: None,
+            })
+            .collect();
+        (points, self.lengths)
+    }
+}
+
+/// A flat tessellation list. Carries its own precision so it decodes without outside state
+struct TessellationRef<'a> {
+    values: &'a [f64],
+    stride: usize,
+    precision: u8,
+}
+impl ProtoWrite for TessellationRef<'_> {
+    fn write(&self, pb: &mut Protobuf) {
+        pb.write_varint_field(1, self.precision);
+        pb.write_varint_field(2, self.stride);
+        pb.write_packed_s_varint(3, &delta_encode(self.values, self.stride, self.precision));
+    }
+}
+/// A flat tessellation list being read
+#[derive(Default)]
+struct Tessellation {
+    precision: u8,
+    stride: usize,
+    deltas: Vec<i64>,
+}
+impl ProtoRead for Tessellation {
+    fn read(&mut self, tag: u64, pb: &mut Protobuf) {
+        match tag {
+            1 => self.precision = pb.read_varint(),
+            2 => self.stride = pb.read_varint(),
+            3 => self.deltas = pb.read_s_packed(),
+            _ => {}
+        }
+    }
+}
+
+// VectorBaseGeometry
+
+/// Write a geometry with its coordinates and tessellation quantized to `precision` decimal places
+fn write_geometry<
+    M: Clone + ProtoWrite,
+    G: ProtoCoordinates<Point = VectorPoint<M>>,
+    O: ProtoOffset,
+>(
+    geometry: &VectorBaseGeometry<G, O>,
+    pb: &mut Protobuf,
+    precision: u8,
+) {
+    let precision = precision.min(MAX_PRECISION);
+    pb.write_varint_field(1, u8::from(geometry._type));
+    if geometry.is_3d {
+        pb.write_varint_field(2, true);
+    }
+    let mut coordinates = CoordinatesRef { points: vec![], lengths: vec![], precision };
+    geometry.coordinates.flatten(&mut coordinates.points, &mut coordinates.lengths);
+    pb.write_message(3, &coordinates);
+    if let Some(offset) = &geometry.offset {
+        pb.write_message(4, &offset.to_offsets(geometry._type));
+    }
+    if let Some(bbox) = &geometry.bbox {
+        pb.write_message(5, bbox);
+    }
+    if let Some(vec_bbox) = &geometry.vec_bbox {
+        pb.write_message(6, vec_bbox);
+    }
+    if let Some(indices) = &geometry.indices {
+        pb.write_packed_varint(7, indices);
+    }
+    if let Some(tessellation) = &geometry.tessellation {
+        let stride = if geometry.is_3d { 3 } else { 2 };
+        pb.write_message(8, &TessellationRef { values: tessellation, stride, precision });
+    }
+}
+impl<M: Clone + ProtoWrite, G: ProtoCoordinates<Point = VectorPoint<M>>, O: ProtoOffset> ProtoWrite
+    for VectorBaseGeometry<G, O>
+{
+    fn write(&self, pb: &mut Protobuf) {
+        write_geometry(self, pb, DEFAULT_PRECISION);
+    }
+}
+impl<M: Clone + Default + ProtoRead, G: ProtoCoordinates<Point = VectorPoint<M>>, O: ProtoOffset>
+    ProtoRead for VectorBaseGeometry<G, O>
+{
+    fn read(&mut self, tag: u64, pb: &mut Protobuf) {
+        match tag {
+            1 => {
+                if let Ok(_type) = pb.read_varint::<u8>().try_into() {
+                    self._type = _type;
+                }
+            }
+            2 => self.is_3d = pb.read_varint(),
+            3 => {
+                let mut coordinates = Coordinates::default();
+                pb.read_message(&mut coordinates);
+                let (points, lengths) = coordinates.into_parts();
+                self.coordinates = G::unflatten(&mut points.into_iter(), &mut lengths.into_iter())
+                    .unwrap_or_default();
+            }
+            4 => {
+                let mut offsets = VectorOffsets::default();
+                pb.read_message(&mut offsets);
+                self.offset = O::from_offsets(offsets);
+            }
+            5 | 6 => {
+                let mut bbox = BBox3D::default();
+                pb.read_message(&mut bbox);
+                if tag == 5 {
+                    self.bbox = Some(bbox);
+                } else {
+                    self.vec_bbox = Some(bbox);
+                }
+            }
+            7 => self.indices = Some(pb.read_packed()),
+            8 => {
+                let mut tessellation = Tessellation::default();
+                pb.read_message(&mut tessellation);
+                self.tessellation = Some(delta_decode(
+                    &tessellation.deltas,
+                    tessellation.stride,
+                    tessellation.precision,
+                ));
+            }
+            _ => {}
+        }
+    }
+}
+
+// VectorGeometry
+
+impl<M: Clone + Default + ProtoWrite> VectorGeometry<M> {
+    /// Write the geometry with its coordinates and tessellation quantized to `precision` decimal
+    /// places (at most [`MAX_PRECISION`]). [`ProtoWrite`] uses [`DEFAULT_PRECISION`]
+    pub fn write_with_precision(&self, pb: &mut Protobuf, precision: u8) {
+        match self {
+            VectorGeometry::Point(g) => write_geometry(g, pb, precision),
+            VectorGeometry::MultiPoint(g) => write_geometry(g, pb, precision),
+            VectorGeometry::LineString(g) => write_geometry(g, pb, precision),
+            VectorGeometry::MultiLineString(g) => write_geometry(g, pb, precision),
+            VectorGeometry::Polygon(g) => write_geometry(g, pb, precision),
+            VectorGeometry::MultiPolygon(g) => write_geometry(g, pb, precision),
+        }
+    }
+}
+impl<M: Clone + Default + ProtoWrite> ProtoWrite for VectorGeometry<M> {
+    fn write(&self, pb: &mut Protobuf) {
+        self.write_with_precision(pb, DEFAULT_PRECISION);
+    }
+}
+impl<M: Clone + Default + ProtoRead> ProtoRead for VectorGeometry<M> {
+    fn read(&mut self, tag: u64, pb: &mut Protobuf) {
+        // the type always comes first and picks the variant the remaining fields are read into.
+        // Unknown types keep the current variant. [`VectorFeature::from_protobuf`] rejects them
+        if tag == 1 {
+            let Ok(_type) = pb.read_varint::<u8>().try_into() else { return };
+            *self = match _type {
+                VectorGeometryType::Point => {
+                    VectorGeometry::Point(VectorBaseGeometry { _type, ..Default::default() })
+                }
+                VectorGeometryType::MultiPoint => {
+                    VectorGeometry::MultiPoint(VectorBaseGeometry { _type, ..Default::default() })
+                }
+                VectorGeometryType::LineString => {
+                    VectorGeometry::LineString(VectorBaseGeometry { _type, ..Default::default() })
+                }
+                VectorGeometryType::MultiLineString => {
+                    VectorGeometry::MultiLineString(VectorBaseGeometry {
+                        _type,
+                        ..Default::default()
+                    })
+                }
+                VectorGeometryType::Polygon => {
+                    VectorGeometry::Polygon(VectorBaseGeometry { _type, ..Default::default() })
+                }
+                VectorGeometryType::MultiPolygon => {
+                    VectorGeometry::MultiPolygon(VectorBaseGeometry { _type, ..Default::default() })
+                }
+            };
+            return;
+        }
+        match self {
+            VectorGeometry::Point(g) => g.read(tag, pb),
+            VectorGeometry::MultiPoint(g) => g.read(tag, pb),
+            VectorGeometry::LineString(g) => g.read(tag, pb),
+            VectorGeometry::MultiLineString(g) => g.read(tag, pb),
+            VectorGeometry::Polygon(g) => g.read(tag, pb),
+            VectorGeometry::MultiPolygon(g) => g.read(tag, pb),
+        }
+    }
+}
+
+// VectorOffsets
+
+impl ProtoWrite for VectorOffsets {
+    fn write(&self, pb: &mut Protobuf) {
+        match self {
+            VectorOffsets::LineOffset(offset) => {
+                pb.write_varint_field(1, 0_u8);
+                write_f64s(pb, 2, &[*offset]);
+            }
+            VectorOffsets::MultiLineOffset(offsets) => {
+                pb.write_varint_field(1, 1_u8);
+                write_f64s(pb, 2, offsets);
+            }
+            VectorOffsets::PolygonOffset(offsets) => {
+                pb.write_varint_field(1, 2_u8);
+                write_f64s(pb, 2, offsets);
+            }
+            VectorOffsets::MultiPolygonOffset(polygons) => {
+                pb.write_varint_field(1, 3_u8);
+                for offsets in polygons {
+                    write_f64s(pb, 2, offsets);
+                }
+            }
+        }
+    }
+}
+impl ProtoRead for VectorOffsets {
+    fn read(&mut self, tag: u64, pb: &mut Protobuf) {
+        match tag {
+            1 => {
+                *self = match pb.read_varint::<u8>() {
+                    1 => VectorOffsets::MultiLineOffset(vec![]),
+                    2 => VectorOffsets::PolygonOffset(vec![]),
+                    3 => VectorOffsets::MultiPolygonOffset(vec![]),
+                    _ => VectorOffsets::LineOffset(0.),
+                }
+            }
+            2 => {
+                let values = read_f64s(pb);
+                match self {
+                    VectorOffsets::LineOffset(offset) => {
+                        *offset = values.first().copied().unwrap_or_default()
+                    }
+                    VectorOffsets::MultiLineOffset(offsets)
+                    | VectorOffsets::PolygonOffset(offsets) => offsets.extend(values),
+                    VectorOffsets::MultiPolygonOffset(polygons) => polygons.push(values),
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+// VectorPoint
+
+impl<M: Clone + ProtoWrite> ProtoWrite for VectorPoint<M> {
+    fn write(&self, pb: &mut Protobuf) {
+        pb.write_fixed_field(1, self.x);
+        pb.write_fixed_field(2, self.y);
+        if let Some(z) = self.z {
+            pb.write_fixed_field(3, z);
+        }
+        if let Some(m) = &self.m {
+            pb.write_message(4, m);
+        }
+    }
+}
+impl<M: Clone + Default + ProtoRead> ProtoRead for VectorPoint<M> {
+    fn read(&mut self, tag: u64, pb: &mut Protobuf) {
+        match tag {
+            1 => self.x = pb.read_fixed(),
+            2 => self.y = pb.read_fixed(),
+            3 => self.z = Some(pb.read_fixed()),
+            4 => {
+                let mut m = M::default();
+                pb.read_message(&mut m);
+                self.m = Some(m);
+            }
+            _ => {}
+        }
+    }
+}
+
+// BBox3D
+
+impl ProtoWrite for BBox3D {
+    fn write(&self, pb: &mut Protobuf) {
+        pb.write_fixed_field(1, self.left);
+        pb.write_fixed_field(2, self.bottom);
+        pb.write_fixed_field(3, self.right);
+        pb.write_fixed_field(4, self.top);
+        pb.write_fixed_field(5, self.near);
+        pb.write_fixed_field(6, self.far);
+    }
+}
+impl ProtoRead for BBox3D {
+    fn read(&mut self, tag: u64, pb: &mut Protobuf) {
+        match tag {
+            1 => self.left = pb.read_fixed(),
+            2 => self.bottom = pb.read_fixed(),
+            3 => self.right = pb.read_fixed(),
+            4 => self.top = pb.read_fixed(),
+            5 => self.near = pb.read_fixed(),
+            6 => self.far = pb.read_fixed(),
+            _ => {}
+        }
+    }
+}
+
+// VectorFeature
+
+impl<M: Serialize, P: Clone + Default + ProtoWrite, D: Clone + Default + ProtoWrite>
+    VectorFeature<M, P, D>
+{
+    /// Write the feature with its geometry quantized to `precision` decimal places (at most
+    /// [`MAX_PRECISION`]). [`ProtoWrite`] uses [`DEFAULT_PRECISION`]. Metadata is stored as JSON
+    pub fn write_with_precision(&self, pb: &mut Protobuf, precision: u8) {
+        pb.write_varint_field(1, u8::from(self._type));
+        if let Some(id) = self.id {
+            pb.write_varint_field(2, id);
+        }
+        pb.write_varint_field(3, u8::from(self.face));
+        pb.write_message(4, &self.properties);
+        let mut geometry = Protobuf::new();
+        self.geometry.write_with_precision(&mut geometry, precision);
+        pb.write_bytes_field(5, &geometry.take());
+        if let Some(metadata) = &self.metadata
+            && let Ok(json) = serde_json::to_string(metadata)
+        {
+            pb.write_string_field(6, &json);
+        }
+    }
+}
+impl<M: Serialize, P: Clone + Default + ProtoWrite, D: Clone + Default + ProtoWrite> ProtoWrite
+    for VectorFeature<M, P, D>
+{
+    fn write(&self, pb: &mut Protobuf) {
+        self.write_with_precision(pb, DEFAULT_PRECISION);
+    }
+}
+impl<M: DeserializeOwned, P: Clone + Default + ProtoRead, D: Clone + Default + ProtoRead> ProtoRead
+    for VectorFeature<M, P, D>
+{
+    fn read(&mut self, tag: u64, pb: &mut Protobuf) {
+        match tag {
+            1 => {
+                if let Ok(_type) = pb.read_varint::<u8>().try_into() {
+                    self._type = _type;
+                }
+            }
+            2 => self.id = Some(pb.read_varint()),
+            3 => self.face = pb.read_varint::<u8>().into(),
+            4 => pb.read_message(&mut self.properties),
+            5 => pb.read_message(&mut self.geometry),
+            6 => self.metadata = serde_json::from_str(&pb.read_string()).ok(),
+            _ => {}
+        }
+    }
+}
+impl<M: DeserializeOwned> VectorFeature<M> {
+    /// Read a feature written as the top level fields of a buffer, e.g. with
+    /// [`Protobuf::write_fields`]. Unlike reading with [`ProtoRead`], malformed data and unknown
+    /// type codes are errors rather than panics or defaults
+    pub fn from_protobuf(data: &[u8]) -> Result<Self, S2JsonError> {
+        check_message(data, vector_feature_schema)?;
+        let mut feature = VectorFeature::default();
+        Protobuf::from_input(data.to_vec()).read_fields(&mut feature, None);
+        Ok(feature)
+    }
+}
@@ -0,0 +1,326 @@
+use crate::{
+    PrimitiveShape, PrimitiveShapeType, S2JsonError, Shape, ShapePrimitive, ShapeType,
+    impls::shape::join_path,
+};
+use alloc::{format, string::String, vec, vec::Vec};
+use serde_json::{Map as JsonMap, Value as JsonValue, json};
+
+// JSON Schema (draft-07) mapping, following section 5 of the specification:
+// - string, bool and null map to their JSON types
+// - u64 and i64 are integers bounded to their range, tagged with the `uint64` or `int64` format
+// - f32 is a number bounded to the f32 range with the `float` format, f64 has the `double` format
+// - arrays hold at most 16 items, each a primitive or an object of primitives
+// - nested shapes are objects that allow no other keys. Every key is required unless optional
+// - nullable shapes add "null" to their type
+// Reading a schema accepts the same constructs, along with unions of a schema and null through
+// `anyOf` or `oneOf`, and local `$ref`s. Integers without a format read as u64 if their minimum is
+// at least 0 and i64 otherwise, and numbers without the `float` format read as f64. Array items
+// carry no markers in a shape, so a nullable item reads as its non-null type. Shapes are finite,
+// so a schema that refers back to itself is an error.
+
+/// The JSON Schema draft that generated schemas declare
+const JSON_SCHEMA_DRAFT: &str = "http://json-schema.org/draft-07/schema#";
+/// The maximum number of items in an array (spec 5.2)
+const MAX_ARRAY_ITEMS: u64 = 16;
+/// How many `$ref`s may be followed in a row before giving up on a cycle
+const MAX_REF_DEPTH: usize = 32;
+
+fn primitive_schema(shape: &PrimitiveShape) -> JsonValue {
+    match shape {
+        PrimitiveShape::String => json!({ "type": "string" }),
+        PrimitiveShape::U64 => {
+            json!({ "type": "integer", "format": "uint64", "minimum": 0, "maximum": u64::MAX })
+        }
+        PrimitiveShape::I64 => {
+            json!({ "type": "integer", "format": "int64", "minimum": i64::MIN, "maximum": i64::MAX })
+        }
+        PrimitiveShape::F32 => json!({
+            "type": "number",
+            "format": "float",
+            "minimum": f32::MIN as f64,
+            "maximum": f32::MAX as f64,
+        }),
+        PrimitiveShape::F64 => json!({ "type": "number", "format": "double" }),
+        PrimitiveShape::Bool => json!({ "type": "boolean" }),
+        PrimitiveShape::Null => json!({ "type": "null" }),
+    }
+}
+
+/// A closed object schema from its keys, their schemas and whether each is required
+fn object_schema<'a>(entries: impl Iterator<Item = (&'a String, JsonValue, bool)>) -> JsonValue {
+    let mut properties = JsonMap::new();
+    let mut required = Vec::new();
+    for (key, schema, is_required) in entries {
+        if is_required {
+            required.push(JsonValue::from(key.as_str()));
+        }
+        properties.insert(key.clone(), schema);
+    }
+    json!({
+        "type": "object",
+        "properties": properties,
+        "required": required,
+        "additionalProperties": false,
+    })
+}
+
+fn shape_schema(shape: &Shape) -> JsonValue {
+    object_schema(
+        shape.iter().map(|(key, value)| (key, shape_type_schema(value), !value.is_optional())),
+    )
+}
+
+fn shape_type_schema(shape: &ShapeType) -> JsonValue {
+    match shape {
+        ShapeType::Primitive(primitive) => primitive_schema(primitive),
+        ShapeType::Array(items) => {
+            let mut schema = json!({ "type": "array", "maxItems": MAX_ARRAY_ITEMS });
+            if let Some(item) = items.first() {
+                schema["items"] = match item {
+                    PrimitiveShapeType::Primitive(primitive) => primitive_schema(primitive),
+                    PrimitiveShapeType::NestedPrimitive(nested) => object_schema(
+                        nested.iter().map(|(key, value)| (key, primitive_schema(value), true)),
+                    ),
+                };
+            }
+            schema
+        }
+        ShapeType::Nullable(inner) => {
+            let mut schema = shape_type_schema(inner);
+            if let Some(kind) = schema.get_mut("type") {
+                *kind = json!([kind.take(), "null"]);
+            }
+            schema
+        }
+        // the parent object leaves an optional key out of "required"
+        ShapeType::Optional(inner) => shape_type_schema(inner),
+        ShapeType::Nested(nested) => shape_schema(nested),
+    }
+}
+
+/// Reads shapes from a schema, resolving references against its root
+struct SchemaReader<'a> {
+    root: &'a JsonValue,
+    /// The objects and unions being read, so a reference back to one of them is caught instead of
+    /// followed forever
+    open: Vec<&'a JsonValue>,
+}
+impl<'a> SchemaReader<'a> {
+    /// Follow local `$ref`s, e.g. "#/definitions/name", to the schema they point at
+    fn resolve(&self, schema: &'a JsonValue, path: &str) -> Result<&'a JsonValue, S2JsonError> {
+        let mut schema = schema;
+        for _ in 0..MAX_REF_DEPTH {
+            let Some(reference) = schema.get("$ref").and_then(JsonValue::as_str) else {
+                return Ok(schema);
+            };
+            let pointer = reference.strip_prefix('#').ok_or_else(|| {
+                S2JsonError::invalid_json_schema(
+                    path,
+                    format!("only local references are supported, found \"{reference}\""),
+                )
+            })?;
+            schema = self.root.pointer(pointer).ok_or_else(|| {
+                S2JsonError::invalid_json_schema(
+                    path,
+                    format!("unresolved reference \"{reference}\""),
+                )
+            })?;
+        }
+        Err(S2JsonError::invalid_json_schema(path, "too many nested references"))
+    }
+
+    /// The resolved schema, its single non-null type and whether it is nullable
+    fn kind(
+        &mut self,
+        schema: &'a JsonValue,
+        path: &str,
+    ) -> Result<(&'a JsonValue, &'a str, bool), S2JsonError> {
+        let schema = self.resolve(schema, path)?;
+        let union = schema.get("anyOf").or_else(|| schema.get("oneOf"));
+        if let Some(members) = union.and_then(JsonValue::as_array) {
+            let mut nullable = false;
+            let mut others = Vec::new();
+            for member in members {
+                let member = self.resolve(member, path)?;
+                match member.get("type").and_then(JsonValue::as_str) {
+                    Some("null") => nullable = true,
+                    _ => others.push(member),
+                }
+            }
+            return match others[..] {
+                [] => Ok((schema, "null", true)),
+                [other] => {
+                    let (other, kind, other_nullable) =
+                        self.enter(schema, path, |reader| reader.kind(other, path))?;
+                    Ok((other, kind, nullable || other_nullable))
+                }
+                _ => Err(S2JsonError::invalid_json_schema(
+                    path,
+                    "only unions of a single schema with null are supported",
+                )),
+            };
+        }
+
+        let (kinds, nullable): (Vec<&str>, bool) = match schema.get("type") {
+            Some(JsonValue::String(kind)) => (vec![kind.as_str()], false),
+            Some(JsonValue::Array(kinds)) => {
+                let kinds: Vec<&str> = kinds.iter().filter_map(JsonValue::as_str).collect();
+                let nullable = kinds.contains(&"null");
+                (kinds.into_iter().filter(|kind| *kind != "null").collect(), nullable)
+            }
+            None if schema.get("properties").is_some() => (vec!["object"], false),
+            _ => return Err(S2JsonError::invalid_json_schema(path, "missing a type")),
+        };
+        match kinds[..] {
+            [] => Ok((schema, "null", true)),
+            [kind] => Ok((schema, kind, nullable)),
+            _ => Err(S2JsonError::invalid_json_schema(
+                path,
+                format!("only one non-null type is supported, found {kinds:?}"),
+            )),
+        }
+    }
+
+    fn primitive(
+        &self,
+        schema: &JsonValue,
+        kind: &str,
+        path: &str,
+    ) -> Result<PrimitiveShape, S2JsonError> {
+        Ok(match (kind, schema.get("format").and_then(JsonValue::as_str)) {
+            ("string", _) => PrimitiveShape::String,
+            ("boolean", _) => PrimitiveShape::Bool,
+            ("null", _) => PrimitiveShape::Null,
+            ("integer", Some("uint64")) => PrimitiveShape::U64,
+            ("integer", Some("int64")) => PrimitiveShape::I64,
+            ("integer", _) => {
+                let minimum = schema.get("minimum").and_then(JsonValue::as_f64);
+                if minimum.is_some_and(|minimum| minimum >= 0.) {
+                    PrimitiveShape::U64
+                } else {
+                    PrimitiveShape::I64
+                }
+            }
+            ("number", Some("float")) => PrimitiveShape::F32,
+            ("number", _) => PrimitiveShape::F64,
+            (kind, _) => {
+                return Err(S2JsonError::invalid_json_schema(
+                    path,
+                    format!("expected a primitive type, found \"{kind}\""),
+                ));
+            }
+        })
+    }
+
+    fn array_item(
+        &mut self,
+        schema: &'a JsonValue,
+        path: &str,
+    ) -> Result<PrimitiveShapeType, S2JsonError> {
+        let (schema, kind, _) = self.kind(schema, path)?;
+        if kind != "object" {
+            return Ok(PrimitiveShapeType::Primitive(self.primitive(schema, kind, path)?));
+        }
+        let mut nested = ShapePrimitive::new();
+        for (key, value) in self.properties(schema, path)? {
+            let key_path = join_path(path, key);
+            let (value, kind, _) = self.kind(value, &key_path)?;
+            nested.insert(key.clone(), self.primitive(value, kind, &key_path)?);
+        }
+        Ok(PrimitiveShapeType::NestedPrimitive(nested))
+    }
+
+    fn properties(
+        &self,
+        schema: &'a JsonValue,
+        path: &str,
+    ) -> Result<Vec<(&'a String, &'a JsonValue)>, S2JsonError> {
+        match schema.get("properties") {
+            None => Ok(Vec::new()),
+            Some(JsonValue::Object(properties)) => Ok(properties.iter().collect()),
+            Some(_) => Err(S2JsonError::invalid_json_schema(path, "properties must be an object")),
+        }
+    }
+
+    fn shape_type(&mut self, schema: &'a JsonValue, path: &str) -> Result<ShapeType, S2JsonError> {
+        let (schema, kind, nullable) = self.kind(schema, path)?;
+        let shape = match kind {
+            "object" => ShapeType::Nested(self.shape(schema, path)?),
+            "array" => ShapeType::Array(match schema.get("items") {
+                Some(items) => vec![self.array_item(items, &format!("{path}[]"))?],
+                None => Vec::new(),
+            }),
+            kind => ShapeType::Primitive(self.primitive(schema, kind, path)?),
+        };
+        Ok(if nullable { shape.nullable() } else { shape })
+    }
+
+    /// Read an object schema. Keys it does not require are optional
+    fn shape(&mut self, schema: &'a JsonValue, path: &str) -> Result<Shape, S2JsonError> {
+        let required: Vec<&str> = schema
+            .get("required")
+            .and_then(JsonValue::as_array)
+            .map(|required| required.iter().filter_map(JsonValue::as_str).collect())
+            .unwrap_or_default();
+        self.enter(schema, path, |reader| {
+            let mut shape = Shape::new();
+            for (key, value) in reader.properties(schema, path)? {
+                let shape_type = reader.shape_type(value, &join_path(path, key))?;
+                let shape_type = if required.contains(&key.as_str()) {
+                    shape_type
+                } else {
+                    shape_type.optional()
+                };
+                shape.insert(key.clone(), shape_type);
+            }
+            Ok(shape)
+        })
+    }
+
+    /// Read the inside of an object or union, failing if it is already being read further up
+    fn enter<T>(
+        &mut self,
+        schema: &'a JsonValue,
+        path: &str,
+        read: impl FnOnce(&mut Self) -> Result<T, S2JsonError>,
+    ) -> Result<T, S2JsonError> {
+        if self.open.iter().any(|open| core::ptr::eq(*open, schema)) {
+            return Err(S2JsonError::invalid_json_schema(
+                path,
+                "recursive schemas are not supported",
+            ));
+        }
+        self.open.push(schema);
+        let res = read(self);
+        self.open.pop();
+        res
+    }
+}
+
+impl Shape {
+    /// Describe the properties object this shape accepts as a JSON Schema (draft-07) document.
+    /// A [`Properties`](crate::Properties) deriving struct can be described through the shape of
+    /// its value, e.g. `Shape::from(&Properties::from(MyProperties::default()))`. That shape is
+    /// inferred from the values rather than the field types, so a `None` field and the items of an
+    /// empty `Vec` are only ever `null`. Take the shape of a value with those fields filled in, and
+    /// mark the `Option` ones with [`ShapeType::nullable`], to describe them fully
+    pub fn to_json_schema(&self) -> JsonValue {
+        let mut schema = shape_schema(self);
+        schema["$schema"] = JSON_SCHEMA_DRAFT.into();
+        schema
+    }
+
+    /// Read a shape from a JSON Schema describing an object. Local references are followed, and
+    /// constructs that no shape can express, such as unions of several types, are an error
+    pub fn try_from_json_schema(schema: &JsonValue) -> Result<Self, S2JsonError> {
+        let mut reader = SchemaReader { root: schema, open: Vec::new() };
+        let (schema, kind, _) = reader.kind(schema, "")?;
+        if kind != "object" {
+            return Err(S2JsonError::invalid_json_schema(
+                "",
+                format!("expected an object schema, found \"{kind}\""),
+            ));
+        }
+        reader.shape(schema, "")
+    }
+}
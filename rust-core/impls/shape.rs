@@ -1,8 +1,15 @@
 use crate::{
-    Map, PrimitiveShape, PrimitiveShapeType, PrimitiveValue, Shape, ShapeType, Value,
-    ValuePrimitiveType, ValueType,
+    Map, PrimitiveShape, PrimitiveShapeType, PrimitiveValue, S2JsonError, Shape, ShapeMergePolicy,
+    ShapePrimitive, ShapeType, Value, ValuePrimitiveType, ValueType,
+    impls::proto::{Wire, check_message},
 };
-use alloc::{string::String, vec};
+use alloc::{boxed::Box, format, string::String, vec};
+use pbf::{ProtoRead, ProtoWrite, Protobuf};
+
+/// Extend a key path with a key, e.g. "a" + "b" -> "a.b"
+pub(crate) fn join_path(path: &str, key: &str) -> String {
+    if path.is_empty() { key.into() } else { format!("{path}.{key}") }
+}
 
 // ? Primitive Shape
 
@@ -17,7 +24,7 @@ impl PrimitiveShape {
 
     /// returns true if two shapes are the same. Numeric types are considered the same.
     pub fn matching_shape(&self, other: &PrimitiveShape) -> bool {
-        self == other || self.is_number() == other.is_number()
+        self == other || (self.is_number() && other.is_number())
     }
 
     /// returns the highest order number type
@@ -36,13 +43,36 @@ impl PrimitiveShape {
         }
     }
 
-    fn merge(&mut self, other: &Self) {
+    /// Merge another primitive shape into this one. Numbers merge into the highest order number
+    /// and null merges into any other type. Any other conflict is handled by the policy
+    pub fn try_merge(&mut self, other: &Self, policy: ShapeMergePolicy) -> Result<(), S2JsonError> {
+        self.merge_at(other, "", policy)
+    }
+
+    fn merge_at(
+        &mut self,
+        other: &Self,
+        path: &str,
+        policy: ShapeMergePolicy,
+    ) -> Result<(), S2JsonError> {
         if self.is_number() && other.is_number() {
             *self = Self::get_highest_order_number(self, other);
-        } else if !self.matching_shape(other) {
-            panic!("shape mismatch: {:?} vs {:?}", self, other);
+        } else if *self == PrimitiveShape::Null {
+            *self = other.clone();
+        } else if *other != PrimitiveShape::Null && self != other {
+            match policy {
+                ShapeMergePolicy::Strict => {
+                    return Err(S2JsonError::shape_mismatch(
+                        path,
+                        ShapeType::Primitive(self.clone()),
+                        ShapeType::Primitive(other.clone()),
+                    ));
+                }
+                ShapeMergePolicy::Widen => *self = PrimitiveShape::String,
+            }
         }
         // othewrise, do nothing
+        Ok(())
     }
 }
 impl From<&PrimitiveShape> for usize {
@@ -60,18 +90,25 @@ impl From<&PrimitiveShape> for usize {
 }
 impl From<usize> for PrimitiveShape {
     fn from(num: usize) -> Self {
-        match num {
-            0 => PrimitiveShape::String,
-            1 => PrimitiveShape::U64,
-            2 => PrimitiveShape::I64,
-            3 => PrimitiveShape::F32,
-            4 => PrimitiveShape::F64,
-            5 => PrimitiveShape::Bool,
-            6 => PrimitiveShape::Null,
-            _ => panic!("unknown value: {}", num),
+        match primitive_shape(num) {
+            Some(prim) => prim,
+            None => panic!("unknown value: {}", num),
         }
     }
 }
+/// The primitive shape of a code, if it is a known one
+fn primitive_shape(num: usize) -> Option<PrimitiveShape> {
+    match num {
+        0 => Some(PrimitiveShape::String),
+        1 => Some(PrimitiveShape::U64),
+        2 => Some(PrimitiveShape::I64),
+        3 => Some(PrimitiveShape::F32),
+        4 => Some(PrimitiveShape::F64),
+        5 => Some(PrimitiveShape::Bool),
+        6 => Some(PrimitiveShape::Null),
+        _ => None,
+    }
+}
 impl From<&PrimitiveValue> for PrimitiveShape {
     fn from(val: &PrimitiveValue) -> Self {
         match val {
@@ -102,28 +139,71 @@ impl From<&ValuePrimitiveType> for PrimitiveShapeType {
         }
     }
 }
+impl From<&PrimitiveShapeType> for ShapeType {
+    fn from(shape: &PrimitiveShapeType) -> Self {
+        match shape {
+            PrimitiveShapeType::Primitive(prim) => ShapeType::Primitive(prim.clone()),
+            PrimitiveShapeType::NestedPrimitive(nested) => {
+                let mut nested_map = Shape::new();
+                for (key, value) in nested.iter() {
+                    nested_map.insert(key.clone(), ShapeType::Primitive(value.clone()));
+                }
+                ShapeType::Nested(nested_map)
+            }
+        }
+    }
+}
 impl PrimitiveShapeType {
-    fn merge(&mut self, other: &Self) {
+    /// Merge another array element shape into this one. Conflicts are handled by the policy
+    pub fn try_merge(&mut self, other: &Self, policy: ShapeMergePolicy) -> Result<(), S2JsonError> {
+        self.merge_at(other, "", policy)
+    }
+
+    fn merge_at(
+        &mut self,
+        other: &Self,
+        path: &str,
+        policy: ShapeMergePolicy,
+    ) -> Result<(), S2JsonError> {
         match (self, other) {
             (
                 PrimitiveShapeType::Primitive(self_prim),
                 PrimitiveShapeType::Primitive(other_prim),
-            ) => {
-                self_prim.merge(other_prim);
-            }
+            ) => self_prim.merge_at(other_prim, path, policy),
             (
                 PrimitiveShapeType::NestedPrimitive(self_nested),
                 PrimitiveShapeType::NestedPrimitive(other_nested),
             ) => {
                 for (key, value) in other_nested.iter() {
-                    if self_nested.contains_key(key) {
-                        self_nested.get_mut(key).unwrap().merge(value);
-                    } else {
-                        self_nested.insert(key.clone(), value.clone());
+                    match self_nested.get_mut(key) {
+                        Some(prev) => prev.merge_at(value, &join_path(path, key), policy)?,
+                        None => {
+                            self_nested.insert(key.clone(), value.clone());
+                        }
+                    }
+                }
+                Ok(())
+            }
+            (this, other) => {
+                let null = PrimitiveShapeType::Primitive(PrimitiveShape::Null);
+                if *this == null {
+                    *this = other.clone();
+                } else if *other != null {
+                    match policy {
+                        ShapeMergePolicy::Strict => {
+                            return Err(S2JsonError::shape_mismatch(
+                                path,
+                                (&*this).into(),
+                                other.into(),
+                            ));
+                        }
+                        ShapeMergePolicy::Widen => {
+                            *this = PrimitiveShapeType::Primitive(PrimitiveShape::String)
+                        }
                     }
                 }
+                Ok(())
             }
-            _ => panic!("shape mismatch"),
         }
     }
 }
@@ -137,32 +217,133 @@ impl Default for ShapeType {
 }
 impl From<&ValueType> for ShapeType {
     fn from(val: &ValueType) -> Self {
-        match val {
+        ShapeType::try_from_value_type(val, ShapeMergePolicy::Strict)
+            .unwrap_or_else(|err| panic!("{err}"))
+    }
+}
+impl ShapeType {
+    /// Mark the shape type as nullable. Null itself is already nullable
+    pub fn nullable(self) -> Self {
+        match self {
+            Self::Optional(inner) => inner.nullable().optional(),
+            Self::Nullable(_) | Self::Primitive(PrimitiveShape::Null) => self,
+            _ => Self::Nullable(Box::new(self)),
+        }
+    }
+
+    /// Mark the shape type as optional, i.e. its key may be absent
+    pub fn optional(self) -> Self {
+        match self {
+            Self::Optional(_) => self,
+            _ => Self::Optional(Box::new(self)),
+        }
+    }
+
+    /// returns true if the value may be null
+    pub fn is_nullable(&self) -> bool {
+        match self {
+            Self::Nullable(_) | Self::Primitive(PrimitiveShape::Null) => true,
+            Self::Optional(inner) => inner.is_nullable(),
+            _ => false,
+        }
+    }
+
+    /// returns true if the key may be absent
+    pub fn is_optional(&self) -> bool {
+        matches!(self, Self::Optional(_))
+    }
+
+    /// returns the shape type without any nullable or optional markers
+    pub fn unmarked(&self) -> &ShapeType {
+        match self {
+            Self::Nullable(inner) | Self::Optional(inner) => inner.unmarked(),
+            _ => self,
+        }
+    }
+
+    fn marked(self, nullable: bool, optional: bool) -> Self {
+        let shape = if nullable { self.nullable() } else { self };
+        if optional { shape.optional() } else { shape }
+    }
+
+    /// Infer the shape of a value type. Arrays whose elements don't share a single shape are
+    /// handled by the policy
+    pub fn try_from_value_type(
+        val: &ValueType,
+        policy: ShapeMergePolicy,
+    ) -> Result<Self, S2JsonError> {
+        Self::from_value_type_at(val, "", policy)
+    }
+
+    fn from_value_type_at(
+        val: &ValueType,
+        path: &str,
+        policy: ShapeMergePolicy,
+    ) -> Result<Self, S2JsonError> {
+        Ok(match val {
             ValueType::Primitive(prim) => ShapeType::Primitive(prim.into()),
             ValueType::Nested(nested) => {
-                let mut nested_map: Map<String, ShapeType> = Map::new();
-                for (key, value) in nested.iter() {
-                    nested_map.insert(key.into(), value.into());
-                }
-                ShapeType::Nested(nested_map)
+                ShapeType::Nested(Shape::from_value_at(nested, path, policy)?)
             }
             ValueType::Array(array) => {
-                let validated = validate_types(array);
-                ShapeType::Array(vec![validated])
+                ShapeType::Array(vec![validate_types_at(array, path, policy)?])
             }
+        })
+    }
+
+    /// Merge another shape type into this one. The result is nullable or optional if either side
+    /// is, and merging with null makes the shape nullable. Conflicts are handled by the policy
+    pub fn try_merge(&mut self, other: &Self, policy: ShapeMergePolicy) -> Result<(), S2JsonError> {
+        self.merge_at(other, "", policy)
+    }
+
+    fn merge_at(
+        &mut self,
+        other: &Self,
+        path: &str,
+        policy: ShapeMergePolicy,
+    ) -> Result<(), S2JsonError> {
+        let null = Self::Primitive(PrimitiveShape::Null);
+        let nullable = self.is_nullable() || other.is_nullable();
+        let optional = self.is_optional() || other.is_optional();
+        let (a, b) = (self.unmarked(), other.unmarked());
+        let mut merged = if *a == null { b.clone() } else { a.clone() };
+        if *a != null && *b != null {
+            merged.merge_unmarked_at(b, path, policy)?;
         }
+        *self = merged.marked(nullable, optional);
+        Ok(())
     }
-}
-impl ShapeType {
-    fn merge(&mut self, other: &Self) {
+
+    fn merge_unmarked_at(
+        &mut self,
+        other: &Self,
+        path: &str,
+        policy: ShapeMergePolicy,
+    ) -> Result<(), S2JsonError> {
         match (self, other) {
-            (Self::Primitive(a), Self::Primitive(b)) => a.merge(b),
-            (Self::Array(a), Self::Array(b)) => {
-                a.first_mut().unwrap().merge(b.first().unwrap());
-            }
-            (Self::Nested(a), Self::Nested(b)) => a.merge(b),
-            _ => panic!("Can't merge"),
-        };
+            (Self::Primitive(a), Self::Primitive(b)) => a.merge_at(b, path, policy),
+            (Self::Array(a), Self::Array(b)) => match (a.first_mut(), b.first()) {
+                (Some(a_elem), Some(b_elem)) => {
+                    a_elem.merge_at(b_elem, &format!("{path}[]"), policy)
+                }
+                (None, Some(_)) => {
+                    a.clone_from(b);
+                    Ok(())
+                }
+                _ => Ok(()),
+            },
+            (Self::Nested(a), Self::Nested(b)) => a.merge_at(b, path, policy),
+            (this, other) => match policy {
+                ShapeMergePolicy::Strict => {
+                    Err(S2JsonError::shape_mismatch(path, this.clone(), other.clone()))
+                }
+                ShapeMergePolicy::Widen => {
+                    *this = Self::Primitive(PrimitiveShape::String);
+                    Ok(())
+                }
+            },
+        }
     }
 }
 
@@ -170,32 +351,305 @@ impl ShapeType {
 
 impl From<&Value> for Shape {
     fn from(val: &Value) -> Self {
+        Shape::try_from_value(val, ShapeMergePolicy::Strict).unwrap_or_else(|err| panic!("{err}"))
+    }
+}
+impl From<&[Value]> for Shape {
+    fn from(val: &[Value]) -> Self {
+        Shape::try_from_values(val, ShapeMergePolicy::Strict).unwrap_or_else(|err| panic!("{err}"))
+    }
+}
+impl Shape {
+    /// Merge two shapes. Panics if the shapes conflict, see [`Shape::try_merge`]
+    pub fn merge(&mut self, other: &Self) {
+        self.try_merge(other, ShapeMergePolicy::Strict).unwrap_or_else(|err| panic!("{err}"));
+    }
+
+    /// Merge two shapes. Numbers merge into the highest order number, merging with null makes a
+    /// shape nullable and, when neither shape is empty, a key missing from either shape becomes
+    /// optional. Any other conflict is handled by the policy, and a strict failure reports the key
+    /// path and both conflicting shapes, leaving the shape unchanged
+    pub fn try_merge(&mut self, other: &Self, policy: ShapeMergePolicy) -> Result<(), S2JsonError> {
+        let mut merged = self.clone();
+        merged.merge_at(other, "", policy)?;
+        *self = merged;
+        Ok(())
+    }
+
+    fn merge_at(
+        &mut self,
+        other: &Self,
+        path: &str,
+        policy: ShapeMergePolicy,
+    ) -> Result<(), S2JsonError> {
+        // an empty shape has nothing to compare against, so merging it marks nothing optional
+        if other.is_empty() {
+            return Ok(());
+        }
+        if self.is_empty() {
+            self.clone_from(other);
+            return Ok(());
+        }
+        for (key, value) in self.iter_mut() {
+            if !other.contains_key(key) {
+                *value = value.clone().optional();
+            }
+        }
+        for (key, value) in other.iter() {
+            match self.get_mut(key) {
+                Some(prev) => prev.merge_at(value, &join_path(path, key), policy)?,
+                None => {
+                    self.insert(key.clone(), value.clone().optional());
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Infer the shape of a value without panicking
+    pub fn try_from_value(val: &Value, policy: ShapeMergePolicy) -> Result<Self, S2JsonError> {
+        Self::from_value_at(val, "", policy)
+    }
+
+    /// Infer a single shape that describes all the values without panicking
+    /// Keys that are missing from some of the values are marked optional
+    pub fn try_from_values(vals: &[Value], policy: ShapeMergePolicy) -> Result<Self, S2JsonError> {
+        let Some((first, rest)) = vals.split_first() else { return Ok(Shape::new()) };
+        let mut shape = Self::try_from_value(first, policy)?;
+        for val in rest {
+            shape.merge_at(&Self::try_from_value(val, policy)?, "", policy)?;
+        }
+        // empty values mark nothing when merged, so catch the keys they are missing here
+        for (key, value) in shape.iter_mut() {
+            if !value.is_optional() && vals.iter().any(|val| !val.contains_key(key)) {
+                *value = value.clone().optional();
+            }
+        }
+        Ok(shape)
+    }
+
+    fn from_value_at(
+        val: &Value,
+        path: &str,
+        policy: ShapeMergePolicy,
+    ) -> Result<Self, S2JsonError> {
         let mut shape = Shape::new();
         for (key, value) in val.iter() {
-            shape.insert(key.into(), value.into());
+            let shape_type = ShapeType::from_value_type_at(value, &join_path(path, key), policy)?;
+            shape.insert(key.into(), shape_type);
         }
+        Ok(shape)
+    }
+}
+
+// ? Protobuf
+//
+// A shape is a message of repeated key-shape entries (1). A shape type sets one of: a primitive (1),
+// an array element (2, repeated), a nested shape (3), a nullable (4) or optional (5) inner shape
+// type, or an empty array (6). An array element sets either a primitive (1) or a nested primitive
+// shape (2), which is itself a message of repeated key-primitive entries (1). Unknown primitive codes
+// are skipped like unknown fields, leaving the default in their place. [`Shape::from_protobuf`]
+// rejects them.
 
-        shape
+/// A key and its shape, the entry of a shape message
+#[derive(Default)]
+struct ShapeEntry<T> {
+    key: String,
+    value: T,
+}
+impl ProtoRead for ShapeEntry<ShapeType> {
+    fn read(&mut self, tag: u64, pb: &mut Protobuf) {
+        match tag {
+            1 => self.key = pb.read_string(),
+            2 => pb.read_message(&mut self.value),
+            _ => {}
+        }
     }
 }
-impl From<&[Value]> for Shape {
-    fn from(val: &[Value]) -> Self {
-        let mut shape = Shape::new();
-        for v in val {
-            shape.merge(&(v.into()));
+impl ProtoRead for ShapeEntry<PrimitiveShape> {
+    fn read(&mut self, tag: u64, pb: &mut Protobuf) {
+        match tag {
+            1 => self.key = pb.read_string(),
+            2 => {
+                if let Some(prim) = primitive_shape(pb.read_varint()) {
+                    self.value = prim;
+                }
+            }
+            _ => {}
+        }
+    }
+}
+impl ProtoWrite for Shape {
+    fn write(&self, pb: &mut Protobuf) {
+        for (key, value) in self.iter() {
+            let mut entry = Protobuf::new();
+            entry.write_string_field(1, key);
+            entry.write_message(2, value);
+            pb.write_bytes_field(1, &entry.take());
+        }
+    }
+}
+impl ProtoRead for Shape {
+    fn read(&mut self, tag: u64, pb: &mut Protobuf) {
+        if tag == 1 {
+            let mut entry = ShapeEntry::<ShapeType>::default();
+            pb.read_message(&mut entry);
+            self.insert(entry.key, entry.value);
+        }
+    }
+}
+impl ProtoWrite for ShapeType {
+    fn write(&self, pb: &mut Protobuf) {
+        match self {
+            ShapeType::Primitive(prim) => pb.write_varint_field(1, usize::from(prim)),
+            ShapeType::Array(array) if array.is_empty() => pb.write_varint_field(6, true),
+            ShapeType::Array(array) => {
+                for elem in array {
+                    pb.write_message(2, elem);
+                }
+            }
+            ShapeType::Nested(nested) => pb.write_message(3, nested),
+            ShapeType::Nullable(inner) => pb.write_message(4, inner.as_ref()),
+            ShapeType::Optional(inner) => pb.write_message(5, inner.as_ref()),
+        }
+    }
+}
+impl ProtoRead for ShapeType {
+    fn read(&mut self, tag: u64, pb: &mut Protobuf) {
+        match tag {
+            1 => {
+                if let Some(prim) = primitive_shape(pb.read_varint()) {
+                    *self = ShapeType::Primitive(prim);
+                }
+            }
+            2 => {
+                let mut elem = PrimitiveShapeType::Primitive(PrimitiveShape::Null);
+                pb.read_message(&mut elem);
+                match self {
+                    ShapeType::Array(array) => array.push(elem),
+                    _ => *self = ShapeType::Array(vec![elem]),
+                }
+            }
+            3 => {
+                let mut nested = Shape::new();
+                pb.read_message(&mut nested);
+                *self = ShapeType::Nested(nested);
+            }
+            4 | 5 => {
+                let mut inner = ShapeType::default();
+                pb.read_message(&mut inner);
+                *self = if tag == 4 { inner.nullable() } else { inner.optional() };
+            }
+            6 => {
+                pb.read_varint::<u64>();
+                *self = ShapeType::Array(vec![]);
+            }
+            _ => {}
+        }
+    }
+}
+impl ProtoWrite for PrimitiveShapeType {
+    fn write(&self, pb: &mut Protobuf) {
+        match self {
+            PrimitiveShapeType::Primitive(prim) => pb.write_varint_field(1, usize::from(prim)),
+            PrimitiveShapeType::NestedPrimitive(nested) => pb.write_message(2, nested),
+        }
+    }
+}
+impl ProtoRead for PrimitiveShapeType {
+    fn read(&mut self, tag: u64, pb: &mut Protobuf) {
+        match tag {
+            1 => {
+                if let Some(prim) = primitive_shape(pb.read_varint()) {
+                    *self = PrimitiveShapeType::Primitive(prim);
+                }
+            }
+            2 => {
+                let mut nested = ShapePrimitive::new();
+                pb.read_message(&mut nested);
+                *self = PrimitiveShapeType::NestedPrimitive(nested);
+            }
+            _ => {}
+        }
+    }
+}
+impl ProtoWrite for ShapePrimitive {
+    fn write(&self, pb: &mut Protobuf) {
+        for (key, prim) in self.iter() {
+            let mut entry = Protobuf::new();
+            entry.write_string_field(1, key);
+            entry.write_varint_field(2, usize::from(prim));
+            pb.write_bytes_field(1, &entry.take());
+        }
+    }
+}
+impl ProtoRead for ShapePrimitive {
+    fn read(&mut self, tag: u64, pb: &mut Protobuf) {
+        if tag == 1 {
+            let mut entry = ShapeEntry::<PrimitiveShape>::default();
+            pb.read_message(&mut entry);
+            self.insert(entry.key, entry.value);
         }
-        shape
     }
 }
 impl Shape {
-    /// Merge two shapes
-    pub fn merge(&mut self, other: &Self) {
-        for (key, value) in other.iter() {
-            self.entry(key.clone())
-                .and_modify(|val| val.merge(value))
-                .or_insert_with(|| value.clone());
+    /// Read a shape written as the top level fields of a buffer, e.g. with
+    /// [`Protobuf::write_fields`]. Unlike reading with [`ProtoRead`], malformed data, unknown
+    /// primitive codes and shapes nested too deeply are errors rather than panics or defaults
+    pub fn from_protobuf(data: &[u8]) -> Result<Self, S2JsonError> {
+        check_message(data, shape_schema)?;
+        let mut shape = Shape::new();
+        Protobuf::from_input(data.to_vec()).read_fields(&mut shape, None);
+        Ok(shape)
+    }
+}
+
+/// Check a primitive shape code
+fn check_primitive_shape(code: u64) -> Result<(), S2JsonError> {
+    match usize::try_from(code).ok().and_then(primitive_shape) {
+        Some(_) => Ok(()),
+        None => Err(S2JsonError::invalid_type("PrimitiveShape", &format!("{code}"))),
+    }
+}
+/// Schema of a [`Shape`]
+fn shape_schema(tag: u64) -> Option<Wire> {
+    fn entry(tag: u64) -> Option<Wire> {
+        match tag {
+            1 => Some(Wire::Bytes),
+            2 => Some(Wire::Message(shape_type_schema)),
+            _ => None,
         }
     }
+    (tag == 1).then_some(Wire::Message(entry))
+}
+/// Schema of a [`ShapeType`]
+fn shape_type_schema(tag: u64) -> Option<Wire> {
+    match tag {
+        1 => Some(Wire::Code(check_primitive_shape)),
+        2 => Some(Wire::Message(primitive_shape_type_schema)),
+        3 => Some(Wire::Message(shape_schema)),
+        4 | 5 => Some(Wire::Message(shape_type_schema)),
+        6 => Some(Wire::Varint),
+        _ => None,
+    }
+}
+/// Schema of a [`PrimitiveShapeType`]
+fn primitive_shape_type_schema(tag: u64) -> Option<Wire> {
+    fn entry(tag: u64) -> Option<Wire> {
+        match tag {
+            1 => Some(Wire::Bytes),
+            2 => Some(Wire::Code(check_primitive_shape)),
+            _ => None,
+        }
+    }
+    fn shape_primitive(tag: u64) -> Option<Wire> {
+        (tag == 1).then_some(Wire::Message(entry))
+    }
+    match tag {
+        1 => Some(Wire::Code(check_primitive_shape)),
+        2 => Some(Wire::Message(shape_primitive)),
+        _ => None,
+    }
 }
 
 //? Primitive Value
@@ -213,19 +667,6 @@ impl PrimitiveValue {
             PrimitiveShape::Null => PrimitiveValue::Null,
         }
     }
-
-    fn matching_shape(&self, other: &PrimitiveValue) -> bool {
-        matches!(
-            (self, other),
-            (PrimitiveValue::String(_), PrimitiveValue::String(_))
-                | (PrimitiveValue::U64(_), PrimitiveValue::U64(_))
-                | (PrimitiveValue::I64(_), PrimitiveValue::I64(_))
-                | (PrimitiveValue::F32(_), PrimitiveValue::F32(_))
-                | (PrimitiveValue::F64(_), PrimitiveValue::F64(_))
-                | (PrimitiveValue::Bool(_), PrimitiveValue::Bool(_))
-                | (PrimitiveValue::Null, PrimitiveValue::Null)
-        )
-    }
 }
 impl From<&PrimitiveValue> for PrimitiveValue {
     fn from(mval: &PrimitiveValue) -> Self {
@@ -241,24 +682,6 @@ impl From<&PrimitiveValue> for PrimitiveValue {
     }
 }
 
-// ? Value Primitive Type
-
-impl ValuePrimitiveType {
-    fn same_nested(&self, nested: &Map<String, PrimitiveValue>) -> bool {
-        match self {
-            ValuePrimitiveType::Primitive(_) => false,
-            ValuePrimitiveType::NestedPrimitive(val) => {
-                for (key, val) in val.iter() {
-                    if !val.matching_shape(nested.get(key).unwrap()) {
-                        return false;
-                    }
-                }
-                true
-            }
-        }
-    }
-}
-
 // ? ValueType
 
 impl ValueType {
@@ -269,6 +692,8 @@ impl ValueType {
                 ValueType::Primitive(PrimitiveValue::default_from_shape(shape))
             }
             ShapeType::Array(_) => ValueType::Array(vec![]),
+            ShapeType::Nullable(_) => ValueType::Primitive(PrimitiveValue::Null),
+            ShapeType::Optional(shape) => ValueType::default_from_shape(shape),
             ShapeType::Nested(shape) => ValueType::Nested(Value::default_from_shape(shape)),
         }
     }
@@ -290,10 +715,10 @@ impl From<&ValueType> for PrimitiveValue {
 // ? Value
 
 impl Value {
-    /// Get the default value from a shape
+    /// Get the default value from a shape. Optional keys are left out and nullable keys are null
     pub fn default_from_shape(shape: &Shape) -> Self {
         let mut value = Value::new();
-        for (key, shape_type) in shape.iter() {
+        for (key, shape_type) in shape.iter().filter(|(_, shape_type)| !shape_type.is_optional()) {
             value.insert(key.into(), ValueType::default_from_shape(shape_type));
         }
         value
@@ -309,37 +734,26 @@ impl Value {
 /// If the type is NOT a primitive, ensure that all types in the array match
 /// returns - a single type from the list to validate the correct type to be parsed from values later
 pub fn validate_types(types: &[ValuePrimitiveType]) -> PrimitiveShapeType {
-    match types.first() {
-        Some(ValuePrimitiveType::Primitive(primitive)) => {
-            let mut base: PrimitiveShape = primitive.into();
-            let is_number = base.is_number();
-            for t in types {
-                match t {
-                    ValuePrimitiveType::Primitive(t_prim) => {
-                        let prim_shape = t_prim.into();
-                        if !base.matching_shape(&prim_shape) {
-                            panic!("All types must be the same");
-                        } else if is_number {
-                            base = PrimitiveShape::get_highest_order_number(&base, &prim_shape);
-                        }
-                        // otherwise do nothing
-                    }
-                    _ => panic!("All types must be the same"),
-                }
-            }
+    try_validate_types(types, ShapeMergePolicy::Strict).unwrap_or_else(|err| panic!("{err}"))
+}
 
-            PrimitiveShapeType::Primitive(base)
-        }
-        Some(ValuePrimitiveType::NestedPrimitive(nested)) => {
-            // iterate and check if each following types match
-            for t in types[1..].iter() {
-                if !t.same_nested(nested) {
-                    panic!("All types must be the same");
-                }
-            }
+/// The non-panicking [`validate_types`]. A strict failure reports the index of the first
+/// element that conflicts, e.g. `[2]`, along with both shapes
+pub fn try_validate_types(
+    types: &[ValuePrimitiveType],
+    policy: ShapeMergePolicy,
+) -> Result<PrimitiveShapeType, S2JsonError> {
+    validate_types_at(types, "", policy)
+}
 
-            (&ValuePrimitiveType::NestedPrimitive(nested.clone())).into()
-        }
-        None => PrimitiveShapeType::Primitive(PrimitiveShape::Null),
+pub(crate) fn validate_types_at(
+    types: &[ValuePrimitiveType],
+    path: &str,
+    policy: ShapeMergePolicy,
+) -> Result<PrimitiveShapeType, S2JsonError> {
+    let mut base = PrimitiveShapeType::Primitive(PrimitiveShape::Null);
+    for (i, t) in types.iter().enumerate() {
+        base.merge_at(&t.into(), &format!("{path}[{i}]"), policy)?;
     }
+    Ok(base)
 }
@@ -0,0 +1,260 @@
+use crate::{
+    PrimitiveShape, PrimitiveShapeType, PrimitiveValue, S2JsonError, Shape, ShapePrimitive,
+    ShapeType, Value, ValuePrimitive, ValuePrimitiveType, ValueType,
+};
+use alloc::{string::String, vec::Vec};
+use pbf::{Protobuf, zagzig};
+
+// Shaped encoding:
+// A value is written as a flat sequence of its primitives in the key order of its shape. No keys
+// or tags are written, so the exact same shape is required to decode it.
+// - string: a varint length followed by utf8 bytes
+// - u64 & bool: a varint
+// - i64: a zig-zag varint
+// - f32 & f64: little endian fixed 32 & 64 bits
+// - null: nothing
+// - array: a varint length followed by each element
+// - nested: each key of the nested shape in order
+// - nullable & optional: a varint flag, 1 if the value follows, 0 if it is null or absent
+// Array elements and the keys of objects in an array carry no markers in a shape, so each
+// primitive of an array is written behind the same flag.
+//
+// Values that don't conform to the shape are written as close as possible: numbers are cast, and
+// anything missing or of the wrong type is written as the default of its shape. Use
+// [`Value::validate`] first if that is not acceptable.
+
+impl Value {
+    /// Append the value to `out` using the shaped encoding. Values may be written back to back
+    pub fn encode_shaped(&self, shape: &Shape, out: &mut Vec<u8>) {
+        let mut pbf = Protobuf::new();
+        write_value(self, shape, &mut pbf);
+        out.append(&mut pbf.take());
+    }
+
+    /// Read a value written by [`Value::encode_shaped`] from the front of `data`, advancing it
+    /// past the value
+    pub fn decode_shaped(data: &mut &[u8], shape: &Shape) -> Result<Value, S2JsonError> {
+        let mut reader = Reader { data };
+        let value = read_value(&mut reader, shape)?;
+        *data = reader.data;
+        Ok(value)
+    }
+}
+
+// ? writing
+
+fn write_value(value: &Value, shape: &Shape, pbf: &mut Protobuf) {
+    for (key, shape_type) in shape.iter() {
+        write_type(value.get(key), shape_type, pbf);
+    }
+}
+
+fn write_type(value: Option<&ValueType>, shape: &ShapeType, pbf: &mut Protobuf) {
+    match shape {
+        ShapeType::Optional(inner) => {
+            pbf.write_varint(value.is_some());
+            if value.is_some() {
+                write_type(value, inner, pbf);
+            }
+        }
+        ShapeType::Nullable(inner) => {
+            let is_null = matches!(value, None | Some(ValueType::Primitive(PrimitiveValue::Null)));
+            pbf.write_varint(!is_null);
+            if !is_null {
+                write_type(value, inner, pbf);
+            }
+        }
+        ShapeType::Primitive(prim_shape) => {
+            let prim = match value {
+                Some(ValueType::Primitive(prim)) => Some(prim),
+                _ => None,
+            };
+            write_prim(prim, prim_shape, pbf);
+        }
+        ShapeType::Array(array_shape) => {
+            let (Some(ValueType::Array(array)), Some(elem_shape)) = (value, array_shape.first())
+            else {
+                pbf.write_varint(0);
+                return;
+            };
+            pbf.write_varint(array.len());
+            for elem in array {
+                write_elem(elem, elem_shape, pbf);
+            }
+        }
+        ShapeType::Nested(nested_shape) => match value {
+            Some(ValueType::Nested(nested)) => write_value(nested, nested_shape, pbf),
+            _ => write_value(&Value::new(), nested_shape, pbf),
+        },
+    }
+}
+
+fn write_elem(elem: &ValuePrimitiveType, shape: &PrimitiveShapeType, pbf: &mut Protobuf) {
+    match shape {
+        PrimitiveShapeType::Primitive(prim_shape) => write_slot(elem.to_prim(), prim_shape, pbf),
+        PrimitiveShapeType::NestedPrimitive(nested_shape) => {
+            let nested = elem.to_nested();
+            for (key, prim_shape) in nested_shape.iter() {
+                write_slot(nested.and_then(|n| n.get(key)), prim_shape, pbf);
+            }
+        }
+    }
+}
+
+/// Write a primitive behind a flag, used where the shape carries no markers
+fn write_slot(prim: Option<&PrimitiveValue>, shape: &PrimitiveShape, pbf: &mut Protobuf) {
+    let prim = prim.filter(|prim| !prim.is_null());
+    pbf.write_varint(prim.is_some());
+    if prim.is_some() {
+        write_prim(prim, shape, pbf);
+    }
+}
+
+fn write_prim(prim: Option<&PrimitiveValue>, shape: &PrimitiveShape, pbf: &mut Protobuf) {
+    match shape {
+        PrimitiveShape::String => {
+            pbf.write_string(&prim.and_then(|p| p.to_string()).unwrap_or_default())
+        }
+        PrimitiveShape::U64 => pbf.write_varint(prim.and_then(|p| p.to_u64()).unwrap_or_default()),
+        PrimitiveShape::I64 => {
+            pbf.write_s_varint(prim.and_then(|p| p.to_i64()).unwrap_or_default())
+        }
+        PrimitiveShape::F32 => pbf.write_fixed(prim.and_then(|p| p.to_f32()).unwrap_or_default()),
+        PrimitiveShape::F64 => pbf.write_fixed(prim.and_then(|p| p.to_f64()).unwrap_or_default()),
+        PrimitiveShape::Bool => {
+            pbf.write_varint(prim.and_then(|p| p.to_bool()).unwrap_or_default())
+        }
+        PrimitiveShape::Null => {}
+    }
+}
+
+// ? reading
+
+/// A bounds checked cursor over shaped data
+struct Reader<'a> {
+    data: &'a [u8],
+}
+impl<'a> Reader<'a> {
+    fn read_bytes(&mut self, len: usize) -> Result<&'a [u8], S2JsonError> {
+        if len > self.data.len() {
+            return Err(S2JsonError::UnexpectedEof);
+        }
+        let (bytes, rest) = self.data.split_at(len);
+        self.data = rest;
+        Ok(bytes)
+    }
+
+    fn read_varint(&mut self) -> Result<u64, S2JsonError> {
+        let mut val: u64 = 0;
+        for shift in (0..64).step_by(7) {
+            let byte = self.read_bytes(1)?[0];
+            val |= ((byte & 0x7f) as u64) << shift;
+            if byte < 0x80 {
+                return Ok(val);
+            }
+        }
+        Ok(val)
+    }
+
+    fn read_flag(&mut self) -> Result<bool, S2JsonError> {
+        Ok(self.read_varint()? != 0)
+    }
+
+    fn read_len(&mut self) -> Result<usize, S2JsonError> {
+        let len = self.read_varint()? as usize;
+        // every element takes at least a byte, so a larger length can only be corrupt
+        if len > self.data.len() { Err(S2JsonError::UnexpectedEof) } else { Ok(len) }
+    }
+
+    fn read_prim(&mut self, shape: &PrimitiveShape) -> Result<PrimitiveValue, S2JsonError> {
+        Ok(match shape {
+            PrimitiveShape::String => {
+                let len = self.read_len()?;
+                PrimitiveValue::String(String::from_utf8_lossy(self.read_bytes(len)?).into())
+            }
+            PrimitiveShape::U64 => PrimitiveValue::U64(self.read_varint()?),
+            PrimitiveShape::I64 => PrimitiveValue::I64(zagzig(self.read_varint()?)),
+            PrimitiveShape::F32 => {
+                let bytes = self.read_bytes(4)?;
+                PrimitiveValue::F32(f32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+            }
+            PrimitiveShape::F64 => {
+                let mut bytes = [0; 8];
+                bytes.copy_from_slice(self.read_bytes(8)?);
+                PrimitiveValue::F64(f64::from_le_bytes(bytes))
+            }
+            PrimitiveShape::Bool => PrimitiveValue::Bool(self.read_flag()?),
+            PrimitiveShape::Null => PrimitiveValue::Null,
+        })
+    }
+
+    fn read_slot(&mut self, shape: &PrimitiveShape) -> Result<PrimitiveValue, S2JsonError> {
+        if self.read_flag()? { self.read_prim(shape) } else { Ok(PrimitiveValue::Null) }
+    }
+}
+
+fn read_value(reader: &mut Reader, shape: &Shape) -> Result<Value, S2JsonError> {
+    let mut value = Value::new();
+    for (key, shape_type) in shape.iter() {
+        if let Some(value_type) = read_type(reader, shape_type)? {
+            value.insert(key.clone(), value_type);
+        }
+    }
+    Ok(value)
+}
+
+/// returns None if an optional value is absent
+fn read_type(reader: &mut Reader, shape: &ShapeType) -> Result<Option<ValueType>, S2JsonError> {
+    Ok(Some(match shape {
+        ShapeType::Optional(inner) => {
+            return if reader.read_flag()? { read_type(reader, inner) } else { Ok(None) };
+        }
+        ShapeType::Nullable(inner) => {
+            if !reader.read_flag()? {
+                return Ok(Some(ValueType::Primitive(PrimitiveValue::Null)));
+            }
+            return read_type(reader, inner);
+        }
+        ShapeType::Primitive(prim_shape) => ValueType::Primitive(reader.read_prim(prim_shape)?),
+        ShapeType::Array(array_shape) => {
+            let len = reader.read_len()?;
+            let mut array = Vec::with_capacity(len);
+            if let Some(elem_shape) = array_shape.first() {
+                for _ in 0..len {
+                    array.push(read_elem(reader, elem_shape)?);
+                }
+            }
+            ValueType::Array(array)
+        }
+        ShapeType::Nested(nested_shape) => ValueType::Nested(read_value(reader, nested_shape)?),
+    }))
+}
+
+fn read_elem(
+    reader: &mut Reader,
+    shape: &PrimitiveShapeType,
+) -> Result<ValuePrimitiveType, S2JsonError> {
+    Ok(match shape {
+        PrimitiveShapeType::Primitive(prim_shape) => {
+            ValuePrimitiveType::Primitive(reader.read_slot(prim_shape)?)
+        }
+        PrimitiveShapeType::NestedPrimitive(nested_shape) => {
+            ValuePrimitiveType::NestedPrimitive(read_nested_primitive(reader, nested_shape)?)
+        }
+    })
+}
+
+/// Nulls are left out of the object, mirroring how missing keys are written
+fn read_nested_primitive(
+    reader: &mut Reader,
+    shape: &ShapePrimitive,
+) -> Result<ValuePrimitive, S2JsonError> {
+    let mut nested = ValuePrimitive::new();
+    for (key, prim_shape) in shape.iter() {
+        let prim = reader.read_slot(prim_shape)?;
+        if !prim.is_null() {
+            nested.insert(key.clone(), prim);
+        }
+    }
+    Ok(nested)
+}
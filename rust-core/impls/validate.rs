@@ -0,0 +1,278 @@
+use crate::{
+    PrimitiveShape, PrimitiveShapeType, PrimitiveValue, S2JsonError, Shape, ShapeMergePolicy,
+    ShapePrimitive, ShapeType, ShapeViolation, ShapeViolationKind, Value, ValuePrimitive,
+    ValuePrimitiveType, ValueType,
+    impls::shape::{join_path, validate_types_at},
+};
+use alloc::{format, vec::Vec};
+use libm::trunc;
+
+// Validation rules:
+// - every key of the shape must be present unless it is optional, and no other keys may exist
+// - null is accepted wherever the shape is nullable
+// - primitives must match. A number also matches any number shape it would merge into, e.g. a
+//   `U64` matches an `F64` shape but not the reverse. Use coercion to cast numbers first
+// - arrays must be homogeneous and each element must match the array shape. Array shapes carry no
+//   markers, so like shape inference, null elements and keys missing from the objects of an array
+//   are accepted
+
+impl Value {
+    /// Check that the value conforms to the shape, reporting every violation
+    pub fn validate(&self, shape: &Shape) -> Result<(), Vec<ShapeViolation>> {
+        let mut violations = Vec::new();
+        validate_value(self, shape, "", &mut violations);
+        if violations.is_empty() { Ok(()) } else { Err(violations) }
+    }
+
+    /// Cast any number to the number type the shape expects, then validate. Casts that would lose
+    /// information (e.g. a negative number to `U64` or `2.5` to `I64`) are left as is and reported
+    pub fn coerce(&mut self, shape: &Shape) -> Result<(), Vec<ShapeViolation>> {
+        coerce_value(self, shape);
+        self.validate(shape)
+    }
+}
+
+impl ValueType {
+    /// Check that the value type conforms to the shape type, reporting every violation
+    pub fn validate(&self, shape: &ShapeType) -> Result<(), Vec<ShapeViolation>> {
+        let mut violations = Vec::new();
+        validate_type(self, shape, "", &mut violations);
+        if violations.is_empty() { Ok(()) } else { Err(violations) }
+    }
+
+    /// Cast any number to the number type the shape expects, then validate
+    pub fn coerce(&mut self, shape: &ShapeType) -> Result<(), Vec<ShapeViolation>> {
+        coerce_type(self, shape);
+        self.validate(shape)
+    }
+}
+
+impl PrimitiveValue {
+    /// Cast a number to the given number type if it can be done without losing information.
+    /// returns true if the value now matches the shape
+    pub fn coerce(&mut self, shape: &PrimitiveShape) -> bool {
+        if !self.is_number() || !shape.is_number() {
+            return PrimitiveShape::from(&*self) == *shape;
+        }
+        let cast = match (&*self, shape) {
+            (PrimitiveValue::U64(v), PrimitiveShape::I64) => {
+                i64::try_from(*v).ok().map(PrimitiveValue::I64)
+            }
+            (PrimitiveValue::I64(v), PrimitiveShape::U64) => {
+                u64::try_from(*v).ok().map(PrimitiveValue::U64)
+            }
+            (PrimitiveValue::F32(v), PrimitiveShape::U64 | PrimitiveShape::I64) => {
+                float_to_int(*v as f64, shape)
+            }
+            (PrimitiveValue::F64(v), PrimitiveShape::U64 | PrimitiveShape::I64) => {
+                float_to_int(*v, shape)
+            }
+            (_, PrimitiveShape::F32) => {
+                self.to_f32().filter(|&v| is_lossless(&*self, v as f64)).map(PrimitiveValue::F32)
+            }
+            (_, PrimitiveShape::F64) => {
+                self.to_f64().filter(|&v| is_lossless(&*self, v)).map(PrimitiveValue::F64)
+            }
+            _ => None,
+        };
+        if let Some(cast) = cast {
+            *self = cast;
+        }
+        PrimitiveShape::from(&*self) == *shape
+    }
+}
+
+/// returns true if the primitive matches the shape, see the validation rules above
+fn prim_matches(prim: &PrimitiveValue, shape: &PrimitiveShape) -> bool {
+    let prim_shape = PrimitiveShape::from(prim);
+    prim_shape == *shape
+        || (prim_shape.is_number()
+            && shape.is_number()
+            && PrimitiveShape::get_highest_order_number(&prim_shape, shape) == *shape)
+}
+
+/// returns true if the number cast to a float still holds the exact value of the original
+fn is_lossless(original: &PrimitiveValue, cast: f64) -> bool {
+    match original {
+        // wider integers catch floats that saturate when cast back
+        PrimitiveValue::U64(v) => cast as u128 == *v as u128,
+        PrimitiveValue::I64(v) => cast as i128 == *v as i128,
+        PrimitiveValue::F32(v) => cast == *v as f64,
+        PrimitiveValue::F64(v) => cast == *v || (cast.is_nan() && v.is_nan()),
+        _ => false,
+    }
+}
+
+/// Cast a float to an integer only if it is integral and in range
+fn float_to_int(v: f64, shape: &PrimitiveShape) -> Option<PrimitiveValue> {
+    if trunc(v) != v {
+        return None;
+    }
+    match shape {
+        PrimitiveShape::U64 if v >= 0. && v < u64::MAX as f64 => {
+            Some(PrimitiveValue::U64(v as u64))
+        }
+        PrimitiveShape::I64 if v >= i64::MIN as f64 && v < i64::MAX as f64 => {
+            Some(PrimitiveValue::I64(v as i64))
+        }
+        _ => None,
+    }
+}
+
+fn wrong_type(path: &str, expected: &ShapeType, found: &ValueType) -> ShapeViolation {
+    // the found shape never fails to build under the widen policy
+    let found = ShapeType::try_from_value_type(found, ShapeMergePolicy::Widen).unwrap_or_default();
+    ShapeViolation::new(path, ShapeViolationKind::WrongType { expected: expected.clone(), found })
+}
+
+fn validate_value(value: &Value, shape: &Shape, path: &str, violations: &mut Vec<ShapeViolation>) {
+    for (key, shape_type) in shape.iter() {
+        let key_path = join_path(path, key);
+        match value.get(key) {
+            Some(value_type) => validate_type(value_type, shape_type, &key_path, violations),
+            None if !shape_type.is_optional() => {
+                violations.push(ShapeViolation::new(&key_path, ShapeViolationKind::MissingKey))
+            }
+            None => {}
+        }
+    }
+    for key in value.keys().filter(|key| !shape.contains_key(*key)) {
+        violations.push(ShapeViolation::new(&join_path(path, key), ShapeViolationKind::ExtraKey));
+    }
+}
+
+fn validate_type(
+    value: &ValueType,
+    shape: &ShapeType,
+    path: &str,
+    violations: &mut Vec<ShapeViolation>,
+) {
+    if matches!(value, ValueType::Primitive(PrimitiveValue::Null)) && shape.is_nullable() {
+        return;
+    }
+    match (value, shape.unmarked()) {
+        (ValueType::Primitive(prim), ShapeType::Primitive(prim_shape)) => {
+            if !prim_matches(prim, prim_shape) {
+                violations.push(wrong_type(path, shape, value));
+            }
+        }
+        (ValueType::Array(array), ShapeType::Array(array_shape)) => {
+            if let Err(S2JsonError::ShapeMismatch { path, expected, found }) =
+                validate_types_at(array, path, ShapeMergePolicy::Strict)
+            {
+                violations.push(ShapeViolation::new(
+                    &path,
+                    ShapeViolationKind::HeterogeneousArray { expected, found },
+                ));
+            }
+            if let Some(elem_shape) = array_shape.first() {
+                for (i, elem) in array.iter().enumerate() {
+                    validate_elem(elem, elem_shape, &format!("{path}[{i}]"), violations);
+                }
+            }
+        }
+        (ValueType::Nested(nested), ShapeType::Nested(nested_shape)) => {
+            validate_value(nested, nested_shape, path, violations)
+        }
+        _ => violations.push(wrong_type(path, shape, value)),
+    }
+}
+
+fn validate_elem(
+    elem: &ValuePrimitiveType,
+    shape: &PrimitiveShapeType,
+    path: &str,
+    violations: &mut Vec<ShapeViolation>,
+) {
+    match (elem, shape) {
+        (ValuePrimitiveType::Primitive(PrimitiveValue::Null), _) => {}
+        (ValuePrimitiveType::Primitive(prim), PrimitiveShapeType::Primitive(prim_shape)) => {
+            if !prim_matches(prim, prim_shape) {
+                violations.push(ShapeViolation::new(
+                    path,
+                    ShapeViolationKind::WrongType {
+                        expected: shape.into(),
+                        found: ShapeType::Primitive(prim.into()),
+                    },
+                ));
+            }
+        }
+        (
+            ValuePrimitiveType::NestedPrimitive(nested),
+            PrimitiveShapeType::NestedPrimitive(nested_shape),
+        ) => validate_nested_primitive(nested, nested_shape, path, violations),
+        _ => violations.push(ShapeViolation::new(
+            path,
+            ShapeViolationKind::WrongType {
+                expected: shape.into(),
+                found: (&PrimitiveShapeType::from(elem)).into(),
+            },
+        )),
+    }
+}
+
+fn validate_nested_primitive(
+    nested: &ValuePrimitive,
+    shape: &ShapePrimitive,
+    path: &str,
+    violations: &mut Vec<ShapeViolation>,
+) {
+    for (key, prim) in nested.iter() {
+        let key_path = join_path(path, key);
+        match shape.get(key) {
+            Some(prim_shape) if prim.is_null() || prim_matches(prim, prim_shape) => {}
+            Some(prim_shape) => violations.push(ShapeViolation::new(
+                &key_path,
+                ShapeViolationKind::WrongType {
+                    expected: ShapeType::Primitive(prim_shape.clone()),
+                    found: ShapeType::Primitive(prim.into()),
+                },
+            )),
+            None => violations.push(ShapeViolation::new(&key_path, ShapeViolationKind::ExtraKey)),
+        }
+    }
+}
+
+fn coerce_value(value: &mut Value, shape: &Shape) {
+    for (key, value_type) in value.iter_mut() {
+        if let Some(shape_type) = shape.get(key) {
+            coerce_type(value_type, shape_type);
+        }
+    }
+}
+
+fn coerce_type(value: &mut ValueType, shape: &ShapeType) {
+    match (value, shape.unmarked()) {
+        (ValueType::Primitive(prim), ShapeType::Primitive(prim_shape)) => {
+            prim.coerce(prim_shape);
+        }
+        (ValueType::Array(array), ShapeType::Array(array_shape)) => {
+            let Some(elem_shape) = array_shape.first() else { return };
+            for elem in array.iter_mut() {
+                match (elem, elem_shape) {
+                    (
+                        ValuePrimitiveType::Primitive(prim),
+                        PrimitiveShapeType::Primitive(prim_shape),
+                    ) => {
+                        prim.coerce(prim_shape);
+                    }
+                    (
+                        ValuePrimitiveType::NestedPrimitive(nested),
+                        PrimitiveShapeType::NestedPrimitive(nested_shape),
+                    ) => {
+                        for (key, prim) in nested.iter_mut() {
+                            if let Some(prim_shape) = nested_shape.get(key) {
+                                prim.coerce(prim_shape);
+                            }
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+        (ValueType::Nested(nested), ShapeType::Nested(nested_shape)) => {
+            coerce_value(nested, nested_shape)
+        }
+        _ => {}
+    }
+}
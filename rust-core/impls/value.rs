@@ -1030,6 +1030,127 @@ impl From<&MValue> for MapboxProperties {
     }
 }
 
+// Protobuf
+
+#[derive(Default)]
+struct ValueEntry<T> {
+    key: String,
+    value: T,
+}
+impl<T: ProtoRead> ProtoRead for ValueEntry<T> {
+    fn read(&mut self, tag: u64, pb: &mut Protobuf) {
+        match tag {
+            1 => self.key = pb.read_string(),
+            2 => pb.read_message(&mut self.value),
+            _ => {}
+        }
+    }
+}
+fn write_entries<T: ProtoWrite>(pb: &mut Protobuf, map: &Map<String, T>) {
+    for (key, value) in map.iter() {
+        let mut entry = Protobuf::new();
+        entry.write_string_field(1, key);
+        entry.write_message(2, value);
+        pb.write_bytes_field(1, &entry.take());
+    }
+}
+impl ProtoWrite for Value {
+    fn write(&self, pb: &mut Protobuf) {
+        write_entries(pb, self);
+    }
+}
+impl ProtoRead for Value {
+    fn read(&mut self, tag: u64, pb: &mut Protobuf) {
+        if tag == 1 {
+            let mut entry = ValueEntry::<ValueType>::default();
+            pb.read_message(&mut entry);
+            self.insert(entry.key, entry.value);
+        }
+    }
+}
+impl ProtoWrite for ValuePrimitive {
+    fn write(&self, pb: &mut Protobuf) {
+        write_entries(pb, self);
+    }
+}
+impl ProtoRead for ValuePrimitive {
+    fn read(&mut self, tag: u64, pb: &mut Protobuf) {
+        if tag == 1 {
+            let mut entry = ValueEntry::<PrimitiveValue>::default();
+            pb.read_message(&mut entry);
+            self.insert(entry.key, entry.value);
+        }
+    }
+}
+impl ProtoWrite for ValueType {
+    fn write(&self, pb: &mut Protobuf) {
+        match self {
+            ValueType::Primitive(prim) => pb.write_message(1, prim),
+            ValueType::Array(array) if array.is_empty() => pb.write_varint_field(4, true),
+            ValueType::Array(array) => {
+                for elem in array {
+                    pb.write_message(2, elem);
+                }
+            }
+            ValueType::Nested(nested) => pb.write_message(3, nested),
+        }
+    }
+}
+impl ProtoRead for ValueType {
+    fn read(&mut self, tag: u64, pb: &mut Protobuf) {
+        match tag {
+            1 => {
+                let mut prim = PrimitiveValue::Null;
+                pb.read_message(&mut prim);
+                *self = ValueType::Primitive(prim);
+            }
+            2 => {
+                let mut elem = ValuePrimitiveType::Primitive(PrimitiveValue::Null);
+                pb.read_message(&mut elem);
+                match self {
+                    ValueType::Array(array) => array.push(elem),
+                    _ => *self = ValueType::Array(vec![elem]),
+                }
+            }
+            3 => {
+                let mut nested = Value::new();
+                pb.read_message(&mut nested);
+                *self = ValueType::Nested(nested);
+            }
+            4 => {
+                pb.read_varint::<u64>();
+                *self = ValueType::Array(vec![]);
+            }
+            _ => {}
+        }
+    }
+}
+impl ProtoWrite for ValuePrimitiveType {
+    fn write(&self, pb: &mut Protobuf) {
+        match self {
+            ValuePrimitiveType::Primitive(prim) => pb.write_message(1, prim),
+            ValuePrimitiveType::NestedPrimitive(nested) => pb.write_message(2, nested),
+        }
+    }
+}
+impl ProtoRead for ValuePrimitiveType {
+    fn read(&mut self, tag: u64, pb: &mut Protobuf) {
+        match tag {
+            1 => {
+                let mut prim = PrimitiveValue::Null;
+                pb.read_message(&mut prim);
+                *self = ValuePrimitiveType::Primitive(prim);
+            }
+            2 => {
+                let mut nested = ValuePrimitive::new();
+                pb.read_message(&mut nested);
+                *self = ValuePrimitiveType::NestedPrimitive(nested);
+            }
+            _ => {}
+        }
+    }
+}
+
 // Geometry
 
 impl From<&Point> for ValueType {
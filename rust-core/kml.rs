@@ -0,0 +1,208 @@
+use crate::{
+    xml::{XmlElement, parse_xml, xml_value},
+    *,
+};
+use alloc::{format, vec::Vec};
+
+/// The m-value key of each point's timestamp in a `gx:Track`
+pub const KML_TIME_KEY: &str = "when";
+
+/// The geometries of a placemark, grouped by kind
+struct KmlGeometries<D: Clone + Default> {
+    points: VectorMultiPoint<D>,
+    lines: VectorMultiLineString<D>,
+    polygons: VectorMultiPolygon<D>,
+}
+impl<D: Clone + Default> Default for KmlGeometries<D> {
+    fn default() -> Self {
+        KmlGeometries { points: Vec::new(), lines: Vec::new(), polygons: Vec::new() }
+    }
+}
+impl<D: MValueCompatible> KmlGeometries<D> {
+    /// Read a geometry element, flattening multi geometries. Returns false if it is not one
+    fn read(&mut self, element: &XmlElement) -> Result<bool, S2JsonError> {
+        match element.name.as_str() {
+            "Point" => self.points.extend(coordinates(element)?),
+            "LineString" | "LinearRing" => self.lines.push(coordinates(element)?),
+            "Polygon" => {
+                let mut rings = Vec::new();
+                for boundary in ["outerBoundaryIs", "innerBoundaryIs"] {
+                    for boundary in element.children_named(boundary) {
+                        for ring in boundary.children_named("LinearRing") {
+                            rings.push(coordinates(ring)?);
+                        }
+                    }
+                }
+                self.polygons.push(rings);
+            }
+            "Track" => self.lines.push(track(element)?),
+            "MultiTrack" => {
+                for child in element.children_named("Track") {
+                    self.lines.push(track(child)?);
+                }
+            }
+            "MultiGeometry" => {
+                for child in &element.children {
+                    self.read(child)?;
+                }
+            }
+            _ => return Ok(false),
+        }
+        Ok(true)
+    }
+
+    /// A geometry for each kind present, multi if it has several members
+    fn into_geometries(mut self) -> Vec<VectorGeometry<D>> {
+        let mut geometries = Vec::new();
+        match self.points.len() {
+            0 => {}
+            1 => geometries.push(VectorGeometry::new_point(self.points.remove(0), None)),
+            _ => geometries.push(VectorGeometry::new_multipoint(self.points, None)),
+        }
+        match self.lines.len() {
+            0 => {}
+            1 => geometries.push(VectorGeometry::new_linestring(self.lines.remove(0), None)),
+            _ => geometries.push(VectorGeometry::new_multilinestring(self.lines, None)),
+        }
+        match self.polygons.len() {
+            0 => {}
+            1 => geometries.push(VectorGeometry::new_polygon(self.polygons.remove(0), None)),
+            _ => geometries.push(VectorGeometry::new_multipolygon(self.polygons, None)),
+        }
+        geometries
+    }
+}
+
+/// Read the `lon,lat[,alt]` tuples of an element's coordinates
+fn coordinates<D: Clone>(element: &XmlElement) -> Result<Vec<VectorPoint<D>>, S2JsonError> {
+    let Some(coordinates) = element.child("coordinates") else {
+        return Err(S2JsonError::invalid_xml(
+            element.offset,
+            format!("<{}> has no coordinates", element.name),
+        ));
+    };
+    coordinates
+        .text
+        .split_whitespace()
+        .map(|tuple| {
+            let ordinates: Option<Vec<f64>> =
+                tuple.split(',').map(|ordinate| ordinate.parse().ok()).collect();
+            match ordinates.as_deref() {
+                Some([x, y]) => Ok(VectorPoint::new(*x, *y, None, None)),
+                Some([x, y, z]) => Ok(VectorPoint::new(*x, *y, Some(*z), None)),
+                _ => Err(S2JsonError::invalid_xml(
+                    coordinates.offset,
+                    format!("invalid coordinate \"{tuple}\""),
+                )),
+            }
+        })
+        .collect()
+}
+
+/// Read a `gx:Track`. Each `when` and each value of its `gx:SimpleArrayData` become m-values of
+/// the matching `gx:coord`
+fn track<D: MValueCompatible>(element: &XmlElement) -> Result<VectorLineString<D>, S2JsonError> {
+    let mut m_values: Vec<MValue> = element
+        .children_named("when")
+        .map(|when| MValue::from([(KML_TIME_KEY.into(), when.text.trim().into())]))
+        .collect();
+    let arrays = element
+        .children_named("ExtendedData")
+        .flat_map(|data| data.children_named("SchemaData"))
+        .flat_map(|data| data.children_named("SimpleArrayData"));
+    for array in arrays {
+        let Some(name) = array.attribute("name") else { continue };
+        for (i, value) in array.children_named("value").enumerate() {
+            if m_values.len() <= i {
+                m_values.resize(i + 1, MValue::new());
+            }
+            m_values[i].insert(name.into(), xml_value(&value.text));
+        }
+    }
+
+    let mut m_values = m_values.into_iter();
+    element
+        .children_named("coord")
+        .map(|coord| {
+            let ordinates: Option<Vec<f64>> =
+                coord.text.split_whitespace().map(|ordinate| ordinate.parse().ok()).collect();
+            let z = match ordinates.as_deref() {
+                Some([_, _]) => None,
+                Some([_, _, z]) => Some(*z),
+                _ => {
+                    return Err(S2JsonError::invalid_xml(
+                        coord.offset,
+                        format!("invalid coordinate \"{}\"", coord.text.trim()),
+                    ));
+                }
+            };
+            let ordinates = ordinates.unwrap_or_default();
+            let m = m_values.next().filter(|m| !m.is_empty()).map(Into::into);
+            Ok(VectorPoint::new(ordinates[0], ordinates[1], z, m))
+        })
+        .collect()
+}
+
+/// Read the name, description and extended data of a placemark
+fn kml_properties(placemark: &XmlElement) -> Properties {
+    let mut properties = Properties::new();
+    for tag in ["name", "description"] {
+        if let Some(text) = placemark.child_text(tag) {
+            properties.insert(tag.into(), text.into());
+        }
+    }
+    for extended in placemark.children_named("ExtendedData") {
+        for data in extended.children_named("Data") {
+            if let (Some(name), Some(value)) = (data.attribute("name"), data.child_text("value")) {
+                properties.insert(name.into(), xml_value(value));
+            }
+        }
+        for simple in extended
+            .children_named("SchemaData")
+            .flat_map(|schema| schema.children_named("SimpleData"))
+        {
+            if let Some(name) = simple.attribute("name") {
+                properties.insert(name.into(), xml_value(&simple.text));
+            }
+        }
+    }
+    properties
+}
+
+/// Read the placemarks of a KML document, however deeply nested in documents and folders, into
+/// [`VectorFeature`]s.
+///
+/// Points, line strings, linear rings, polygons and `gx:Track`s are supported, with altitude read
+/// as z. Each point of a track carries its `when` timestamp under [`KML_TIME_KEY`] and its
+/// `gx:SimpleArrayData` values in its m-values. A multi geometry becomes a multi point, line or
+/// polygon, or a feature for each of these kinds if it mixes them. Names, descriptions and
+/// `ExtendedData` become properties. Placemarks without a geometry are skipped
+pub fn read_kml<D: MValueCompatible>(
+    kml: &str,
+) -> Result<Vec<VectorFeature<(), Properties, D>>, S2JsonError> {
+    let root = parse_xml(kml)?;
+    if root.name != "kml" {
+        return Err(S2JsonError::invalid_xml(
+            root.offset,
+            format!("expected a <kml> root element, found <{}>", root.name),
+        ));
+    }
+
+    let mut placemarks = Vec::new();
+    root.descendants("Placemark", &mut placemarks);
+    let mut features = Vec::new();
+    for placemark in placemarks {
+        let mut geometries = KmlGeometries::default();
+        for child in &placemark.children {
+            if geometries.read(child)? {
+                break;
+            }
+        }
+        let properties = kml_properties(placemark);
+        let id = placemark.attribute("id").and_then(|id| id.parse().ok());
+        for geometry in geometries.into_geometries() {
+            features.push(VectorFeature::new_wm(id, properties.clone(), geometry, None));
+        }
+    }
+    Ok(features)
+}
@@ -11,24 +11,65 @@
 
 extern crate alloc;
 
+/// CSV and TSV point reading and writing
+pub mod csv;
+/// The crate wide error type
+pub mod error;
+/// Geobuf inspired encoding of WG FeatureCollections
+pub mod geobuf;
 /// All geometry types and structs
 pub mod geometry;
+/// GPX reading
+pub mod gpx;
 /// All json, value, shape impl
 pub mod impls;
+/// KML reading
+pub mod kml;
+/// Line delimited S2JSON reader and writer
+pub mod line_delimited;
 /// BTreeMap wrapper
 pub mod map;
+/// Mapbox Vector Tile encoding and decoding
+pub mod mvt;
+/// Encoded polylines (Google and flexible polyline algorithms)
+pub mod polyline;
+/// S2 Geometry specific tooling
+pub mod s2;
 /// All shape types and structs
 pub mod shape;
+/// Quad-tree tiling of WM and S2 vector features
+pub mod tile;
+/// TopoJSON reading
+pub mod topojson;
 /// All values types and structs
 pub mod value;
+/// Well-known binary (WKB and EWKB) encoding and decoding
+pub mod wkb;
+/// Well-known text (WKT and EWKT) reading and writing
+pub mod wkt;
+/// Minimal XML parsing shared by the GPX and KML readers
+mod xml;
 
-use alloc::{string::String, vec::Vec};
+use alloc::{format, string::String, vec::Vec};
+use core::str::FromStr;
+pub use csv::*;
+pub use error::*;
 pub use geometry::*;
+pub use gpx::*;
 pub use impls::*;
+pub use kml::*;
+pub use line_delimited::*;
 pub use map::*;
+pub use mvt::*;
+pub use polyline::*;
+pub use s2::*;
 use serde::{Deserialize, Serialize};
 pub use shape::*;
+pub use tile::*;
+pub use topojson::*;
 pub use value::*;
+pub use wkb::*;
+pub use wkt::*;
 
 /// All projections that can be used
 #[derive(Serialize, Deserialize, Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Default)]
@@ -110,9 +151,21 @@ pub enum FeatureCollectionType {
     #[default]
     FeatureCollection,
 }
-impl From<&str> for FeatureCollectionType {
-    fn from(_: &str) -> Self {
-        FeatureCollectionType::FeatureCollection
+impl TryFrom<&str> for FeatureCollectionType {
+    type Error = S2JsonError;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        match s {
+            "FeatureCollection" => Ok(FeatureCollectionType::FeatureCollection),
+            _ => Err(S2JsonError::invalid_type("FeatureCollectionType", s)),
+        }
+    }
+}
+impl FromStr for FeatureCollectionType {
+    type Err = S2JsonError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.try_into()
     }
 }
 
@@ -123,9 +176,21 @@ pub enum S2FeatureCollectionType {
     #[default]
     S2FeatureCollection,
 }
-impl From<&str> for S2FeatureCollectionType {
-    fn from(_: &str) -> Self {
-        S2FeatureCollectionType::S2FeatureCollection
+impl TryFrom<&str> for S2FeatureCollectionType {
+    type Error = S2JsonError;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        match s {
+            "S2FeatureCollection" => Ok(S2FeatureCollectionType::S2FeatureCollection),
+            _ => Err(S2JsonError::invalid_type("S2FeatureCollectionType", s)),
+        }
+    }
+}
+impl FromStr for S2FeatureCollectionType {
+    type Err = S2JsonError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.try_into()
     }
 }
 
@@ -149,7 +214,12 @@ pub struct FeatureCollection<M = (), P: Clone + Default = Properties, D: Clone +
 impl<M, P: Clone + Default, D: Clone + Default> FeatureCollection<M, P, D> {
     /// Create a new FeatureCollection
     pub fn new(attributions: Option<Attributions>) -> Self {
-        Self { _type: "FeatureCollection".into(), features: Vec::new(), attributions, bbox: None }
+        Self {
+            _type: FeatureCollectionType::FeatureCollection,
+            features: Vec::new(),
+            attributions,
+            bbox: None,
+        }
     }
 
     /// update the bounding box
@@ -182,7 +252,7 @@ impl<M, P: Clone + Default, D: Clone + Default> S2FeatureCollection<M, P, D> {
     /// Create a new S2FeatureCollection
     pub fn new(attributions: Option<Attributions>) -> Self {
         Self {
-            _type: "S2FeatureCollection".into(),
+            _type: S2FeatureCollectionType::S2FeatureCollection,
             features: Vec::new(),
             faces: Vec::new(),
             attributions,
@@ -214,9 +284,21 @@ pub enum FeatureType {
     #[default]
     Feature,
 }
-impl From<&str> for FeatureType {
-    fn from(_: &str) -> Self {
-        FeatureType::Feature
+impl TryFrom<&str> for FeatureType {
+    type Error = S2JsonError;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        match s {
+            "Feature" => Ok(FeatureType::Feature),
+            _ => Err(S2JsonError::invalid_type("FeatureType", s)),
+        }
+    }
+}
+impl FromStr for FeatureType {
+    type Err = S2JsonError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.try_into()
     }
 }
 
@@ -241,13 +323,13 @@ pub struct Feature<M = (), P: Clone + Default = Properties, D: Clone + Default =
 impl<M, P: Clone + Default, D: Clone + Default> Feature<M, P, D> {
     /// Create a new Feature
     pub fn new(id: Option<u64>, properties: P, geometry: Geometry<D>, metadata: Option<M>) -> Self {
-        Self { _type: "Feature".into(), id, properties, geometry, metadata }
+        Self { _type: FeatureType::Feature, id, properties, geometry, metadata }
     }
 }
 impl<M, P: Clone + Default, D: Clone + Default> Default for Feature<M, P, D> {
     fn default() -> Self {
         Self {
-            _type: "Feature".into(),
+            _type: FeatureType::Feature,
             id: None,
             properties: Default::default(),
             geometry: Default::default(),
@@ -257,7 +339,7 @@ impl<M, P: Clone + Default, D: Clone + Default> Default for Feature<M, P, D> {
 }
 
 /// Feature type string
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default)]
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, PartialEq, Default)]
 pub enum VectorFeatureType {
     /// WG Feature
     #[default]
@@ -265,11 +347,40 @@ pub enum VectorFeatureType {
     /// S2 Feature
     S2Feature,
 }
-impl From<&str> for VectorFeatureType {
-    fn from(s: &str) -> Self {
+impl TryFrom<&str> for VectorFeatureType {
+    type Error = S2JsonError;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
         match s {
-            "S2Feature" => VectorFeatureType::S2Feature,
-            _ => VectorFeatureType::VectorFeature,
+            "VectorFeature" => Ok(VectorFeatureType::VectorFeature),
+            "S2Feature" => Ok(VectorFeatureType::S2Feature),
+            _ => Err(S2JsonError::invalid_type("VectorFeatureType", s)),
+        }
+    }
+}
+impl FromStr for VectorFeatureType {
+    type Err = S2JsonError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.try_into()
+    }
+}
+impl From<VectorFeatureType> for u8 {
+    fn from(feature_type: VectorFeatureType) -> Self {
+        match feature_type {
+            VectorFeatureType::VectorFeature => 0,
+            VectorFeatureType::S2Feature => 1,
+        }
+    }
+}
+impl TryFrom<u8> for VectorFeatureType {
+    type Error = S2JsonError;
+
+    fn try_from(feature_type: u8) -> Result<Self, Self::Error> {
+        match feature_type {
+            0 => Ok(VectorFeatureType::VectorFeature),
+            1 => Ok(VectorFeatureType::S2Feature),
+            _ => Err(S2JsonError::invalid_type("VectorFeatureType", &format!("{feature_type}"))),
         }
     }
 }
@@ -298,7 +409,7 @@ pub struct VectorFeature<M = (), P: Clone + Default = Properties, D: Clone + Def
 impl<M, P: Clone + Default, D: Clone + Default> Default for VectorFeature<M, P, D> {
     fn default() -> Self {
         Self {
-            _type: "VectorFeature".into(),
+            _type: VectorFeatureType::VectorFeature,
             face: 0.into(),
             id: None,
             properties: Default::default(),
@@ -315,7 +426,14 @@ impl<M, P: Clone + Default, D: Clone + Default> VectorFeature<M, P, D> {
         geometry: VectorGeometry<D>,
         metadata: Option<M>,
     ) -> Self {
-        Self { _type: "VectorFeature".into(), face: 0.into(), id, properties, geometry, metadata }
+        Self {
+            _type: VectorFeatureType::VectorFeature,
+            face: 0.into(),
+            id,
+            properties,
+            geometry,
+            metadata,
+        }
     }
 
     /// Create a new VectorFeature in the WG format
@@ -326,7 +444,7 @@ impl<M, P: Clone + Default, D: Clone + Default> VectorFeature<M, P, D> {
         geometry: VectorGeometry<D>,
         metadata: Option<M>,
     ) -> Self {
-        Self { _type: "S2Feature".into(), face, id, properties, geometry, metadata }
+        Self { _type: VectorFeatureType::S2Feature, face, id, properties, geometry, metadata }
     }
 
     /// Create a new VectorFeature using an input VectorFeature. Assign new geometry if provided
@@ -338,7 +456,7 @@ impl<M, P: Clone + Default, D: Clone + Default> VectorFeature<M, P, D> {
         M: Clone,
     {
         Self {
-            _type: feature._type.clone(),
+            _type: feature._type,
             id: feature.id,
             face: feature.face,
             properties: feature.properties.clone(),
@@ -359,7 +477,7 @@ impl<M, P: Clone + Default, D: Clone + Default> VectorFeature<M, P, D> {
         D: MValueCompatible,
     {
         VectorFeature {
-            _type: self._type.clone(),
+            _type: self._type,
             id: self.id,
             face: self.face,
             properties: self.properties.clone().into(),
@@ -0,0 +1,166 @@
+use crate::*;
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::{fmt, marker::PhantomData};
+use serde::{Serialize, de::DeserializeOwned};
+
+/// An error found while reading or writing a single line of line-delimited S2JSON
+#[derive(Debug, Clone, PartialEq)]
+pub struct LineError {
+    /// The 1-based line number the error was found on
+    pub line: usize,
+    /// Description of what went wrong
+    pub message: String,
+}
+impl LineError {
+    /// Create a new LineError
+    pub fn new(line: usize, message: impl Into<String>) -> Self {
+        Self { line, message: message.into() }
+    }
+}
+impl fmt::Display for LineError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+impl core::error::Error for LineError {}
+
+/// # Line Delimited Reader
+///
+/// ## Description
+/// Reads `.s2jsonld` / `.geojsonld` data one line at a time from any byte source. Each non-empty
+/// line is parsed into a [`Features`]. Lines may be separated by `\n` or `\r\n` (spec 2.2.1).
+///
+/// A line that fails to parse yields an `Err` holding its line number, but the stream continues
+/// with the next line. Only one line is buffered at a time.
+///
+/// ## Usage
+/// - [`LineDelimitedReader::new`]: Create a new reader from a byte iterator
+/// - [`LineDelimitedReader::line`]: The number of lines consumed so far
+#[derive(Debug)]
+pub struct LineDelimitedReader<
+    I: Iterator<Item = u8>,
+    M = (),
+    P: Clone + Default = Properties,
+    D: Clone + Default = MValue,
+> {
+    bytes: I,
+    line: usize,
+    buf: Vec<u8>,
+    _marker: PhantomData<(M, P, D)>,
+}
+impl<I: Iterator<Item = u8>, M, P: Clone + Default, D: Clone + Default>
+    LineDelimitedReader<I, M, P, D>
+{
+    /// Create a new reader from a byte iterator
+    pub fn new(bytes: I) -> Self {
+        Self { bytes, line: 0, buf: Vec::new(), _marker: PhantomData }
+    }
+
+    /// The number of lines consumed so far
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    /// Fill the internal buffer with the next line. Returns false if the source is exhausted
+    fn next_line(&mut self) -> bool {
+        self.buf.clear();
+        let mut found = false;
+        for byte in self.bytes.by_ref() {
+            found = true;
+            if byte == b'\n' {
+                break;
+            }
+            self.buf.push(byte);
+        }
+        if !found {
+            return false;
+        }
+        if self.buf.last() == Some(&b'\r') {
+            self.buf.pop();
+        }
+        self.line += 1;
+        true
+    }
+}
+impl<I: Iterator<Item = u8>, M, P: Clone + Default, D: Clone + Default> Iterator
+    for LineDelimitedReader<I, M, P, D>
+where
+    M: DeserializeOwned,
+    P: DeserializeOwned,
+    D: DeserializeOwned,
+{
+    type Item = Result<Features<M, P, D>, LineError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.next_line() {
+            if self.buf.iter().all(u8::is_ascii_whitespace) {
+                continue;
+            }
+            return Some(
+                serde_json::from_slice(&self.buf)
+                    .map_err(|e| LineError::new(self.line, e.to_string())),
+            );
+        }
+        None
+    }
+}
+
+/// # Line Delimited Writer
+///
+/// ## Description
+/// Writes features to any [`fmt::Write`] sink, one JSON object per line terminated by `\n`.
+///
+/// ## Usage
+/// - [`LineDelimitedWriter::new`]: Create a new writer wrapping a sink
+/// - [`LineDelimitedWriter::write_feature`]: Write a single feature as one line
+/// - [`LineDelimitedWriter::write_features`]: Write a collection of features
+/// - [`LineDelimitedWriter::lines`]: The number of lines written so far
+/// - [`LineDelimitedWriter::into_inner`]: Consume the writer and return the sink
+#[derive(Debug)]
+pub struct LineDelimitedWriter<W: fmt::Write> {
+    writer: W,
+    lines: usize,
+}
+impl<W: fmt::Write> LineDelimitedWriter<W> {
+    /// Create a new writer wrapping a sink
+    pub fn new(writer: W) -> Self {
+        Self { writer, lines: 0 }
+    }
+
+    /// Write a single feature as one line. Accepts a [`Feature`], [`VectorFeature`] or [`Features`]
+    pub fn write_feature<T: Serialize>(&mut self, feature: &T) -> Result<(), LineError> {
+        let line = self.lines + 1;
+        let json =
+            serde_json::to_string(feature).map_err(|e| LineError::new(line, e.to_string()))?;
+        self.writer
+            .write_str(&json)
+            .and_then(|_| self.writer.write_char('\n'))
+            .map_err(|_| LineError::new(line, "failed to write to the sink"))?;
+        self.lines = line;
+        Ok(())
+    }
+
+    /// Write a collection of features, stopping at the first failure
+    pub fn write_features<'a, T: Serialize + 'a>(
+        &mut self,
+        features: impl IntoIterator<Item = &'a T>,
+    ) -> Result<(), LineError> {
+        for feature in features {
+            self.write_feature(feature)?;
+        }
+        Ok(())
+    }
+
+    /// The number of lines written so far
+    pub fn lines(&self) -> usize {
+        self.lines
+    }
+
+    /// Consume the writer and return the sink
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
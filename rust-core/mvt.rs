@@ -0,0 +1,544 @@
+use crate::*;
+use alloc::{collections::BTreeMap, string::String, vec, vec::Vec};
+use libm::round;
+use pbf::{ProtoRead, ProtoWrite, Protobuf, zagzig, zigzag};
+
+/// The extent used by most Mapbox Vector Tile layers
+pub const MVT_DEFAULT_EXTENT: u32 = 4_096;
+
+const GEOM_POINT: u8 = 1;
+const GEOM_LINESTRING: u8 = 2;
+const GEOM_POLYGON: u8 = 3;
+
+const CMD_MOVE_TO: u32 = 1;
+const CMD_LINE_TO: u32 = 2;
+const CMD_CLOSE_PATH: u32 = 7;
+
+/// # Mapbox Vector Tile
+///
+/// ## Description
+/// A Mapbox Vector Tile (MVT v2) made up of named layers. Encode it with `ProtoWrite` and decode it
+/// with `ProtoRead`.
+///
+/// ## Usage
+/// - [`MapboxVectorTile::new`]: Create an empty tile
+/// - [`MapboxVectorTile::layer`]: Find a layer by name
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct MapboxVectorTile<P: Clone + Default = MapboxProperties, D: Clone + Default = MValue> {
+    /// The layers of the tile
+    pub layers: Vec<MapboxVectorLayer<P, D>>,
+}
+impl<P: Clone + Default, D: Clone + Default> MapboxVectorTile<P, D> {
+    /// Create an empty tile
+    pub fn new() -> Self {
+        Self { layers: vec![] }
+    }
+
+    /// Find a layer by name
+    pub fn layer(&self, name: &str) -> Option<&MapboxVectorLayer<P, D>> {
+        self.layers.iter().find(|layer| layer.name == name)
+    }
+}
+
+/// # Mapbox Vector Layer
+///
+/// ## Description
+/// A named layer of features whose geometry is already projected to 0->1 tile space (see
+/// [`VectorPoint::project`]). Coordinates are scaled by `extent` when encoded and back when
+/// decoded.
+///
+/// Encoding builds the deduplicated key and value tables from the properties. Only primitive
+/// properties survive, and null values are dropped. Polygon rings are rewound so that exterior
+/// rings have a positive area and holes a negative one, and lines and rings are deduplicated
+/// after snapping to the extent. M-values, z values and bounding boxes are not part of MVT.
+///
+/// Decoding classifies polygon rings by the winding of the first ring, so tiles written with
+/// either winding convention are read.
+///
+/// ## Usage
+/// - [`MapboxVectorLayer::new`]: Create an empty layer with a name and extent
+#[derive(Debug, Clone, PartialEq)]
+pub struct MapboxVectorLayer<P: Clone + Default = MapboxProperties, D: Clone + Default = MValue> {
+    /// The MVT version. Layers are always written as version 2
+    pub version: u32,
+    /// The name of the layer
+    pub name: String,
+    /// The extent of the layer, e.g. 4096
+    pub extent: u32,
+    /// The features of the layer in 0->1 tile space
+    pub features: Vec<VectorFeature<(), P, D>>,
+}
+impl<P: Clone + Default, D: Clone + Default> MapboxVectorLayer<P, D> {
+    /// Create an empty layer with a name and extent
+    pub fn new(name: &str, extent: u32) -> Self {
+        Self { version: 2, name: name.into(), extent, features: vec![] }
+    }
+}
+
+// Encoding
+
+/// A deduplicated table of layer keys or values
+pub(crate) struct Table<T: Ord + Clone> {
+    indices: BTreeMap<T, u32>,
+    pub(crate) list: Vec<T>,
+}
+impl<T: Ord + Clone> Table<T> {
+    pub(crate) fn new() -> Self {
+        Self { indices: BTreeMap::new(), list: vec![] }
+    }
+
+    pub(crate) fn index(&mut self, item: &T) -> u32 {
+        if let Some(index) = self.indices.get(item) {
+            return *index;
+        }
+        let index = self.list.len() as u32;
+        self.indices.insert(item.clone(), index);
+        self.list.push(item.clone());
+        index
+    }
+}
+
+/// An MVT value message. Nulls have no MVT representation and are never written
+struct MapboxValue<'a>(&'a PrimitiveValue);
+impl ProtoWrite for MapboxValue<'_> {
+    fn write(&self, pb: &mut Protobuf) {
+        match self.0 {
+            PrimitiveValue::String(value) => pb.write_string_field(1, value),
+            PrimitiveValue::F32(value) => pb.write_fixed_field(2, *value),
+            PrimitiveValue::F64(value) => pb.write_fixed_field(3, *value),
+            PrimitiveValue::U64(value) => pb.write_varint_field(5, *value),
+            PrimitiveValue::I64(value) => pb.write_s_varint_field(6, *value),
+            PrimitiveValue::Bool(value) => pb.write_varint_field(7, *value),
+            PrimitiveValue::Null => {}
+        }
+    }
+}
+
+/// Builds the command integers of a feature geometry, tracking the cursor between commands
+struct CommandWriter {
+    cursor: (i64, i64),
+    commands: Vec<u32>,
+}
+impl CommandWriter {
+    fn command(&mut self, id: u32, count: usize) {
+        self.commands.push((id & 0x7) | ((count as u32) << 3));
+    }
+
+    fn points(&mut self, id: u32, points: &[(i64, i64)]) {
+        if points.is_empty() {
+            return;
+        }
+        self.command(id, points.len());
+        for &(x, y) in points {
+            self.commands.push(zigzag(x - self.cursor.0) as u32);
+            self.commands.push(zigzag(y - self.cursor.1) as u32);
+            self.cursor = (x, y);
+        }
+    }
+
+    fn line(&mut self, line: &[(i64, i64)]) {
+        self.points(CMD_MOVE_TO, &line[..1]);
+        self.points(CMD_LINE_TO, &line[1..]);
+    }
+}
+
+/// Snapped coordinates are clamped to this magnitude, so the delta between any two of them fits
+/// the 32-bit command parameters
+const MAX_COORDINATE: i64 = i32::MAX as i64 / 2;
+
+/// Snap a point in 0->1 tile space to the extent
+fn snap<D: Clone>(point: &VectorPoint<D>, extent: f64) -> (i64, i64) {
+    let snap = |v: f64| (round(v * extent) as i64).clamp(-MAX_COORDINATE, MAX_COORDINATE);
+    (snap(point.x), snap(point.y))
+}
+
+/// Snap a line to the extent, dropping points that collapse onto the previous one
+fn snap_line<D: Clone>(line: &[VectorPoint<D>], extent: f64) -> Vec<(i64, i64)> {
+    let mut res: Vec<(i64, i64)> = Vec::with_capacity(line.len());
+    for point in line {
+        let point = snap(point, extent);
+        if res.last() != Some(&point) {
+            res.push(point);
+        }
+    }
+    res
+}
+
+/// Twice the signed area of a ring by the surveyor's formula. Positive for exterior rings in tile
+/// space (y down)
+fn ring_area(ring: &[(i64, i64)]) -> i64 {
+    let mut area: i64 = 0;
+    for (i, &(x1, y1)) in ring.iter().enumerate() {
+        let (x2, y2) = ring[(i + 1) % ring.len()];
+        // wraps instead of overflowing on the far out coordinates of crafted tiles
+        area = area.wrapping_add(x1.wrapping_mul(y2).wrapping_sub(x2.wrapping_mul(y1)));
+    }
+    area
+}
+
+/// Snap, close and rewind a polygon, returning None if its exterior ring collapses
+fn snap_polygon<D: Clone>(
+    polygon: &[Vec<VectorPoint<D>>],
+    extent: f64,
+) -> Option<Vec<Vec<(i64, i64)>>> {
+    let mut res = vec![];
+    for (i, ring) in polygon.iter().enumerate() {
+        let mut ring = snap_line(ring, extent);
+        if ring.len() > 1 && ring.first() == ring.last() {
+            ring.pop();
+        }
+        let area = if ring.len() < 3 { 0 } else { ring_area(&ring) };
+        if area == 0 {
+            if i == 0 {
+                return None;
+            }
+            continue;
+        }
+        if (i == 0) != (area > 0) {
+            // keep the starting point
+            ring[1..].reverse();
+        }
+        res.push(ring);
+    }
+    Some(res)
+}
+
+/// Encode a geometry in 0->1 tile space, returning its MVT type and commands. Returns None if
+/// nothing is left to draw once snapped to the extent
+fn encode_geometry<D: Clone + Default>(
+    geometry: &VectorGeometry<D>,
+    extent: u32,
+) -> Option<(u8, Vec<u32>)> {
+    let extent = extent as f64;
+    let mut writer = CommandWriter { cursor: (0, 0), commands: vec![] };
+    let geom_type = match geometry {
+        VectorGeometry::Point(g) => {
+            writer.points(CMD_MOVE_TO, &[snap(&g.coordinates, extent)]);
+            GEOM_POINT
+        }
+        VectorGeometry::MultiPoint(g) => {
+            let points: Vec<(i64, i64)> = g.coordinates.iter().map(|p| snap(p, extent)).collect();
+            writer.points(CMD_MOVE_TO, &points);
+            GEOM_POINT
+        }
+        VectorGeometry::LineString(g) => {
+            encode_lines(&mut writer, core::slice::from_ref(&g.coordinates), extent);
+            GEOM_LINESTRING
+        }
+        VectorGeometry::MultiLineString(g) => {
+            encode_lines(&mut writer, &g.coordinates, extent);
+            GEOM_LINESTRING
+        }
+        VectorGeometry::Polygon(g) => {
+            encode_polygons(&mut writer, core::slice::from_ref(&g.coordinates), extent);
+            GEOM_POLYGON
+        }
+        VectorGeometry::MultiPolygon(g) => {
+            encode_polygons(&mut writer, &g.coordinates, extent);
+            GEOM_POLYGON
+        }
+    };
+    if writer.commands.is_empty() { None } else { Some((geom_type, writer.commands)) }
+}
+
+fn encode_lines<D: Clone>(writer: &mut CommandWriter, lines: &[Vec<VectorPoint<D>>], extent: f64) {
+    for line in lines {
+        let line = snap_line(line, extent);
+        if line.len() >= 2 {
+            writer.line(&line);
+        }
+    }
+}
+
+fn encode_polygons<D: Clone>(
+    writer: &mut CommandWriter,
+    polygons: &[Vec<Vec<VectorPoint<D>>>],
+    extent: f64,
+) {
+    for polygon in polygons {
+        for ring in snap_polygon(polygon, extent).unwrap_or_default() {
+            writer.line(&ring);
+            writer.command(CMD_CLOSE_PATH, 1);
+        }
+    }
+}
+
+impl<P: Clone + Default, D: Clone + Default> ProtoWrite for MapboxVectorLayer<P, D>
+where
+    for<'a> MapboxProperties: From<&'a P>,
+{
+    fn write(&self, pb: &mut Protobuf) {
+        pb.write_varint_field(15, 2_u32);
+        pb.write_string_field(1, &self.name);
+        let mut keys = Table::<String>::new();
+        let mut values = Table::<PrimitiveValue>::new();
+        for feature in &self.features {
+            let Some((geom_type, commands)) = encode_geometry(&feature.geometry, self.extent)
+            else {
+                continue;
+            };
+            let properties = MapboxProperties::from(&feature.properties);
+            let mut tags = vec![];
+            for (key, value) in properties.iter().filter(|(_, value)| !value.is_null()) {
+                tags.push(keys.index(key));
+                tags.push(values.index(value));
+            }
+            let mut mvt_feature = Protobuf::new();
+            if let Some(id) = feature.id {
+                mvt_feature.write_varint_field(1, id);
+            }
+            if !tags.is_empty() {
+                mvt_feature.write_packed_varint(2, &tags);
+            }
+            mvt_feature.write_varint_field(3, geom_type);
+            mvt_feature.write_packed_varint(4, &commands);
+            pb.write_bytes_field(2, &mvt_feature.take());
+        }
+        for key in &keys.list {
+            pb.write_string_field(3, key);
+        }
+        for value in &values.list {
+            pb.write_message(4, &MapboxValue(value));
+        }
+        pb.write_varint_field(5, self.extent);
+    }
+}
+impl<P: Clone + Default, D: Clone + Default> ProtoWrite for MapboxVectorTile<P, D>
+where
+    for<'a> MapboxProperties: From<&'a P>,
+{
+    fn write(&self, pb: &mut Protobuf) {
+        for layer in &self.layers {
+            pb.write_message(3, layer);
+        }
+    }
+}
+
+// Decoding
+
+/// An MVT value message being read
+#[derive(Default)]
+struct MapboxValueReader(PrimitiveValue);
+impl ProtoRead for MapboxValueReader {
+    fn read(&mut self, tag: u64, pb: &mut Protobuf) {
+        self.0 = match tag {
+            1 => PrimitiveValue::String(pb.read_string()),
+            2 => PrimitiveValue::F32(pb.read_fixed()),
+            3 => PrimitiveValue::F64(pb.read_fixed()),
+            4 => PrimitiveValue::I64(pb.read_varint()),
+            5 => PrimitiveValue::U64(pb.read_varint()),
+            6 => PrimitiveValue::I64(pb.read_s_varint()),
+            7 => PrimitiveValue::Bool(pb.read_varint()),
+            _ => return,
+        }
+    }
+}
+
+/// An MVT feature message before its tags and geometry are resolved
+#[derive(Default)]
+struct RawFeature {
+    id: Option<u64>,
+    tags: Vec<u32>,
+    geom_type: u8,
+    commands: Vec<u32>,
+}
+impl ProtoRead for RawFeature {
+    fn read(&mut self, tag: u64, pb: &mut Protobuf) {
+        match tag {
+            1 => self.id = Some(pb.read_varint()),
+            2 => self.tags = pb.read_packed(),
+            3 => self.geom_type = pb.read_varint(),
+            4 => self.commands = pb.read_packed(),
+            _ => {}
+        }
+    }
+}
+
+/// An MVT layer message. Features are resolved once the whole layer is read, since the key and
+/// value tables and the extent may follow them
+struct RawLayer {
+    version: u32,
+    name: String,
+    extent: u32,
+    features: Vec<RawFeature>,
+    keys: Vec<String>,
+    values: Vec<PrimitiveValue>,
+}
+impl ProtoRead for RawLayer {
+    fn read(&mut self, tag: u64, pb: &mut Protobuf) {
+        match tag {
+            15 => self.version = pb.read_varint(),
+            1 => self.name = pb.read_string(),
+            2 => {
+                let mut feature = RawFeature::default();
+                pb.read_message(&mut feature);
+                self.features.push(feature);
+            }
+            3 => self.keys.push(pb.read_string()),
+            4 => {
+                let mut value = MapboxValueReader::default();
+                pb.read_message(&mut value);
+                self.values.push(value.0);
+            }
+            5 => self.extent = pb.read_varint(),
+            _ => {}
+        }
+    }
+}
+
+/// Split geometry commands into paths of absolute tile coordinates. Every MoveTo point starts a
+/// new path and ClosePath repeats the first point of the current one
+fn decode_commands(commands: &[u32]) -> Vec<Vec<(i64, i64)>> {
+    let mut paths: Vec<Vec<(i64, i64)>> = vec![];
+    let mut cursor: (i64, i64) = (0, 0);
+    let mut i = 0;
+    while i < commands.len() {
+        let (id, count) = (commands[i] & 0x7, (commands[i] >> 3) as usize);
+        i += 1;
+        match id {
+            CMD_MOVE_TO | CMD_LINE_TO => {
+                for _ in 0..count {
+                    let (Some(dx), Some(dy)) = (commands.get(i), commands.get(i + 1)) else {
+                        return paths;
+                    };
+                    i += 2;
+                    // crafted tiles can walk the cursor out of range. Stop there like at a
+                    // truncated command
+                    let (Some(x), Some(y)) = (
+                        cursor.0.checked_add(zagzig(*dx as u64)),
+                        cursor.1.checked_add(zagzig(*dy as u64)),
+                    ) else {
+                        return paths;
+                    };
+                    cursor = (x, y);
+                    match paths.last_mut() {
+                        Some(path) if id == CMD_LINE_TO => path.push(cursor),
+                        _ => paths.push(vec![cursor]),
+                    }
+                }
+            }
+            CMD_CLOSE_PATH => {
+                if let Some(path) = paths.last_mut()
+                    && let Some(first) = path.first().copied()
+                {
+                    path.push(first);
+                }
+            }
+            _ => return paths,
+        }
+    }
+    paths
+}
+
+/// Group closed rings into polygons. A ring wound like the first ring starts a new polygon, and
+/// any other ring is a hole of the polygon before it
+fn classify_rings(rings: Vec<Vec<(i64, i64)>>) -> Vec<Vec<Vec<(i64, i64)>>> {
+    let mut polygons: Vec<Vec<Vec<(i64, i64)>>> = vec![];
+    let mut exterior_positive = None;
+    for ring in rings {
+        let area = ring_area(&ring);
+        if area == 0 {
+            continue;
+        }
+        let positive = *exterior_positive.get_or_insert(area > 0);
+        match polygons.last_mut() {
+            Some(polygon) if positive != (area > 0) => polygon.push(ring),
+            _ => polygons.push(vec![ring]),
+        }
+    }
+    polygons
+}
+
+/// Decode MVT geometry commands back into 0->1 tile space
+fn decode_geometry<D: Clone + Default>(
+    geom_type: u8,
+    commands: &[u32],
+    extent: u32,
+) -> Option<VectorGeometry<D>> {
+    let extent = extent.max(1) as f64;
+    let to_point = |&(x, y): &(i64, i64)| VectorPoint::<D> {
+        x: x as f64 / extent,
+        y: y as f64 / extent,
+        ..Default::default()
+    };
+    let to_line = |path: &Vec<(i64, i64)>| path.iter().map(to_point).collect::<Vec<_>>();
+    let paths = decode_commands(commands);
+    match geom_type {
+        GEOM_POINT => {
+            let mut points: Vec<VectorPoint<D>> = paths.iter().flatten().map(to_point).collect();
+            match points.len() {
+                0 => None,
+                1 => Some(VectorGeometry::new_point(points.remove(0), None)),
+                _ => Some(VectorGeometry::new_multipoint(points, None)),
+            }
+        }
+        GEOM_LINESTRING => {
+            let mut lines: Vec<Vec<VectorPoint<D>>> = paths.iter().map(to_line).collect();
+            match lines.len() {
+                0 => None,
+                1 => Some(VectorGeometry::new_linestring(lines.remove(0), None)),
+                _ => Some(VectorGeometry::new_multilinestring(lines, None)),
+            }
+        }
+        GEOM_POLYGON => {
+            let mut polygons: Vec<Vec<Vec<VectorPoint<D>>>> = classify_rings(paths)
+                .iter()
+                .map(|polygon| polygon.iter().map(to_line).collect())
+                .collect();
+            match polygons.len() {
+                0 => None,
+                1 => Some(VectorGeometry::new_polygon(polygons.remove(0), None)),
+                _ => Some(VectorGeometry::new_multipolygon(polygons, None)),
+            }
+        }
+        _ => None,
+    }
+}
+
+impl<P: Clone + Default + From<MapboxProperties>, D: Clone + Default> From<RawLayer>
+    for MapboxVectorLayer<P, D>
+{
+    fn from(raw: RawLayer) -> Self {
+        let mut layer = MapboxVectorLayer::new(&raw.name, raw.extent);
+        layer.version = raw.version;
+        for feature in raw.features {
+            let Some(geometry) = decode_geometry(feature.geom_type, &feature.commands, raw.extent)
+            else {
+                continue;
+            };
+            let mut properties = MapboxProperties::new();
+            for [key, value] in feature.tags.as_chunks::<2>().0 {
+                if let (Some(key), Some(value)) =
+                    (raw.keys.get(*key as usize), raw.values.get(*value as usize))
+                {
+                    properties.insert(key.clone(), value.clone());
+                }
+            }
+            layer.features.push(VectorFeature::new_wm(
+                feature.id,
+                properties.into(),
+                geometry,
+                None,
+            ));
+        }
+        layer
+    }
+}
+impl<P: Clone + Default + From<MapboxProperties>, D: Clone + Default> ProtoRead
+    for MapboxVectorTile<P, D>
+{
+    fn read(&mut self, tag: u64, pb: &mut Protobuf) {
+        if tag == 3 {
+            let mut layer = RawLayer {
+                version: 1,
+                name: String::new(),
+                extent: MVT_DEFAULT_EXTENT,
+                features: vec![],
+                keys: vec![],
+                values: vec![],
+            };
+            pb.read_message(&mut layer);
+            self.layers.push(layer.into());
+        }
+    }
+}
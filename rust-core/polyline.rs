@@ -0,0 +1,252 @@
+use crate::*;
+use alloc::{format, string::String, vec::Vec};
+use libm::{pow, round};
+use pbf::{zagzig, zigzag};
+
+/// The characters of a flexible polyline, indexed by the 6 bit value they encode
+const FLEXIBLE_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+/// The flexible polyline format version
+const FLEXIBLE_VERSION: u64 = 1;
+
+/// What the third dimension of a flexible polyline measures
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum PolylineThirdDimension {
+    /// The polyline is 2D
+    #[default]
+    Absent,
+    /// A floor or level
+    Level,
+    /// Height above the ellipsoid
+    Altitude,
+    /// Height above sea level
+    Elevation,
+    /// Application defined
+    Custom1,
+    /// Application defined
+    Custom2,
+}
+impl From<PolylineThirdDimension> for u64 {
+    fn from(third_dimension: PolylineThirdDimension) -> Self {
+        match third_dimension {
+            PolylineThirdDimension::Absent => 0,
+            PolylineThirdDimension::Level => 1,
+            PolylineThirdDimension::Altitude => 2,
+            PolylineThirdDimension::Elevation => 3,
+            PolylineThirdDimension::Custom1 => 6,
+            PolylineThirdDimension::Custom2 => 7,
+        }
+    }
+}
+
+/// The header of a flexible polyline
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct FlexiblePolylineHeader {
+    /// Decimal places kept for latitude and longitude, up to 15
+    pub precision: u8,
+    /// What the third dimension measures, if present
+    pub third_dimension: PolylineThirdDimension,
+    /// Decimal places kept for the third dimension, up to 15
+    pub third_dimension_precision: u8,
+}
+impl FlexiblePolylineHeader {
+    /// Create a new FlexiblePolylineHeader
+    pub fn new(
+        precision: u8,
+        third_dimension: PolylineThirdDimension,
+        third_dimension_precision: u8,
+    ) -> Self {
+        FlexiblePolylineHeader { precision, third_dimension, third_dimension_precision }
+    }
+}
+
+#[derive(Copy, Clone)]
+enum Alphabet {
+    Google,
+    Flexible,
+}
+impl Alphabet {
+    fn encode(self, chunk: u64) -> char {
+        match self {
+            Alphabet::Google => (chunk as u8 + 63) as char,
+            Alphabet::Flexible => FLEXIBLE_ALPHABET[chunk as usize] as char,
+        }
+    }
+
+    fn decode(self, byte: u8) -> Option<u64> {
+        match self {
+            Alphabet::Google => (63..=126).contains(&byte).then(|| (byte - 63) as u64),
+            Alphabet::Flexible => {
+                FLEXIBLE_ALPHABET.iter().position(|&c| c == byte).map(|i| i as u64)
+            }
+        }
+    }
+}
+
+/// Write a value in 5 bit chunks, lowest first, setting 0x20 on every chunk but the last
+fn write_value(alphabet: Alphabet, mut value: u64, out: &mut String) {
+    while value >= 0x20 {
+        out.push(alphabet.encode((value & 0x1F) | 0x20));
+        value >>= 5;
+    }
+    out.push(alphabet.encode(value));
+}
+
+/// Quantizes values and writes each as the zig-zag encoded change from the one before
+struct DeltaWriter {
+    scale: f64,
+    last: i64,
+}
+impl DeltaWriter {
+    fn new(precision: u8) -> Self {
+        DeltaWriter { scale: pow(10., precision as f64), last: 0 }
+    }
+
+    fn write(&mut self, alphabet: Alphabet, value: f64, out: &mut String) {
+        let quantized = round(value * self.scale) as i64;
+        write_value(alphabet, zigzag(quantized.wrapping_sub(self.last)), out);
+        self.last = quantized;
+    }
+}
+
+/// Reads values and the deltas between them
+struct PolylineReader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+    alphabet: Alphabet,
+}
+impl PolylineReader<'_> {
+    fn is_done(&self) -> bool {
+        self.pos >= self.bytes.len()
+    }
+
+    fn value(&mut self) -> Result<u64, S2JsonError> {
+        let start = self.pos;
+        let (mut value, mut shift) = (0_u64, 0);
+        loop {
+            let Some(&byte) = self.bytes.get(self.pos) else {
+                return Err(S2JsonError::invalid_polyline(start, "unterminated value"));
+            };
+            let Some(chunk) = self.alphabet.decode(byte) else {
+                return Err(S2JsonError::invalid_polyline(
+                    self.pos,
+                    format!("invalid character {:?}", byte as char),
+                ));
+            };
+            if shift >= 64 {
+                return Err(S2JsonError::invalid_polyline(start, "value overflows 64 bits"));
+            }
+            value |= (chunk & 0x1F) << shift;
+            shift += 5;
+            self.pos += 1;
+            if chunk & 0x20 == 0 {
+                return Ok(value);
+            }
+        }
+    }
+
+    /// Read the next delta and apply it to `last`, returning the value at the given precision
+    fn delta(&mut self, last: &mut i64, scale: f64) -> Result<f64, S2JsonError> {
+        *last = last.wrapping_add(zagzig(self.value()?));
+        Ok(*last as f64 / scale)
+    }
+}
+
+/// Encode points with the Google polyline algorithm, keeping `precision` decimal places
+/// (5 for Google, 6 for OSRM and Valhalla). Points are written latitude (y) first
+pub fn encode_polyline<P: GetXY>(points: &[P], precision: u8) -> String {
+    let (mut lat, mut lng) = (DeltaWriter::new(precision), DeltaWriter::new(precision));
+    let mut out = String::new();
+    for point in points {
+        lat.write(Alphabet::Google, point.y(), &mut out);
+        lng.write(Alphabet::Google, point.x(), &mut out);
+    }
+    out
+}
+
+/// Decode a Google polyline written with `precision` decimal places into a [`LineString`],
+/// [`VectorLineString`], or any other list of points
+pub fn decode_polyline<P: NewXY>(polyline: &str, precision: u8) -> Result<Vec<P>, S2JsonError> {
+    let scale = pow(10., precision as f64);
+    let mut reader =
+        PolylineReader { bytes: polyline.as_bytes(), pos: 0, alphabet: Alphabet::Google };
+    let (mut lat, mut lng) = (0, 0);
+    let mut points = Vec::new();
+    while !reader.is_done() {
+        let y = reader.delta(&mut lat, scale)?;
+        let x = reader.delta(&mut lng, scale)?;
+        points.push(P::new_xy(x, y));
+    }
+    Ok(points)
+}
+
+/// Encode points as a flexible polyline, which carries its precision and an optional third
+/// dimension in its header. The third dimension is read from each point's z, or 0 if it has none
+pub fn encode_flexible_polyline<P: GetXYZ>(points: &[P], header: FlexiblePolylineHeader) -> String {
+    let precision = header.precision.min(15);
+    let third_precision = header.third_dimension_precision.min(15);
+    let mut out = String::new();
+    write_value(Alphabet::Flexible, FLEXIBLE_VERSION, &mut out);
+    let content =
+        precision as u64 | u64::from(header.third_dimension) << 4 | (third_precision as u64) << 7;
+    write_value(Alphabet::Flexible, content, &mut out);
+
+    let (mut lat, mut lng) = (DeltaWriter::new(precision), DeltaWriter::new(precision));
+    let mut third = DeltaWriter::new(third_precision);
+    for point in points {
+        lat.write(Alphabet::Flexible, point.y(), &mut out);
+        lng.write(Alphabet::Flexible, point.x(), &mut out);
+        if header.third_dimension != PolylineThirdDimension::Absent {
+            third.write(Alphabet::Flexible, point.z().unwrap_or_default(), &mut out);
+        }
+    }
+    out
+}
+
+/// Decode a flexible polyline into its header and points. Points are 3D if the header has a third
+/// dimension
+pub fn decode_flexible_polyline<P: NewXY + NewXYZ>(
+    polyline: &str,
+) -> Result<(FlexiblePolylineHeader, Vec<P>), S2JsonError> {
+    let mut reader =
+        PolylineReader { bytes: polyline.as_bytes(), pos: 0, alphabet: Alphabet::Flexible };
+    let version = reader.value()?;
+    if version != FLEXIBLE_VERSION {
+        return Err(S2JsonError::invalid_polyline(0, format!("unsupported version {version}")));
+    }
+    let start = reader.pos;
+    let content = reader.value()?;
+    let third_dimension = match (content >> 4) & 7 {
+        0 => PolylineThirdDimension::Absent,
+        1 => PolylineThirdDimension::Level,
+        2 => PolylineThirdDimension::Altitude,
+        3 => PolylineThirdDimension::Elevation,
+        6 => PolylineThirdDimension::Custom1,
+        7 => PolylineThirdDimension::Custom2,
+        reserved => {
+            return Err(S2JsonError::invalid_polyline(
+                start,
+                format!("reserved third dimension {reserved}"),
+            ));
+        }
+    };
+    let header = FlexiblePolylineHeader::new(
+        (content & 15) as u8,
+        third_dimension,
+        ((content >> 7) & 15) as u8,
+    );
+
+    let scale = pow(10., header.precision as f64);
+    let third_scale = pow(10., header.third_dimension_precision as f64);
+    let (mut lat, mut lng, mut third) = (0, 0, 0);
+    let mut points = Vec::new();
+    while !reader.is_done() {
+        let y = reader.delta(&mut lat, scale)?;
+        let x = reader.delta(&mut lng, scale)?;
+        points.push(match third_dimension {
+            PolylineThirdDimension::Absent => P::new_xy(x, y),
+            _ => P::new_xyz(x, y, reader.delta(&mut third, third_scale)?),
+        });
+    }
+    Ok((header, points))
+}
@@ -0,0 +1,382 @@
+use crate::*;
+use alloc::{format, string::String};
+use core::fmt;
+use libm::floor;
+use serde::{Deserialize, Serialize};
+
+/// The maximum level (zoom) of an S2CellId
+pub const S2_MAX_LEVEL: u8 = 30;
+/// Number of bits used by the position along the Hilbert curve (including the trailing marker bit)
+const POS_BITS: u64 = 2 * S2_MAX_LEVEL as u64 + 1;
+/// Number of leaf cells along one edge of a face
+const MAX_SIZE: u32 = 1 << S2_MAX_LEVEL;
+
+/// Swap the i and j axes of a Hilbert curve sub-square
+const SWAP_MASK: u8 = 0x01;
+/// Invert the i and j axes of a Hilbert curve sub-square
+const INVERT_MASK: u8 = 0x02;
+/// The change in orientation when descending into the child at each Hilbert position
+const POS_TO_ORIENTATION: [u8; 4] = [SWAP_MASK, 0, 0, INVERT_MASK | SWAP_MASK];
+/// Maps (orientation, Hilbert position) to the (i, j) quadrant as `i << 1 | j`
+const POS_TO_IJ: [[u8; 4]; 4] = [[0, 1, 3, 2], [0, 2, 3, 1], [3, 2, 0, 1], [3, 1, 0, 2]];
+/// Maps (orientation, `i << 1 | j` quadrant) to the Hilbert position
+const IJ_TO_POS: [[u8; 4]; 4] = [[0, 1, 3, 2], [0, 3, 1, 2], [2, 3, 1, 0], [2, 1, 3, 0]];
+
+/// # S2 Cell ID
+///
+/// ## Description
+/// A 64-bit identifier for a cell in the S2 quad-tree hierarchy. The top 3 bits hold the face,
+/// followed by the position of the cell along the face's Hilbert curve (2 bits per level) and a
+/// trailing marker bit that encodes the level.
+///
+/// A cell at zoom `z` shares its bounds with the tile `(z, x, y)` on the same face, so it can be
+/// converted to and from the s-t [`BBox`] produced by [`BBox::from_st_zoom`].
+///
+/// ## Usage
+/// - [`S2CellId::new`]: Create a new S2CellId from a raw id
+/// - [`S2CellId::from_face`]: Create the level 0 cell covering a face
+/// - [`S2CellId::from_face_ij`]: Create a leaf cell from a face and leaf i-j coordinates
+/// - [`S2CellId::from_face_st`]: Create a leaf cell from a face and s-t coordinates
+/// - [`S2CellId::from_st_point`]: Create a leaf cell from an [`STPoint`]
+/// - [`S2CellId::from_point`]: Create a leaf cell from a point on the sphere
+/// - [`S2CellId::from_lon_lat`]: Create a leaf cell from a lon-lat coordinate in degrees
+/// - [`S2CellId::from_lon_lat_point`]: Create a leaf cell from a lon-lat point in degrees
+/// - [`S2CellId::from_face_zoom_xy`]: Create a cell from a face and tile zoom-x-y
+/// - [`S2CellId::from_st_bbox`]: Create a cell from a face and its s-t bounding box
+/// - [`S2CellId::from_token`]: Parse a cell from its token string
+/// - [`S2CellId::face`]: The face the cell is on
+/// - [`S2CellId::level`]: The level (zoom) of the cell
+/// - [`S2CellId::pos`]: The position of the cell along the face's Hilbert curve
+/// - [`S2CellId::is_valid`]: Check if the id is a valid cell
+/// - [`S2CellId::is_leaf`]: Check if the cell is at the maximum level
+/// - [`S2CellId::is_face`]: Check if the cell is a face cell
+/// - [`S2CellId::parent`]: Get the immediate parent cell
+/// - [`S2CellId::parent_at_level`]: Get the parent cell at a given level
+/// - [`S2CellId::children`]: Get the four child cells
+/// - [`S2CellId::neighbors`]: Get the four edge neighbors, wrapping across faces
+/// - [`S2CellId::range_min`]: The first leaf cell contained by this cell
+/// - [`S2CellId::range_max`]: The last leaf cell contained by this cell
+/// - [`S2CellId::contains`]: Check if a cell is contained by this cell
+/// - [`S2CellId::intersects`]: Check if two cells intersect
+/// - [`S2CellId::to_face_ij`]: The face and i-j coordinates of the cell's lower left leaf
+/// - [`S2CellId::to_face_st`]: The face and s-t coordinates of the cell's center
+/// - [`S2CellId::to_st_point`]: The cell's center as an [`STPoint`]
+/// - [`S2CellId::to_point`]: The cell's center as a point on the unit sphere
+/// - [`S2CellId::to_lon_lat`]: The cell's center as a lon-lat coordinate in degrees
+/// - [`S2CellId::to_face_zoom_xy`]: The face and tile zoom-x-y of the cell
+/// - [`S2CellId::to_st_bbox`]: The s-t bounding box of the cell
+/// - [`S2CellId::to_token`]: The cell's compact hex token
+#[derive(
+    Serialize, Deserialize, Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Default,
+)]
+pub struct S2CellId {
+    /// The raw 64-bit id
+    pub id: u64,
+}
+impl S2CellId {
+    /// Create a new S2CellId from a raw id
+    pub fn new(id: u64) -> Self {
+        Self { id }
+    }
+
+    /// Create the level 0 cell covering a face
+    pub fn from_face(face: Face) -> Self {
+        Self::new(((face as u64) << POS_BITS) + lsb_for_level(0))
+    }
+
+    /// Create a leaf cell from a face and leaf i-j coordinates in [0, 2^30)
+    pub fn from_face_ij(face: Face, i: u32, j: u32) -> Self {
+        let mut pos: u64 = 0;
+        let mut orientation = face as u8 & SWAP_MASK;
+        for k in (0..S2_MAX_LEVEL).rev() {
+            let ij = ((((i >> k) & 1) << 1) | ((j >> k) & 1)) as usize;
+            let p = IJ_TO_POS[orientation as usize][ij];
+            pos = (pos << 2) | p as u64;
+            orientation ^= POS_TO_ORIENTATION[p as usize];
+        }
+        Self::new(((face as u64) << POS_BITS) | (pos << 1) | 1)
+    }
+
+    /// Create a leaf cell from a face and s-t coordinates in [0, 1]
+    pub fn from_face_st(face: Face, s: f64, t: f64) -> Self {
+        Self::from_face_ij(face, st_to_ij(s), st_to_ij(t))
+    }
+
+    /// Create a leaf cell from an STPoint
+    pub fn from_st_point<M>(point: &STPoint<M>) -> Self {
+        Self::from_face_st(point.face, point.s, point.t)
+    }
+
+    /// Create a leaf cell from a point on the sphere. The point need not be unit length
+    pub fn from_point<P: GetXYZ>(point: &P) -> Self {
+        let (face, s, t) = xyz_to_face_st(point.x(), point.y(), point.z().unwrap_or_default());
+        Self::from_face_st(face, s, t)
+    }
+
+    /// Create a leaf cell from a lon-lat coordinate in degrees
+    pub fn from_lon_lat(lon: f64, lat: f64) -> Self {
+        let (face, s, t) = lon_lat_to_face_st(lon, lat);
+        Self::from_face_st(face, s, t)
+    }
+
+    /// Create a leaf cell from a lon-lat point in degrees
+    pub fn from_lon_lat_point<P: GetXY>(point: &P) -> Self {
+        Self::from_lon_lat(point.x(), point.y())
+    }
+
+    /// Create a cell from a face and tile zoom-x-y. Returns None if the zoom is greater than
+    /// [`S2_MAX_LEVEL`] or x or y are outside of the zoom's range
+    pub fn from_face_zoom_xy(face: Face, zoom: u8, x: u32, y: u32) -> Option<Self> {
+        if zoom > S2_MAX_LEVEL {
+            return None;
+        }
+        let shift = S2_MAX_LEVEL - zoom;
+        if (x as u64) << shift >= MAX_SIZE as u64 || (y as u64) << shift >= MAX_SIZE as u64 {
+            return None;
+        }
+        Some(Self::from_face_ij(face, x << shift, y << shift).parent_at_level(zoom))
+    }
+
+    /// Create a cell from a face and its s-t bounding box, as produced by
+    /// [`BBox::from_st_zoom`]. Returns None if the bbox is not the bounds of a cell
+    pub fn from_st_bbox(face: Face, bbox: &BBox) -> Option<Self> {
+        let width = bbox.right - bbox.left;
+        if width <= 0. || (bbox.top - bbox.bottom - width).abs() > f64::EPSILON {
+            return None;
+        }
+        let tiles = 1. / width;
+        let zoom = libm::round(libm::log2(tiles));
+        if !(0. ..=S2_MAX_LEVEL as f64).contains(&zoom) {
+            return None;
+        }
+        let zoom = zoom as u8;
+        let (x, y) = (libm::round(bbox.left * tiles), libm::round(bbox.bottom * tiles));
+        if x < 0. || y < 0. {
+            return None;
+        }
+        let cell = Self::from_face_zoom_xy(face, zoom, x as u32, y as u32)?;
+        if cell.to_st_bbox() == *bbox { Some(cell) } else { None }
+    }
+
+    /// Parse a cell from its token string. Returns None if the token is not valid hex
+    pub fn from_token(token: &str) -> Option<Self> {
+        if token == "X" {
+            return Some(Self::new(0));
+        }
+        if token.is_empty() || token.len() > 16 {
+            return None;
+        }
+        let id = u64::from_str_radix(token, 16).ok()?;
+        Some(Self::new(id << (4 * (16 - token.len()))))
+    }
+
+    /// The face the cell is on
+    pub fn face(&self) -> Face {
+        Face::from((self.id >> POS_BITS) as u8)
+    }
+
+    /// The level (zoom) of the cell, from 0 (face) to 30 (leaf)
+    pub fn level(&self) -> u8 {
+        S2_MAX_LEVEL.saturating_sub((self.id.trailing_zeros() / 2) as u8)
+    }
+
+    /// The position of the cell along the face's Hilbert curve, including the trailing marker bit
+    pub fn pos(&self) -> u64 {
+        self.id & (u64::MAX >> 3)
+    }
+
+    /// Check if the id is a valid cell
+    pub fn is_valid(&self) -> bool {
+        (self.id >> POS_BITS) < 6 && (self.lsb() & 0x1555555555555555) != 0
+    }
+
+    /// Check if the cell is at the maximum level
+    pub fn is_leaf(&self) -> bool {
+        self.id & 1 != 0
+    }
+
+    /// Check if the cell is a face cell
+    pub fn is_face(&self) -> bool {
+        self.id & (lsb_for_level(0) - 1) == 0
+    }
+
+    /// The lowest set bit, which encodes the level
+    fn lsb(&self) -> u64 {
+        self.id & self.id.wrapping_neg()
+    }
+
+    /// Get the immediate parent cell. Returns None for a face cell
+    pub fn parent(&self) -> Option<Self> {
+        let level = self.level();
+        if level == 0 { None } else { Some(self.parent_at_level(level - 1)) }
+    }
+
+    /// Get the parent cell at a given level. If the level is not above the cell's own level, the
+    /// cell itself is returned
+    pub fn parent_at_level(&self, level: u8) -> Self {
+        if level >= self.level() {
+            return *self;
+        }
+        let new_lsb = lsb_for_level(level);
+        Self::new((self.id & new_lsb.wrapping_neg()) | new_lsb)
+    }
+
+    /// Get the four child cells in Hilbert curve order. Returns None for a leaf cell
+    pub fn children(&self) -> Option<[Self; 4]> {
+        if self.is_leaf() {
+            return None;
+        }
+        let lsb = self.lsb();
+        let begin = self.id - lsb + (lsb >> 2);
+        let step = lsb >> 1;
+        Some([0, 1, 2, 3].map(|k| Self::new(begin + k * step)))
+    }
+
+    /// Get the four edge neighbors at the same level, in the order bottom, right, top, left.
+    /// Neighbors across a face edge are wrapped onto the adjacent face
+    pub fn neighbors(&self) -> [Self; 4] {
+        let level = self.level();
+        let size = 1_i64 << (S2_MAX_LEVEL - level);
+        let (face, i, j) = self.to_face_ij();
+        let (i, j) = (i as i64, j as i64);
+        [(i, j - size), (i + size, j), (i, j + size), (i - size, j)]
+            .map(|(i, j)| from_face_ij_wrap(face, i, j).parent_at_level(level))
+    }
+
+    /// The first leaf cell contained by this cell. The invalid id 0 has no level and is its own
+    /// range
+    pub fn range_min(&self) -> Self {
+        Self::new(self.id - self.lsb().saturating_sub(1))
+    }
+
+    /// The last leaf cell contained by this cell. The invalid id 0 has no level and is its own
+    /// range
+    pub fn range_max(&self) -> Self {
+        Self::new(self.id + self.lsb().saturating_sub(1))
+    }
+
+    /// Check if a cell is contained by this cell
+    pub fn contains(&self, other: &Self) -> bool {
+        other.id >= self.range_min().id && other.id <= self.range_max().id
+    }
+
+    /// Check if two cells intersect
+    pub fn intersects(&self, other: &Self) -> bool {
+        other.range_min().id <= self.range_max().id && other.range_max().id >= self.range_min().id
+    }
+
+    /// The face and i-j coordinates of the cell's lower left leaf
+    pub fn to_face_ij(&self) -> (Face, u32, u32) {
+        let face = self.face();
+        let mut orientation = face as u8 & SWAP_MASK;
+        let (mut i, mut j) = (0_u32, 0_u32);
+        for k in (0..S2_MAX_LEVEL).rev() {
+            let p = ((self.id >> (2 * k as u64 + 1)) & 3) as u8;
+            let ij = POS_TO_IJ[orientation as usize][p as usize] as u32;
+            i |= (ij >> 1) << k;
+            j |= (ij & 1) << k;
+            orientation ^= POS_TO_ORIENTATION[p as usize];
+        }
+        // snap to the lower left leaf of the cell
+        let mask = !((1_u32 << (S2_MAX_LEVEL - self.level())) - 1);
+        (face, i & mask, j & mask)
+    }
+
+    /// The face and s-t coordinates of the cell's center
+    pub fn to_face_st(&self) -> (Face, f64, f64) {
+        let (face, i, j) = self.to_face_ij();
+        let half = (1_u64 << (S2_MAX_LEVEL - self.level())) as f64 / 2.;
+        let scale = 1. / MAX_SIZE as f64;
+        (face, (i as f64 + half) * scale, (j as f64 + half) * scale)
+    }
+
+    /// The cell's center as an STPoint
+    pub fn to_st_point<M>(&self) -> STPoint<M> {
+        let (face, s, t) = self.to_face_st();
+        STPoint { face, s, t, z: None, m: None }
+    }
+
+    /// The cell's center as a point on the unit sphere
+    pub fn to_point(&self) -> Point3D {
+        let (face, s, t) = self.to_face_st();
+        let (x, y, z) = face_st_to_xyz(face, s, t);
+        Point3D(x, y, z)
+    }
+
+    /// The cell's center as a lon-lat coordinate in degrees
+    pub fn to_lon_lat(&self) -> (f64, f64) {
+        let (face, s, t) = self.to_face_st();
+        face_st_to_lon_lat(face, s, t)
+    }
+
+    /// The face and tile zoom-x-y of the cell
+    pub fn to_face_zoom_xy(&self) -> (Face, u8, u32, u32) {
+        let level = self.level();
+        let (face, i, j) = self.to_face_ij();
+        let shift = S2_MAX_LEVEL - level;
+        (face, level, i >> shift, j >> shift)
+    }
+
+    /// The s-t bounding box of the cell. Equivalent to [`BBox::from_st_zoom`] of its tile
+    pub fn to_st_bbox(&self) -> BBox {
+        let (_, zoom, x, y) = self.to_face_zoom_xy();
+        BBox::from_st_zoom(x as f64, y as f64, zoom)
+    }
+
+    /// The cell's compact hex token. Trailing zeros are removed and the empty id is "X"
+    pub fn to_token(&self) -> String {
+        if self.id == 0 {
+            return "X".into();
+        }
+        let hex = format!("{:016x}", self.id);
+        hex.trim_end_matches('0').into()
+    }
+}
+impl From<u64> for S2CellId {
+    fn from(id: u64) -> Self {
+        Self::new(id)
+    }
+}
+impl From<S2CellId> for u64 {
+    fn from(cell: S2CellId) -> Self {
+        cell.id
+    }
+}
+impl<M> From<&STPoint<M>> for S2CellId {
+    fn from(point: &STPoint<M>) -> Self {
+        Self::from_st_point(point)
+    }
+}
+impl fmt::Display for S2CellId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.to_token())
+    }
+}
+
+/// The lowest set bit of any cell at the given level
+fn lsb_for_level(level: u8) -> u64 {
+    1 << (2 * (S2_MAX_LEVEL - level) as u64)
+}
+
+/// Convert an s or t value to a leaf i or j coordinate
+fn st_to_ij(s: f64) -> u32 {
+    floor(MAX_SIZE as f64 * s).clamp(0., (MAX_SIZE - 1) as f64) as u32
+}
+
+/// Create a leaf cell from i-j coordinates that may lie outside of the face, wrapping them onto
+/// the adjacent face
+fn from_face_ij_wrap(face: Face, i: i64, j: i64) -> S2CellId {
+    let max = MAX_SIZE as i64;
+    if (0..max).contains(&i) && (0..max).contains(&j) {
+        return S2CellId::from_face_ij(face, i as u32, j as u32);
+    }
+    // project the leaf center just past the face edge onto the cube, then back onto a face
+    let (i, j) = (i.clamp(-1, max), j.clamp(-1, max));
+    let scale = 1. / max as f64;
+    let limit = 1. + f64::EPSILON;
+    let u = (scale * (2 * (i - max / 2) + 1) as f64).clamp(-limit, limit);
+    let v = (scale * (2 * (j - max / 2) + 1) as f64).clamp(-limit, limit);
+    let (x, y, z) = face_uv_to_xyz(face, u, v);
+    let (face, u, v) = xyz_to_face_uv(x, y, z);
+    S2CellId::from_face_ij(face, st_to_ij(0.5 * (u + 1.)), st_to_ij(0.5 * (v + 1.)))
+}
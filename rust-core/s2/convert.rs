@@ -0,0 +1,397 @@
+use crate::*;
+use alloc::{vec, vec::Vec};
+
+//? WG -> S2
+
+impl<M: Clone, P: Clone + Default, D: Clone + Default + Interpolate> Feature<M, P, D> {
+    /// Convert a lon-lat Feature into S2Features. Each point is projected onto the S2 sphere and
+    /// the geometry is clipped to every face it touches, producing one S2Feature per face.
+    /// Edges are treated as great-circle arcs.
+    pub fn to_s2_features(&self) -> Vec<VectorFeature<M, P, D>> {
+        let geometry = VectorGeometry::from(&self.geometry);
+        build_s2_features(self.id, &self.properties, &geometry, &self.metadata)
+    }
+}
+
+impl<M: Clone, P: Clone + Default, D: Clone + Default + Interpolate> VectorFeature<M, P, D> {
+    /// Convert a lon-lat VectorFeature into S2Features, one per face the geometry touches.
+    /// If the feature is already an S2Feature, it is returned as is.
+    pub fn to_s2_features(&self) -> Vec<VectorFeature<M, P, D>> {
+        if self._type == VectorFeatureType::S2Feature {
+            return vec![self.clone()];
+        }
+        build_s2_features(self.id, &self.properties, &self.geometry, &self.metadata)
+    }
+
+    /// Convert into a lon-lat Feature. S2Features are unprojected from their face's s-t space.
+    pub fn to_feature(&self) -> Feature<M, P, D> {
+        let geometry = if self._type == VectorFeatureType::S2Feature {
+            let mut geometry = self.geometry.clone();
+            let face = self.face;
+            map_vector_points(&mut geometry, &mut |point| point_from_st(face, point));
+            geometry
+        } else {
+            self.geometry.clone()
+        };
+        Feature::new(
+            self.id,
+            self.properties.clone(),
+            Geometry::from(&geometry),
+            self.metadata.clone(),
+        )
+    }
+}
+
+impl<M: Clone, P: Clone + Default, D: Clone + Default + Interpolate> FeatureCollection<M, P, D> {
+    /// Convert into an S2FeatureCollection. Every feature is split by the faces it touches
+    /// and the faces used are tracked
+    pub fn to_s2_collection(&self) -> S2FeatureCollection<M, P, D> {
+        let mut res = S2FeatureCollection::new(self.attributions.clone());
+        res.bbox = self.bbox;
+        for feature in &self.features {
+            let features = match feature {
+                Features::Feature(f) => f.to_s2_features(),
+                Features::VectorFeature(f) => f.to_s2_features(),
+            };
+            for feature in features {
+                res.add_face(feature.face);
+                res.features.push(feature);
+            }
+        }
+        res
+    }
+}
+
+impl<M: Clone, P: Clone + Default, D: Clone + Default + Interpolate> S2FeatureCollection<M, P, D> {
+    /// Convert into a lon-lat FeatureCollection. Each S2Feature becomes its own Feature.
+    pub fn to_feature_collection(&self) -> FeatureCollection<M, P, D> {
+        let mut res = FeatureCollection::new(self.attributions.clone());
+        res.bbox = self.bbox;
+        res.features = self.features.iter().map(|f| Features::Feature(f.to_feature())).collect();
+        res
+    }
+}
+
+/// Build the per-face S2Features of a lon-lat vector geometry
+fn build_s2_features<M: Clone, P: Clone + Default, D: Clone + Default + Interpolate>(
+    id: Option<u64>,
+    properties: &P,
+    geometry: &VectorGeometry<D>,
+    metadata: &Option<M>,
+) -> Vec<VectorFeature<M, P, D>> {
+    geometry_to_s2(geometry)
+        .into_iter()
+        .map(|(face, geometry)| {
+            VectorFeature::new_s2(id, face, properties.clone(), geometry, metadata.clone())
+        })
+        .collect()
+}
+
+/// A point on the unit sphere that carries its altitude and m-value through clipping
+#[derive(Debug, Clone)]
+struct SpherePoint<D: Clone> {
+    xyz: [f64; 3],
+    z: Option<f64>,
+    m: Option<D>,
+}
+impl<D: Clone + Interpolate> SpherePoint<D> {
+    fn from_lon_lat(point: &VectorPoint<D>) -> Self {
+        let (x, y, z) = lon_lat_to_xyz(point.x, point.y);
+        Self { xyz: [x, y, z], z: point.z, m: point.m.clone() }
+    }
+
+    fn dot(&self, plane: &[f64; 3]) -> f64 {
+        self.xyz[0] * plane[0] + self.xyz[1] * plane[1] + self.xyz[2] * plane[2]
+    }
+
+    /// Linear interpolation of the chord, whose radial projection follows the great-circle arc
+    fn intermediate(&self, other: &Self, t: f64) -> Self {
+        let xyz = [
+            self.xyz[0].interpolate(&other.xyz[0], t),
+            self.xyz[1].interpolate(&other.xyz[1], t),
+            self.xyz[2].interpolate(&other.xyz[2], t),
+        ];
+        let m = match (&self.m, &other.m) {
+            (Some(a), Some(b)) => Some(a.interpolate(b, t)),
+            _ => self.m.clone(),
+        };
+        Self { xyz, z: self.z.interpolate(&other.z, t), m }
+    }
+
+    /// Find the face this point lies on
+    fn face(&self) -> Face {
+        get_face(self.xyz[0], self.xyz[1], self.xyz[2])
+    }
+
+    /// Project onto a face's s-t space. Assumes the point is on (or clipped to) the face
+    fn to_st(&self, face: Face) -> VectorPoint<D> {
+        let (u, v) = face_xyz_to_uv(face, self.xyz[0], self.xyz[1], self.xyz[2]);
+        let (s, t) = (uv_to_st(u).clamp(0., 1.), uv_to_st(v).clamp(0., 1.));
+        VectorPoint::new(s, t, self.z, self.m.clone())
+    }
+}
+
+/// The four planes through the origin that bound a face: u <= 1, u >= -1, v <= 1, v >= -1
+fn face_planes(face: usize) -> [[f64; 3]; 4] {
+    let face = Face::from(face as u8);
+    [(-1., 0.), (1., 0.), (0., -1.), (0., 1.)].map(|(u, v)| {
+        let (x, y, z) = face_uv_to_xyz(face, u, v);
+        [x, y, z]
+    })
+}
+
+/// Clip a line against a plane, keeping the positive side. May split the line into pieces
+fn clip_line<D: Clone + Interpolate>(
+    line: &[SpherePoint<D>],
+    plane: &[f64; 3],
+) -> Vec<Vec<SpherePoint<D>>> {
+    let mut res = vec![];
+    let mut current = vec![];
+    for (i, a) in line.iter().enumerate() {
+        let da = a.dot(plane);
+        if da >= 0. {
+            current.push(a.clone());
+        }
+        if let Some(b) = line.get(i + 1) {
+            let db = b.dot(plane);
+            if da >= 0. && db < 0. {
+                if da > 0. {
+                    current.push(a.intermediate(b, da / (da - db)));
+                }
+                res.push(core::mem::take(&mut current));
+            } else if da < 0. && db > 0. {
+                current.push(a.intermediate(b, da / (da - db)));
+            }
+        }
+    }
+    res.push(current);
+    res.retain(|line| line.len() > 1);
+    res
+}
+
+/// Clip a closed ring against a plane, keeping the positive side (Sutherland–Hodgman)
+fn clip_ring<D: Clone + Interpolate>(
+    ring: &[SpherePoint<D>],
+    plane: &[f64; 3],
+) -> Vec<SpherePoint<D>> {
+    let mut res = vec![];
+    let len = ring.len();
+    for i in 0..len {
+        let prev = &ring[(i + len - 1) % len];
+        let curr = &ring[i];
+        let (dp, dc) = (prev.dot(plane), curr.dot(plane));
+        if dc >= 0. {
+            if dp < 0. {
+                res.push(prev.intermediate(curr, dp / (dp - dc)));
+            }
+            res.push(curr.clone());
+        } else if dp >= 0. {
+            res.push(prev.intermediate(curr, dp / (dp - dc)));
+        }
+    }
+    res
+}
+
+/// Clip a line to a face and project the pieces to s-t
+fn line_to_face<D: Clone + Interpolate>(
+    line: &[SpherePoint<D>],
+    face: usize,
+) -> Vec<VectorLineString<D>> {
+    let mut pieces = vec![line.to_vec()];
+    for plane in face_planes(face) {
+        pieces = pieces.iter().flat_map(|piece| clip_line(piece, &plane)).collect();
+    }
+    let face = Face::from(face as u8);
+    pieces.iter().map(|piece| piece.iter().map(|p| p.to_st(face)).collect()).collect()
+}
+
+/// Clip a polygon to a face and project to s-t. Returns None if the outer ring is clipped away
+fn polygon_to_face<D: Clone + Interpolate>(
+    polygon: &[Vec<SpherePoint<D>>],
+    face: usize,
+) -> Option<VectorPolygon<D>> {
+    let face_id = Face::from(face as u8);
+    let mut res: VectorPolygon<D> = vec![];
+    for (i, ring) in polygon.iter().enumerate() {
+        // work on the open ring
+        let mut ring = ring.clone();
+        if ring.len() > 1 && ring.first().map(|p| p.xyz) == ring.last().map(|p| p.xyz) {
+            ring.pop();
+        }
+        for plane in face_planes(face) {
+            ring = clip_ring(&ring, &plane);
+        }
+        if ring.len() < 3 {
+            if i == 0 {
+                return None;
+            }
+            continue;
+        }
+        let mut ring: VectorLineString<D> = ring.iter().map(|p| p.to_st(face_id)).collect();
+        ring.push(ring[0].clone());
+        res.push(ring);
+    }
+    Some(res)
+}
+
+/// Project a lon-lat vector geometry onto the S2 sphere, returning its geometry on each face
+fn geometry_to_s2<D: Clone + Default + Interpolate>(
+    geometry: &VectorGeometry<D>,
+) -> Vec<(Face, VectorGeometry<D>)> {
+    let to_sphere =
+        |line: &VectorLineString<D>| line.iter().map(SpherePoint::from_lon_lat).collect::<Vec<_>>();
+    let mut res = vec![];
+    match geometry {
+        VectorGeometry::Point(g) => {
+            let point = SpherePoint::from_lon_lat(&g.coordinates);
+            let face = point.face();
+            res.push((face, VectorGeometry::new_point(point.to_st(face), None)));
+        }
+        VectorGeometry::MultiPoint(g) => {
+            let mut faces: [VectorMultiPoint<D>; 6] = Default::default();
+            for point in g.coordinates.iter().map(SpherePoint::from_lon_lat) {
+                let face = point.face();
+                faces[face as usize].push(point.to_st(face));
+            }
+            for (face, points) in faces.into_iter().enumerate() {
+                if !points.is_empty() {
+                    res.push((
+                        Face::from(face as u8),
+                        VectorGeometry::new_multipoint(points, None),
+                    ));
+                }
+            }
+        }
+        VectorGeometry::LineString(g) => {
+            let line = to_sphere(&g.coordinates);
+            for face in 0..6 {
+                let mut lines = line_to_face(&line, face);
+                let geometry = match lines.len() {
+                    0 => continue,
+                    1 => VectorGeometry::new_linestring(lines.remove(0), None),
+                    _ => VectorGeometry::new_multilinestring(lines, None),
+                };
+                res.push((Face::from(face as u8), geometry));
+            }
+        }
+        VectorGeometry::MultiLineString(g) => {
+            let lines: Vec<_> = g.coordinates.iter().map(to_sphere).collect();
+            for face in 0..6 {
+                let clipped: VectorMultiLineString<D> =
+                    lines.iter().flat_map(|line| line_to_face(line, face)).collect();
+                if !clipped.is_empty() {
+                    res.push((
+                        Face::from(face as u8),
+                        VectorGeometry::new_multilinestring(clipped, None),
+                    ));
+                }
+            }
+        }
+        VectorGeometry::Polygon(g) => {
+            let polygon: Vec<_> = g.coordinates.iter().map(to_sphere).collect();
+            for face in 0..6 {
+                if let Some(clipped) = polygon_to_face(&polygon, face) {
+                    res.push((Face::from(face as u8), VectorGeometry::new_polygon(clipped, None)));
+                }
+            }
+        }
+        VectorGeometry::MultiPolygon(g) => {
+            let polygons: Vec<Vec<_>> = g
+                .coordinates
+                .iter()
+                .map(|polygon| polygon.iter().map(to_sphere).collect())
+                .collect();
+            for face in 0..6 {
+                let clipped: VectorMultiPolygon<D> =
+                    polygons.iter().filter_map(|polygon| polygon_to_face(polygon, face)).collect();
+                if !clipped.is_empty() {
+                    res.push((
+                        Face::from(face as u8),
+                        VectorGeometry::new_multipolygon(clipped, None),
+                    ));
+                }
+            }
+        }
+    }
+    // keep the source dimension, store the s-t bounds, and the lon-lat bounds if the source had one
+    let is_3d = vector_is_3d(geometry);
+    let with_bbox = geometry.bbox().is_some();
+    for (face, geometry) in res.iter_mut() {
+        let points = vector_points(geometry);
+        let mut vec_bbox = BBox3D::default();
+        let mut bbox = BBox3D::default();
+        for point in &points {
+            vec_bbox.extend_from_point(*point);
+            if with_bbox {
+                let (lon, lat) = face_st_to_lon_lat(*face, point.x, point.y);
+                bbox.extend_from_point(&VectorPoint::<D>::new(lon, lat, point.z, None));
+            }
+        }
+        let bbox = if with_bbox { Some(bbox) } else { None };
+        set_face_bounds(geometry, is_3d, bbox, vec_bbox);
+    }
+    res
+}
+
+/// Check if a vector geometry is 3D
+fn vector_is_3d<D: Clone + Default>(geometry: &VectorGeometry<D>) -> bool {
+    match geometry {
+        VectorGeometry::Point(g) => g.is_3d,
+        VectorGeometry::MultiPoint(g) => g.is_3d,
+        VectorGeometry::LineString(g) => g.is_3d,
+        VectorGeometry::MultiLineString(g) => g.is_3d,
+        VectorGeometry::Polygon(g) => g.is_3d,
+        VectorGeometry::MultiPolygon(g) => g.is_3d,
+    }
+}
+
+/// Set the dimension and bounds of a face geometry
+fn set_face_bounds<D: Clone + Default>(
+    geometry: &mut VectorGeometry<D>,
+    is_3d: bool,
+    bbox: Option<BBox3D>,
+    vec_bbox: BBox3D,
+) {
+    macro_rules! set {
+        ($g:expr) => {{
+            $g.is_3d = is_3d;
+            $g.bbox = bbox;
+            $g.vec_bbox = Some(vec_bbox);
+        }};
+    }
+    match geometry {
+        VectorGeometry::Point(g) => set!(g),
+        VectorGeometry::MultiPoint(g) => set!(g),
+        VectorGeometry::LineString(g) => set!(g),
+        VectorGeometry::MultiLineString(g) => set!(g),
+        VectorGeometry::Polygon(g) => set!(g),
+        VectorGeometry::MultiPolygon(g) => set!(g),
+    }
+}
+
+/// Collect every point of a vector geometry
+fn vector_points<D: Clone + Default>(geometry: &VectorGeometry<D>) -> Vec<&VectorPoint<D>> {
+    match geometry {
+        VectorGeometry::Point(g) => vec![&g.coordinates],
+        VectorGeometry::MultiPoint(g) => g.coordinates.iter().collect(),
+        VectorGeometry::LineString(g) => g.coordinates.iter().collect(),
+        VectorGeometry::MultiLineString(g) => g.coordinates.iter().flatten().collect(),
+        VectorGeometry::Polygon(g) => g.coordinates.iter().flatten().collect(),
+        VectorGeometry::MultiPolygon(g) => g.coordinates.iter().flatten().flatten().collect(),
+    }
+}
+
+/// Mutate every point of a vector geometry
+fn map_vector_points<D: Clone + Default>(
+    geometry: &mut VectorGeometry<D>,
+    f: &mut impl FnMut(&mut VectorPoint<D>),
+) {
+    match geometry {
+        VectorGeometry::Point(g) => f(&mut g.coordinates),
+        VectorGeometry::MultiPoint(g) => g.coordinates.iter_mut().for_each(f),
+        VectorGeometry::LineString(g) => g.coordinates.iter_mut().for_each(f),
+        VectorGeometry::MultiLineString(g) => g.coordinates.iter_mut().flatten().for_each(f),
+        VectorGeometry::Polygon(g) => g.coordinates.iter_mut().flatten().for_each(f),
+        VectorGeometry::MultiPolygon(g) => g.coordinates.iter_mut().flatten().flatten().for_each(f),
+    }
+}
@@ -0,0 +1,9 @@
+/// The S2CellId and its hierarchy navigation
+pub mod cell_id;
+/// Conversions between WG Features and S2Features
+pub mod convert;
+/// S2 projection math between lon-lat, xyz, face-uv and face-st
+pub mod projection;
+
+pub use cell_id::*;
+pub use projection::*;
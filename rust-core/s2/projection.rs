@@ -0,0 +1,183 @@
+use crate::*;
+use libm::{atan, atan2, cos, sin, sqrt, tan};
+
+//? uv <-> st
+
+/// Convert an s or t value to a u or v value using the quadratic transform (the S2 default)
+pub fn quadratic_st_to_uv(s: f64) -> f64 {
+    if s >= 0.5 {
+        (1. / 3.) * (4. * s * s - 1.)
+    } else {
+        (1. / 3.) * (1. - 4. * (1. - s) * (1. - s))
+    }
+}
+
+/// Convert a u or v value to an s or t value using the quadratic transform (the S2 default)
+pub fn quadratic_uv_to_st(u: f64) -> f64 {
+    if u >= 0. { 0.5 * sqrt(1. + 3. * u) } else { 1. - 0.5 * sqrt(1. - 3. * u) }
+}
+
+/// Convert an s or t value to a u or v value using the tangent transform
+pub fn tan_st_to_uv(s: f64) -> f64 {
+    tan(core::f64::consts::FRAC_PI_2 * s - core::f64::consts::FRAC_PI_4)
+}
+
+/// Convert a u or v value to an s or t value using the tangent transform
+pub fn tan_uv_to_st(u: f64) -> f64 {
+    (2. * core::f64::consts::FRAC_1_PI) * (atan(u) + core::f64::consts::FRAC_PI_4)
+}
+
+/// Convert an s or t value to a u or v value. Uses the quadratic transform
+pub fn st_to_uv(s: f64) -> f64 {
+    quadratic_st_to_uv(s)
+}
+
+/// Convert a u or v value to an s or t value. Uses the quadratic transform
+pub fn uv_to_st(u: f64) -> f64 {
+    quadratic_uv_to_st(u)
+}
+
+//? lon-lat <-> xyz
+
+/// Convert a lon-lat coordinate in degrees to a point on the unit sphere
+pub fn lon_lat_to_xyz(lon: f64, lat: f64) -> (f64, f64, f64) {
+    let (lon, lat) = (lon.to_radians(), lat.to_radians());
+    (cos(lat) * cos(lon), cos(lat) * sin(lon), sin(lat))
+}
+
+/// Convert a point on the sphere to a lon-lat coordinate in degrees. The point need not be unit
+/// length. The longitude of a pole is reported as 0
+pub fn xyz_to_lon_lat(x: f64, y: f64, z: f64) -> (f64, f64) {
+    let lon = if x == 0. && y == 0. { 0. } else { atan2(y, x).to_degrees() };
+    (lon, atan2(z, sqrt(x * x + y * y)).to_degrees())
+}
+
+//? face-uv <-> xyz
+
+/// Find the face a point on the sphere belongs to
+pub fn get_face(x: f64, y: f64, z: f64) -> Face {
+    let (ax, ay, az) = (x.abs(), y.abs(), z.abs());
+    let (axis, value): (u8, f64) = if ax > ay {
+        if ax > az { (0, x) } else { (2, z) }
+    } else if ay > az {
+        (1, y)
+    } else {
+        (2, z)
+    };
+    Face::from(if value < 0. { axis + 3 } else { axis })
+}
+
+/// Convert a face-uv coordinate to a point on the sphere. The result is not unit length
+pub fn face_uv_to_xyz(face: Face, u: f64, v: f64) -> (f64, f64, f64) {
+    match face {
+        Face::Face0 => (1., u, v),
+        Face::Face1 => (-u, 1., v),
+        Face::Face2 => (-u, -v, 1.),
+        Face::Face3 => (-1., -v, -u),
+        Face::Face4 => (v, -1., -u),
+        Face::Face5 => (v, u, -1.),
+    }
+}
+
+/// Convert a point on the sphere to a uv coordinate relative to the given face. The point should
+/// lie on the hemisphere centered on the face
+pub fn face_xyz_to_uv(face: Face, x: f64, y: f64, z: f64) -> (f64, f64) {
+    match face {
+        Face::Face0 => (y / x, z / x),
+        Face::Face1 => (-x / y, z / y),
+        Face::Face2 => (-x / z, -y / z),
+        Face::Face3 => (z / x, y / x),
+        Face::Face4 => (z / y, -x / y),
+        Face::Face5 => (-y / z, -x / z),
+    }
+}
+
+/// Convert a point on the sphere to the face it belongs to and its uv coordinate
+pub fn xyz_to_face_uv(x: f64, y: f64, z: f64) -> (Face, f64, f64) {
+    let face = get_face(x, y, z);
+    let (u, v) = face_xyz_to_uv(face, x, y, z);
+    (face, u, v)
+}
+
+//? face-st <-> xyz
+
+/// Convert a face-st coordinate to a point on the unit sphere
+pub fn face_st_to_xyz(face: Face, s: f64, t: f64) -> (f64, f64, f64) {
+    let (x, y, z) = face_uv_to_xyz(face, st_to_uv(s), st_to_uv(t));
+    let len = sqrt(x * x + y * y + z * z);
+    (x / len, y / len, z / len)
+}
+
+/// Convert a point on the sphere to the face it belongs to and its st coordinate
+pub fn xyz_to_face_st(x: f64, y: f64, z: f64) -> (Face, f64, f64) {
+    let (face, u, v) = xyz_to_face_uv(x, y, z);
+    (face, uv_to_st(u), uv_to_st(v))
+}
+
+//? face-st <-> lon-lat
+
+/// Convert a lon-lat coordinate in degrees to the face it belongs to and its st coordinate
+pub fn lon_lat_to_face_st(lon: f64, lat: f64) -> (Face, f64, f64) {
+    let (x, y, z) = lon_lat_to_xyz(lon, lat);
+    xyz_to_face_st(x, y, z)
+}
+
+/// Convert a face-st coordinate to a lon-lat coordinate in degrees
+pub fn face_st_to_lon_lat(face: Face, s: f64, t: f64) -> (f64, f64) {
+    let (x, y, z) = face_uv_to_xyz(face, st_to_uv(s), st_to_uv(t));
+    xyz_to_lon_lat(x, y, z)
+}
+
+//? Generic point tooling
+
+/// Convert a lon-lat point in degrees to a new point on the unit sphere
+pub fn point_to_xyz<P: GetXY, R: NewXYZ>(lon_lat: &P) -> R {
+    let (x, y, z) = lon_lat_to_xyz(lon_lat.x(), lon_lat.y());
+    R::new_xyz(x, y, z)
+}
+
+/// Convert a point on the sphere to a new lon-lat point in degrees
+pub fn point_from_xyz<P: GetXYZ, R: NewXY>(xyz: &P) -> R {
+    let (lon, lat) = xyz_to_lon_lat(xyz.x(), xyz.y(), xyz.z().unwrap_or_default());
+    R::new_xy(lon, lat)
+}
+
+/// Project a lon-lat point in degrees to st in place, returning its face. Any z or m value is left
+/// untouched
+pub fn point_to_st<P: GetXY + SetXY>(point: &mut P) -> Face {
+    let (face, s, t) = lon_lat_to_face_st(point.x(), point.y());
+    point.set_xy(s, t);
+    face
+}
+
+/// Unproject a face-st point to lon-lat in degrees in place. Any z or m value is left untouched
+pub fn point_from_st<P: GetXY + SetXY>(face: Face, point: &mut P) {
+    let (lon, lat) = face_st_to_lon_lat(face, point.x(), point.y());
+    point.set_xy(lon, lat);
+}
+
+impl<M: Clone> STPoint<M> {
+    /// Create an STPoint from a lon-lat point in degrees, keeping its z and m values
+    pub fn from_lon_lat<P: GetXYZ + GetM<M>>(point: &P) -> Self {
+        let (face, s, t) = lon_lat_to_face_st(point.x(), point.y());
+        STPoint { face, s, t, z: point.z(), m: point.m().cloned() }
+    }
+
+    /// Create an STPoint from a point on the sphere
+    pub fn from_xyz<P: GetXYZ>(point: &P) -> Self {
+        let (face, s, t) = xyz_to_face_st(point.x(), point.y(), point.z().unwrap_or_default());
+        STPoint { face, s, t, z: None, m: None }
+    }
+
+    /// Convert to a lon-lat point in degrees, keeping the z and m values
+    pub fn to_lon_lat(&self) -> VectorPoint<M> {
+        let (lon, lat) = face_st_to_lon_lat(self.face, self.s, self.t);
+        VectorPoint::new(lon, lat, self.z, self.m.clone())
+    }
+
+    /// Convert to a point on the unit sphere
+    pub fn to_xyz(&self) -> Point3D {
+        let (x, y, z) = face_st_to_xyz(self.face, self.s, self.t);
+        Point3D(x, y, z)
+    }
+}
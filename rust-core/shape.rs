@@ -1,6 +1,6 @@
 use crate::Map;
-use alloc::{string::String, vec::Vec};
-use serde::{Deserialize, Serialize};
+use alloc::{boxed::Box, string::String, vec::Vec};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 //? Shape
 
@@ -12,10 +12,12 @@ use serde::{Deserialize, Serialize};
 // - - primitive types: strings, numbers (f32, f64, u64, i64), true, false, or null
 // - - sub types: an array of a shape or a nested object which is itself a shape
 // - - if the sub type is an array, ensure all elements are of the same type
+// - a value of a shape may be marked nullable (the value may be null) or optional (the key may be
+//   absent). Markers are encoded as an object with a single reserved key: {"$nullable": "f64"}
 // The interfaces below help describe how shapes are built by the user.
 
 /// Primitive types that can be found in a shape
-#[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq)]
+#[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq, Eq)]
 #[serde(rename_all = "lowercase")]
 pub enum PrimitiveShape {
     /// String type utf8 encoded
@@ -36,7 +38,7 @@ pub enum PrimitiveShape {
 }
 
 /// Arrays may contain either a primitive or an object whose values are primitives
-#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
 #[serde(untagged)]
 pub enum PrimitiveShapeType {
     /// Primitive type
@@ -47,19 +49,76 @@ pub enum PrimitiveShapeType {
 
 /// Shape types that can be found in a shapes object.
 /// Either a primitive, an array containing any type, or a nested shape.
-/// If the type is an array, all elements must be the same type
-#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+/// If the type is an array, all elements must be the same type.
+/// Any of these may be wrapped as nullable or optional. Prefer [`ShapeType::nullable`] and
+/// [`ShapeType::optional`] to build them, which keep an optional marker outermost
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
 #[serde(untagged)]
 pub enum ShapeType {
     /// Primitive type
     Primitive(PrimitiveShape),
     /// Nested shape that can only contain primitives
     Array(Vec<PrimitiveShapeType>),
+    /// The value is either the inner shape or null. Encoded as `{"$nullable": shape}`
+    Nullable(
+        #[serde(serialize_with = "ser_nullable", deserialize_with = "de_nullable")] Box<ShapeType>,
+    ),
+    /// The key may be absent from the value. Encoded as `{"$optional": shape}`
+    Optional(
+        #[serde(serialize_with = "ser_optional", deserialize_with = "de_optional")] Box<ShapeType>,
+    ),
     /// Nested shape
     Nested(Shape),
 }
 
+#[derive(Serialize)]
+struct NullableRef<'a> {
+    #[serde(rename = "$nullable")]
+    shape: &'a ShapeType,
+}
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct NullableOwned {
+    #[serde(rename = "$nullable")]
+    shape: Box<ShapeType>,
+}
+#[derive(Serialize)]
+struct OptionalRef<'a> {
+    #[serde(rename = "$optional")]
+    shape: &'a ShapeType,
+}
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct OptionalOwned {
+    #[serde(rename = "$optional")]
+    shape: Box<ShapeType>,
+}
+fn ser_nullable<S: Serializer>(shape: &ShapeType, serializer: S) -> Result<S::Ok, S::Error> {
+    NullableRef { shape }.serialize(serializer)
+}
+fn de_nullable<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Box<ShapeType>, D::Error> {
+    NullableOwned::deserialize(deserializer).map(|n| n.shape)
+}
+fn ser_optional<S: Serializer>(shape: &ShapeType, serializer: S) -> Result<S::Ok, S::Error> {
+    OptionalRef { shape }.serialize(serializer)
+}
+fn de_optional<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Box<ShapeType>, D::Error> {
+    OptionalOwned::deserialize(deserializer).map(|o| o.shape)
+}
+
 /// The Primitive Shape Object
 pub type ShapePrimitive = Map<String, PrimitiveShape>;
 /// The Shape Object
 pub type Shape = Map<String, ShapeType>;
+
+/// How conflicting shapes are handled when merging shapes or inferring a shape from values.
+/// With either policy, numbers merge into the highest order number, merging with `null` makes a
+/// shape nullable and a key missing from either side becomes optional
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ShapeMergePolicy {
+    /// Any other conflict is an error
+    #[default]
+    Strict,
+    /// Conflicts never fail, the conflicting shape widens to a `String`
+    Widen,
+}
@@ -0,0 +1,230 @@
+extern crate alloc;
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+    use s2json_core::*;
+
+    #[test]
+    fn clip_points() {
+        let point: VectorGeometry = VectorGeometry::new_point(VectorPoint::from_xy(5., 5.), None);
+        assert!(point.clip_axis(Axis::X, 6., 10.).is_none());
+        let clipped = point.clip_axis(Axis::Y, 0., 5.).unwrap();
+        assert_eq!(clipped.point(), point.point());
+        assert_eq!(clipped.vec_bbox(), &Some(BBox3D::new(5., 5., 5., 5., f64::MAX, f64::MIN)));
+
+        let points: VectorGeometry = VectorGeometry::new_multipoint(
+            vec![
+                VectorPoint::from_xy(0., 0.),
+                VectorPoint::from_xy(5., 1.),
+                VectorPoint::from_xy(10., 2.),
+            ],
+            None,
+        );
+        let clipped = points.clip_axis(Axis::X, 2., 10.).unwrap();
+        assert_eq!(
+            *clipped.multipoint().unwrap(),
+            [VectorPoint::from_xy(5., 1.), VectorPoint::from_xy(10., 2.)]
+        );
+        assert!(points.clip_axis(Axis::Y, 3., 4.).is_none());
+    }
+
+    #[test]
+    fn clip_line_offsets() {
+        let bbox = BBox3D::new(0., 0., 10., 10., 0., 0.);
+        let geometry: VectorGeometry = VectorGeometry::new_linestring(
+            vec![
+                VectorPoint::from_xy(0., 0.),
+                VectorPoint::from_xy(10., 0.),
+                VectorPoint::from_xy(10., 10.),
+                VectorPoint::from_xy(0., 10.),
+            ],
+            Some(bbox),
+        );
+        // leaving and re-entering the band makes a multi line
+        let clipped = geometry.clip_axis(Axis::X, 2., 8.).unwrap();
+        let VectorGeometry::MultiLineString(lines) = &clipped else { panic!("expected lines") };
+        assert_eq!(lines._type, VectorGeometryType::MultiLineString);
+        assert_eq!(
+            lines.coordinates[0],
+            [VectorPoint::from_xy(2., 0.), VectorPoint::from_xy(8., 0.)]
+        );
+        assert_eq!(
+            lines.coordinates[1],
+            [VectorPoint::from_xy(8., 10.), VectorPoint::from_xy(2., 10.)]
+        );
+        assert_eq!(lines.offset, Some(vec![2., 22.]));
+        assert_eq!(lines.bbox, Some(bbox));
+        assert_eq!(lines.vec_bbox, Some(BBox3D::new(2., 0., 8., 10., f64::MAX, f64::MIN)));
+        // the same pieces straight from the line
+        let VectorGeometry::LineString(line) = &geometry else { panic!("expected a line") };
+        let pieces = clip_line(&line.coordinates, 0., Axis::X, 2., 8., false);
+        assert_eq!(pieces.len(), 2);
+        assert_eq!(pieces[0].points, lines.coordinates[0]);
+        assert_eq!((pieces[0].offset, pieces[1].offset), (2., 22.));
+
+        // a single piece stays a line, and offsets build on the existing one
+        let clipped = geometry.clip_axis(Axis::Y, 5., 20.).unwrap();
+        let VectorGeometry::LineString(piece) = &clipped else { panic!("expected a line") };
+        assert_eq!(
+            piece.coordinates,
+            [
+                VectorPoint::from_xy(10., 5.),
+                VectorPoint::from_xy(10., 10.),
+                VectorPoint::from_xy(0., 10.)
+            ]
+        );
+        assert_eq!(piece.offset, Some(15.));
+        let clipped = clipped.clip_axis(Axis::X, -1., 5.).unwrap();
+        assert_eq!(
+            *clipped.linestring().unwrap(),
+            [VectorPoint::from_xy(5., 10.), VectorPoint::from_xy(0., 10.)]
+        );
+        let VectorGeometry::LineString(piece) = &clipped else { panic!("expected a line") };
+        assert_eq!(piece.offset, Some(25.));
+
+        // lines of a multi line keep their own offsets
+        let geometry: VectorGeometry = VectorGeometry::new_multilinestring(
+            vec![
+                vec![VectorPoint::from_xy(0., 0.), VectorPoint::from_xy(10., 0.)],
+                vec![VectorPoint::from_xy(20., 0.), VectorPoint::from_xy(0., 0.)],
+            ],
+            None,
+        );
+        let clipped = geometry.clip_axis(Axis::X, 4., 6.).unwrap();
+        let VectorGeometry::MultiLineString(lines) = &clipped else { panic!("expected lines") };
+        assert_eq!(
+            lines.coordinates[1],
+            [VectorPoint::from_xy(6., 0.), VectorPoint::from_xy(4., 0.)]
+        );
+        assert_eq!(lines.offset, Some(vec![4., 14.]));
+        assert!(geometry.clip_axis(Axis::Y, 1., 2.).is_none());
+    }
+
+    #[test]
+    fn clip_interpolates() {
+        let a =
+            VectorPoint::new(0., 0., Some(100.), Some(MValue::from([("a".into(), 0.0.into())])));
+        let b =
+            VectorPoint::new(10., 0., Some(200.), Some(MValue::from([("a".into(), 10.0.into())])));
+        let mut geometry: VectorGeometry = VectorGeometry::new_linestring(vec![a, b], None);
+        geometry.build_simplification(SimplifyMethod::DouglasPeucker);
+        let clipped = geometry.clip_axis(Axis::X, 2.5, 5.).unwrap();
+        let points = clipped.linestring().unwrap();
+        assert_eq!(
+            *points,
+            [VectorPoint::from_xyz(2.5, 0., 125.), VectorPoint::from_xyz(5., 0., 150.)]
+        );
+        assert_eq!(points[0].m, Some(MValue::from([("a".into(), 2.5.into())])));
+        assert_eq!(points[1].m, Some(MValue::from([("a".into(), 5.0.into())])));
+        // cut points survive any simplification
+        assert_eq!(points[0].t, Some(f64::INFINITY));
+        let VectorGeometry::LineString(piece) = &clipped else { panic!("expected a line") };
+        assert!(piece.is_3d);
+    }
+
+    #[test]
+    fn clip_polygons() {
+        let square = vec![
+            VectorPoint::from_xy(0., 0.),
+            VectorPoint::from_xy(10., 0.),
+            VectorPoint::from_xy(10., 10.),
+            VectorPoint::from_xy(0., 10.),
+            VectorPoint::from_xy(0., 0.),
+        ];
+        let hole = vec![
+            VectorPoint::from_xy(4., 4.),
+            VectorPoint::from_xy(4., 6.),
+            VectorPoint::from_xy(6., 6.),
+            VectorPoint::from_xy(6., 4.),
+            VectorPoint::from_xy(4., 4.),
+        ];
+        let mut geometry: VectorGeometry =
+            VectorGeometry::new_polygon(vec![square.clone(), hole.clone()], None);
+        geometry.tessellate();
+        let clipped = geometry.clip_axis(Axis::X, 5., 20.).unwrap();
+        let VectorGeometry::Polygon(polygon) = &clipped else { panic!("expected a polygon") };
+        assert_eq!(
+            polygon.coordinates[0],
+            [
+                VectorPoint::from_xy(5., 0.),
+                VectorPoint::from_xy(10., 0.),
+                VectorPoint::from_xy(10., 10.),
+                VectorPoint::from_xy(5., 10.),
+                VectorPoint::from_xy(5., 0.)
+            ]
+        );
+        assert_eq!(
+            polygon.coordinates[1],
+            [
+                VectorPoint::from_xy(5., 6.),
+                VectorPoint::from_xy(6., 6.),
+                VectorPoint::from_xy(6., 4.),
+                VectorPoint::from_xy(5., 4.),
+                VectorPoint::from_xy(5., 6.)
+            ]
+        );
+        assert_eq!(polygon.offset, Some(vec![5., 3.]));
+        // the tessellation no longer matches
+        assert_eq!(polygon.indices, None);
+        assert_eq!(polygon.tessellation, None);
+
+        // holes can be clipped away
+        let clipped = geometry.clip_axis(Axis::Y, 7., 10.).unwrap();
+        assert_eq!(clipped.polygon().unwrap().len(), 1);
+        assert!(geometry.clip_axis(Axis::Y, 11., 12.).is_none());
+
+        let geometry: VectorGeometry = VectorGeometry::new_multipolygon(
+            vec![
+                vec![square],
+                vec![vec![
+                    VectorPoint::from_xy(20., 0.),
+                    VectorPoint::from_xy(30., 0.),
+                    VectorPoint::from_xy(30., 10.),
+                    VectorPoint::from_xy(20., 0.),
+                ]],
+            ],
+            None,
+        );
+        let clipped = geometry.clip_axis(Axis::X, 15., 25.).unwrap();
+        let VectorGeometry::MultiPolygon(polygons) = &clipped else { panic!("expected polygons") };
+        assert_eq!(polygons.coordinates.len(), 1);
+        assert_eq!(
+            polygons.coordinates[0][0],
+            [
+                VectorPoint::from_xy(20., 0.),
+                VectorPoint::from_xy(25., 0.),
+                VectorPoint::from_xy(25., 5.),
+                VectorPoint::from_xy(20., 0.)
+            ]
+        );
+        assert_eq!(polygons.offset, Some(vec![vec![0.]]));
+        assert!(geometry.clip_axis(Axis::X, 40., 50.).is_none());
+    }
+
+    #[test]
+    fn clip_bbox() {
+        let geometry: VectorGeometry = VectorGeometry::new_linestring(
+            vec![
+                VectorPoint::from_xy(-10., 5.),
+                VectorPoint::from_xy(20., 5.),
+                VectorPoint::from_xy(20., 20.),
+            ],
+            None,
+        );
+        let bbox = BBox::new(0., 0., 10., 10.);
+        let clipped = geometry.clip_bbox(&bbox, None).unwrap();
+        assert_eq!(
+            *clipped.linestring().unwrap(),
+            [VectorPoint::from_xy(0., 5.), VectorPoint::from_xy(10., 5.)]
+        );
+        let clipped = geometry.clip_bbox(&bbox, Some(2.)).unwrap();
+        assert_eq!(
+            *clipped.linestring().unwrap(),
+            [VectorPoint::from_xy(-2., 5.), VectorPoint::from_xy(12., 5.)]
+        );
+        let VectorGeometry::LineString(piece) = &clipped else { panic!("expected a line") };
+        assert_eq!(piece.offset, Some(8.));
+        assert!(geometry.clip_bbox(&BBox::new(30., 30., 40., 40.), Some(1.)).is_none());
+    }
+}
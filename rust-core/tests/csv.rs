@@ -0,0 +1,137 @@
+extern crate alloc;
+
+#[cfg(test)]
+mod tests {
+    use alloc::{string::String, vec};
+    use s2json_core::*;
+
+    #[test]
+    fn read() {
+        let csv = "name,Latitude,Longitude,count,delta,score,open,note\r\n\
+                   \"Cafe, \"\"Blue\"\"\",47.5,-122.25,3,-1,1,true,\n\
+                   \n\
+                   Park,47.6,-122.2,4,2,2.5,false,\"two\nlines\"\n\
+                   Pier,47.7,-122.3,5,3,3,maybe,x";
+        let (shape, features) = read_csv(csv, &CsvOptions::default()).unwrap();
+        assert_eq!(
+            shape,
+            Shape::from([
+                ("name".into(), ShapeType::Primitive(PrimitiveShape::String)),
+                ("count".into(), ShapeType::Primitive(PrimitiveShape::U64)),
+                // numbers widen to the highest order number in the column
+                ("delta".into(), ShapeType::Primitive(PrimitiveShape::I64)),
+                ("score".into(), ShapeType::Primitive(PrimitiveShape::F64)),
+                // a column mixing kinds is a string
+                ("open".into(), ShapeType::Primitive(PrimitiveShape::String)),
+                ("note".into(), ShapeType::Primitive(PrimitiveShape::String).nullable()),
+            ])
+        );
+        assert_eq!(features.len(), 3);
+        assert_eq!(
+            features[0].geometry,
+            Geometry::Point(PointGeometry {
+                _type: GeometryType::Point,
+                coordinates: Point(-122.25, 47.5),
+                ..Default::default()
+            })
+        );
+        assert_eq!(
+            features[0].properties,
+            Properties::from([
+                ("name".into(), "Cafe, \"Blue\"".into()),
+                ("count".into(), 3_u64.into()),
+                ("delta".into(), (-1_i64).into()),
+                ("score".into(), 1_f64.into()),
+                ("open".into(), "true".into()),
+                ("note".into(), ValueType::Primitive(PrimitiveValue::Null)),
+            ])
+        );
+        assert_eq!(features[1].properties["note"], "two\nlines".into());
+        assert_eq!(features[2].properties["score"], 3_f64.into());
+
+        // tab separated, with configured coordinate columns, into vector features
+        let tsv = "id\tE\tN\n1\t2.5\t-3\n";
+        let options = CsvOptions {
+            lon_column: Some("E".into()),
+            lat_column: Some("N".into()),
+            ..CsvOptions::tsv()
+        };
+        let (shape, features) = read_csv_vector(tsv, &options).unwrap();
+        assert_eq!(shape, Shape::from([("id".into(), ShapeType::Primitive(PrimitiveShape::U64))]));
+        assert_eq!(
+            features[0].geometry,
+            VectorGeometry::new_point(VectorPoint::from_xy(2.5, -3.), None)
+        );
+        assert_eq!(features[0].properties, Properties::from([("id".into(), 1_u64.into())]));
+    }
+
+    #[test]
+    fn round_trip() {
+        let csv = "lon,lat,label,rank\n1.5,-2,\"a,b\",1\n3,4,,2\n";
+        let (_, features) = read_csv(csv, &CsvOptions::default()).unwrap();
+        assert_eq!(write_csv(&features, &CsvOptions::default()), Ok(String::from(csv)));
+
+        // multi points write a row per point and missing properties are empty
+        let features: Vec<Feature> = vec![
+            Feature::new(
+                None,
+                Properties::from([("a".into(), 1_u64.into())]),
+                Geometry::MultiPoint(MultiPointGeometry {
+                    _type: GeometryType::MultiPoint,
+                    coordinates: vec![Point(0., 0.), Point(1., 1.)],
+                    ..Default::default()
+                }),
+                None,
+            ),
+            Feature::new(
+                None,
+                Properties::from([("b".into(), "x\ty".into())]),
+                Geometry::Point3D(Point3DGeometry {
+                    _type: GeometryType::Point3D,
+                    coordinates: Point3D(2., 3., 4.),
+                    ..Default::default()
+                }),
+                None,
+            ),
+        ];
+        assert_eq!(
+            write_csv(&features, &CsvOptions::tsv()),
+            Ok(String::from("lon\tlat\ta\tb\n0\t0\t1\t\n1\t1\t1\t\n2\t3\t\t\"x\ty\"\n"))
+        );
+    }
+
+    #[test]
+    fn errors() {
+        let options = CsvOptions::default();
+        assert_eq!(
+            read_csv("name,lat\na,1\n", &options),
+            Err(S2JsonError::invalid_csv(1, "no longitude column"))
+        );
+        assert_eq!(
+            read_csv("lon,lat,name\n1,2,a\n\n3,4\n", &options),
+            Err(S2JsonError::invalid_csv(4, "expected 3 fields, found 2"))
+        );
+        assert_eq!(
+            read_csv("lon,lat\n1,north\n", &options),
+            Err(S2JsonError::invalid_csv(2, "invalid coordinate \"north\""))
+        );
+        assert_eq!(
+            read_csv("lon,lat\n1,2\n\"3,4\n", &options),
+            Err(S2JsonError::invalid_csv(3, "unterminated quoted field"))
+        );
+        let line: Vec<Feature> = vec![Feature::new(
+            None,
+            Properties::new(),
+            Geometry::LineString(LineStringGeometry {
+                _type: GeometryType::LineString,
+                coordinates: vec![Point(0., 0.), Point(1., 1.)],
+                ..Default::default()
+            }),
+            None,
+        )];
+        assert_eq!(
+            write_csv(&line, &options),
+            Err(S2JsonError::invalid_type("point geometry", "LineString"))
+        );
+    }
+}
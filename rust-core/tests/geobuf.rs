@@ -0,0 +1,136 @@
+extern crate alloc;
+
+#[cfg(test)]
+mod tests {
+    use alloc::{format, string::String, vec::Vec};
+    use s2json_core::*;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+    struct Meta {
+        layer: String,
+    }
+
+    fn geometries() -> Vec<&'static str> {
+        alloc::vec![
+            r#"{"type":"Point","coordinates":[1.5,-2.25],"mValues":{"a":1},"bbox":[1.5,-2.25,1.5,-2.25]}"#,
+            r#"{"type":"MultiPoint","coordinates":[[0,0],[10.123456,-5.5]],"mValues":[{"a":1},{"a":2}]}"#,
+            r#"{"type":"LineString","coordinates":[[0,0],[1,1],[2,0.5]],"mValues":[]}"#,
+            r#"{"type":"MultiLineString","coordinates":[[[0,0],[1,1]],[],[[2,2],[3,3],[4,4]]],"mValues":[[{"t":1},{"t":2}],[],[{},{"t":{"nested":[1,2]}},{"t":3}]]}"#,
+            r#"{"type":"Polygon","coordinates":[[[0,0],[4,0],[4,4],[0,0]],[[1,1],[2,1],[2,2],[1,1]]]}"#,
+            r#"{"type":"MultiPolygon","coordinates":[[[[0,0],[1,0],[1,1],[0,0]]],[[[5,5],[6,5],[6,6],[5,5]]]],"bbox":[0,0,6,6]}"#,
+            r#"{"type":"Point3D","coordinates":[1,2,3.75],"mValues":{"b":"x"},"bbox":[1,2,1,2,3.75,3.75]}"#,
+            r#"{"type":"MultiPoint3D","coordinates":[[0,0,0],[1,1,-1]]}"#,
+            r#"{"type":"LineString3D","coordinates":[[0,0,100],[1,1,200]],"mValues":[{"c":true},{"c":false}]}"#,
+            r#"{"type":"MultiLineString3D","coordinates":[[[0,0,1],[1,1,2]],[[2,2,3],[3,3,4]]]}"#,
+            r#"{"type":"Polygon3D","coordinates":[[[0,0,1],[4,0,1],[4,4,1],[0,0,1]]],"mValues":[[{"d":1},{"d":2},{"d":3},{"d":1}]]}"#,
+            r#"{"type":"MultiPolygon3D","coordinates":[[[[0,0,5],[1,0,5],[1,1,5],[0,0,5]]]],"bbox":[0,0,1,1,5,5]}"#,
+        ]
+    }
+
+    #[test]
+    fn round_trip_every_geometry() {
+        let features: Vec<String> = geometries()
+            .into_iter()
+            .enumerate()
+            .map(|(i, geometry)| {
+                format!(
+                    r#"{{"type":"Feature","id":{i},"properties":{{"name":"f{i}","n":{i},"deep":{{"list":[1,"a",{{"k":null}}],"empty":[]}}}},"geometry":{geometry},"metadata":{{"layer":"l{i}"}}}}"#
+                )
+            })
+            .collect();
+        let json = format!(
+            r#"{{"type":"FeatureCollection","features":[{},{}],"attributions":{{"Open S2":"https://opens2.com/legal/data"}},"bbox":[-180,-90,180,90]}}"#,
+            features.join(","),
+            r#"{"type":"VectorFeature","face":0,"properties":{"v":1},"geometry":{"type":"Point","is3D":false,"coordinates":{"x":0.25,"y":0.5}}}"#
+        );
+        let collection: FeatureCollection<Meta> = serde_json::from_str(&json).unwrap();
+        assert_eq!(collection.features.len(), 13);
+
+        let data = collection.to_geobuf();
+        let back = FeatureCollection::<Meta>::from_geobuf(&data).unwrap();
+        assert_eq!(back, collection);
+        // a feature without an id, properties, or metadata stays that way
+        let mut collection = FeatureCollection::<Meta>::new(None);
+        collection.features.push(Features::Feature(Feature::default()));
+        assert_eq!(FeatureCollection::from_geobuf(&collection.to_geobuf()), Ok(collection));
+    }
+
+    #[test]
+    fn irregular_coordinates_are_lossless() {
+        let json = format!(
+            r#"{{"type":"FeatureCollection","features":[{{"type":"Feature","properties":{{}},"geometry":{{"type":"LineString3D","coordinates":[[{},1e-300,{}],[1.7976931348623157e308,-0.0,5e-324]]}}}}]}}"#,
+            0.1 + 0.2,
+            core::f64::consts::PI,
+        );
+        let collection: FeatureCollection = serde_json::from_str(&json).unwrap();
+        let back = FeatureCollection::from_geobuf(&collection.to_geobuf()).unwrap();
+        assert_eq!(back, collection);
+        let Features::Feature(feature) = &back.features[0] else { panic!("expected a feature") };
+        let Geometry::LineString3D(line) = &feature.geometry else { panic!("expected a line") };
+        assert_eq!(line.coordinates[0].0.to_bits(), (0.1_f64 + 0.2).to_bits());
+    }
+
+    #[test]
+    fn smaller_than_json() {
+        let mut collection = FeatureCollection::<()>::new(None);
+        for i in 0..100 {
+            let base = i as f64 * 0.013_7;
+            let ring: Vec<Point> = (0..32)
+                .map(|j| {
+                    let angle = j as f64 / 32. * core::f64::consts::TAU;
+                    let x = base + libm::cos(angle) * 0.01;
+                    let y = 45. + libm::sin(angle) * 0.01;
+                    Point(libm::round(x * 1e6) / 1e6, libm::round(y * 1e6) / 1e6)
+                })
+                .collect();
+            let properties = Properties::from([
+                ("name".into(), format!("parcel {i}").into()),
+                ("area".into(), (i * 10).into()),
+            ]);
+            collection.features.push(Features::Feature(Feature::new(
+                Some(i),
+                properties,
+                Geometry::Polygon(PolygonGeometry {
+                    _type: GeometryType::Polygon,
+                    coordinates: alloc::vec![ring],
+                    ..Default::default()
+                }),
+                None,
+            )));
+        }
+        let json = serde_json::to_string(&collection).unwrap();
+        let data = collection.to_geobuf();
+        assert!(data.len() * 3 < json.len(), "{} vs {}", data.len(), json.len());
+        assert_eq!(FeatureCollection::from_geobuf(&data), Ok(collection));
+    }
+
+    #[test]
+    fn malformed_data() {
+        let json = format!(
+            r#"{{"type":"FeatureCollection","features":[{{"type":"Feature","properties":{{"a":1}},"geometry":{}}}]}}"#,
+            geometries()[3]
+        );
+        let collection: FeatureCollection = serde_json::from_str(&json).unwrap();
+        let data = collection.to_geobuf();
+        for len in 0..data.len() {
+            let _ = FeatureCollection::<()>::from_geobuf(&data[..len]);
+        }
+        assert_eq!(
+            FeatureCollection::<()>::from_geobuf(&data[..data.len() - 1]),
+            Err(S2JsonError::UnexpectedEof)
+        );
+
+        // a feature whose geometry has the unknown type 12
+        let data = [0x12, 0x04, 0x12, 0x02, 0x08, 0x0c];
+        assert_eq!(
+            FeatureCollection::<()>::from_geobuf(&data),
+            Err(S2JsonError::invalid_type("GeometryType", "12"))
+        );
+        assert_eq!(GeometryType::try_from(11), Ok(GeometryType::MultiPolygon3D));
+        assert_eq!(
+            GeometryType::try_from(12),
+            Err(S2JsonError::invalid_type("GeometryType", "12"))
+        );
+    }
+}
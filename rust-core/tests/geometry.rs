@@ -268,43 +268,43 @@ mod tests {
 
     #[test]
     fn test_bbox_from_linestring() {
-        let bbox = BBox::from_linestring(&vec![
+        let bbox = BBox::from_linestring(&[
             VectorPoint::<MValue>::new(0., 0., None, None),
             VectorPoint::new(1., 1.5, None, None),
         ]);
         assert_eq!(bbox, BBox { left: 0.0, bottom: 0.0, right: 1.0, top: 1.5 });
 
-        let bbox = BBox::from_linestring(&vec![] as &[VectorPoint<MValue>]);
+        let bbox = BBox::from_linestring(&[] as &[VectorPoint<MValue>]);
         assert_eq!(bbox, BBox::default());
     }
 
     #[test]
     fn test_bbox_from_multilinestring() {
-        let bbox = BBox::from_multi_linestring(&vec![vec![
+        let bbox = BBox::from_multi_linestring(&[vec![
             VectorPoint::<MValue>::new(0., 0., None, None),
             VectorPoint::new(1., 1.5, None, None),
         ]]);
         assert_eq!(bbox, BBox { left: 0.0, bottom: 0.0, right: 1.0, top: 1.5 });
 
-        let bbox = BBox::from_multi_linestring(&vec![] as &[Vec<VectorPoint<MValue>>]);
+        let bbox = BBox::from_multi_linestring(&[] as &[Vec<VectorPoint<MValue>>]);
         assert_eq!(bbox, BBox::default());
     }
 
     #[test]
     fn test_bbox_from_polygon() {
-        let bbox = BBox::from_polygon(&vec![vec![
+        let bbox = BBox::from_polygon(&[vec![
             VectorPoint::<MValue>::new(0., 0., None, None),
             VectorPoint::new(2., 1.5, None, None),
         ]]);
         assert_eq!(bbox, BBox { left: 0.0, bottom: 0.0, right: 2.0, top: 1.5 });
 
-        let bbox = BBox::from_polygon(&vec![] as &[Vec<VectorPoint<MValue>>]);
+        let bbox = BBox::from_polygon(&[] as &[Vec<VectorPoint<MValue>>]);
         assert_eq!(bbox, BBox::default());
     }
 
     #[test]
     fn test_bbox_from_multipolygon() {
-        let bbox = BBox::from_multi_polygon(&vec![
+        let bbox = BBox::from_multi_polygon(&[
             vec![vec![
                 VectorPoint::<MValue>::new(0., 0., None, None),
                 VectorPoint::new(2., 1.5, None, None),
@@ -316,7 +316,7 @@ mod tests {
         ]);
         assert_eq!(bbox, BBox { left: -1.0, bottom: 0.0, right: 2.0, top: 3.5 });
 
-        let bbox = BBox::from_multi_polygon(&vec![] as &[Vec<Vec<VectorPoint<MValue>>>]);
+        let bbox = BBox::from_multi_polygon(&[] as &[Vec<Vec<VectorPoint<MValue>>>]);
         assert_eq!(bbox, BBox::default());
     }
 
@@ -416,7 +416,7 @@ mod tests {
 
     #[test]
     fn test_bbox_3_d_from_linestring() {
-        let bbox = BBox3D::from_linestring(&vec![
+        let bbox = BBox3D::from_linestring(&[
             VectorPoint::<MValue>::new(0., 0., None, None),
             VectorPoint::new(1., 1.5, None, None),
         ]);
@@ -425,13 +425,13 @@ mod tests {
             BBox3D { left: 0.0, bottom: 0.0, right: 1.0, top: 1.5, near: f64::MAX, far: f64::MIN }
         );
 
-        let bbox = BBox3D::from_linestring(&vec![] as &[VectorPoint<MValue>]);
+        let bbox = BBox3D::from_linestring(&[] as &[VectorPoint<MValue>]);
         assert_eq!(bbox, BBox3D::default());
     }
 
     #[test]
     fn test_bbox_3_d_from_multilinestring() {
-        let bbox = BBox3D::from_multi_linestring(&vec![vec![
+        let bbox = BBox3D::from_multi_linestring(&[vec![
             VectorPoint::<MValue>::new(0., 0., None, None),
             VectorPoint::new(1., 1.5, None, None),
         ]]);
@@ -440,13 +440,13 @@ mod tests {
             BBox3D { left: 0.0, bottom: 0.0, right: 1.0, top: 1.5, near: f64::MAX, far: f64::MIN }
         );
 
-        let bbox = BBox3D::from_multi_linestring(&vec![] as &[Vec<VectorPoint<MValue>>]);
+        let bbox = BBox3D::from_multi_linestring(&[] as &[Vec<VectorPoint<MValue>>]);
         assert_eq!(bbox, BBox3D::default());
     }
 
     #[test]
     fn test_bbox_3_d_from_polygon() {
-        let bbox = BBox3D::from_polygon(&vec![vec![
+        let bbox = BBox3D::from_polygon(&[vec![
             VectorPoint::<MValue>::new(0., 0., None, None),
             VectorPoint::new(2., 1.5, None, None),
         ]]);
@@ -455,13 +455,13 @@ mod tests {
             BBox3D { left: 0.0, bottom: 0.0, right: 2.0, top: 1.5, near: f64::MAX, far: f64::MIN }
         );
 
-        let bbox = BBox3D::from_polygon(&vec![] as &[Vec<VectorPoint<MValue>>]);
+        let bbox = BBox3D::from_polygon(&[] as &[Vec<VectorPoint<MValue>>]);
         assert_eq!(bbox, BBox3D::default());
     }
 
     #[test]
     fn test_bbox_3_d_from_multipolygon() {
-        let bbox = BBox3D::from_multi_polygon(&vec![
+        let bbox = BBox3D::from_multi_polygon(&[
             vec![vec![
                 VectorPoint::<MValue>::new(0., 0., None, None),
                 VectorPoint::new(2., 1.5, None, None),
@@ -476,7 +476,7 @@ mod tests {
             BBox3D { left: -1.0, bottom: 0.0, right: 2.0, top: 3.5, near: f64::MAX, far: f64::MIN }
         );
 
-        let bbox = BBox3D::from_multi_polygon(&vec![] as &[Vec<Vec<VectorPoint<MValue>>>]);
+        let bbox = BBox3D::from_multi_polygon(&[] as &[Vec<Vec<VectorPoint<MValue>>>]);
         assert_eq!(bbox, BBox3D::default());
     }
 
@@ -562,7 +562,7 @@ mod tests {
     #[test]
     fn test_point_geometry() {
         let point = PointGeometry {
-            _type: "Point".into(),
+            _type: GeometryType::Point,
             coordinates: Point(0.0, 0.0),
             m_values: None,
             bbox: None,
@@ -570,7 +570,7 @@ mod tests {
         assert_eq!(
             point,
             PointGeometry {
-                _type: "Point".into(),
+                _type: GeometryType::Point,
                 coordinates: Point(0.0, 0.0),
                 m_values: None,
                 bbox: None
@@ -594,7 +594,7 @@ mod tests {
     #[test]
     fn test_point3d_geometry() {
         let point = Point3DGeometry {
-            _type: "Point3D".into(),
+            _type: GeometryType::Point3D,
             coordinates: Point3D(0.0, 0.0, 0.0),
             m_values: None,
             bbox: Some(BBox3D {
@@ -609,7 +609,7 @@ mod tests {
         assert_eq!(
             point,
             Point3DGeometry {
-                _type: "Point3D".into(),
+                _type: GeometryType::Point3D,
                 coordinates: Point3D(0.0, 0.0, 0.0),
                 m_values: None,
                 bbox: Some(BBox3D {
@@ -635,7 +635,7 @@ mod tests {
     #[test]
     fn test_line_string_geometry() {
         let line = LineStringGeometry {
-            _type: "LineString".into(),
+            _type: GeometryType::LineString,
             coordinates: vec![Point(0.0, 0.0), Point(1.0, 1.0)],
             m_values: None,
             bbox: None,
@@ -643,7 +643,7 @@ mod tests {
         assert_eq!(
             line,
             LineStringGeometry {
-                _type: "LineString".into(),
+                _type: GeometryType::LineString,
                 coordinates: vec![Point(0.0, 0.0), Point(1.0, 1.0)],
                 m_values: None,
                 bbox: None
@@ -658,7 +658,7 @@ mod tests {
     #[test]
     fn test_line_string3d_geometry() {
         let line = LineString3DGeometry::<MValue> {
-            _type: "LineString3D".into(),
+            _type: GeometryType::LineString3D,
             coordinates: vec![Point3D(0.0, 0.0, 0.0), Point3D(1.0, 1.0, 1.0)],
             m_values: None,
             bbox: None,
@@ -666,7 +666,7 @@ mod tests {
         assert_eq!(
             line,
             LineString3DGeometry {
-                _type: "LineString3D".into(),
+                _type: GeometryType::LineString3D,
                 coordinates: vec![Point3D(0.0, 0.0, 0.0), Point3D(1.0, 1.0, 1.0)],
                 m_values: None,
                 bbox: None
@@ -684,7 +684,7 @@ mod tests {
     #[test]
     fn test_multi_point_geometry() {
         let multi_point = MultiPointGeometry {
-            _type: "MultiPoint".into(),
+            _type: GeometryType::MultiPoint,
             coordinates: vec![Point(0.0, 0.0), Point(1.0, 1.0)],
             m_values: None,
             bbox: None,
@@ -692,7 +692,7 @@ mod tests {
         assert_eq!(
             multi_point,
             MultiPointGeometry {
-                _type: "MultiPoint".into(),
+                _type: GeometryType::MultiPoint,
                 coordinates: vec![Point(0.0, 0.0), Point(1.0, 1.0)],
                 m_values: None,
                 bbox: None
@@ -710,7 +710,7 @@ mod tests {
     #[test]
     fn test_multi_point3d_geometry() {
         let multi_point = MultiPoint3DGeometry {
-            _type: "MultiPoint3D".into(),
+            _type: GeometryType::MultiPoint3D,
             coordinates: vec![Point3D(0.0, 0.0, 0.0), Point3D(1.0, 1.0, 1.0)],
             m_values: None,
             bbox: None,
@@ -718,7 +718,7 @@ mod tests {
         assert_eq!(
             multi_point,
             MultiPoint3DGeometry {
-                _type: "MultiPoint3D".into(),
+                _type: GeometryType::MultiPoint3D,
                 coordinates: vec![Point3D(0.0, 0.0, 0.0), Point3D(1.0, 1.0, 1.0)],
                 m_values: None,
                 bbox: None
@@ -736,7 +736,7 @@ mod tests {
     #[test]
     fn test_polygon_geometry() {
         let polygon = PolygonGeometry {
-            _type: "Polygon".into(),
+            _type: GeometryType::Polygon,
             coordinates: vec![vec![Point(0.0, 0.0), Point(1.0, 1.0), Point(0.0, 1.0)]],
             m_values: None,
             bbox: None,
@@ -744,7 +744,7 @@ mod tests {
         assert_eq!(
             polygon,
             PolygonGeometry {
-                _type: "Polygon".into(),
+                _type: GeometryType::Polygon,
                 coordinates: vec![vec![Point(0.0, 0.0), Point(1.0, 1.0), Point(0.0, 1.0)]],
                 m_values: None,
                 bbox: None
@@ -762,7 +762,7 @@ mod tests {
     #[test]
     fn test_polygon3d_geometry() {
         let polygon = Polygon3DGeometry {
-            _type: "Polygon3D".into(),
+            _type: GeometryType::Polygon3D,
             coordinates: vec![vec![
                 Point3D(0.0, 0.0, 0.0),
                 Point3D(1.0, 1.0, 1.0),
@@ -774,7 +774,7 @@ mod tests {
         assert_eq!(
             polygon,
             Polygon3DGeometry {
-                _type: "Polygon3D".into(),
+                _type: GeometryType::Polygon3D,
                 coordinates: vec![vec![
                     Point3D(0.0, 0.0, 0.0),
                     Point3D(1.0, 1.0, 1.0),
@@ -797,7 +797,7 @@ mod tests {
     #[test]
     fn test_multi_polygon_geometry() {
         let multi_polygon = MultiPolygonGeometry {
-            _type: "MultiPolygon".into(),
+            _type: GeometryType::MultiPolygon,
             coordinates: vec![vec![vec![Point(0.0, 0.0), Point(1.0, 1.0), Point(0.0, 1.0)]]],
             m_values: None,
             bbox: None,
@@ -805,7 +805,7 @@ mod tests {
         assert_eq!(
             multi_polygon,
             MultiPolygonGeometry {
-                _type: "MultiPolygon".into(),
+                _type: GeometryType::MultiPolygon,
                 coordinates: vec![vec![vec![Point(0.0, 0.0), Point(1.0, 1.0), Point(0.0, 1.0)]]],
                 m_values: None,
                 bbox: None
@@ -824,7 +824,7 @@ mod tests {
     #[test]
     fn test_multi_polygon3d_geometry() {
         let multi_polygon = MultiPolygon3DGeometry {
-            _type: "MultiPolygon3D".into(),
+            _type: GeometryType::MultiPolygon3D,
             coordinates: vec![vec![vec![
                 Point3D(0.0, 0.0, 0.0),
                 Point3D(1.0, 1.0, 1.0),
@@ -836,7 +836,7 @@ mod tests {
         assert_eq!(
             multi_polygon,
             MultiPolygon3DGeometry {
-                _type: "MultiPolygon3D".into(),
+                _type: GeometryType::MultiPolygon3D,
                 coordinates: vec![vec![vec![
                     Point3D(0.0, 0.0, 0.0),
                     Point3D(1.0, 1.0, 1.0),
@@ -869,7 +869,7 @@ mod tests {
     #[test]
     fn test_vector_geometry_bbox() {
         let vgt_point: VectorGeometry = VectorGeometry::Point(VectorPointGeometry {
-            _type: "Point".into(),
+            _type: VectorGeometryType::Point,
             coordinates: VectorPoint { x: 0.0, y: 1.0, z: Some(2.0), m: None, t: None },
             bbox: Some(BBox3D {
                 left: -1.0,
@@ -896,7 +896,7 @@ mod tests {
         assert_eq!(vgt_point.vec_bbox().unwrap(), BBox3D::new(0.0, 1.0, 0.0, 1.0, 2.0, 2.0));
         let vgt_multi_point: VectorGeometry =
             VectorGeometry::MultiPoint(VectorMultiPointGeometry {
-                _type: "MultiPoint".into(),
+                _type: VectorGeometryType::MultiPoint,
                 coordinates: vec![VectorPoint { x: 0.0, y: 1.0, z: Some(2.0), m: None, t: None }],
                 bbox: Some(BBox3D {
                     left: -1.0,
@@ -926,7 +926,7 @@ mod tests {
         assert_eq!(vgt_multi_point.vec_bbox().unwrap(), BBox3D::new(0.0, 1.0, 0.0, 1.0, 2.0, 2.0));
         let vgt_line_string: VectorGeometry =
             VectorGeometry::LineString(VectorLineStringGeometry {
-                _type: "LineString".into(),
+                _type: VectorGeometryType::LineString,
                 coordinates: vec![VectorPoint { x: 0.0, y: 1.0, z: Some(2.0), m: None, t: None }],
                 bbox: Some(BBox3D {
                     left: -1.0,
@@ -956,7 +956,7 @@ mod tests {
         assert_eq!(vgt_line_string.vec_bbox().unwrap(), BBox3D::new(0.0, 1.0, 0.0, 1.0, 2.0, 2.0));
         let vgt_multi_line_string: VectorGeometry =
             VectorGeometry::MultiLineString(VectorMultiLineStringGeometry {
-                _type: "MultiLineString".into(),
+                _type: VectorGeometryType::MultiLineString,
                 coordinates: vec![vec![VectorPoint {
                     x: 0.0,
                     y: 1.0,
@@ -994,7 +994,7 @@ mod tests {
             BBox3D::new(0.0, 1.0, 0.0, 1.0, 2.0, 2.0)
         );
         let vgt_polygon: VectorGeometry = VectorGeometry::Polygon(VectorPolygonGeometry {
-            _type: "Polygon".into(),
+            _type: VectorGeometryType::Polygon,
             coordinates: vec![vec![VectorPoint { x: 0.0, y: 1.0, z: Some(2.0), m: None, t: None }]],
             bbox: Some(BBox3D {
                 left: -1.0,
@@ -1021,7 +1021,7 @@ mod tests {
         assert_eq!(vgt_polygon.vec_bbox().unwrap(), BBox3D::new(0.0, 1.0, 0.0, 1.0, 2.0, 2.0));
         let vgt_multi_polygon: VectorGeometry =
             VectorGeometry::MultiPolygon(VectorMultiPolygonGeometry {
-                _type: "MultiPolygon".into(),
+                _type: VectorGeometryType::MultiPolygon,
                 coordinates: vec![vec![vec![VectorPoint {
                     x: 0.0,
                     y: 1.0,
@@ -1131,11 +1131,11 @@ mod tests {
             assert_eq!(fc.features.len(), 4);
             if let Features::Feature(first_feature) = &fc.features[0] {
                 assert_eq!(first_feature.id, None);
-                assert_eq!(first_feature._type, "Feature".into());
+                assert_eq!(first_feature._type, FeatureType::Feature);
                 assert_eq!(
                     first_feature.geometry,
                     Geometry::Point(PointGeometry {
-                        _type: "Point".into(),
+                        _type: GeometryType::Point,
                         coordinates: Point(0.0, 0.0),
                         ..Default::default()
                     })
@@ -1157,7 +1157,7 @@ mod tests {
         assert_eq!(
             geometry,
             VectorGeometry::Point(VectorPointGeometry {
-                _type: "Point".into(),
+                _type: VectorGeometryType::Point,
                 coordinates: point.clone(),
                 bbox: Some(bbox),
                 is_3d: false,
@@ -1177,7 +1177,7 @@ mod tests {
         assert_eq!(
             geometry,
             VectorGeometry::Point(VectorPointGeometry {
-                _type: "Point".into(),
+                _type: VectorGeometryType::Point,
                 coordinates: point.clone(),
                 bbox: Some(bbox),
                 is_3d: true,
@@ -1205,7 +1205,7 @@ mod tests {
         assert_eq!(
             geometry,
             VectorGeometry::MultiPoint(VectorMultiPointGeometry {
-                _type: "MultiPoint".into(),
+                _type: VectorGeometryType::MultiPoint,
                 coordinates: multipoint.clone(),
                 bbox: Some(bbox),
                 is_3d: false,
@@ -1227,7 +1227,7 @@ mod tests {
         assert_eq!(
             geometry,
             VectorGeometry::MultiPoint(VectorMultiPointGeometry {
-                _type: "MultiPoint".into(),
+                _type: VectorGeometryType::MultiPoint,
                 coordinates: multipoint.clone(),
                 bbox: Some(bbox),
                 is_3d: true,
@@ -1247,7 +1247,7 @@ mod tests {
         assert_eq!(
             geometry,
             VectorGeometry::LineString(VectorLineStringGeometry {
-                _type: "LineString".into(),
+                _type: VectorGeometryType::LineString,
                 coordinates: line_string.clone(),
                 bbox: Some(bbox),
                 is_3d: false,
@@ -1268,7 +1268,7 @@ mod tests {
         assert_eq!(
             geometry,
             VectorGeometry::LineString(VectorLineStringGeometry {
-                _type: "LineString".into(),
+                _type: VectorGeometryType::LineString,
                 coordinates: linestring.clone(),
                 bbox: Some(bbox),
                 is_3d: true,
@@ -1291,7 +1291,7 @@ mod tests {
         assert_eq!(
             geometry,
             VectorGeometry::MultiLineString(VectorMultiLineStringGeometry {
-                _type: "MultiLineString".into(),
+                _type: VectorGeometryType::MultiLineString,
                 coordinates: multi_line_string.clone(),
                 bbox: Some(bbox),
                 is_3d: false,
@@ -1314,7 +1314,7 @@ mod tests {
         assert_eq!(
             geometry,
             VectorGeometry::MultiLineString(VectorMultiLineStringGeometry {
-                _type: "MultiLineString".into(),
+                _type: VectorGeometryType::MultiLineString,
                 coordinates: multi_line_string.clone(),
                 bbox: Some(bbox),
                 is_3d: true,
@@ -1339,7 +1339,7 @@ mod tests {
         assert_eq!(
             geometry,
             VectorGeometry::Polygon(VectorPolygonGeometry {
-                _type: "Polygon".into(),
+                _type: VectorGeometryType::Polygon,
                 coordinates: polygon.clone(),
                 bbox: Some(bbox),
                 is_3d: false,
@@ -1364,7 +1364,7 @@ mod tests {
         assert_eq!(
             geometry,
             VectorGeometry::Polygon(VectorPolygonGeometry {
-                _type: "Polygon".into(),
+                _type: VectorGeometryType::Polygon,
                 coordinates: polygon_3d.clone(),
                 bbox: Some(bbox),
                 is_3d: true,
@@ -1395,7 +1395,7 @@ mod tests {
         assert_eq!(
             geometry,
             VectorGeometry::MultiPolygon(VectorMultiPolygonGeometry {
-                _type: "MultiPolygon".into(),
+                _type: VectorGeometryType::MultiPolygon,
                 coordinates: multipolygon.clone(),
                 bbox: Some(bbox),
                 is_3d: false,
@@ -1426,7 +1426,7 @@ mod tests {
         assert_eq!(
             geometry,
             VectorGeometry::MultiPolygon(VectorMultiPolygonGeometry {
-                _type: "MultiPolygon".into(),
+                _type: VectorGeometryType::MultiPolygon,
                 coordinates: multipolygon.clone(),
                 bbox: Some(bbox),
                 is_3d: true,
@@ -1636,7 +1636,7 @@ mod tests {
         assert_eq!(
             geo_m,
             VectorGeometry::Point(VectorPointGeometry {
-                _type: "Point".into(),
+                _type: VectorGeometryType::Point,
                 coordinates: VectorPoint::new_xy(
                     0.5,
                     0.75,
@@ -1686,7 +1686,7 @@ mod tests {
         assert_eq!(
             geo_m,
             VectorGeometry::MultiPoint(VectorMultiPointGeometry {
-                _type: "MultiPoint".into(),
+                _type: VectorGeometryType::MultiPoint,
                 coordinates: vec![
                     VectorPoint::new_xy(0.5, 0.75, Some(MValue::from([("x".into(), 1.0.into())]))),
                     VectorPoint::new_xy(
@@ -1739,7 +1739,7 @@ mod tests {
         assert_eq!(
             geo_m,
             VectorGeometry::LineString(VectorLineStringGeometry {
-                _type: "LineString".into(),
+                _type: VectorGeometryType::LineString,
                 coordinates: vec![
                     VectorPoint::new_xy(0.5, 0.75, Some(MValue::from([("x".into(), 1.0.into())]))),
                     VectorPoint::new_xy(
@@ -1798,7 +1798,7 @@ mod tests {
         assert_eq!(
             geo_m,
             VectorGeometry::MultiLineString(VectorMultiLineStringGeometry {
-                _type: "MultiLineString".into(),
+                _type: VectorGeometryType::MultiLineString,
                 coordinates: vec![
                     vec![
                         VectorPoint::new_xy(
@@ -1875,7 +1875,7 @@ mod tests {
         assert_eq!(
             geo_m,
             VectorGeometry::Polygon(VectorPolygonGeometry {
-                _type: "Polygon".into(),
+                _type: VectorGeometryType::Polygon,
                 coordinates: vec![
                     vec![
                         VectorPoint::new_xy(
@@ -1952,7 +1952,7 @@ mod tests {
         assert_eq!(
             geo_m,
             VectorGeometry::MultiPolygon(VectorMultiPolygonGeometry {
-                _type: "MultiPolygon".into(),
+                _type: VectorGeometryType::MultiPolygon,
                 coordinates: vec![vec![
                     vec![
                         VectorPoint::new_xy(
@@ -0,0 +1,235 @@
+extern crate alloc;
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+    use s2json_core::*;
+
+    fn m(n: u64) -> MValue {
+        MValue::from([("n".into(), n.into())])
+    }
+
+    #[test]
+    fn point() {
+        let point: PointGeometry = PointGeometry {
+            _type: GeometryType::Point,
+            coordinates: Point(1., 2.),
+            m_values: Some(m(1)),
+            bbox: Some(BBox::new(1., 2., 1., 2.)),
+        };
+        let vector: VectorPointGeometry = (&point).into();
+        assert_eq!(vector._type, VectorGeometryType::Point);
+        assert!(!vector.is_3d);
+        assert_eq!(vector.coordinates, VectorPoint::new(1., 2., None, Some(m(1))));
+        assert_eq!(vector.coordinates.m, Some(m(1)));
+        assert_eq!(vector.bbox, Some(BBox3D::new(1., 2., 1., 2., 0., 0.)));
+        assert_eq!(PointGeometry::try_from(&vector), Ok(point));
+        assert_eq!(
+            Point3DGeometry::try_from(&vector),
+            Err(S2JsonError::DimensionMismatch { expected_3d: true })
+        );
+
+        let point3d: Point3DGeometry = Point3DGeometry {
+            _type: GeometryType::Point3D,
+            coordinates: Point3D(1., 2., 3.),
+            m_values: None,
+            bbox: Some(BBox3D::new(1., 2., 1., 2., 3., 3.)),
+        };
+        let vector: VectorPointGeometry = (&point3d).into();
+        assert!(vector.is_3d);
+        assert_eq!(vector.coordinates.z, Some(3.));
+        assert_eq!(vector.bbox, point3d.bbox);
+        assert_eq!(Point3DGeometry::try_from(&vector), Ok(point3d));
+        let err = PointGeometry::try_from(&vector).unwrap_err();
+        assert_eq!(err, S2JsonError::DimensionMismatch { expected_3d: false });
+        assert_eq!(alloc::format!("{err}"), "expected a 2D vector geometry but found a 3D one");
+    }
+
+    #[test]
+    fn multi_line_string_m_values() {
+        let lines: MultiLineStringGeometry = MultiLineStringGeometry {
+            _type: GeometryType::MultiLineString,
+            coordinates: vec![vec![Point(0., 0.), Point(1., 1.)], vec![Point(2., 2.)]],
+            m_values: Some(vec![vec![m(1), m(2)], vec![m(3)]]),
+            bbox: None,
+        };
+        let vector: VectorMultiLineStringGeometry = (&lines).into();
+        assert_eq!(vector._type, VectorGeometryType::MultiLineString);
+        assert_eq!(vector.coordinates[0][1].m, Some(m(2)));
+        assert_eq!(vector.coordinates[1][0].m, Some(m(3)));
+        assert_eq!(MultiLineStringGeometry::try_from(&vector), Ok(lines));
+
+        // missing m-values are left empty, then defaulted on the way back
+        let polygon: PolygonGeometry = PolygonGeometry {
+            _type: GeometryType::Polygon,
+            coordinates: vec![vec![Point(0., 0.), Point(1., 0.), Point(0., 1.)]],
+            m_values: Some(vec![vec![m(1)]]),
+            bbox: None,
+        };
+        let vector: VectorPolygonGeometry = (&polygon).into();
+        assert_eq!(vector._type, VectorGeometryType::Polygon);
+        assert_eq!(vector.coordinates[0][0].m, Some(m(1)));
+        assert_eq!(vector.coordinates[0][1].m, None);
+        let back = PolygonGeometry::try_from(&vector).unwrap();
+        assert_eq!(back._type, GeometryType::Polygon);
+        assert_eq!(back.m_values, Some(vec![vec![m(1), MValue::default(), MValue::default()]]));
+
+        // lines without any m-values still get one default per point when a sibling has them
+        let lines: MultiLineStringGeometry = MultiLineStringGeometry {
+            _type: GeometryType::MultiLineString,
+            coordinates: vec![vec![Point(0., 0.), Point(1., 1.)], vec![Point(2., 2.)]],
+            m_values: Some(vec![vec![], vec![m(3)]]),
+            bbox: None,
+        };
+        let vector: VectorMultiLineStringGeometry = (&lines).into();
+        assert_eq!(vector.coordinates[0][0].m, None);
+        let back = MultiLineStringGeometry::try_from(&vector).unwrap();
+        assert_eq!(
+            back.m_values,
+            Some(vec![vec![MValue::default(), MValue::default()], vec![m(3)]])
+        );
+
+        let polygons: MultiPolygonGeometry = MultiPolygonGeometry {
+            _type: GeometryType::MultiPolygon,
+            coordinates: vec![
+                vec![vec![Point(0., 0.), Point(1., 0.), Point(0., 1.)], vec![Point(2., 2.)]],
+                vec![vec![Point(5., 5.), Point(6., 5.)]],
+            ],
+            m_values: Some(vec![vec![], vec![vec![m(1), m(2)]]]),
+            bbox: None,
+        };
+        let vector: VectorMultiPolygonGeometry = (&polygons).into();
+        let back = MultiPolygonGeometry::try_from(&vector).unwrap();
+        assert_eq!(
+            back.m_values,
+            Some(vec![
+                vec![vec![MValue::default(); 3], vec![MValue::default()]],
+                vec![vec![m(1), m(2)]],
+            ])
+        );
+    }
+
+    #[test]
+    fn geometry_round_trip() {
+        let p2 = vec![Point(0., 0.), Point(1., 2.), Point(3., 4.)];
+        let p3 = vec![Point3D(0., 0., 1.), Point3D(1., 2., 3.), Point3D(3., 4., 5.)];
+        let m1 = vec![m(1), m(2), m(3)];
+        let bbox = Some(BBox::new(0., 0., 3., 4.));
+        let bbox3d = Some(BBox3D::new(0., 0., 3., 4., 1., 5.));
+        let geometries: Vec<Geometry> = vec![
+            Geometry::Point(PointGeometry {
+                _type: GeometryType::Point,
+                coordinates: Point(1., 2.),
+                m_values: Some(m(9)),
+                bbox,
+            }),
+            Geometry::MultiPoint(MultiPointGeometry {
+                _type: GeometryType::MultiPoint,
+                coordinates: p2.clone(),
+                m_values: Some(m1.clone()),
+                bbox,
+            }),
+            Geometry::LineString(LineStringGeometry {
+                _type: GeometryType::LineString,
+                coordinates: p2.clone(),
+                m_values: None,
+                bbox: None,
+            }),
+            Geometry::MultiLineString(MultiLineStringGeometry {
+                _type: GeometryType::MultiLineString,
+                coordinates: vec![p2.clone(), p2.clone()],
+                m_values: Some(vec![m1.clone(), m1.clone()]),
+                bbox,
+            }),
+            Geometry::Polygon(PolygonGeometry {
+                _type: GeometryType::Polygon,
+                coordinates: vec![p2.clone()],
+                m_values: None,
+                bbox,
+            }),
+            Geometry::MultiPolygon(MultiPolygonGeometry {
+                _type: GeometryType::MultiPolygon,
+                coordinates: vec![vec![p2.clone()], vec![p2.clone(), p2.clone()]],
+                m_values: Some(vec![vec![m1.clone()], vec![m1.clone(), m1.clone()]]),
+                bbox,
+            }),
+            Geometry::Point3D(Point3DGeometry {
+                _type: GeometryType::Point3D,
+                coordinates: Point3D(1., 2., 3.),
+                m_values: None,
+                bbox: bbox3d,
+            }),
+            Geometry::MultiPoint3D(MultiPoint3DGeometry {
+                _type: GeometryType::MultiPoint3D,
+                coordinates: p3.clone(),
+                m_values: Some(m1.clone()),
+                bbox: bbox3d,
+            }),
+            Geometry::LineString3D(LineString3DGeometry {
+                _type: GeometryType::LineString3D,
+                coordinates: p3.clone(),
+                m_values: Some(m1.clone()),
+                bbox: None,
+            }),
+            Geometry::MultiLineString3D(MultiLineString3DGeometry {
+                _type: GeometryType::MultiLineString3D,
+                coordinates: vec![p3.clone()],
+                m_values: None,
+                bbox: bbox3d,
+            }),
+            Geometry::Polygon3D(Polygon3DGeometry {
+                _type: GeometryType::Polygon3D,
+                coordinates: vec![p3.clone(), p3.clone()],
+                m_values: Some(vec![m1.clone(), m1.clone()]),
+                bbox: bbox3d,
+            }),
+            Geometry::MultiPolygon3D(MultiPolygon3DGeometry {
+                _type: GeometryType::MultiPolygon3D,
+                coordinates: vec![vec![p3.clone()]],
+                m_values: None,
+                bbox: None,
+            }),
+        ];
+        let vector_types = [
+            VectorGeometryType::Point,
+            VectorGeometryType::MultiPoint,
+            VectorGeometryType::LineString,
+            VectorGeometryType::MultiLineString,
+            VectorGeometryType::Polygon,
+            VectorGeometryType::MultiPolygon,
+        ];
+        for (i, geometry) in geometries.into_iter().enumerate() {
+            let vector = VectorGeometry::from(&geometry);
+            let (vector_type, is_3d) = match &vector {
+                VectorGeometry::Point(g) => (g._type, g.is_3d),
+                VectorGeometry::MultiPoint(g) => (g._type, g.is_3d),
+                VectorGeometry::LineString(g) => (g._type, g.is_3d),
+                VectorGeometry::MultiLineString(g) => (g._type, g.is_3d),
+                VectorGeometry::Polygon(g) => (g._type, g.is_3d),
+                VectorGeometry::MultiPolygon(g) => (g._type, g.is_3d),
+            };
+            assert_eq!(vector_type, vector_types[i % 6]);
+            assert_eq!(is_3d, i >= 6);
+            assert_eq!(Geometry::from(vector), geometry);
+        }
+    }
+
+    #[test]
+    fn geometry_types() {
+        assert_eq!(VectorGeometryType::from(GeometryType::Polygon3D), VectorGeometryType::Polygon);
+        assert_eq!(
+            VectorGeometryType::from(GeometryType::MultiPoint),
+            VectorGeometryType::MultiPoint
+        );
+        assert_eq!(
+            GeometryType::from_vector_type(VectorGeometryType::LineString, true),
+            GeometryType::LineString3D
+        );
+        assert_eq!(
+            GeometryType::from_vector_type(VectorGeometryType::MultiPolygon, false),
+            GeometryType::MultiPolygon
+        );
+        assert!(GeometryType::Point3D.is_3d());
+        assert!(!GeometryType::Point.is_3d());
+    }
+}
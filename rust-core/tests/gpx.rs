@@ -0,0 +1,158 @@
+extern crate alloc;
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+    use s2json_core::*;
+
+    const GPX: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<!-- exported from a watch -->
+<gpx version="1.1" creator="test" xmlns="http://www.topografix.com/GPX/1/1"
+     xmlns:gpxtpx="http://www.garmin.com/xmlschemas/TrackPointExtension/v1">
+  <metadata><name>Morning</name></metadata>
+  <wpt lat="47.5" lon="-122.25">
+    <ele>12.5</ele>
+    <name>Start &amp; finish</name>
+    <desc><![CDATA[Meet <here>]]></desc>
+    <sym>Flag</sym>
+  </wpt>
+  <rte>
+    <name>Planned</name>
+    <rtept lat="1" lon="2"/>
+    <rtept lat="3" lon="4"/>
+  </rte>
+  <trk>
+    <name>Run</name>
+    <type>running</type>
+    <trkseg>
+      <trkpt lat="47.5" lon="-122.25">
+        <ele>10</ele>
+        <time>2024-05-01T06:00:00Z</time>
+        <extensions>
+          <gpxtpx:TrackPointExtension>
+            <gpxtpx:hr>120</gpxtpx:hr>
+            <gpxtpx:atemp>-1.5</gpxtpx:atemp>
+          </gpxtpx:TrackPointExtension>
+        </extensions>
+      </trkpt>
+      <trkpt lat="47.6" lon="-122.2"><ele>11</ele></trkpt>
+    </trkseg>
+    <trkseg></trkseg>
+  </trk>
+  <trk><name>Empty</name><trkseg/></trk>
+</gpx>"#;
+
+    #[test]
+    fn read() {
+        let features: Vec<VectorFeature> = read_gpx(GPX).unwrap();
+        assert_eq!(features.len(), 3);
+
+        let waypoint = &features[0];
+        assert_eq!(
+            waypoint.properties,
+            Properties::from([
+                ("name".into(), "Start & finish".into()),
+                ("desc".into(), "Meet <here>".into()),
+            ])
+        );
+        assert_eq!(
+            waypoint.geometry,
+            VectorGeometry::new_point(
+                VectorPoint::new(
+                    -122.25,
+                    47.5,
+                    Some(12.5),
+                    Some(MValue::from([("sym".into(), "Flag".into())]))
+                ),
+                None
+            )
+        );
+
+        let route = &features[1];
+        assert_eq!(route.properties, Properties::from([("name".into(), "Planned".into())]));
+        assert_eq!(
+            route.geometry,
+            VectorGeometry::new_linestring(
+                vec![VectorPoint::from_xy(2., 1.), VectorPoint::from_xy(4., 3.)],
+                None
+            )
+        );
+
+        // empty segments are dropped, so the track is a single line
+        let track = &features[2];
+        assert_eq!(
+            track.properties,
+            Properties::from([("name".into(), "Run".into()), ("type".into(), "running".into())])
+        );
+        assert_eq!(
+            track.geometry,
+            VectorGeometry::new_linestring(
+                vec![
+                    VectorPoint::new(
+                        -122.25,
+                        47.5,
+                        Some(10.),
+                        Some(MValue::from([
+                            ("time".into(), "2024-05-01T06:00:00Z".into()),
+                            ("hr".into(), 120_u64.into()),
+                            ("atemp".into(), (-1.5).into()),
+                        ]))
+                    ),
+                    VectorPoint::from_xyz(-122.2, 47.6, 11.),
+                ],
+                None
+            )
+        );
+    }
+
+    #[test]
+    fn multiple_segments() {
+        let gpx = r#"<gpx><trk>
+            <trkseg><trkpt lat="0" lon="0"/><trkpt lat="1" lon="1"/></trkseg>
+            <trkseg><trkpt lat="2" lon="2"/><trkpt lat="3" lon="3"/></trkseg>
+        </trk></gpx>"#;
+        let features: Vec<VectorFeature> = read_gpx(gpx).unwrap();
+        assert_eq!(
+            features[0].geometry,
+            VectorGeometry::new_multilinestring(
+                vec![
+                    vec![VectorPoint::from_xy(0., 0.), VectorPoint::from_xy(1., 1.)],
+                    vec![VectorPoint::from_xy(2., 2.), VectorPoint::from_xy(3., 3.)],
+                ],
+                None
+            )
+        );
+    }
+
+    #[test]
+    fn errors() {
+        let read = |gpx: &str| read_gpx::<MValue>(gpx).map(|_| ());
+        assert_eq!(
+            read("<kml></kml>"),
+            Err(S2JsonError::invalid_xml(0, "expected a <gpx> root element, found <kml>"))
+        );
+        assert_eq!(
+            read(r#"<gpx><wpt lat="1"/></gpx>"#),
+            Err(S2JsonError::invalid_xml(5, "<wpt> needs numeric lat and lon attributes"))
+        );
+        assert_eq!(
+            read("<gpx><trk></gpx>"),
+            Err(S2JsonError::invalid_xml(12, "expected </trk>, found </gpx>"))
+        );
+        assert_eq!(
+            read("<gpx><name>a &nbsp; b</name></gpx>"),
+            Err(S2JsonError::invalid_xml(11, "unknown entity &nbsp;"))
+        );
+        assert_eq!(read("<gpx>"), Err(S2JsonError::invalid_xml(5, "unexpected end of input")));
+        assert_eq!(
+            read("<gpx/><gpx/>"),
+            Err(S2JsonError::invalid_xml(6, "unexpected content after the root element"))
+        );
+        // deep nesting is an error rather than a stack overflow
+        let nested = format!("<gpx>{}", "<a>".repeat(200_000));
+        assert_eq!(
+            read(&nested),
+            Err(S2JsonError::invalid_xml(5 + 127 * 3, "elements are nested too deeply"))
+        );
+    }
+}
@@ -1,3 +1,7 @@
 mod json;
+mod proto;
+mod schema;
 mod shape;
+mod shaped;
+mod validate;
 mod value;
@@ -0,0 +1,245 @@
+use pbf::{ProtoRead, ProtoWrite, Protobuf};
+use s2json_core::{impls::proto::DEFAULT_PRECISION, *};
+
+fn round_trip<T: ProtoRead + ProtoWrite>(value: &T, mut res: T) -> T {
+    let mut pb = Protobuf::new();
+    pb.write_fields(value);
+    let mut pb = Protobuf::from_input(pb.take());
+    pb.read_fields(&mut res, None);
+    res
+}
+
+fn m_values<M: Clone>(points: &[VectorPoint<M>]) -> Vec<Option<M>> {
+    points.iter().map(|p| p.m.clone()).collect()
+}
+
+#[test]
+fn value_round_trip() {
+    let value: Value = serde_json::from_str(
+        r#"{"a": 1, "b": -2, "c": 1.5, "d": "x", "e": null, "f": [1, {"g": true}], "h": [], "i": {"j": false}}"#,
+    )
+    .unwrap();
+    assert_eq!(round_trip(&value, Value::default()), value);
+}
+
+#[test]
+fn feature_round_trip() {
+    let m = |t: u64| Some(MValue::from([("t".into(), t.into())]));
+    let outer = vec![
+        VectorPoint { x: 0.1, y: 0.2, z: Some(10.), m: m(1), t: None },
+        VectorPoint { x: 0.9, y: 0.2, z: Some(12.5), m: None, t: None },
+        VectorPoint { x: 0.5, y: 0.123456789, z: Some(-3.), m: m(3), t: None },
+        VectorPoint { x: 0.1, y: 0.2, z: Some(10.), m: m(1), t: None },
+    ];
+    let hole = vec![
+        VectorPoint { x: 0.4, y: 0.25, z: Some(1.), m: None, t: None },
+        VectorPoint { x: 0.5, y: 0.3, z: Some(1.), m: None, t: None },
+        VectorPoint { x: 0.4, y: 0.25, z: Some(1.), m: None, t: None },
+    ];
+    let mut geometry =
+        VectorGeometry::new_multipolygon(vec![vec![outer.clone(), hole.clone()], vec![]], None);
+    if let VectorGeometry::MultiPolygon(g) = &mut geometry {
+        g.offset = Some(vec![vec![0.5, 1.25], vec![]]);
+        g.bbox = Some(BBox3D::new(-10., -5., 10., 5., -3., 12.5));
+        g.vec_bbox = Some(BBox3D::new(0.1, 0.123456789, 0.9, 0.3, -3., 12.5));
+    }
+    geometry.set_indices(vec![0, 1, 2, 3, 4, 5]);
+    geometry.set_tess(vec![0.45, 0.26, 1., 0.46, 0.27, 2.]);
+    let feature: VectorFeature<Value> = VectorFeature::new_s2(
+        Some(42),
+        Face::Face3,
+        Properties::from([("name".into(), "a".into()), ("n".into(), 2_u64.into())]),
+        geometry,
+        Some(Value::from([("layer".into(), "water".into())])),
+    );
+
+    let res = round_trip(&feature, VectorFeature::default());
+    assert_eq!(res, feature);
+    let polygons = res.geometry.multipolygon().unwrap();
+    assert_eq!(m_values(&polygons[0][0]), m_values(&outer));
+    assert_eq!(m_values(&polygons[0][1]), m_values(&hole));
+    assert!(polygons[1].is_empty());
+    assert_eq!(res.geometry.vec_bbox(), feature.geometry.vec_bbox());
+
+    // every primitive geometry survives, including the shared MultiPoint/LineString layout
+    let points = vec![VectorPoint::from_xy(-120.5, 45.25), VectorPoint::from_xy(-121., 44.)];
+    let mut line = VectorGeometry::new_linestring(points.clone(), None);
+    if let VectorGeometry::LineString(g) = &mut line {
+        g.offset = Some(3.5);
+    }
+    let geometries = [
+        VectorGeometry::new_point(VectorPoint::from_xy(1., 2.), Some(BBox3D::default())),
+        VectorGeometry::new_multipoint(points.clone(), None),
+        line,
+        VectorGeometry::new_multilinestring(vec![points.clone(), vec![]], None),
+        VectorGeometry::new_polygon(vec![points], None),
+    ];
+    for geometry in geometries {
+        let feature: VectorFeature =
+            VectorFeature::new_wm(None, Properties::default(), geometry, None);
+        let res = round_trip(&feature, VectorFeature::default());
+        assert_eq!(res, feature);
+        assert_eq!(res.face, Face::Face0);
+        assert_eq!(res.id, None);
+    }
+}
+
+#[test]
+fn feature_precision() {
+    let geometry: VectorGeometry = VectorGeometry::new_linestring(
+        vec![VectorPoint::from_xy(1.23456, -7.891), VectorPoint::from_xy(1.239, -7.8)],
+        None,
+    );
+    let feature: VectorFeature =
+        VectorFeature::new_wm(Some(1), Properties::default(), geometry, None);
+
+    let mut pb = Protobuf::new();
+    feature.write_with_precision(&mut pb, 2);
+    let coarse = pb.take();
+    let mut res: VectorFeature = VectorFeature::default();
+    Protobuf::from_input(coarse.clone()).read_fields(&mut res, None);
+    assert_eq!(
+        res.geometry.linestring().unwrap(),
+        &vec![VectorPoint::from_xy(1.23, -7.89), VectorPoint::from_xy(1.24, -7.8)]
+    );
+
+    let mut pb = Protobuf::new();
+    feature.write_with_precision(&mut pb, DEFAULT_PRECISION);
+    assert!(coarse.len() < pb.len());
+    assert!(pb.len() < serde_json::to_string(&feature).unwrap().len());
+}
+
+#[test]
+fn parts_round_trip() {
+    let point: VectorPoint = VectorPoint {
+        x: 0.1,
+        y: -0.3,
+        z: Some(1e-12),
+        m: Some(MValue::from([("a".into(), "b".into())])),
+        t: None,
+    };
+    let res = round_trip(&point, VectorPoint::default());
+    assert_eq!(res, point);
+    assert_eq!(res.m, point.m);
+
+    let bbox = BBox3D::new(-180., -90., 180., 90., 0., 1.5);
+    assert_eq!(round_trip(&bbox, BBox3D::default()), bbox);
+
+    let offsets = [
+        VectorOffsets::LineOffset(2.5),
+        VectorOffsets::MultiLineOffset(vec![1., 2.]),
+        VectorOffsets::PolygonOffset(vec![]),
+        VectorOffsets::MultiPolygonOffset(vec![vec![1.], vec![], vec![2., 3.]]),
+    ];
+    for offset in offsets {
+        assert_eq!(round_trip(&offset, VectorOffsets::default()), offset);
+    }
+
+    let geometry: VectorPolygonGeometry = VectorPolygonGeometry {
+        _type: VectorGeometryType::Polygon,
+        offset: Some(vec![1.]),
+        ..Default::default()
+    };
+    assert_eq!(round_trip(&geometry, VectorPolygonGeometry::default()), geometry);
+}
+
+#[test]
+fn feature_from_protobuf() {
+    let geometry = VectorGeometry::new_polygon(
+        vec![vec![
+            VectorPoint::from_xy(0., 0.),
+            VectorPoint::from_xy(1., 0.),
+            VectorPoint::from_xy(0., 1.),
+        ]],
+        None,
+    );
+    let feature: VectorFeature<Value> = VectorFeature::new_s2(
+        Some(7),
+        Face::Face2,
+        Properties::from([("name".into(), "a".into())]),
+        geometry,
+        Some(Value::from([("layer".into(), "water".into())])),
+    );
+    let mut pb = Protobuf::new();
+    pb.write_fields(&feature);
+    let data = pb.take();
+    assert_eq!(VectorFeature::from_protobuf(&data), Ok(feature));
+
+    // truncated data is an error at every length instead of a panic
+    for len in 0..data.len() {
+        let _ = VectorFeature::<Value>::from_protobuf(&data[..len]);
+    }
+    assert_eq!(
+        VectorFeature::<Value>::from_protobuf(&data[..data.len() - 1]),
+        Err(S2JsonError::UnexpectedEof)
+    );
+
+    // unknown type codes
+    let mut pb = Protobuf::new();
+    pb.write_varint_field(1, 9_u8);
+    assert_eq!(
+        VectorFeature::<Value>::from_protobuf(&pb.take()),
+        Err(S2JsonError::invalid_type("VectorFeatureType", "9"))
+    );
+    let mut geometry = Protobuf::new();
+    geometry.write_varint_field(1, 300_u64);
+    let mut pb = Protobuf::new();
+    pb.write_bytes_field(5, &geometry.take());
+    let data = pb.take();
+    assert_eq!(
+        VectorFeature::<Value>::from_protobuf(&data),
+        Err(S2JsonError::invalid_type("VectorGeometryType", "300"))
+    );
+    // reading directly skips them instead
+    let mut res: VectorFeature = VectorFeature::default();
+    Protobuf::from_input(data).read_fields(&mut res, None);
+    assert_eq!(res, VectorFeature::default());
+
+    // a known field with the wrong wire type
+    let mut pb = Protobuf::new();
+    pb.write_string_field(2, "id");
+    assert_eq!(
+        VectorFeature::<Value>::from_protobuf(&pb.take()),
+        Err(S2JsonError::invalid_protobuf(0, "field 2 has wire type 2"))
+    );
+    assert_eq!(
+        S2JsonError::invalid_protobuf(3, "unknown wire type 3").to_string(),
+        "invalid protobuf at byte 3: unknown wire type 3"
+    );
+
+    // list lengths that outgrow the points don't allocate them
+    let mut coordinates = Protobuf::new();
+    coordinates.write_packed_varint(3, &[u64::MAX, u64::MAX]);
+    let mut geometry = Protobuf::new();
+    geometry.write_varint_field(1, 5_u8);
+    geometry.write_bytes_field(3, &coordinates.take());
+    let mut pb = Protobuf::new();
+    pb.write_bytes_field(5, &geometry.take());
+    let res = VectorFeature::<Value>::from_protobuf(&pb.take()).unwrap();
+    assert_eq!(res.geometry.multipolygon(), Some(&vec![vec![]]));
+}
+
+#[test]
+fn type_codes() {
+    for geo_type in [
+        VectorGeometryType::Point,
+        VectorGeometryType::MultiPoint,
+        VectorGeometryType::LineString,
+        VectorGeometryType::MultiLineString,
+        VectorGeometryType::Polygon,
+        VectorGeometryType::MultiPolygon,
+    ] {
+        assert_eq!(VectorGeometryType::try_from(u8::from(geo_type)), Ok(geo_type));
+    }
+    assert_eq!(
+        VectorGeometryType::try_from(6),
+        Err(S2JsonError::invalid_type("VectorGeometryType", "6"))
+    );
+    for feature_type in [VectorFeatureType::VectorFeature, VectorFeatureType::S2Feature] {
+        assert_eq!(VectorFeatureType::try_from(u8::from(feature_type)), Ok(feature_type));
+    }
+    assert_eq!(
+        VectorFeatureType::try_from(2),
+        Err(S2JsonError::invalid_type("VectorFeatureType", "2"))
+    );
+}
@@ -0,0 +1,161 @@
+use s2json_core::*;
+use serde_json::json;
+
+fn shape() -> Shape {
+    serde_json::from_str(
+        r#"{
+            "a": "u64",
+            "b": {"$nullable": "string"},
+            "c": {"$optional": "bool"},
+            "d": ["f64"],
+            "e": [{"f": "i64", "g": "string"}],
+            "h": {"i": "f32", "j": {"$optional": "null"}},
+            "k": []
+        }"#,
+    )
+    .unwrap()
+}
+
+#[test]
+fn to_json_schema() {
+    let shape: Shape = serde_json::from_str(
+        r#"{ "a": "u64", "b": {"$optional": {"$nullable": "string"}}, "c": [{"d": "bool"}] }"#,
+    )
+    .unwrap();
+    assert_eq!(
+        shape.to_json_schema(),
+        json!({
+            "$schema": "http://json-schema.org/draft-07/schema#",
+            "type": "object",
+            "properties": {
+                "a": { "type": "integer", "format": "uint64", "minimum": 0, "maximum": u64::MAX },
+                "b": { "type": ["string", "null"] },
+                "c": {
+                    "type": "array",
+                    "maxItems": 16,
+                    "items": {
+                        "type": "object",
+                        "properties": { "d": { "type": "boolean" } },
+                        "required": ["d"],
+                        "additionalProperties": false
+                    }
+                }
+            },
+            "required": ["a", "c"],
+            "additionalProperties": false
+        })
+    );
+}
+
+#[test]
+fn json_schema_round_trip() {
+    let shape = shape();
+    let schema = shape.to_json_schema();
+    assert_eq!(schema["properties"]["h"]["properties"]["i"]["format"], "float");
+    assert_eq!(schema["properties"]["k"], json!({ "type": "array", "maxItems": 16 }));
+    assert_eq!(Shape::try_from_json_schema(&schema), Ok(shape));
+}
+
+#[test]
+fn from_json_schema() {
+    let schema = json!({
+        "type": "object",
+        "definitions": {
+            "count": { "type": "integer", "minimum": 0 },
+            "point": { "properties": { "x": { "type": "number" }, "y": { "type": "number" } } }
+        },
+        "properties": {
+            "count": { "$ref": "#/definitions/count" },
+            "offset": { "type": "integer" },
+            "label": { "anyOf": [{ "type": "string" }, { "type": "null" }] },
+            "point": { "$ref": "#/definitions/point" },
+            "tags": { "type": "array", "items": { "type": ["string", "null"] } }
+        },
+        "required": ["count", "offset", "label", "tags"]
+    });
+    let point: Shape =
+        serde_json::from_str(r#"{ "x": {"$optional": "f64"}, "y": {"$optional": "f64"} }"#)
+            .unwrap();
+    assert_eq!(
+        Shape::try_from_json_schema(&schema),
+        Ok(Shape::from([
+            ("count".into(), ShapeType::Primitive(PrimitiveShape::U64)),
+            ("offset".into(), ShapeType::Primitive(PrimitiveShape::I64)),
+            ("label".into(), ShapeType::Primitive(PrimitiveShape::String).nullable()),
+            ("point".into(), ShapeType::Nested(point).optional()),
+            (
+                "tags".into(),
+                ShapeType::Array(vec![PrimitiveShapeType::Primitive(PrimitiveShape::String)])
+            ),
+        ]))
+    );
+}
+
+#[test]
+fn from_json_schema_errors() {
+    let schema = |properties| json!({ "type": "object", "properties": properties });
+    assert_eq!(
+        Shape::try_from_json_schema(&json!({ "type": "array" })),
+        Err(S2JsonError::invalid_json_schema("", "expected an object schema, found \"array\""))
+    );
+    assert_eq!(
+        Shape::try_from_json_schema(&schema(json!({ "a": { "type": ["string", "number"] } }))),
+        Err(S2JsonError::invalid_json_schema(
+            "a",
+            "only one non-null type is supported, found [\"string\", \"number\"]"
+        ))
+    );
+    assert_eq!(
+        Shape::try_from_json_schema(&schema(json!({ "a": { "properties": { "b": {} } } }))),
+        Err(S2JsonError::invalid_json_schema("a.b", "missing a type"))
+    );
+    assert_eq!(
+        Shape::try_from_json_schema(&schema(json!({
+            "a": { "type": "array", "items": { "type": "array" } }
+        }))),
+        Err(S2JsonError::invalid_json_schema("a[]", "expected a primitive type, found \"array\""))
+    );
+    assert_eq!(
+        Shape::try_from_json_schema(&schema(json!({ "a": { "$ref": "other.json#/a" } }))),
+        Err(S2JsonError::invalid_json_schema(
+            "a",
+            "only local references are supported, found \"other.json#/a\""
+        ))
+    );
+    assert_eq!(
+        Shape::try_from_json_schema(&schema(json!({ "a": { "$ref": "#/properties/a" } }))),
+        Err(S2JsonError::invalid_json_schema("a", "too many nested references"))
+    );
+    // recursion through properties and unions
+    assert_eq!(
+        Shape::try_from_json_schema(&schema(json!({ "child": { "$ref": "#" } }))),
+        Err(S2JsonError::invalid_json_schema("child", "recursive schemas are not supported"))
+    );
+    assert_eq!(
+        Shape::try_from_json_schema(&json!({
+            "type": "object",
+            "properties": { "a": { "$ref": "#/definitions/node" } },
+            "definitions": {
+                "node": {
+                    "type": "object",
+                    "properties": { "next": { "anyOf": [{ "$ref": "#/definitions/node" }, { "type": "null" }] } }
+                }
+            }
+        })),
+        Err(S2JsonError::invalid_json_schema("a.next", "recursive schemas are not supported"))
+    );
+    let looping = json!({ "anyOf": [{ "$ref": "#" }] });
+    assert_eq!(
+        Shape::try_from_json_schema(&looping),
+        Err(S2JsonError::invalid_json_schema("", "recursive schemas are not supported"))
+    );
+    // a schema shared by several keys is not recursive
+    let shape = Shape::try_from_json_schema(&json!({
+        "type": "object",
+        "properties": { "a": { "$ref": "#/definitions/p" }, "b": { "$ref": "#/definitions/p" } },
+        "required": ["a", "b"],
+        "definitions": { "p": { "type": "object", "properties": { "x": { "type": "string" } } } }
+    }))
+    .unwrap();
+    assert_eq!(shape.len(), 2);
+}
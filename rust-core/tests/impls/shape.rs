@@ -1,4 +1,4 @@
-use impls::shape::validate_types;
+use impls::shape::{try_validate_types, validate_types};
 use s2json_core::*;
 use std::panic::{self, AssertUnwindSafe};
 
@@ -421,3 +421,296 @@ fn test_value_type_to_prim_value() {
 
     assert_eq!(prim_value, PrimitiveValue::Null);
 }
+
+#[test]
+fn try_merge_primitive_shapes() {
+    let mut shape = PrimitiveShape::U64;
+    assert!(shape.try_merge(&PrimitiveShape::F32, ShapeMergePolicy::Strict).is_ok());
+    assert_eq!(shape, PrimitiveShape::F32);
+    assert!(shape.try_merge(&PrimitiveShape::Null, ShapeMergePolicy::Strict).is_ok());
+    assert_eq!(shape, PrimitiveShape::F32);
+
+    let mut shape = PrimitiveShape::Null;
+    assert!(shape.try_merge(&PrimitiveShape::Bool, ShapeMergePolicy::Strict).is_ok());
+    assert_eq!(shape, PrimitiveShape::Bool);
+    assert_eq!(
+        shape.try_merge(&PrimitiveShape::String, ShapeMergePolicy::Strict),
+        Err(S2JsonError::shape_mismatch(
+            "",
+            ShapeType::Primitive(PrimitiveShape::Bool),
+            ShapeType::Primitive(PrimitiveShape::String)
+        ))
+    );
+    // a failed merge leaves the shape untouched
+    assert_eq!(shape, PrimitiveShape::Bool);
+    assert!(shape.try_merge(&PrimitiveShape::String, ShapeMergePolicy::Widen).is_ok());
+    assert_eq!(shape, PrimitiveShape::String);
+    // strings and bools are not the same shape
+    assert!(!PrimitiveShape::String.matching_shape(&PrimitiveShape::Bool));
+}
+
+#[test]
+fn try_merge_shapes_reports_path() {
+    let mut shape = Shape::from([(
+        "a".into(),
+        ShapeType::Nested(Shape::from([
+            ("b".into(), ShapeType::Primitive(PrimitiveShape::I64)),
+            (
+                "c".into(),
+                ShapeType::Array(vec![PrimitiveShapeType::NestedPrimitive(ShapePrimitive::from(
+                    [("d".into(), PrimitiveShape::Bool)],
+                ))]),
+            ),
+        ])),
+    )]);
+    let other = Shape::from([(
+        "a".into(),
+        ShapeType::Nested(Shape::from([(
+            "c".into(),
+            ShapeType::Array(vec![PrimitiveShapeType::NestedPrimitive(ShapePrimitive::from([(
+                "d".into(),
+                PrimitiveShape::F64,
+            )]))]),
+        )])),
+    )]);
+    let err = shape.clone().try_merge(&other, ShapeMergePolicy::Strict).unwrap_err();
+    assert_eq!(
+        err,
+        S2JsonError::shape_mismatch(
+            "a.c[].d",
+            ShapeType::Primitive(PrimitiveShape::Bool),
+            ShapeType::Primitive(PrimitiveShape::F64)
+        )
+    );
+    assert_eq!(
+        err.to_string(),
+        "shape mismatch at \"a.c[].d\": expected Primitive(Bool), found Primitive(F64)"
+    );
+
+    let other = Shape::from([("a".into(), ShapeType::Primitive(PrimitiveShape::U64))]);
+    match shape.clone().try_merge(&other, ShapeMergePolicy::Strict) {
+        Err(S2JsonError::ShapeMismatch { path, expected, found }) => {
+            assert_eq!(path, "a");
+            assert!(matches!(expected, ShapeType::Nested(_)));
+            assert_eq!(found, ShapeType::Primitive(PrimitiveShape::U64));
+        }
+        res => panic!("expected a shape mismatch, got {res:?}"),
+    }
+    shape.try_merge(&other, ShapeMergePolicy::Widen).unwrap();
+    assert_eq!(shape, Shape::from([("a".into(), ShapeType::Primitive(PrimitiveShape::String))]));
+}
+
+#[test]
+fn try_validate_types_and_infer() {
+    let types = [
+        ValuePrimitiveType::Primitive(PrimitiveValue::U64(1)),
+        ValuePrimitiveType::Primitive(PrimitiveValue::Null),
+        ValuePrimitiveType::Primitive(PrimitiveValue::Bool(true)),
+    ];
+    assert_eq!(
+        try_validate_types(&types, ShapeMergePolicy::Strict),
+        Err(S2JsonError::shape_mismatch(
+            "[2]",
+            ShapeType::Primitive(PrimitiveShape::U64),
+            ShapeType::Primitive(PrimitiveShape::Bool)
+        ))
+    );
+    assert_eq!(
+        try_validate_types(&types, ShapeMergePolicy::Widen),
+        Ok(PrimitiveShapeType::Primitive(PrimitiveShape::String))
+    );
+
+    let values = [
+        Value::from([("a".into(), (&PrimitiveValue::U64(1)).into())]),
+        Value::from([
+            ("a".into(), (&PrimitiveValue::String("x".into())).into()),
+            (
+                "b".into(),
+                ValueType::Array(vec![
+                    ValuePrimitiveType::Primitive(PrimitiveValue::F32(1.)),
+                    ValuePrimitiveType::NestedPrimitive(ValuePrimitive::new()),
+                ]),
+            ),
+        ]),
+    ];
+    let err = Shape::try_from_values(&values, ShapeMergePolicy::Strict).unwrap_err();
+    assert!(matches!(err, S2JsonError::ShapeMismatch { ref path, .. } if path == "b[1]"));
+    let err = Shape::try_from_values(&values[..1], ShapeMergePolicy::Strict)
+        .and_then(|mut shape| {
+            shape.try_merge(
+                &Shape::from(&Value::from([(
+                    "a".into(),
+                    (&PrimitiveValue::String("x".into())).into(),
+                )])),
+                ShapeMergePolicy::Strict,
+            )
+        })
+        .unwrap_err();
+    assert!(matches!(err, S2JsonError::ShapeMismatch { ref path, .. } if path == "a"));
+    assert_eq!(
+        Shape::try_from_values(&values, ShapeMergePolicy::Widen),
+        Ok(Shape::from([
+            ("a".into(), ShapeType::Primitive(PrimitiveShape::String)),
+            (
+                "b".into(),
+                ShapeType::Array(vec![PrimitiveShapeType::Primitive(PrimitiveShape::String)])
+                    .optional()
+            ),
+        ]))
+    );
+}
+
+#[test]
+fn nullable_and_optional_markers() {
+    let shape = ShapeType::Primitive(PrimitiveShape::F64).nullable().optional();
+    assert_eq!(
+        shape,
+        ShapeType::Optional(Box::new(ShapeType::Nullable(Box::new(ShapeType::Primitive(
+            PrimitiveShape::F64
+        )))))
+    );
+    // markers are idempotent and optional stays outermost
+    assert_eq!(shape.clone().nullable().optional(), shape);
+    assert!(shape.is_nullable() && shape.is_optional());
+    assert_eq!(shape.unmarked(), &ShapeType::Primitive(PrimitiveShape::F64));
+    assert!(ShapeType::Primitive(PrimitiveShape::Null).is_nullable());
+    assert_eq!(
+        ShapeType::Primitive(PrimitiveShape::Null).nullable(),
+        ShapeType::Primitive(PrimitiveShape::Null)
+    );
+
+    let json_shape = r#"{"a":{"$nullable":"f64"},"b":{"$optional":{"$nullable":["string"]}},"c":{"$optional":{"d":"bool"}},"e":{"$nullable":"i64","f":"u64"}}"#;
+    let shape = serde_json::from_str::<Shape>(json_shape).unwrap();
+    assert_eq!(
+        shape,
+        Shape::from([
+            ("a".into(), ShapeType::Primitive(PrimitiveShape::F64).nullable()),
+            (
+                "b".into(),
+                ShapeType::Array(vec![PrimitiveShapeType::Primitive(PrimitiveShape::String)])
+                    .nullable()
+                    .optional()
+            ),
+            (
+                "c".into(),
+                ShapeType::Nested(Shape::from([(
+                    "d".into(),
+                    ShapeType::Primitive(PrimitiveShape::Bool)
+                )]))
+                .optional()
+            ),
+            // not a marker, just a nested shape that happens to use the reserved key
+            (
+                "e".into(),
+                ShapeType::Nested(Shape::from([
+                    ("$nullable".into(), ShapeType::Primitive(PrimitiveShape::I64)),
+                    ("f".into(), ShapeType::Primitive(PrimitiveShape::U64)),
+                ]))
+            ),
+        ])
+    );
+    assert_eq!(serde_json::to_string(&shape).unwrap(), json_shape);
+}
+
+#[test]
+fn merge_with_markers() {
+    let values = [
+        Value::from([
+            ("a".into(), (&PrimitiveValue::F64(1.)).into()),
+            ("b".into(), (&PrimitiveValue::Null).into()),
+            ("c".into(), (&PrimitiveValue::Null).into()),
+        ]),
+        Value::from([
+            ("a".into(), (&PrimitiveValue::Null).into()),
+            ("b".into(), (&PrimitiveValue::U64(2)).into()),
+            ("c".into(), (&PrimitiveValue::Null).into()),
+            (
+                "d".into(),
+                ValueType::Nested(Value::from([(
+                    "e".into(),
+                    (&PrimitiveValue::Bool(true)).into(),
+                )])),
+            ),
+        ]),
+        Value::from([
+            ("a".into(), (&PrimitiveValue::U64(3)).into()),
+            ("b".into(), (&PrimitiveValue::I64(-2)).into()),
+            ("c".into(), (&PrimitiveValue::Null).into()),
+            (
+                "d".into(),
+                ValueType::Nested(Value::from([("f".into(), (&PrimitiveValue::Null).into())])),
+            ),
+        ]),
+    ];
+    let shape = Shape::from(values.as_slice());
+    assert_eq!(
+        shape,
+        Shape::from([
+            ("a".into(), ShapeType::Primitive(PrimitiveShape::F64).nullable()),
+            ("b".into(), ShapeType::Primitive(PrimitiveShape::I64).nullable()),
+            ("c".into(), ShapeType::Primitive(PrimitiveShape::Null)),
+            (
+                "d".into(),
+                ShapeType::Nested(Shape::from([
+                    ("e".into(), ShapeType::Primitive(PrimitiveShape::Bool).optional()),
+                    ("f".into(), ShapeType::Primitive(PrimitiveShape::Null).optional()),
+                ]))
+                .optional()
+            ),
+        ])
+    );
+
+    // merging with an empty shape marks nothing optional, but inference still sees empty values
+    let mut merged = Shape::new();
+    merged.merge(&shape);
+    assert_eq!(merged, shape);
+    merged.merge(&Shape::new());
+    assert_eq!(merged, shape);
+    let shape = Shape::from([Value::new(), values[0].clone()].as_slice());
+    assert!(shape.values().all(|value| value.is_optional()));
+
+    // a failed merge leaves the shape as it was
+    let mut shape = Shape::from([
+        ("a".into(), ShapeType::Primitive(PrimitiveShape::Bool)),
+        ("b".into(), ShapeType::Primitive(PrimitiveShape::U64)),
+    ]);
+    let before = shape.clone();
+    let other = Shape::from([("b".into(), ShapeType::Primitive(PrimitiveShape::String))]);
+    assert!(shape.try_merge(&other, ShapeMergePolicy::Strict).is_err());
+    assert_eq!(shape, before);
+
+    // markers survive a conflicting merge under the widen policy
+    let mut shape =
+        Shape::from([("a".into(), ShapeType::Primitive(PrimitiveShape::Bool).optional())]);
+    let other = Shape::from([("a".into(), ShapeType::Primitive(PrimitiveShape::U64).nullable())]);
+    assert!(matches!(
+        shape.clone().try_merge(&other, ShapeMergePolicy::Strict),
+        Err(S2JsonError::ShapeMismatch { ref path, .. }) if path == "a"
+    ));
+    shape.try_merge(&other, ShapeMergePolicy::Widen).unwrap();
+    assert_eq!(
+        shape,
+        Shape::from([(
+            "a".into(),
+            ShapeType::Primitive(PrimitiveShape::String).nullable().optional()
+        )])
+    );
+
+    // defaults leave out optional keys and set nullable keys to null
+    let shape = Shape::from([
+        ("a".into(), ShapeType::Primitive(PrimitiveShape::F64).nullable()),
+        ("b".into(), ShapeType::Primitive(PrimitiveShape::U64).optional()),
+        ("c".into(), ShapeType::Primitive(PrimitiveShape::String)),
+    ]);
+    assert_eq!(
+        Value::default_from_shape(&shape),
+        Value::from([
+            ("a".into(), (&PrimitiveValue::Null).into()),
+            ("c".into(), (&PrimitiveValue::String("".into())).into()),
+        ])
+    );
+    assert_eq!(
+        ValueType::default_from_shape(&ShapeType::Primitive(PrimitiveShape::U64).optional()),
+        (&PrimitiveValue::U64(0)).into()
+    );
+}
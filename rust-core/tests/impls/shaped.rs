@@ -0,0 +1,195 @@
+use pbf::Protobuf;
+use s2json_core::*;
+
+fn shape() -> Shape {
+    serde_json::from_str(
+        r#"{
+            "a": "u64",
+            "b": "i64",
+            "c": "f32",
+            "d": "f64",
+            "e": "bool",
+            "f": "string",
+            "g": "null",
+            "h": {"$nullable": "string"},
+            "i": {"$optional": {"$nullable": "u64"}},
+            "j": ["i64"],
+            "k": [{"l": "string", "m": "f64"}],
+            "n": {"o": "bool", "p": {"q": "u64"}}
+        }"#,
+    )
+    .unwrap()
+}
+
+#[test]
+fn shaped_round_trip() {
+    let shape = shape();
+    let value: Value = serde_json::from_str(
+        r#"{
+            "a": 1, "b": -2, "c": 1.5, "d": 0.1, "e": true, "f": "hello", "g": null, "h": null,
+            "j": [-1, null, 3], "k": [{"l": "x", "m": 2.5}, {"m": 0.25}, {}],
+            "n": {"o": false, "p": {"q": 22}}
+        }"#,
+    )
+    .unwrap();
+    assert_eq!(value.validate(&shape), Ok(()));
+
+    let mut data = vec![];
+    value.encode_shaped(&shape, &mut data);
+    // no keys on the wire
+    assert_eq!(data.len(), 56);
+
+    let mut reader = data.as_slice();
+    let decoded = Value::decode_shaped(&mut reader, &shape).unwrap();
+    assert!(reader.is_empty());
+    // numbers come back as the type of the shape. Nulls inside arrays are kept as nulls, and
+    // missing keys of array objects are kept absent
+    let mut expected = value.clone();
+    expected.coerce(&shape).unwrap();
+    assert_eq!(decoded, expected);
+
+    // optional & nullable present
+    let mut value = expected;
+    value.insert("h".into(), ValueType::Primitive("world".into()));
+    value.insert("i".into(), ValueType::Primitive(PrimitiveValue::U64(7)));
+    let mut data = vec![];
+    value.encode_shaped(&shape, &mut data);
+    assert_eq!(Value::decode_shaped(&mut data.as_slice(), &shape), Ok(value.clone()));
+    value.insert("i".into(), ValueType::Primitive(PrimitiveValue::Null));
+    let mut data = vec![];
+    value.encode_shaped(&shape, &mut data);
+    assert_eq!(Value::decode_shaped(&mut data.as_slice(), &shape), Ok(value));
+}
+
+#[test]
+fn shaped_back_to_back_and_truncated() {
+    let shape: Shape = serde_json::from_str(r#"{"a": "string", "b": "f64"}"#).unwrap();
+    let values: Vec<Value> = (0..3)
+        .map(|i| {
+            Value::from([
+                ("a".into(), ValueType::Primitive(format!("v{i}").into())),
+                ("b".into(), ValueType::Primitive(PrimitiveValue::F64(i as f64))),
+            ])
+        })
+        .collect();
+    let mut data = vec![];
+    for value in &values {
+        value.encode_shaped(&shape, &mut data);
+    }
+    // a varint length, 2 bytes of string and 8 bytes of f64
+    assert_eq!(data.len(), 3 * 11);
+    let mut reader = data.as_slice();
+    for value in &values {
+        assert_eq!(&Value::decode_shaped(&mut reader, &shape).unwrap(), value);
+    }
+    assert!(reader.is_empty());
+    assert_eq!(Value::decode_shaped(&mut reader, &shape), Err(S2JsonError::UnexpectedEof));
+    for len in 0..11 {
+        assert_eq!(
+            Value::decode_shaped(&mut &data[..len], &shape),
+            Err(S2JsonError::UnexpectedEof)
+        );
+    }
+    assert_eq!(S2JsonError::UnexpectedEof.to_string(), "unexpected end of data");
+}
+
+#[test]
+fn shaped_non_conforming_values() {
+    let shape: Shape =
+        serde_json::from_str(r#"{"a": "u64", "b": "string", "c": {"d": "i64"}}"#).unwrap();
+    // numbers are cast, anything else missing or mismatched falls back to the default
+    let value: Value = serde_json::from_str(r#"{"a": 2.0, "b": true, "e": 1}"#).unwrap();
+    let mut data = vec![];
+    value.encode_shaped(&shape, &mut data);
+    let mut expected = Value::default_from_shape(&shape);
+    expected.insert("a".into(), ValueType::Primitive(PrimitiveValue::U64(2)));
+    assert_eq!(Value::decode_shaped(&mut data.as_slice(), &shape), Ok(expected));
+}
+
+#[test]
+fn shape_protobuf_round_trip() {
+    let mut shape = shape();
+    shape.insert("r".into(), ShapeType::Array(vec![]));
+    let mut pb = Protobuf::new();
+    pb.write_message(1, &shape);
+    let bytes = pb.take();
+
+    let mut pb_read = Protobuf::from(bytes);
+    pb_read.read_field();
+    let mut read_shape = Shape::new();
+    pb_read.read_message(&mut read_shape);
+    assert_eq!(read_shape, shape);
+
+    // a shape can be written as the top level fields of a buffer too
+    let mut pb = Protobuf::new();
+    pb.write_fields(&shape);
+    let mut pb_read = Protobuf::from(pb.take());
+    let mut read_shape = Shape::new();
+    pb_read.read_fields(&mut read_shape, None);
+    assert_eq!(read_shape, shape);
+
+    // unknown primitive codes are skipped rather than panicking
+    let mut entry = Protobuf::new();
+    entry.write_string_field(1, "a");
+    entry.write_varint_field(2, 99_usize);
+    let mut nested = Protobuf::new();
+    nested.write_bytes_field(1, &entry.take());
+    let mut shape_type = Protobuf::new();
+    shape_type.write_varint_field(1, 42_usize);
+    shape_type.write_bytes_field(2, &{
+        let mut elem = Protobuf::new();
+        elem.write_varint_field(1, 7_usize);
+        elem.write_bytes_field(2, &nested.take());
+        elem.take()
+    });
+    let mut entry = Protobuf::new();
+    entry.write_string_field(1, "b");
+    entry.write_bytes_field(2, &shape_type.take());
+    let mut pb = Protobuf::new();
+    pb.write_bytes_field(1, &entry.take());
+    let mut read_shape = Shape::new();
+    Protobuf::from(pb.take()).read_fields(&mut read_shape, None);
+    assert_eq!(
+        read_shape,
+        Shape::from([(
+            "b".into(),
+            ShapeType::Array(vec![PrimitiveShapeType::NestedPrimitive(ShapePrimitive::from([(
+                "a".into(),
+                PrimitiveShape::default()
+            )]))])
+        )])
+    );
+}
+
+#[test]
+fn shape_from_protobuf() {
+    let mut shape = shape();
+    shape.insert("r".into(), ShapeType::Array(vec![]));
+    let mut pb = Protobuf::new();
+    pb.write_fields(&shape);
+    let bytes = pb.take();
+    assert_eq!(Shape::from_protobuf(&bytes), Ok(shape));
+    assert_eq!(Shape::from_protobuf(&bytes[..bytes.len() - 1]), Err(S2JsonError::UnexpectedEof));
+
+    // unknown primitive codes are errors
+    let mut pb = Protobuf::new();
+    pb.write_fields(&Shape::from([("a".into(), ShapeType::Primitive(PrimitiveShape::Null))]));
+    let mut bytes = pb.take();
+    *bytes.last_mut().unwrap() = 42;
+    assert_eq!(
+        Shape::from_protobuf(&bytes),
+        Err(S2JsonError::invalid_type("PrimitiveShape", "42"))
+    );
+
+    // deeply nested shapes are rejected before they are read
+    let mut shape_type = ShapeType::Primitive(PrimitiveShape::Bool);
+    for _ in 0..1_000 {
+        shape_type = ShapeType::Optional(Box::new(shape_type));
+    }
+    let mut pb = Protobuf::new();
+    pb.write_fields(&Shape::from([("deep".into(), shape_type)]));
+    let Err(S2JsonError::InvalidProtobuf { message, .. }) = Shape::from_protobuf(&pb.take()) else {
+        panic!("expected deeply nested shapes to be rejected");
+    };
+    assert_eq!(message, "messages are nested too deeply");
+}
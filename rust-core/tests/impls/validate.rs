@@ -0,0 +1,187 @@
+use s2json_core::*;
+
+fn shape() -> Shape {
+    serde_json::from_str(
+        r#"{
+            "a": "u64",
+            "b": {"$nullable": "string"},
+            "c": {"$optional": "bool"},
+            "d": ["f64"],
+            "e": [{"f": "i64", "g": "string"}],
+            "h": {"i": "f32"}
+        }"#,
+    )
+    .unwrap()
+}
+
+fn value(json: &str) -> Value {
+    serde_json::from_str(json).unwrap()
+}
+
+#[test]
+fn validate_ok() {
+    let val = value(
+        r#"{"a": 1, "b": null, "d": [1.5, 2.5], "e": [{"f": -1, "g": "x"}, {"f": null}], "h": {"i": 2.5}}"#,
+    );
+    assert_eq!(val.validate(&shape()), Ok(()));
+    // numbers match any number shape they merge into, but not the reverse
+    let val = value(r#"{"a": 1.5, "b": "x", "d": [1, -1], "e": [], "h": {"i": 3}}"#);
+    let violations = val.validate(&shape()).unwrap_err();
+    assert_eq!(violations.len(), 1);
+    assert_eq!(violations[0].path, "a");
+
+    // a value always conforms to its own shape
+    let val = value(r#"{"a": 1, "b": [1, null, 3], "c": {"d": [{"e": true}, {}]}}"#);
+    assert_eq!(val.validate(&Shape::from(&val)), Ok(()));
+}
+
+#[test]
+fn validate_reports_every_violation() {
+    let val = value(
+        r#"{"b": 2, "c": null, "d": [1.5, "x", {"z": 1}], "e": [{"f": 1.5, "j": true}], "h": {}, "k": 0}"#,
+    );
+    let violations = val.validate(&shape()).unwrap_err();
+    assert_eq!(
+        violations,
+        vec![
+            ShapeViolation::new("a", ShapeViolationKind::MissingKey),
+            ShapeViolation::new(
+                "b",
+                ShapeViolationKind::WrongType {
+                    expected: ShapeType::Primitive(PrimitiveShape::String).nullable(),
+                    found: ShapeType::Primitive(PrimitiveShape::U64),
+                }
+            ),
+            ShapeViolation::new(
+                "c",
+                ShapeViolationKind::WrongType {
+                    expected: ShapeType::Primitive(PrimitiveShape::Bool).optional(),
+                    found: ShapeType::Primitive(PrimitiveShape::Null),
+                }
+            ),
+            ShapeViolation::new(
+                "d[1]",
+                ShapeViolationKind::HeterogeneousArray {
+                    // 1.5 is parsed as an f32
+                    expected: ShapeType::Primitive(PrimitiveShape::F32),
+                    found: ShapeType::Primitive(PrimitiveShape::String),
+                }
+            ),
+            ShapeViolation::new(
+                "d[1]",
+                ShapeViolationKind::WrongType {
+                    expected: ShapeType::Primitive(PrimitiveShape::F64),
+                    found: ShapeType::Primitive(PrimitiveShape::String),
+                }
+            ),
+            ShapeViolation::new(
+                "d[2]",
+                ShapeViolationKind::WrongType {
+                    expected: ShapeType::Primitive(PrimitiveShape::F64),
+                    found: ShapeType::Nested(Shape::from([(
+                        "z".into(),
+                        ShapeType::Primitive(PrimitiveShape::U64)
+                    )])),
+                }
+            ),
+            ShapeViolation::new(
+                "e[0].f",
+                ShapeViolationKind::WrongType {
+                    expected: ShapeType::Primitive(PrimitiveShape::I64),
+                    found: ShapeType::Primitive(PrimitiveShape::F32),
+                }
+            ),
+            ShapeViolation::new("e[0].j", ShapeViolationKind::ExtraKey),
+            ShapeViolation::new("h.i", ShapeViolationKind::MissingKey),
+            ShapeViolation::new("k", ShapeViolationKind::ExtraKey),
+        ]
+    );
+    assert_eq!(violations[0].to_string(), "missing key \"a\"");
+    assert_eq!(violations[8].to_string(), "missing key \"h.i\"");
+    assert_eq!(violations[9].to_string(), "unexpected key \"k\"");
+    assert_eq!(
+        violations[4].to_string(),
+        "wrong type at \"d[1]\": expected Primitive(F64), found Primitive(String)"
+    );
+}
+
+#[test]
+fn validate_value_type() {
+    let shape = ShapeType::Array(vec![PrimitiveShapeType::Primitive(PrimitiveShape::U64)]);
+    assert_eq!(ValueType::Array(vec![1_u64.into(), 2_u64.into()]).validate(&shape), Ok(()));
+    let violations = ValueType::Primitive(1_u64.into()).validate(&shape).unwrap_err();
+    assert_eq!(violations.len(), 1);
+    assert_eq!(violations[0].path, "");
+}
+
+#[test]
+fn coerce_numbers() {
+    let mut shape = shape();
+    shape.insert(
+        "d".into(),
+        ShapeType::Array(vec![PrimitiveShapeType::Primitive(PrimitiveShape::F32)]),
+    );
+    let mut val = value(
+        r#"{"a": 3.0, "b": "x", "d": [1, -2, 2.5], "e": [{"f": 4, "g": "y"}, {"f": -4.0}], "h": {"i": 7}}"#,
+    );
+    assert_eq!(val.coerce(&shape), Ok(()));
+    assert_eq!(
+        val,
+        Value::from([
+            ("a".into(), ValueType::Primitive(PrimitiveValue::U64(3))),
+            ("b".into(), ValueType::Primitive("x".into())),
+            (
+                "d".into(),
+                ValueType::Array(vec![
+                    ValuePrimitiveType::Primitive(PrimitiveValue::F32(1.)),
+                    ValuePrimitiveType::Primitive(PrimitiveValue::F32(-2.)),
+                    ValuePrimitiveType::Primitive(PrimitiveValue::F32(2.5)),
+                ])
+            ),
+            (
+                "e".into(),
+                ValueType::Array(vec![
+                    ValuePrimitiveType::NestedPrimitive(ValuePrimitive::from([
+                        ("f".into(), PrimitiveValue::I64(4)),
+                        ("g".into(), "y".into()),
+                    ])),
+                    ValuePrimitiveType::NestedPrimitive(ValuePrimitive::from([(
+                        "f".into(),
+                        PrimitiveValue::I64(-4)
+                    )])),
+                ])
+            ),
+            (
+                "h".into(),
+                ValueType::Nested(Value::from([(
+                    "i".into(),
+                    ValueType::Primitive(PrimitiveValue::F32(7.))
+                )]))
+            ),
+        ])
+    );
+
+    // lossy casts are left alone and reported
+    let mut val = value(r#"{"a": -1, "b": null, "d": [], "e": [{"f": 1.5}], "h": {"i": true}}"#);
+    let violations = val.coerce(&shape).unwrap_err();
+    let paths: Vec<&str> = violations.iter().map(|v| v.path.as_str()).collect();
+    assert_eq!(paths, vec!["a", "e[0].f", "h.i"]);
+    assert_eq!(val.get("a"), Some(&ValueType::Primitive(PrimitiveValue::I64(-1))));
+
+    let mut prim = PrimitiveValue::U64(u64::MAX);
+    assert!(!prim.coerce(&PrimitiveShape::I64));
+    assert!(prim.coerce(&PrimitiveShape::U64));
+    let mut prim = PrimitiveValue::F64(-3.);
+    assert!(prim.coerce(&PrimitiveShape::I64));
+    assert_eq!(prim, PrimitiveValue::I64(-3));
+    assert!(!PrimitiveValue::Bool(true).coerce(&PrimitiveShape::U64));
+    // floats that f32 can't hold exactly and integers beyond f64's precision stay as they are
+    let mut prim = PrimitiveValue::F64(0.1);
+    assert!(!prim.coerce(&PrimitiveShape::F32));
+    assert_eq!(prim, PrimitiveValue::F64(0.1));
+    let mut prim = PrimitiveValue::U64(u64::MAX);
+    assert!(!prim.coerce(&PrimitiveShape::F64));
+    let mut prim = PrimitiveValue::I64(-(1 << 24) - 1);
+    assert!(!prim.coerce(&PrimitiveShape::F32));
+    assert!(prim.coerce(&PrimitiveShape::F64));
+}
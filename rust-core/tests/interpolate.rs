@@ -7,6 +7,7 @@ mod tests {
         // ()
         let a = ();
         let b = ();
+        #[allow(clippy::let_unit_value)]
         let c = a.interpolate(&b, 0.5);
         assert_eq!(c, ());
         // f64
@@ -0,0 +1,141 @@
+extern crate alloc;
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+    use s2json_core::*;
+
+    const KML: &str = r##"<?xml version="1.0" encoding="UTF-8"?>
+<kml xmlns="http://www.opengis.net/kml/2.2" xmlns:gx="http://www.google.com/kml/ext/2.2">
+  <Document>
+    <name>Trip</name>
+    <Folder>
+      <Placemark id="7">
+        <name>Summit</name>
+        <description>View point</description>
+        <ExtendedData>
+          <Data name="height"><value>1200</value></Data>
+          <SchemaData schemaUrl="#s"><SimpleData name="open">yes</SimpleData></SchemaData>
+        </ExtendedData>
+        <Point><coordinates>8.5,47.25,1200</coordinates></Point>
+      </Placemark>
+    </Folder>
+    <Placemark>
+      <name>Lake</name>
+      <Polygon>
+        <outerBoundaryIs><LinearRing><coordinates>
+          0,0 4,0 4,4 0,4 0,0
+        </coordinates></LinearRing></outerBoundaryIs>
+        <innerBoundaryIs><LinearRing><coordinates>
+          1,1 2,1 2,2 1,1
+        </coordinates></LinearRing></innerBoundaryIs>
+      </Polygon>
+    </Placemark>
+    <Placemark>
+      <gx:Track>
+        <when>2024-05-01T06:00:00Z</when>
+        <when>2024-05-01T06:00:05Z</when>
+        <gx:coord>8.5 47.25 1200</gx:coord>
+        <gx:coord>8.51 47.26 1210</gx:coord>
+        <ExtendedData><SchemaData schemaUrl="#hr">
+          <gx:SimpleArrayData name="heartrate">
+            <gx:value>120</gx:value>
+            <gx:value>125</gx:value>
+          </gx:SimpleArrayData>
+        </SchemaData></ExtendedData>
+      </gx:Track>
+    </Placemark>
+    <Placemark>
+      <MultiGeometry>
+        <Point><coordinates>1,2</coordinates></Point>
+        <Point><coordinates>3,4</coordinates></Point>
+        <LineString><coordinates>0,0 1,1</coordinates></LineString>
+      </MultiGeometry>
+    </Placemark>
+    <Placemark><name>Nowhere</name></Placemark>
+  </Document>
+</kml>"##;
+
+    #[test]
+    fn read() {
+        let features: Vec<VectorFeature> = read_kml(KML).unwrap();
+        assert_eq!(features.len(), 5);
+
+        let summit = &features[0];
+        assert_eq!(summit.id, Some(7));
+        assert_eq!(
+            summit.properties,
+            Properties::from([
+                ("name".into(), "Summit".into()),
+                ("description".into(), "View point".into()),
+                ("height".into(), 1200_u64.into()),
+                ("open".into(), "yes".into()),
+            ])
+        );
+        assert_eq!(
+            summit.geometry,
+            VectorGeometry::new_point(VectorPoint::from_xyz(8.5, 47.25, 1200.), None)
+        );
+
+        let xy = VectorPoint::from_xy;
+        assert_eq!(
+            features[1].geometry,
+            VectorGeometry::new_polygon(
+                vec![
+                    vec![xy(0., 0.), xy(4., 0.), xy(4., 4.), xy(0., 4.), xy(0., 0.)],
+                    vec![xy(1., 1.), xy(2., 1.), xy(2., 2.), xy(1., 1.)],
+                ],
+                None
+            )
+        );
+
+        let m = |when: &str, heartrate: u64| {
+            Some(MValue::from([
+                (KML_TIME_KEY.into(), when.into()),
+                ("heartrate".into(), heartrate.into()),
+            ]))
+        };
+        assert_eq!(
+            features[2].geometry,
+            VectorGeometry::new_linestring(
+                vec![
+                    VectorPoint::new(8.5, 47.25, Some(1200.), m("2024-05-01T06:00:00Z", 120)),
+                    VectorPoint::new(8.51, 47.26, Some(1210.), m("2024-05-01T06:00:05Z", 125)),
+                ],
+                None
+            )
+        );
+
+        // a mixed multi geometry is split by kind
+        assert_eq!(
+            features[3].geometry,
+            VectorGeometry::new_multipoint(vec![xy(1., 2.), xy(3., 4.)], None)
+        );
+        assert_eq!(
+            features[4].geometry,
+            VectorGeometry::new_linestring(vec![xy(0., 0.), xy(1., 1.)], None)
+        );
+        assert_eq!(features[3].properties, Properties::default());
+    }
+
+    #[test]
+    fn errors() {
+        let read = |kml: &str| read_kml::<MValue>(kml).map(|_| ());
+        assert_eq!(
+            read("<gpx/>"),
+            Err(S2JsonError::invalid_xml(0, "expected a <kml> root element, found <gpx>"))
+        );
+        assert_eq!(
+            read("<kml><Placemark><Point/></Placemark></kml>"),
+            Err(S2JsonError::invalid_xml(16, "<Point> has no coordinates"))
+        );
+        assert_eq!(
+            read("<kml><Placemark><Point><coordinates>1,x</coordinates></Point></Placemark></kml>"),
+            Err(S2JsonError::invalid_xml(23, "invalid coordinate \"1,x\""))
+        );
+        assert_eq!(
+            read("<kml><Placemark name=x/></kml>"),
+            Err(S2JsonError::invalid_xml(21, "expected a quoted attribute value"))
+        );
+    }
+}
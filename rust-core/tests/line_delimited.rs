@@ -0,0 +1,102 @@
+extern crate alloc;
+
+#[cfg(test)]
+mod tests {
+    use alloc::{string::String, vec, vec::Vec};
+    use s2json_core::*;
+
+    #[test]
+    fn read_features() {
+        let data = "{\"type\":\"Feature\",\"id\":1,\"properties\":{\"a\":1},\"geometry\":{\"type\":\"Point\",\"coordinates\":[1,2]}}\r\n\
+            \n\
+            {\"type\":\"S2Feature\",\"face\":3,\"properties\":{},\"geometry\":{\"type\":\"Point\",\"is3D\":false,\"coordinates\":{\"x\":0.5,\"y\":0.25}}}\n";
+        let mut reader = LineDelimitedReader::<_>::new(data.bytes());
+        let first = reader.next().unwrap().unwrap();
+        match first {
+            Features::Feature(f) => {
+                assert_eq!(f.id, Some(1));
+                assert_eq!(
+                    f.geometry,
+                    Geometry::Point(PointGeometry {
+                        _type: GeometryType::Point,
+                        coordinates: Point(1., 2.),
+                        ..Default::default()
+                    })
+                );
+            }
+            _ => panic!("expected a Feature"),
+        }
+        assert_eq!(reader.line(), 1);
+        let second = reader.next().unwrap().unwrap();
+        match second {
+            Features::VectorFeature(f) => {
+                assert_eq!(f._type, VectorFeatureType::S2Feature);
+                assert_eq!(f.face, Face::Face3);
+                assert_eq!(f.geometry.point(), Some(&VectorPoint::from_xy(0.5, 0.25)));
+            }
+            _ => panic!("expected a VectorFeature"),
+        }
+        assert_eq!(reader.line(), 3);
+        assert!(reader.next().is_none());
+    }
+
+    #[test]
+    fn read_reports_line_errors() {
+        let data = "{\"type\":\"Feature\",\"properties\":{},\"geometry\":{\"type\":\"Point\",\"coordinates\":[0,0]}}\n\
+            {\"type\":\"Feature\",\n\
+            not json\n\
+            {\"type\":\"Feature\",\"properties\":{},\"geometry\":{\"type\":\"Point\",\"coordinates\":[3,4]}}";
+        let results: Vec<_> = LineDelimitedReader::<_>::new(data.bytes()).collect();
+        assert_eq!(results.len(), 4);
+        assert!(results[0].is_ok());
+        assert_eq!(results[1].as_ref().unwrap_err().line, 2);
+        assert_eq!(results[2].as_ref().unwrap_err().line, 3);
+        // the last line has no trailing newline but is still read
+        assert!(results[3].is_ok());
+
+        let err = results[2].clone().unwrap_err();
+        assert!(alloc::format!("{err}").starts_with("line 3: "));
+    }
+
+    #[test]
+    fn read_empty() {
+        let mut reader = LineDelimitedReader::<_>::new("".bytes());
+        assert!(reader.next().is_none());
+        let mut reader = LineDelimitedReader::<_>::new("\n  \r\n\n".bytes());
+        assert!(reader.next().is_none());
+        assert_eq!(reader.line(), 3);
+    }
+
+    #[test]
+    fn write_then_read() {
+        let features: Vec<Features> = vec![
+            Features::Feature(Feature::new(
+                Some(2),
+                Properties::from([("name".into(), "a".into())]),
+                Geometry::Point(PointGeometry {
+                    _type: GeometryType::Point,
+                    coordinates: Point(5., 6.),
+                    ..Default::default()
+                }),
+                None,
+            )),
+            Features::VectorFeature(VectorFeature::new_s2(
+                None,
+                Face::Face1,
+                Properties::default(),
+                VectorGeometry::new_point(VectorPoint::from_xyz(0.1, 0.2, 3.), None),
+                None,
+            )),
+        ];
+        let mut writer = LineDelimitedWriter::new(String::new());
+        writer.write_features(&features).unwrap();
+        assert_eq!(writer.lines(), 2);
+        let out = writer.into_inner();
+        assert_eq!(out.lines().count(), 2);
+        assert!(out.ends_with('\n'));
+
+        let back: Vec<Features> =
+            LineDelimitedReader::<_>::new(out.bytes()).map(Result::unwrap).collect();
+        assert_eq!(back, features);
+    }
+}
@@ -0,0 +1,151 @@
+extern crate alloc;
+
+#[cfg(test)]
+mod tests {
+    use alloc::{vec, vec::Vec};
+    use core::f64::consts::PI;
+    use s2json_core::*;
+
+    /// Degrees, minutes and seconds to decimal degrees
+    fn dms(degrees: f64, minutes: f64, seconds: f64) -> f64 {
+        degrees.signum() * (degrees.abs() + minutes / 60. + seconds / 3_600.)
+    }
+
+    fn ring(points: &[(f64, f64)]) -> Vec<Point> {
+        points.iter().map(|&(x, y)| Point(x, y)).collect()
+    }
+
+    #[test]
+    fn haversine() {
+        let london = Point(-0.1278, 51.5074);
+        let paris = Point(2.3522, 48.8566);
+        let d = haversine_distance(&london, &paris);
+        assert!((d - 343_556.).abs() < 1., "{d}");
+        assert_eq!(haversine_distance(&london, &london), 0.);
+        // one degree along the equator
+        let d = haversine_distance(&(0., 0.), &(1., 0.));
+        assert!((d - EARTH_RADIUS * PI / 180.).abs() < 1e-6);
+    }
+
+    #[test]
+    fn vincenty() {
+        let flinders_peak = (dms(144., 25., 29.52440), dms(-37., 57., 3.72030));
+        let buninyong = (dms(143., 55., 35.38390), dms(-37., 39., 10.15610));
+        let d = vincenty_distance(&flinders_peak, &buninyong).unwrap();
+        assert!((d - 54_972.271).abs() < 1e-3, "{d}");
+        // one degree along the equator follows the semi-major axis
+        let d = vincenty_distance(&Point(0., 0.), &Point(1., 0.)).unwrap();
+        assert!((d - WGS84_SEMI_MAJOR_AXIS * PI / 180.).abs() < 1e-6, "{d}");
+        assert_eq!(vincenty_distance(&Point(10., 10.), &Point(10., 10.)), Some(0.));
+        // nearly antipodal points do not converge
+        let (a, b) = (Point(0., 0.), Point(179.7, 0.5));
+        assert_eq!(vincenty_distance(&a, &b), None);
+    }
+
+    #[test]
+    fn antipodal_distance() {
+        // Karney's worked example of a nearly antipodal inverse problem
+        let (a, b) = (Point(0., -30.), Point(179.8, 29.9));
+        assert_eq!(vincenty_distance(&a, &b), None);
+        let d = distance(&a, &b, EarthModel::Wgs84);
+        assert!((d - 19_989_832.827_610).abs() < 1e-3, "{d}");
+        assert_eq!(distance(&b, &a, EarthModel::Wgs84), d);
+        // antipodes on the equator are closest over the poles, half a meridian apart
+        let d = distance(&Point(0., 0.), &Point(180., 0.), EarthModel::Wgs84);
+        assert!((d - 20_003_931.458_6).abs() < 1e-3, "{d}");
+        // and along it once they are less than 180(1 - f) degrees apart
+        let d = distance(&Point(-10., 0.), &Point(169.3, 0.), EarthModel::Wgs84);
+        assert!((d - WGS84_SEMI_MAJOR_AXIS * 179.3 * PI / 180.).abs() < 1e-6, "{d}");
+    }
+
+    #[test]
+    fn lengths() {
+        let line = ring(&[(0., 0.), (1., 0.), (2., 0.)]);
+        let sphere = line_length(&line, EarthModel::Sphere);
+        assert!((sphere - 2. * EARTH_RADIUS * PI / 180.).abs() < 1e-6);
+        let wgs84 = line_length(&line, EarthModel::Wgs84);
+        assert!((wgs84 - 2. * WGS84_SEMI_MAJOR_AXIS * PI / 180.).abs() < 1e-6);
+        assert_eq!(line_length::<Point>(&[], EarthModel::Sphere), 0.);
+
+        let geometry: Geometry = Geometry::LineString(LineStringGeometry {
+            _type: GeometryType::LineString,
+            coordinates: line.clone(),
+            ..Default::default()
+        });
+        assert_eq!(geometry.length(EarthModel::Sphere), sphere);
+        assert_eq!(geometry.area(EarthModel::Sphere), 0.);
+        let point: Geometry = Geometry::Point(PointGeometry {
+            _type: GeometryType::Point,
+            coordinates: Point(1., 1.),
+            ..Default::default()
+        });
+        assert_eq!(point.length(EarthModel::Wgs84), 0.);
+
+        let vector: VectorGeometry = VectorGeometry::new_linestring(
+            line.iter().map(|p| VectorPoint::from_xy(p.0, p.1)).collect(),
+            None,
+        );
+        assert_eq!(vector.length(EarthModel::Wgs84), wgs84);
+    }
+
+    #[test]
+    fn octant_area() {
+        // a triangle covering an eighth of the globe, in either winding
+        let octant = ring(&[(0., 0.), (90., 0.), (0., 90.), (0., 0.)]);
+        let sphere = ring_area(&octant, EarthModel::Sphere);
+        assert!((sphere / (PI / 2. * EARTH_RADIUS * EARTH_RADIUS) - 1.).abs() < 1e-12);
+        let reversed: Vec<Point> = octant.iter().rev().cloned().collect();
+        assert!((ring_area(&reversed, EarthModel::Sphere) / sphere - 1.).abs() < 1e-12);
+        // an eighth of the WGS84 surface area
+        let wgs84 = ring_area(&octant, EarthModel::Wgs84);
+        assert!((wgs84 / (510_065_621_724_088.5 / 8.) - 1.).abs() < 1e-9, "{wgs84}");
+    }
+
+    #[test]
+    fn polygon_with_hole() {
+        let outer = ring(&[(0., 0.), (10., 0.), (10., 10.), (0., 10.), (0., 0.)]);
+        let hole = ring(&[(2., 2.), (2., 8.), (8., 8.), (8., 2.), (2., 2.)]);
+        let polygon = vec![outer.clone(), hole.clone()];
+        for model in [EarthModel::Sphere, EarthModel::Wgs84] {
+            let expected = ring_area(&outer, model) - ring_area(&hole, model);
+            assert!((polygon_area(&polygon, model) - expected).abs() < 1e-3);
+        }
+        // roughly 1,111 km by 1,100 km less 667 km by 665 km
+        let area = polygon_area(&polygon, EarthModel::Sphere);
+        assert!(area > 7.7e11 && area < 7.9e11, "{area}");
+
+        let geometry: Geometry = Geometry::Polygon(PolygonGeometry {
+            _type: GeometryType::Polygon,
+            coordinates: polygon.clone(),
+            ..Default::default()
+        });
+        assert_eq!(geometry.area(EarthModel::Sphere), area);
+        let perimeter =
+            line_length(&outer, EarthModel::Sphere) + line_length(&hole, EarthModel::Sphere);
+        assert_eq!(geometry.length(EarthModel::Sphere), perimeter);
+
+        let vector: VectorGeometry = VectorGeometry::new_polygon(
+            polygon
+                .iter()
+                .map(|ring| ring.iter().map(|p| VectorPoint::from_xy(p.0, p.1)).collect())
+                .collect(),
+            None,
+        );
+        assert_eq!(vector.area(EarthModel::Sphere), area);
+        assert_eq!(vector.length(EarthModel::Sphere), perimeter);
+    }
+
+    #[test]
+    fn custom_points() {
+        // any GetXY type can be measured
+        let tuples = [(0., 0.), (10., 0.), (10., 10.), (0., 0.)];
+        let points = ring(&tuples);
+        assert_eq!(
+            line_length(&tuples, EarthModel::Wgs84),
+            line_length(&points, EarthModel::Wgs84)
+        );
+        assert_eq!(ring_area(&tuples, EarthModel::Sphere), ring_area(&points, EarthModel::Sphere));
+        let (a, b) = (VectorPoint::<MValue>::from_xy(0., 0.), Point3D(1., 0., 100.));
+        assert_eq!(haversine_distance(&a, &b), haversine_distance(&(0., 0.), &(1., 0.)));
+    }
+}
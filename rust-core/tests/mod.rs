@@ -1,10 +1,29 @@
 extern crate alloc;
 
+mod clip;
+mod csv;
+mod geobuf;
 mod geometry;
+mod geometry_conversion;
+mod gpx;
 mod impls;
+mod kml;
+mod line_delimited;
 mod map;
+mod measure;
+mod mvt;
+mod polyline;
+mod s2_cell_id;
+mod s2_convert;
+mod s2_projection;
+mod simplify;
+mod tessellate;
+mod tile;
+mod topojson;
 mod value;
 mod vector_point;
+mod wkb;
+mod wkt;
 
 #[cfg(test)]
 mod tests {
@@ -38,14 +57,14 @@ mod tests {
     #[test]
     fn defaults() {
         let f: Feature = Default::default();
-        assert_eq!(f._type, "Feature".into());
+        assert_eq!(f._type, FeatureType::Feature);
         assert_eq!(f.id, None);
         assert_eq!(f.properties, Properties::default());
         assert_eq!(f.geometry, Geometry::default());
         assert_eq!(f.metadata, None);
 
         let f: VectorFeature = Default::default();
-        assert_eq!(f._type, "VectorFeature".into());
+        assert_eq!(f._type, VectorFeatureType::VectorFeature);
         assert_eq!(f.id, None);
         assert_eq!(f.face, 0.into());
         assert_eq!(f.properties, Properties::default());
@@ -105,7 +124,7 @@ mod tests {
             Some(22),
             Properties::new(),
             Geometry::Point(PointGeometry {
-                _type: "Point".into(),
+                _type: GeometryType::Point,
                 coordinates: Point(0.0, 0.0),
                 m_values: None,
                 bbox: None,
@@ -113,11 +132,11 @@ mod tests {
             None,
         );
         assert_eq!(fc.id, Some(22));
-        assert_eq!(fc._type, "Feature".into());
+        assert_eq!(fc._type, FeatureType::Feature);
         assert_eq!(
             fc.geometry,
             Geometry::Point(PointGeometry {
-                _type: "Point".into(),
+                _type: GeometryType::Point,
                 coordinates: Point(0.0, 0.0),
                 m_values: None,
                 bbox: None,
@@ -133,7 +152,7 @@ mod tests {
             Some(55),
             Properties::new(),
             VectorGeometry::Point(VectorPointGeometry {
-                _type: "Point".into(),
+                _type: VectorGeometryType::Point,
                 coordinates: VectorPoint { x: 0.0, y: 1.0, z: Some(3.), m: None, t: None },
                 bbox: None,
                 is_3d: true,
@@ -145,11 +164,11 @@ mod tests {
             None,
         );
         assert_eq!(fc.id, Some(55));
-        assert_eq!(fc._type, "VectorFeature".into());
+        assert_eq!(fc._type, VectorFeatureType::VectorFeature);
         assert_eq!(
             fc.geometry,
             VectorGeometry::Point(VectorPointGeometry {
-                _type: "Point".into(),
+                _type: VectorGeometryType::Point,
                 coordinates: VectorPoint { x: 0.0, y: 1.0, z: Some(3.), m: None, t: None },
                 bbox: None,
                 is_3d: true,
@@ -176,7 +195,7 @@ mod tests {
             3.into(),
             Properties::new(),
             VectorGeometry::Point(VectorPointGeometry {
-                _type: "Point".into(),
+                _type: VectorGeometryType::Point,
                 coordinates: VectorPoint { x: 0.0, y: 1.0, z: Some(3.), m: None, t: None },
                 bbox: None,
                 is_3d: true,
@@ -188,11 +207,11 @@ mod tests {
             Some(MetaTest { name: "test".into(), value: "value".into() }),
         );
         assert_eq!(fc.id, Some(55));
-        assert_eq!(fc._type, "S2Feature".into());
+        assert_eq!(fc._type, VectorFeatureType::S2Feature);
         assert_eq!(
             fc.geometry,
             VectorGeometry::Point(VectorPointGeometry {
-                _type: "Point".into(),
+                _type: VectorGeometryType::Point,
                 coordinates: VectorPoint { x: 0.0, y: 1.0, z: Some(3.), m: None, t: None },
                 bbox: None,
                 is_3d: true,
@@ -218,7 +237,7 @@ mod tests {
         // from_vector_feature
 
         let new_geo = VectorGeometry::Point(VectorPointGeometry {
-            _type: "Point".into(),
+            _type: VectorGeometryType::Point,
             coordinates: VectorPoint { x: 5.0, y: 4.0, z: Some(-3.), m: None, t: None },
             bbox: None,
             is_3d: true,
@@ -250,11 +269,11 @@ mod tests {
         }"#;
 
         let feature: Feature = serde_json::from_str(json_string).unwrap();
-        assert_eq!(feature._type, "Feature".into());
+        assert_eq!(feature._type, FeatureType::Feature);
         assert_eq!(
             feature.geometry,
             Geometry::MultiPoint(MultiPointGeometry {
-                _type: "MultiPoint".into(),
+                _type: GeometryType::MultiPoint,
                 coordinates: vec![
                     Point(-13.292352825505162, 54.34883408204476),
                     Point(36.83102287804303, 59.56941785818924),
@@ -292,11 +311,11 @@ mod tests {
         }"#;
 
         let feature: Feature = serde_json::from_str(json_string).unwrap();
-        assert_eq!(feature._type, "Feature".into());
+        assert_eq!(feature._type, FeatureType::Feature);
         assert_eq!(
             feature.geometry,
             Geometry::LineString(LineStringGeometry {
-                _type: "LineString".into(),
+                _type: GeometryType::LineString,
                 coordinates: vec![
                     Point(-13.292352825505162, 54.34883408204476),
                     Point(36.83102287804303, 59.56941785818924),
@@ -336,7 +355,7 @@ mod tests {
         }"#;
 
         let feature: VectorFeature = serde_json::from_str(json_string).unwrap();
-        assert_eq!(feature._type, "VectorFeature".into());
+        assert_eq!(feature._type, VectorFeatureType::VectorFeature);
         let geometry = feature.geometry;
         assert_eq!(
             geometry,
@@ -355,10 +374,13 @@ mod tests {
     }
 
     #[test]
-    #[should_panic(expected = "Invalid vector geometry type: LinesString")]
     fn vector_geometry_type_from() {
         let str_bad = "LinesString";
-        let _ = VectorGeometryType::from(str_bad);
+        let err = VectorGeometryType::try_from(str_bad).unwrap_err();
+        assert_eq!(err, S2JsonError::invalid_type("VectorGeometryType", "LinesString"));
+        assert_eq!(alloc::format!("{err}"), "invalid VectorGeometryType: \"LinesString\"");
+        assert_eq!(VectorGeometryType::try_from("LineString"), Ok(VectorGeometryType::LineString));
+        assert_eq!("Polygon".parse::<VectorGeometryType>(), Ok(VectorGeometryType::Polygon));
     }
 
     #[test]
@@ -380,7 +402,7 @@ mod tests {
         }"#;
 
         let feature: VectorFeature = serde_json::from_str(json_string).unwrap();
-        assert_eq!(feature._type, "VectorFeature".into());
+        assert_eq!(feature._type, VectorFeatureType::VectorFeature);
         let geometry = feature.geometry;
         assert_eq!(
             geometry,
@@ -399,10 +421,67 @@ mod tests {
     }
 
     #[test]
-    #[should_panic(expected = "Invalid geometry type: MultiLinesString")]
     fn vector_geometry_type_from_bad() {
         let str_bad = "MultiLinesString";
-        let _ = GeometryType::from(str_bad);
+        assert_eq!(
+            GeometryType::try_from(str_bad),
+            Err(S2JsonError::invalid_type("GeometryType", "MultiLinesString"))
+        );
+        assert_eq!("MultiPolygon3D".parse::<GeometryType>(), Ok(GeometryType::MultiPolygon3D));
+    }
+
+    #[test]
+    fn feature_types_from_str() {
+        assert_eq!(FeatureType::try_from("Feature"), Ok(FeatureType::Feature));
+        assert!(FeatureType::try_from("feature").is_err());
+        assert_eq!("S2Feature".parse::<VectorFeatureType>(), Ok(VectorFeatureType::S2Feature));
+        assert_eq!(
+            VectorFeatureType::try_from("VectorFeature"),
+            Ok(VectorFeatureType::VectorFeature)
+        );
+        assert_eq!(
+            "Feature".parse::<VectorFeatureType>(),
+            Err(S2JsonError::invalid_type("VectorFeatureType", "Feature"))
+        );
+        assert_eq!(
+            "FeatureCollection".parse::<FeatureCollectionType>(),
+            Ok(FeatureCollectionType::FeatureCollection)
+        );
+        assert!("S2FeatureCollection".parse::<FeatureCollectionType>().is_err());
+        assert_eq!(
+            S2FeatureCollectionType::try_from("S2FeatureCollection"),
+            Ok(S2FeatureCollectionType::S2FeatureCollection)
+        );
+        assert!(S2FeatureCollectionType::try_from("").is_err());
+    }
+
+    #[test]
+    fn deserialize_rejects_bad_type_tags() {
+        // unknown geometry type
+        let json = r#"{"type":"Feature","properties":{},"geometry":{"type":"Pointy","coordinates":[0,0]}}"#;
+        assert!(serde_json::from_str::<Feature>(json).is_err());
+        // coordinates that don't match the tagged type are not coerced into another geometry
+        let json = r#"{"type":"Polygon","coordinates":[[0,0],[1,1]]}"#;
+        assert!(serde_json::from_str::<Geometry>(json).is_err());
+        let json = r#"{"type":"Point","coordinates":[0,0,1]}"#;
+        assert!(serde_json::from_str::<Geometry>(json).is_err());
+        let json = r#"{"type":"Point3D","coordinates":[0,0,1]}"#;
+        assert!(serde_json::from_str::<Geometry>(json).is_ok());
+        // missing type tag
+        let json = r#"{"coordinates":[0,0]}"#;
+        assert!(serde_json::from_str::<Geometry>(json).is_err());
+        // wrong vector geometry type
+        let json = r#"{"type":"Polygon3D","is3D":false,"coordinates":{"x":0,"y":0}}"#;
+        assert!(serde_json::from_str::<VectorGeometry>(json).is_err());
+        let json = r#"{"type":"Polygon","is3D":false,"coordinates":[{"x":0,"y":0}]}"#;
+        assert!(serde_json::from_str::<VectorGeometry>(json).is_err());
+        // wrong feature types
+        let json = r#"{"type":"Feature","face":0,"properties":{},"geometry":{"type":"Point","is3D":false,"coordinates":{"x":0,"y":0}}}"#;
+        assert!(serde_json::from_str::<VectorFeature>(json).is_err());
+        let json = r#"{"type":"NotAFeature","properties":{},"geometry":{"type":"Point","coordinates":[0,0]}}"#;
+        assert!(serde_json::from_str::<Feature>(json).is_err());
+        let json = r#"{"type":"FeatureCollections","features":[]}"#;
+        assert!(serde_json::from_str::<FeatureCollection>(json).is_err());
     }
 
     #[test]
@@ -463,7 +542,7 @@ mod tests {
             3.into(),
             Properties::new(),
             VectorGeometry::Point(VectorPointGeometry {
-                _type: "Point".into(),
+                _type: VectorGeometryType::Point,
                 coordinates: VectorPoint { x: 0.0, y: 1.0, z: Some(3.), m: None, t: None },
                 bbox: None,
                 is_3d: true,
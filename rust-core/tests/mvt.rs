@@ -0,0 +1,223 @@
+extern crate alloc;
+
+#[cfg(test)]
+mod tests {
+    use alloc::{string::String, vec, vec::Vec};
+    use pbf::{Protobuf, Type, zigzag};
+    use s2json_core::*;
+
+    fn point(x: f64, y: f64) -> VectorPoint {
+        VectorPoint::from_xy(x / 4096., y / 4096.)
+    }
+
+    fn encode(tile: &MapboxVectorTile) -> Vec<u8> {
+        let mut pb = Protobuf::new();
+        pb.write_fields(tile);
+        pb.take()
+    }
+
+    fn decode(data: Vec<u8>) -> MapboxVectorTile {
+        let mut tile = MapboxVectorTile::new();
+        Protobuf::from_input(data).read_fields(&mut tile, None);
+        tile
+    }
+
+    /// Read the raw messages of the first layer: (keys, value count, feature commands)
+    fn raw_layer(data: Vec<u8>) -> (Vec<String>, usize, Vec<Vec<u32>>) {
+        let mut pb = Protobuf::from_input(data);
+        assert_eq!(pb.read_field().tag, 3);
+        let mut layer = Protobuf::from_input(pb.read_bytes());
+        let (mut keys, mut values, mut commands) = (vec![], 0, vec![]);
+        while layer.get_pos() < layer.len() {
+            let field = layer.read_field();
+            match field.tag {
+                2 => {
+                    let mut feature = Protobuf::from_input(layer.read_bytes());
+                    while feature.get_pos() < feature.len() {
+                        let field = feature.read_field();
+                        if field.tag == 4 {
+                            commands.push(feature.read_packed());
+                        } else {
+                            feature.skip(field.r#type);
+                        }
+                    }
+                }
+                3 => keys.push(layer.read_string()),
+                4 => {
+                    values += 1;
+                    layer.skip(Type::Bytes);
+                }
+                _ => layer.skip(field.r#type),
+            }
+        }
+        (keys, values, commands)
+    }
+
+    #[test]
+    fn spec_geometry_commands() {
+        let mut layer = MapboxVectorLayer::new("spec", MVT_DEFAULT_EXTENT);
+        let geometries = vec![
+            VectorGeometry::new_point(point(25., 17.), None),
+            VectorGeometry::new_multipoint(vec![point(5., 7.), point(3., 2.)], None),
+            VectorGeometry::new_linestring(
+                vec![point(2., 2.), point(2., 10.), point(10., 10.)],
+                None,
+            ),
+            VectorGeometry::new_polygon(
+                vec![vec![point(3., 6.), point(8., 12.), point(20., 34.), point(3., 6.)]],
+                None,
+            ),
+        ];
+        for geometry in geometries {
+            layer.features.push(VectorFeature::new_wm(
+                None,
+                MapboxProperties::new(),
+                geometry,
+                None,
+            ));
+        }
+        let tile = MapboxVectorTile { layers: vec![layer] };
+        let data = encode(&tile);
+        let (_, _, commands) = raw_layer(data.clone());
+        assert_eq!(
+            commands,
+            vec![
+                vec![9, 50, 34],
+                vec![17, 10, 14, 3, 9],
+                vec![9, 4, 4, 18, 0, 16, 16, 0],
+                vec![9, 6, 12, 18, 10, 12, 24, 44, 15],
+            ]
+        );
+        assert_eq!(decode(data), tile);
+    }
+
+    #[test]
+    fn round_trip_with_tables() {
+        let mut layer: MapboxVectorLayer = MapboxVectorLayer::new("water", 8_192);
+        let properties = |name: &str, depth: u64| {
+            MapboxProperties::from([
+                ("name".into(), name.into()),
+                ("depth".into(), depth.into()),
+                ("deleted".into(), PrimitiveValue::Null),
+            ])
+        };
+        // exterior wound the wrong way, hole wound like the exterior, both get rewound
+        let outer = vec![
+            VectorPoint::from_xy(0., 0.),
+            VectorPoint::from_xy(0., 0.5),
+            VectorPoint::from_xy(0.5, 0.5),
+            VectorPoint::from_xy(0.5, 0.),
+            VectorPoint::from_xy(0., 0.),
+        ];
+        let hole = vec![
+            VectorPoint::from_xy(0.125, 0.125),
+            VectorPoint::from_xy(0.25, 0.125),
+            VectorPoint::from_xy(0.25, 0.25),
+            VectorPoint::from_xy(0.125, 0.125),
+        ];
+        let second = vec![
+            VectorPoint::from_xy(0.75, 0.75),
+            VectorPoint::from_xy(1., 0.75),
+            VectorPoint::from_xy(1., 1.),
+            VectorPoint::from_xy(0.75, 0.75),
+        ];
+        layer.features.push(VectorFeature::new_wm(
+            Some(1),
+            properties("lake", 3),
+            VectorGeometry::new_multipolygon(vec![vec![outer, hole], vec![second]], None),
+            None,
+        ));
+        layer.features.push(VectorFeature::new_wm(
+            Some(2),
+            properties("lake", 7),
+            VectorGeometry::new_multilinestring(
+                vec![
+                    vec![VectorPoint::from_xy(0., 0.), VectorPoint::from_xy(1., 1.)],
+                    // collapses to a single point once snapped and is dropped
+                    vec![VectorPoint::from_xy(0.5, 0.5), VectorPoint::from_xy(0.50001, 0.5)],
+                ],
+                None,
+            ),
+            None,
+        ));
+        let tile = MapboxVectorTile { layers: vec![layer] };
+        let data = encode(&tile);
+        let (keys, values, _) = raw_layer(data.clone());
+        assert_eq!(keys, vec!["depth", "name"]);
+        assert_eq!(values, 3);
+
+        let decoded = decode(data);
+        let layer = decoded.layer("water").unwrap();
+        assert_eq!(layer.version, 2);
+        assert_eq!(layer.extent, 8_192);
+        assert_eq!(layer.features.len(), 2);
+        assert_eq!(layer.features[0].id, Some(1));
+        assert_eq!(
+            layer.features[0].properties,
+            MapboxProperties::from([
+                ("name".into(), "lake".into()),
+                ("depth".into(), 3_u64.into())
+            ])
+        );
+        let polygons = layer.features[0].geometry.multipolygon().unwrap();
+        assert_eq!(polygons.len(), 2);
+        assert_eq!(polygons[0].len(), 2);
+        assert_eq!(
+            polygons[0][0],
+            vec![
+                VectorPoint::from_xy(0., 0.),
+                VectorPoint::from_xy(0.5, 0.),
+                VectorPoint::from_xy(0.5, 0.5),
+                VectorPoint::from_xy(0., 0.5),
+                VectorPoint::from_xy(0., 0.),
+            ]
+        );
+        assert_eq!(polygons[0][1][1], VectorPoint::from_xy(0.25, 0.25));
+        assert_eq!(
+            layer.features[1].geometry.linestring().unwrap(),
+            &vec![VectorPoint::from_xy(0., 0.), VectorPoint::from_xy(1., 1.)]
+        );
+
+        // properties can be decoded straight into a full Properties object
+        let mut tile: MapboxVectorTile<Properties> = MapboxVectorTile::new();
+        Protobuf::from_input(encode(&decoded)).read_fields(&mut tile, None);
+        assert_eq!(tile.layers[0].features[1].properties.get("depth"), Some(&7_u64.into()));
+    }
+
+    #[test]
+    fn far_out_commands() {
+        // a ring whose points are 2^31 tile units apart, so its area doesn't fit an i64
+        let far = zigzag(i32::MAX as i64) as u32;
+        let commands: Vec<u32> = vec![9, far, far, 18, far, 0, 0, far, 15];
+        let mut feature = Protobuf::new();
+        feature.write_varint_field(3, 3_u8);
+        feature.write_packed_varint(4, &commands);
+        let mut layer = Protobuf::new();
+        layer.write_string_field(1, "far");
+        layer.write_bytes_field(2, &feature.take());
+        let mut pb = Protobuf::new();
+        pb.write_bytes_field(3, &layer.take());
+        let tile = decode(pb.take());
+        let layer = tile.layer("far").unwrap();
+        assert_eq!(layer.features.len(), 1);
+        let polygon = layer.features[0].geometry.polygon().unwrap();
+        assert_eq!(polygon[0].len(), 4);
+        assert_eq!(polygon[0][2], point(2. * i32::MAX as f64, 2. * i32::MAX as f64));
+    }
+
+    #[test]
+    fn far_out_coordinates_are_clamped() {
+        let mut layer = MapboxVectorLayer::new("far", MVT_DEFAULT_EXTENT);
+        let line = vec![point(-1e12, 0.), point(1e12, f64::INFINITY)];
+        let geometry = VectorGeometry::new_linestring(line, None);
+        layer.features.push(VectorFeature::new_wm(None, MapboxProperties::new(), geometry, None));
+        let data = encode(&MapboxVectorTile { layers: vec![layer] });
+        let (_, _, commands) = raw_layer(data.clone());
+        let max = (i32::MAX / 2) as i64;
+        let far = zigzag(-max) as u32;
+        assert_eq!(commands, vec![vec![9, far, 0, 10, zigzag(2 * max) as u32, zigzag(max) as u32]]);
+        let tile = decode(data);
+        let line = tile.layer("far").unwrap().features[0].geometry.linestring().unwrap().clone();
+        assert_eq!(line, vec![point(-max as f64, 0.), point(max as f64, max as f64)]);
+    }
+}
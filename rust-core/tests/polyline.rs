@@ -0,0 +1,102 @@
+extern crate alloc;
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+    use s2json_core::*;
+
+    #[test]
+    fn google_polyline() {
+        // the example from the algorithm's documentation
+        let line: LineString =
+            vec![Point(-120.2, 38.5), Point(-120.95, 40.7), Point(-126.453, 43.252)];
+        let encoded = encode_polyline(&line, 5);
+        assert_eq!(encoded, "_p~iF~ps|U_ulLnnqC_mqNvxq`@");
+        assert_eq!(decode_polyline::<Point>(&encoded, 5), Ok(line.clone()));
+
+        // precision 6, decoded into vector points
+        let encoded = encode_polyline(&line, 6);
+        let decoded: VectorLineString = decode_polyline(&encoded, 6).unwrap();
+        assert_eq!(
+            decoded,
+            vec![
+                VectorPoint::from_xy(-120.2, 38.5),
+                VectorPoint::from_xy(-120.95, 40.7),
+                VectorPoint::from_xy(-126.453, 43.252),
+            ]
+        );
+        // values are rounded to the precision
+        let decoded: LineString =
+            decode_polyline(&encode_polyline(&[Point(1.234_567, -0.000_004)], 5), 5).unwrap();
+        assert_eq!(decoded, vec![Point(1.23457, -0.)]);
+        assert_eq!(encode_polyline::<Point>(&[], 5), "");
+        assert_eq!(decode_polyline::<Point>("", 5), Ok(vec![]));
+    }
+
+    #[test]
+    fn flexible_polyline() {
+        let points = vec![
+            Point3D(8.6982122, 50.1022829, 10.),
+            Point3D(8.6956695, 50.1020076, 20.),
+            Point3D(8.6914960, 50.1006313, 30.),
+            Point3D(8.6875156, 50.0987800, 40.),
+        ];
+        let header = FlexiblePolylineHeader::new(5, PolylineThirdDimension::Absent, 0);
+        let encoded = encode_flexible_polyline(&points, header);
+        assert_eq!(encoded, "BFoz5xJ67i1B1B7PzIhaxL7Y");
+        let (decoded_header, decoded) = decode_flexible_polyline::<VectorPoint>(&encoded).unwrap();
+        assert_eq!(decoded_header, header);
+        assert_eq!(decoded[1], VectorPoint::from_xy(8.69567, 50.10201));
+        assert_eq!(decoded[1].z, None);
+
+        let header = FlexiblePolylineHeader::new(5, PolylineThirdDimension::Altitude, 0);
+        let encoded = encode_flexible_polyline(&points, header);
+        assert_eq!(encoded, "BlBoz5xJ67i1BU1B7PUzIhaUxL7YU");
+        let (decoded_header, decoded) = decode_flexible_polyline::<Point3D>(&encoded).unwrap();
+        assert_eq!(decoded_header, header);
+        assert_eq!(decoded[3], Point3D(8.68752, 50.09878, 40.));
+
+        let header = FlexiblePolylineHeader::new(7, PolylineThirdDimension::Elevation, 2);
+        let points = vec![
+            Point3D(-0.1234567, 89.9999999, -11.25),
+            Point3D(179.9999999, -89.9999999, 8848.86),
+        ];
+        let (_, decoded) =
+            decode_flexible_polyline::<Point3D>(&encode_flexible_polyline(&points, header))
+                .unwrap();
+        assert_eq!(decoded, points);
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(
+            decode_polyline::<Point>("_p~iF~ps|", 5),
+            Err(S2JsonError::invalid_polyline(5, "unterminated value"))
+        );
+        assert_eq!(
+            decode_polyline::<Point>("_p~iF", 5),
+            Err(S2JsonError::invalid_polyline(5, "unterminated value"))
+        );
+        assert_eq!(
+            decode_polyline::<Point>("_p iF~ps|U", 5),
+            Err(S2JsonError::invalid_polyline(2, "invalid character ' '"))
+        );
+        assert_eq!(
+            decode_flexible_polyline::<Point3D>("CFoz5xJ67i1B"),
+            Err(S2JsonError::invalid_polyline(0, "unsupported version 2"))
+        );
+        assert_eq!(
+            decode_flexible_polyline::<Point3D>("BFoz5xJ67i1B~"),
+            Err(S2JsonError::invalid_polyline(12, "invalid character '~'"))
+        );
+        // third dimension 4 is reserved
+        assert_eq!(
+            decode_flexible_polyline::<Point3D>("BlC"),
+            Err(S2JsonError::invalid_polyline(1, "reserved third dimension 4"))
+        );
+        assert_eq!(
+            decode_polyline::<Point>("~~~~~~~~~~~~~~~~", 5),
+            Err(S2JsonError::invalid_polyline(0, "value overflows 64 bits"))
+        );
+    }
+}
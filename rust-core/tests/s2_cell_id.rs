@@ -0,0 +1,161 @@
+extern crate alloc;
+
+#[cfg(test)]
+mod tests {
+    use s2json_core::*;
+
+    #[test]
+    fn face_cells() {
+        for face in 0..6_u8 {
+            let cell = S2CellId::from_face(Face::from(face));
+            assert_eq!(cell.face(), Face::from(face));
+            assert_eq!(cell.level(), 0);
+            assert!(cell.is_face());
+            assert!(cell.is_valid());
+            assert!(!cell.is_leaf());
+            assert_eq!(cell.parent(), None);
+            assert_eq!(cell.id, ((face as u64) << 61) | (1 << 60));
+        }
+        assert_eq!(S2CellId::from_face(Face::Face0).to_token(), "1");
+        assert_eq!(S2CellId::from_face(Face::Face3).to_token(), "7");
+        assert_eq!(S2CellId::from_face(Face::Face5).to_token(), "b");
+    }
+
+    #[test]
+    fn from_points() {
+        let cell = S2CellId::from_lon_lat(0., 0.);
+        assert_eq!(cell.face(), Face::Face0);
+        assert_eq!(cell.level(), 30);
+        assert!(cell.is_leaf());
+        assert!(cell.is_valid());
+        assert_eq!(cell.to_face_ij(), (Face::Face0, 1 << 29, 1 << 29));
+        assert_eq!(cell.id, 0x1000000000000001);
+
+        let st = STPoint::<()> { face: Face::Face0, s: 0.5, t: 0.5, z: None, m: None };
+        assert_eq!(S2CellId::from_st_point(&st), cell);
+        assert_eq!(S2CellId::from(&st), cell);
+        assert_eq!(S2CellId::from_point(&VectorPoint::from_xyz(2., 0., 0.)), cell);
+        assert_eq!(S2CellId::from_lon_lat_point(&Point(0., 0.)), cell);
+
+        let cell = S2CellId::from_lon_lat(-73.9857, 40.7484);
+        let (lon, lat) = cell.to_lon_lat();
+        assert!((lon + 73.9857).abs() < 1e-7 && (lat - 40.7484).abs() < 1e-7);
+        let point = cell.to_point();
+        assert_eq!(S2CellId::from_point(&point), cell);
+        let st: STPoint<()> = cell.to_st_point();
+        assert_eq!(S2CellId::from_st_point(&st), cell);
+    }
+
+    #[test]
+    fn face_ij_round_trip() {
+        for face in 0..6_u8 {
+            let face = Face::from(face);
+            for (i, j) in [(0, 0), (1, 2), (123_456_789, 987_654_321), ((1 << 30) - 1, 0)] {
+                let cell = S2CellId::from_face_ij(face, i, j);
+                assert_eq!(cell.to_face_ij(), (face, i, j));
+                assert!(cell.is_leaf());
+            }
+        }
+    }
+
+    #[test]
+    fn hierarchy() {
+        let leaf = S2CellId::from_lon_lat(12.5, -33.3);
+        let cell = leaf.parent_at_level(10);
+        assert_eq!(cell.level(), 10);
+        assert!(cell.contains(&leaf));
+        assert!(!leaf.contains(&cell));
+        assert!(cell.intersects(&leaf));
+        assert_eq!(cell.parent().unwrap().level(), 9);
+        assert_eq!(cell.parent_at_level(12), cell);
+        assert_eq!(leaf.parent_at_level(0), S2CellId::from_face(leaf.face()));
+
+        let children = cell.children().unwrap();
+        for child in children {
+            assert_eq!(child.level(), 11);
+            assert_eq!(child.parent(), Some(cell));
+            assert!(cell.contains(&child));
+        }
+        assert!(children.windows(2).all(|w| w[0] < w[1]));
+        assert_eq!(children[0].range_min(), cell.range_min());
+        assert_eq!(children[3].range_max(), cell.range_max());
+        assert_eq!(children.iter().filter(|c| c.contains(&leaf)).count(), 1);
+        assert!(!children[0].intersects(&children[1]));
+        assert_eq!(leaf.children(), None);
+
+        assert!(cell.range_min().is_leaf() && cell.range_max().is_leaf());
+        assert_eq!(leaf.range_min(), leaf);
+        assert_eq!(leaf.range_max(), leaf);
+        assert_eq!(cell.pos(), cell.id & 0x1fffffffffffffff);
+    }
+
+    #[test]
+    fn tokens() {
+        let cell = S2CellId::from_lon_lat(151.2, -33.85).parent_at_level(12);
+        let token = cell.to_token();
+        assert!(!token.ends_with('0'));
+        assert_eq!(S2CellId::from_token(&token), Some(cell));
+        assert_eq!(alloc::format!("{cell}"), token);
+        assert_eq!(S2CellId::new(0).to_token(), "X");
+        assert_eq!(S2CellId::from_token("X"), Some(S2CellId::new(0)));
+        assert_eq!(S2CellId::from_token("3"), Some(S2CellId::new(0x3000000000000000)));
+        assert_eq!(S2CellId::from_token("zz"), None);
+        assert_eq!(S2CellId::from_token(""), None);
+        assert_eq!(S2CellId::from_token("11111111111111111"), None);
+
+        // the invalid id 0 has no level, and is its own range instead of underflowing
+        for token in ["0", "X"] {
+            let cell = S2CellId::from_token(token).unwrap();
+            assert!(!cell.is_valid());
+            assert_eq!(cell.range_min(), cell);
+            assert_eq!(cell.range_max(), cell);
+            assert!(cell.contains(&cell));
+            assert!(cell.intersects(&cell));
+        }
+    }
+
+    #[test]
+    fn neighbors() {
+        // face 0, zoom 1, bottom left tile
+        let cell = S2CellId::from_face_zoom_xy(Face::Face0, 1, 0, 0).unwrap();
+        let [bottom, right, top, left] = cell.neighbors();
+        assert_eq!(right.to_face_zoom_xy(), (Face::Face0, 1, 1, 0));
+        assert_eq!(top.to_face_zoom_xy(), (Face::Face0, 1, 0, 1));
+        assert_eq!(bottom.face(), Face::Face5);
+        assert_eq!(left.face(), Face::Face4);
+        for n in [bottom, right, top, left] {
+            assert_eq!(n.level(), 1);
+            assert!(n.neighbors().contains(&cell));
+        }
+
+        let cell = S2CellId::from_lon_lat(10., 10.).parent_at_level(8);
+        for n in cell.neighbors() {
+            assert_eq!(n.level(), 8);
+            assert_ne!(n, cell);
+            assert!(n.neighbors().contains(&cell));
+        }
+    }
+
+    #[test]
+    fn bbox() {
+        let cell = S2CellId::from_face_zoom_xy(Face::Face2, 3, 5, 2).unwrap();
+        assert_eq!(cell.level(), 3);
+        assert_eq!(cell.face(), Face::Face2);
+        assert_eq!(cell.to_face_zoom_xy(), (Face::Face2, 3, 5, 2));
+        let bbox = cell.to_st_bbox();
+        assert_eq!(bbox, BBox::from_st_zoom(5., 2., 3));
+        assert_eq!(bbox, BBox::new(0.625, 0.25, 0.75, 0.375));
+        assert_eq!(S2CellId::from_st_bbox(Face::Face2, &bbox), Some(cell));
+        let (_, s, t) = cell.to_face_st();
+        assert_eq!((s, t), (0.6875, 0.3125));
+
+        assert_eq!(
+            S2CellId::from_st_bbox(Face::Face0, &BBox::new(0., 0., 1., 1.)),
+            Some(S2CellId::from_face(Face::Face0))
+        );
+        assert_eq!(S2CellId::from_st_bbox(Face::Face0, &BBox::new(0., 0., 0.5, 0.25)), None);
+        assert_eq!(S2CellId::from_st_bbox(Face::Face0, &BBox::new(0.1, 0., 0.6, 0.5)), None);
+        assert_eq!(S2CellId::from_face_zoom_xy(Face::Face0, 2, 4, 0), None);
+        assert_eq!(S2CellId::from_face_zoom_xy(Face::Face0, 31, 0, 0), None);
+    }
+}
@@ -0,0 +1,202 @@
+extern crate alloc;
+
+#[cfg(test)]
+mod tests {
+    use alloc::{vec, vec::Vec};
+    use s2json_core::*;
+
+    #[test]
+    fn point_to_s2_and_back() {
+        let points = [(0., 0., Face::Face0), (90., 0., Face::Face1), (0., 90., Face::Face2)];
+        for (lon, lat, face) in points {
+            let feature: Feature = Feature::new(
+                Some(7),
+                Properties::from([("a".into(), 1_u64.into())]),
+                Geometry::Point(PointGeometry {
+                    _type: GeometryType::Point,
+                    coordinates: Point(lon, lat),
+                    ..Default::default()
+                }),
+                None,
+            );
+            let s2 = feature.to_s2_features();
+            assert_eq!(s2.len(), 1);
+            assert_eq!(s2[0]._type, VectorFeatureType::S2Feature);
+            assert_eq!(s2[0].face, face);
+            assert_eq!(s2[0].id, Some(7));
+            assert_eq!(s2[0].properties, feature.properties);
+            let point = s2[0].geometry.point().unwrap();
+            assert!((point.x - 0.5).abs() < 1e-9);
+            assert!((point.y - 0.5).abs() < 1e-9);
+
+            let back = s2[0].to_feature();
+            assert_eq!(back._type, FeatureType::Feature);
+            match back.geometry {
+                Geometry::Point(g) => {
+                    assert!((g.coordinates.0 - lon).abs() < 1e-9, "{:?}", g.coordinates);
+                    assert!((g.coordinates.1 - lat).abs() < 1e-9, "{:?}", g.coordinates);
+                }
+                _ => panic!("expected a point"),
+            }
+        }
+    }
+
+    #[test]
+    fn point3d_keeps_z_and_m() {
+        let feature: Feature<(), Properties, MValue> = Feature::new(
+            None,
+            Properties::default(),
+            Geometry::Point3D(Point3DGeometry {
+                _type: GeometryType::Point3D,
+                coordinates: Point3D(-120., 30., 12.),
+                m_values: Some(MValue::from([("t".into(), 2.5_f64.into())])),
+                bbox: None,
+            }),
+            None,
+        );
+        let s2 = feature.to_s2_features();
+        assert_eq!(s2.len(), 1);
+        let point = s2[0].geometry.point().unwrap();
+        assert_eq!(point.z, Some(12.));
+        assert_eq!(point.m, Some(MValue::from([("t".into(), 2.5_f64.into())])));
+
+        let back = s2[0].to_feature();
+        match back.geometry {
+            Geometry::Point3D(g) => {
+                assert!((g.coordinates.0 + 120.).abs() < 1e-9);
+                assert!((g.coordinates.1 - 30.).abs() < 1e-9);
+                assert_eq!(g.coordinates.2, 12.);
+                assert_eq!(g.m_values, Some(MValue::from([("t".into(), 2.5_f64.into())])));
+            }
+            _ => panic!("expected a 3D point"),
+        }
+    }
+
+    #[test]
+    fn linestring_split_across_faces() {
+        // crosses from face 0 into face 1 at lon 45
+        let feature: Feature<(), Properties, f64> = Feature::new(
+            None,
+            Properties::default(),
+            Geometry::LineString(LineStringGeometry {
+                _type: GeometryType::LineString,
+                coordinates: vec![Point(0., 0.), Point(90., 0.)],
+                m_values: Some(vec![0., 10.]),
+                bbox: None,
+            }),
+            None,
+        );
+        let s2 = feature.to_s2_features();
+        let faces: Vec<Face> = s2.iter().map(|f| f.face).collect();
+        assert_eq!(faces, vec![Face::Face0, Face::Face1]);
+
+        let line0 = s2[0].geometry.linestring().unwrap();
+        assert_eq!(line0.len(), 2);
+        assert!((line0[1].x - 1.).abs() < 1e-9);
+        assert!((line0[1].y - 0.5).abs() < 1e-9);
+        assert!((line0[1].m.unwrap() - 5.).abs() < 1e-9);
+        let line1 = s2[1].geometry.linestring().unwrap();
+        assert!(line1[0].x.abs() < 1e-9);
+        assert!((line1[0].y - 0.5).abs() < 1e-9);
+        assert!((line1[0].m.unwrap() - 5.).abs() < 1e-9);
+
+        let vec_bbox = s2[0].geometry.vec_bbox().unwrap();
+        assert!((vec_bbox.left - 0.5).abs() < 1e-9);
+        assert!((vec_bbox.right - 1.).abs() < 1e-9);
+
+        let back = s2[1].to_feature();
+        match back.geometry {
+            Geometry::LineString(g) => {
+                assert!((g.coordinates[0].0 - 45.).abs() < 1e-9);
+                assert!((g.coordinates[1].0 - 90.).abs() < 1e-9);
+                assert_eq!(g.m_values, Some(vec![5., 10.]));
+            }
+            _ => panic!("expected a linestring"),
+        }
+    }
+
+    #[test]
+    fn polygon_split_across_faces() {
+        let feature: Feature = Feature::new(
+            None,
+            Properties::default(),
+            Geometry::Polygon(PolygonGeometry {
+                _type: GeometryType::Polygon,
+                coordinates: vec![vec![
+                    Point(30., -10.),
+                    Point(60., -10.),
+                    Point(60., 10.),
+                    Point(30., 10.),
+                    Point(30., -10.),
+                ]],
+                m_values: None,
+                bbox: Some(BBox::new(30., -10., 60., 10.)),
+            }),
+            None,
+        );
+        let s2 = feature.to_s2_features();
+        assert_eq!(s2.len(), 2);
+        for f in &s2 {
+            let ring = &f.geometry.polygon().unwrap()[0];
+            assert!(ring.len() >= 4);
+            assert_eq!(ring.first(), ring.last());
+            for p in ring {
+                assert!((0. ..=1.).contains(&p.x) && (0. ..=1.).contains(&p.y));
+            }
+        }
+        let bbox0 = s2[0].geometry.bbox().unwrap();
+        assert!((bbox0.left - 30.).abs() < 1e-9);
+        assert!((bbox0.right - 45.).abs() < 1e-9);
+        let bbox1 = s2[1].geometry.bbox().unwrap();
+        assert!((bbox1.left - 45.).abs() < 1e-9);
+        assert!((bbox1.right - 60.).abs() < 1e-9);
+    }
+
+    #[test]
+    fn collection_to_s2_and_back() {
+        let mut collection: FeatureCollection = FeatureCollection::new(None);
+        collection.features.push(Features::Feature(Feature::new(
+            Some(1),
+            Properties::default(),
+            Geometry::MultiPoint(MultiPointGeometry {
+                _type: GeometryType::MultiPoint,
+                coordinates: vec![Point(0., 0.), Point(180., 0.), Point(1., 1.)],
+                ..Default::default()
+            }),
+            None,
+        )));
+        let s2 = collection.to_s2_collection();
+        assert_eq!(s2._type, S2FeatureCollectionType::S2FeatureCollection);
+        assert_eq!(s2.faces, vec![Face::Face0, Face::Face3]);
+        assert_eq!(s2.features.len(), 2);
+        assert_eq!(s2.features[0].geometry.multipoint().unwrap().len(), 2);
+
+        let back = s2.to_feature_collection();
+        assert_eq!(back.features.len(), 2);
+        match &back.features[1] {
+            Features::Feature(f) => match &f.geometry {
+                Geometry::MultiPoint(g) => {
+                    assert!((g.coordinates[0].0.abs() - 180.).abs() < 1e-9);
+                    assert!(g.coordinates[0].1.abs() < 1e-9);
+                }
+                _ => panic!("expected a multipoint"),
+            },
+            _ => panic!("expected a Feature"),
+        }
+    }
+
+    #[test]
+    fn vector_feature_to_s2() {
+        let wm: VectorFeature = VectorFeature::new_wm(
+            None,
+            Properties::default(),
+            VectorGeometry::new_point(VectorPoint::from_xy(0., 90.), None),
+            None,
+        );
+        let s2 = wm.to_s2_features();
+        assert_eq!(s2.len(), 1);
+        assert_eq!(s2[0].face, Face::Face2);
+        // already an S2Feature
+        assert_eq!(s2[0].to_s2_features(), s2);
+    }
+}
@@ -0,0 +1,150 @@
+#[cfg(test)]
+mod tests {
+    use s2json_core::*;
+
+    #[test]
+    fn uv_st() {
+        for (st, uv) in [(0., -1.), (0.5, 0.), (1., 1.)] {
+            assert!((quadratic_st_to_uv(st) - uv).abs() < 1e-12);
+            assert!((quadratic_uv_to_st(uv) - st).abs() < 1e-12);
+            assert!((tan_st_to_uv(st) - uv).abs() < 1e-12);
+            assert!((tan_uv_to_st(uv) - st).abs() < 1e-12);
+            assert!((st_to_uv(st) - uv).abs() < 1e-12);
+            assert!((uv_to_st(uv) - st).abs() < 1e-12);
+        }
+        for s in [0.1, 0.25, 0.6, 0.9] {
+            assert!((quadratic_uv_to_st(quadratic_st_to_uv(s)) - s).abs() < 1e-12);
+            assert!((tan_uv_to_st(tan_st_to_uv(s)) - s).abs() < 1e-12);
+        }
+        assert!((quadratic_st_to_uv(0.75) - 5. / 12.).abs() < 1e-12);
+    }
+
+    #[test]
+    fn lon_lat_xyz() {
+        let (x, y, z) = lon_lat_to_xyz(0., 0.);
+        assert!((x - 1.).abs() < 1e-12);
+        assert!(y.abs() < 1e-12);
+        assert!(z.abs() < 1e-12);
+        let (x, y, z) = lon_lat_to_xyz(90., 0.);
+        assert!(x.abs() < 1e-12);
+        assert!((y - 1.).abs() < 1e-12);
+        assert!(z.abs() < 1e-12);
+        let (x, y, z) = lon_lat_to_xyz(0., -90.);
+        assert!(x.abs() < 1e-12);
+        assert!(y.abs() < 1e-12);
+        assert!((z + 1.).abs() < 1e-12);
+
+        let (lon, lat) = xyz_to_lon_lat(0., 0., 2.);
+        assert_eq!((lon, lat), (0., 90.));
+        let (lon, lat) = xyz_to_lon_lat(-3., 0., 0.);
+        assert!((lon - 180.).abs() < 1e-12);
+        assert!(lat.abs() < 1e-12);
+
+        let (x, y, z) = lon_lat_to_xyz(-123.4, 45.6);
+        let (lon, lat) = xyz_to_lon_lat(x, y, z);
+        assert!((lon + 123.4).abs() < 1e-12);
+        assert!((lat - 45.6).abs() < 1e-12);
+    }
+
+    #[test]
+    fn faces() {
+        assert_eq!(get_face(1., 0., 0.), Face::Face0);
+        assert_eq!(get_face(0., 1., 0.), Face::Face1);
+        assert_eq!(get_face(0., 0., 1.), Face::Face2);
+        assert_eq!(get_face(-1., 0., 0.), Face::Face3);
+        assert_eq!(get_face(0., -1., 0.), Face::Face4);
+        assert_eq!(get_face(0., 0., -1.), Face::Face5);
+
+        for face in 0..6_u8 {
+            let face = Face::from(face);
+            for (u, v) in [(0., 0.), (0.3, -0.7), (-0.99, 0.99)] {
+                let (x, y, z) = face_uv_to_xyz(face, u, v);
+                assert_eq!(get_face(x, y, z), face);
+                let (f, u2, v2) = xyz_to_face_uv(x, y, z);
+                assert_eq!(f, face);
+                assert!((u - u2).abs() < 1e-12);
+                assert!((v - v2).abs() < 1e-12);
+                let (u2, v2) = face_xyz_to_uv(face, x * 2., y * 2., z * 2.);
+                assert!((u - u2).abs() < 1e-12);
+                assert!((v - v2).abs() < 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn face_st() {
+        for face in 0..6_u8 {
+            let face = Face::from(face);
+            for (s, t) in [(0.5, 0.5), (0.1, 0.8), (0.95, 0.05)] {
+                let (x, y, z) = face_st_to_xyz(face, s, t);
+                assert!((x * x + y * y + z * z - 1.).abs() < 1e-12);
+                let (f, s2, t2) = xyz_to_face_st(x, y, z);
+                assert_eq!(f, face);
+                assert!((s - s2).abs() < 1e-12);
+                assert!((t - t2).abs() < 1e-12);
+
+                let (lon, lat) = face_st_to_lon_lat(face, s, t);
+                let (f, s2, t2) = lon_lat_to_face_st(lon, lat);
+                assert_eq!(f, face);
+                assert!((s - s2).abs() < 1e-12);
+                assert!((t - t2).abs() < 1e-12);
+            }
+        }
+        assert_eq!(lon_lat_to_face_st(0., 0.), (Face::Face0, 0.5, 0.5));
+        assert_eq!(face_st_to_lon_lat(Face::Face2, 0.5, 0.5), (0., 90.));
+    }
+
+    #[test]
+    fn generic_points() {
+        let xyz: Point3D = point_to_xyz(&Point(90., 0.));
+        assert!(xyz.0.abs() < 1e-12);
+        assert!((xyz.1 - 1.).abs() < 1e-12);
+        assert!(xyz.2.abs() < 1e-12);
+        let lon_lat: Point = point_from_xyz(&xyz);
+        assert!((lon_lat.0 - 90.).abs() < 1e-12);
+        assert!(lon_lat.1.abs() < 1e-12);
+
+        let mut point = VectorPoint::new(-90., 0., Some(10.), Some(2_u32));
+        let face = point_to_st(&mut point);
+        assert_eq!(face, Face::Face4);
+        assert!((point.x - 0.5).abs() < 1e-12);
+        assert!((point.y - 0.5).abs() < 1e-12);
+        assert_eq!(point.z, Some(10.));
+        assert_eq!(point.m, Some(2));
+        point_from_st(face, &mut point);
+        assert!((point.x + 90.).abs() < 1e-12);
+        assert!(point.y.abs() < 1e-12);
+
+        let mut st: STPoint<()> =
+            STPoint { face: Face::Face1, s: 0.5, t: 0.5, z: Some(1.), m: None };
+        point_from_st(st.face, &mut st);
+        assert!((st.s - 90.).abs() < 1e-12);
+        assert!(st.t.abs() < 1e-12);
+    }
+
+    #[test]
+    fn st_point() {
+        let point = VectorPoint::new(10., 20., Some(3.), Some(1.5_f64));
+        let st = STPoint::from_lon_lat(&point);
+        assert_eq!(st.face, Face::Face0);
+        assert_eq!(st.z, Some(3.));
+        assert_eq!(st.m, Some(1.5));
+        let back = st.to_lon_lat();
+        assert!((back.x - 10.).abs() < 1e-12);
+        assert!((back.y - 20.).abs() < 1e-12);
+        assert_eq!(back.z, Some(3.));
+        assert_eq!(back.m, Some(1.5));
+
+        let xyz = st.to_xyz();
+        let st2: STPoint<f64> = STPoint::from_xyz(&xyz);
+        assert_eq!(st2.face, st.face);
+        assert!((st2.s - st.s).abs() < 1e-12);
+        assert!((st2.t - st.t).abs() < 1e-12);
+        assert_eq!(st2.z, None);
+
+        assert_eq!(st.x(), st.s);
+        assert_eq!(st.y(), st.t);
+        assert_eq!(st.z(), Some(3.));
+        assert_eq!(st.m(), Some(&1.5));
+    }
+}
@@ -0,0 +1,244 @@
+extern crate alloc;
+
+#[cfg(test)]
+mod tests {
+    use alloc::{vec, vec::Vec};
+    use s2json_core::*;
+
+    #[test]
+    fn douglas_peucker() {
+        let mut geometry: VectorGeometry = VectorGeometry::new_linestring(
+            vec![
+                VectorPoint::from_xy(0., 0.),
+                VectorPoint::from_xy(1., 0.1),
+                VectorPoint::from_xy(2., -0.1),
+                VectorPoint::from_xy(3., 5.),
+                VectorPoint::from_xy(4., 6.),
+                VectorPoint::from_xy(5., 7.),
+                VectorPoint::from_xy(6., 8.1),
+                VectorPoint::from_xy(7., 9.),
+            ],
+            None,
+        );
+        geometry.build_simplification(SimplifyMethod::DouglasPeucker);
+        let importance: Vec<Option<f64>> =
+            geometry.linestring().unwrap().iter().map(|p| p.t).collect();
+        assert_eq!(importance[0], Some(f64::INFINITY));
+        assert_eq!(importance[7], Some(f64::INFINITY));
+        assert!(importance.iter().all(Option::is_some));
+
+        let mut coarse = geometry.clone();
+        coarse.simplify(1.);
+        assert_eq!(
+            *coarse.linestring().unwrap(),
+            [
+                VectorPoint::from_xy(0., 0.),
+                VectorPoint::from_xy(2., -0.1),
+                VectorPoint::from_xy(3., 5.),
+                VectorPoint::from_xy(7., 9.)
+            ]
+        );
+        // a finer tolerance keeps more of the same pre-pass
+        let mut fine = geometry.clone();
+        fine.simplify(0.05);
+        assert!(fine.linestring().unwrap().len() > 4);
+        // nothing is dropped at zero
+        let mut all = geometry.clone();
+        all.simplify(0.);
+        assert_eq!(all.linestring().unwrap().len(), 8);
+        // an infinite tolerance keeps the endpoints
+        geometry.simplify(f64::INFINITY);
+        assert_eq!(
+            *geometry.linestring().unwrap(),
+            [VectorPoint::from_xy(0., 0.), VectorPoint::from_xy(7., 9.)]
+        );
+    }
+
+    #[test]
+    fn douglas_peucker_monotonic() {
+        // the point at (2, 3) is further from the segment of its parent than the parent itself
+        // is from the full line, so it inherits the parent's importance
+        let mut geometry: VectorGeometry = VectorGeometry::new_linestring(
+            vec![
+                VectorPoint::from_xy(0., 0.),
+                VectorPoint::from_xy(1., 1.),
+                VectorPoint::from_xy(2., 3.),
+                VectorPoint::from_xy(3., 1.),
+                VectorPoint::from_xy(10., 0.),
+            ],
+            None,
+        );
+        geometry.build_simplification(SimplifyMethod::DouglasPeucker);
+        let points = geometry.linestring().unwrap();
+        for point in &points[1..4] {
+            assert!(point.t.unwrap() <= points[2].t.unwrap());
+        }
+    }
+
+    #[test]
+    fn visvalingam() {
+        let mut geometry: VectorGeometry = VectorGeometry::new_multilinestring(
+            vec![
+                vec![
+                    VectorPoint::from_xy(0., 0.),
+                    VectorPoint::from_xy(1., 0.),
+                    VectorPoint::from_xy(2., 2.),
+                    VectorPoint::from_xy(3., 0.),
+                    VectorPoint::from_xy(4., 0.),
+                ],
+                vec![VectorPoint::from_xy(0., 0.), VectorPoint::from_xy(1., 1.)],
+            ],
+            None,
+        );
+        geometry.build_simplification(SimplifyMethod::Visvalingam);
+        let lines = geometry.multilinestring().unwrap();
+        let importance: Vec<f64> = lines[0].iter().map(|p| p.t.unwrap()).collect();
+        // the shoulders go first, each with an area of 1, then the peak with its area of 4
+        assert_eq!(importance, vec![f64::INFINITY, 1., 4., 1., f64::INFINITY]);
+        assert_eq!(lines[1][0].t, Some(f64::INFINITY));
+
+        geometry.simplify(2.);
+        let lines = geometry.multilinestring().unwrap();
+        assert_eq!(
+            lines[0],
+            [
+                VectorPoint::from_xy(0., 0.),
+                VectorPoint::from_xy(2., 2.),
+                VectorPoint::from_xy(4., 0.)
+            ]
+        );
+        assert_eq!(lines[1], [VectorPoint::from_xy(0., 0.), VectorPoint::from_xy(1., 1.)]);
+    }
+
+    #[test]
+    fn polygon_rings_stay_valid() {
+        let outer = vec![
+            VectorPoint::from_xy(0., 0.),
+            VectorPoint::from_xy(5., 0.1),
+            VectorPoint::from_xy(10., 0.),
+            VectorPoint::from_xy(10.1, 5.),
+            VectorPoint::from_xy(10., 10.),
+            VectorPoint::from_xy(5., 9.9),
+            VectorPoint::from_xy(0., 10.),
+            VectorPoint::from_xy(-0.1, 5.),
+            VectorPoint::from_xy(0., 0.),
+        ];
+        let hole = vec![
+            VectorPoint::from_xy(4., 4.),
+            VectorPoint::from_xy(4.1, 5.),
+            VectorPoint::from_xy(4., 6.),
+            VectorPoint::from_xy(6., 6.),
+            VectorPoint::from_xy(6., 4.),
+            VectorPoint::from_xy(4., 4.),
+        ];
+        // a distance for Douglas-Peucker, an area for Visvalingam
+        for (method, tolerance) in
+            [(SimplifyMethod::DouglasPeucker, 0.5), (SimplifyMethod::Visvalingam, 1.)]
+        {
+            let mut geometry: VectorGeometry =
+                VectorGeometry::new_polygon(vec![outer.clone(), hole.clone()], None);
+            geometry.build_simplification(method);
+            let mut simplified = geometry.clone();
+            simplified.simplify(tolerance);
+            let rings = simplified.polygon().unwrap();
+            assert_eq!(
+                rings[0],
+                [
+                    VectorPoint::from_xy(0., 0.),
+                    VectorPoint::from_xy(10., 0.),
+                    VectorPoint::from_xy(10., 10.),
+                    VectorPoint::from_xy(0., 10.),
+                    VectorPoint::from_xy(0., 0.)
+                ],
+                "{method:?}"
+            );
+            assert_eq!(
+                rings[1],
+                [
+                    VectorPoint::from_xy(4., 4.),
+                    VectorPoint::from_xy(4., 6.),
+                    VectorPoint::from_xy(6., 6.),
+                    VectorPoint::from_xy(6., 4.),
+                    VectorPoint::from_xy(4., 4.)
+                ]
+            );
+
+            // a huge tolerance still leaves a triangle in every ring
+            geometry.simplify(1e9);
+            for ring in geometry.polygon().unwrap() {
+                assert_eq!(ring.len(), 4);
+                assert_eq!((ring[0].x, ring[0].y), (ring[3].x, ring[3].y));
+            }
+        }
+
+        // open rings come back closed and rings too small to simplify are left alone
+        let mut geometry: VectorGeometry = VectorGeometry::new_multipolygon(
+            vec![
+                vec![vec![
+                    VectorPoint::from_xy(0., 0.),
+                    VectorPoint::from_xy(1., 0.),
+                    VectorPoint::from_xy(2., 0.01),
+                    VectorPoint::from_xy(2., 2.),
+                    VectorPoint::from_xy(0., 2.),
+                ]],
+                vec![vec![
+                    VectorPoint::from_xy(0., 0.),
+                    VectorPoint::from_xy(1., 1.),
+                    VectorPoint::from_xy(0., 0.),
+                ]],
+            ],
+            None,
+        );
+        geometry.build_simplification(SimplifyMethod::DouglasPeucker);
+        geometry.simplify(0.5);
+        let polygons = geometry.multipolygon().unwrap();
+        assert_eq!(
+            polygons[0][0],
+            [
+                VectorPoint::from_xy(0., 0.),
+                VectorPoint::from_xy(2., 0.01),
+                VectorPoint::from_xy(2., 2.),
+                VectorPoint::from_xy(0., 2.),
+                VectorPoint::from_xy(0., 0.)
+            ]
+        );
+        assert_eq!(
+            polygons[1][0],
+            [
+                VectorPoint::from_xy(0., 0.),
+                VectorPoint::from_xy(1., 1.),
+                VectorPoint::from_xy(0., 0.)
+            ]
+        );
+    }
+
+    #[test]
+    fn points_are_kept() {
+        let mut geometry: VectorGeometry = VectorGeometry::new_multipoint(
+            vec![
+                VectorPoint::from_xy(0., 0.),
+                VectorPoint::from_xy(0., 0.),
+                VectorPoint::from_xy(1., 1.),
+            ],
+            None,
+        );
+        geometry.build_simplification(SimplifyMethod::Visvalingam);
+        geometry.simplify(10.);
+        assert_eq!(geometry.multipoint().unwrap().len(), 3);
+        let mut geometry: VectorGeometry =
+            VectorGeometry::new_point(VectorPoint::from_xy(1., 2.), None);
+        geometry.build_simplification(SimplifyMethod::DouglasPeucker);
+        assert_eq!(geometry.point().unwrap().t, Some(f64::INFINITY));
+        // without a pre-pass every point is kept
+        let mut geometry: VectorGeometry = VectorGeometry::new_linestring(
+            vec![
+                VectorPoint::from_xy(0., 0.),
+                VectorPoint::from_xy(1., 0.),
+                VectorPoint::from_xy(2., 0.),
+            ],
+            None,
+        );
+        geometry.simplify(10.);
+        assert_eq!(geometry.linestring().unwrap().len(), 3);
+    }
+}
@@ -0,0 +1,197 @@
+extern crate alloc;
+
+#[cfg(test)]
+mod tests {
+    use alloc::{vec, vec::Vec};
+    use core::f64::consts::PI;
+    use s2json_core::*;
+
+    /// The summed area of the triangles over the vertices
+    fn triangles_area(vertices: &[f64], indices: &[usize], dim: usize) -> f64 {
+        indices
+            .chunks(3)
+            .map(|t| {
+                let (a, b, c) = (t[0] * dim, t[1] * dim, t[2] * dim);
+                let cross = (vertices[b] - vertices[a]) * (vertices[c + 1] - vertices[a + 1])
+                    - (vertices[c] - vertices[a]) * (vertices[b + 1] - vertices[a + 1]);
+                cross.abs() / 2.
+            })
+            .sum()
+    }
+
+    #[test]
+    fn earcut_square_with_hole() {
+        let data = [0., 0., 10., 0., 10., 10., 0., 10., 2., 2., 2., 8., 8., 8., 8., 2.];
+        let indices = earcut(&data, &[4], 2);
+        assert_eq!(indices.len(), 8 * 3);
+        assert_eq!(triangles_area(&data, &indices, 2), 100. - 36.);
+
+        // a single triangle, in either winding
+        assert_eq!(earcut(&[0., 0., 1., 0., 0., 1.], &[], 2).len(), 3);
+        assert_eq!(earcut(&[0., 0., 0., 1., 1., 0.], &[], 2).len(), 3);
+        // the extra dimension is skipped
+        let indices = earcut(&[0., 0., 5., 4., 0., 5., 4., 4., 5., 0., 4., 5.], &[], 3);
+        assert_eq!(indices.len(), 6);
+        assert!(indices.iter().all(|&i| i < 4));
+    }
+
+    #[test]
+    fn earcut_large() {
+        // a circle with a square hole is indexed on a z-order curve
+        let mut data = Vec::new();
+        for i in 0..200 {
+            let angle = 2. * PI * i as f64 / 200.;
+            data.extend([100. * angle.cos(), 100. * angle.sin()]);
+        }
+        data.extend([-10., -10., 10., -10., 10., 10., -10., 10.]);
+        let indices = earcut(&data, &[200], 2);
+        assert_eq!(indices.len(), (200 + 4) * 3);
+        let circle = 100. * 100. * 100. * (PI / 100.).sin();
+        assert!((triangles_area(&data, &indices, 2) - (circle - 400.)).abs() < 1e-6);
+    }
+
+    #[test]
+    fn earcut_degenerate() {
+        // nothing, a point and a line
+        assert!(earcut(&[], &[], 2).is_empty());
+        assert!(earcut(&[1., 1.], &[], 2).is_empty());
+        assert!(earcut(&[0., 0., 1., 1.], &[], 2).is_empty());
+        // collinear points enclose no area
+        assert!(earcut(&[0., 0., 1., 1., 2., 2., 3., 3.], &[], 2).is_empty());
+        // a closed ring with repeated points
+        let data = [0., 0., 4., 0., 4., 0., 4., 4., 0., 4., 0., 4., 0., 0.];
+        let indices = earcut(&data, &[], 2);
+        assert_eq!(indices.len(), 6);
+        assert_eq!(triangles_area(&data, &indices, 2), 16.);
+        // holes that are empty, a point or a line are ignored
+        let data = [0., 0., 4., 0., 4., 4., 0., 4., 1., 1., 2., 2., 3., 3.];
+        let indices = earcut(&data, &[4, 4, 5], 2);
+        assert_eq!(triangles_area(&data, &indices, 2), 16.);
+        // a trailing partial vertex, and holes past the end or out of order, are ignored
+        let data = [0., 0., 4., 0., 4., 4., 0., 4., 1.];
+        assert_eq!(earcut(&data, &[], 2).len(), 6);
+        assert_eq!(earcut(&data, &[4, 9, usize::MAX], 2).len(), 6);
+        assert_eq!(earcut(&data[..8], &[5, 4], 2).len(), 6);
+        assert_eq!(earcut(&[0., 0., 0., 4., 0., 0., 4., 4., 0., 1.], &[usize::MAX], 3).len(), 3);
+        assert_eq!(earcut_3d(&data, &[usize::MAX]).len(), 3);
+        // a self-intersecting bow tie still covers both halves
+        let data = [0., 0., 2., 2., 2., 0., 0., 2.];
+        let indices = earcut(&data, &[], 2);
+        assert!(!indices.is_empty());
+    }
+
+    #[test]
+    fn earcut_3d_planes() {
+        // a wall in the x-z plane
+        let wall = [0., 5., 0., 4., 5., 0., 4., 5., 3., 0., 5., 3.];
+        let indices = earcut_3d(&wall, &[]);
+        assert_eq!(indices.len(), 6);
+        // a wall in the y-z plane with a window
+        let wall = [
+            1., 0., 0., 1., 6., 0., 1., 6., 6., 1., 0., 6., 1., 2., 2., 1., 4., 2., 1., 4., 4., 1.,
+            2., 4.,
+        ];
+        let indices = earcut_3d(&wall, &[4]);
+        assert_eq!(indices.len(), 8 * 3);
+        // a sloped roof
+        let roof = [0., 0., 0., 4., 0., 0., 4., 4., 2., 0., 4., 2.];
+        assert_eq!(earcut_3d(&roof, &[]).len(), 6);
+        assert!(earcut_3d(&[], &[]).is_empty());
+    }
+
+    #[test]
+    fn tessellate_polygon() {
+        let mut geometry: VectorGeometry = VectorGeometry::new_polygon(
+            vec![
+                vec![
+                    VectorPoint::from_xy(0., 0.),
+                    VectorPoint::from_xy(10., 0.),
+                    VectorPoint::from_xy(10., 10.),
+                    VectorPoint::from_xy(0., 10.),
+                    VectorPoint::from_xy(0., 0.),
+                ],
+                vec![
+                    VectorPoint::from_xy(2., 2.),
+                    VectorPoint::from_xy(2., 8.),
+                    VectorPoint::from_xy(8., 8.),
+                    VectorPoint::from_xy(8., 2.),
+                    VectorPoint::from_xy(2., 2.),
+                ],
+            ],
+            None,
+        );
+        geometry.tessellate();
+        let VectorGeometry::Polygon(polygon) = &geometry else { panic!("expected a polygon") };
+        let tessellation = polygon.tessellation.as_ref().unwrap();
+        assert_eq!(
+            tessellation,
+            &vec![
+                0., 0., 10., 0., 10., 10., 0., 10., 0., 0., 2., 2., 2., 8., 8., 8., 8., 2., 2., 2.
+            ]
+        );
+        let indices: Vec<usize> =
+            polygon.indices.as_ref().unwrap().iter().map(|&i| i as usize).collect();
+        assert_eq!(indices.len(), 8 * 3);
+        assert_eq!(triangles_area(tessellation, &indices, 2), 64.);
+
+        // other geometries are untouched
+        let mut line: VectorGeometry = VectorGeometry::new_linestring(
+            vec![VectorPoint::from_xy(0., 0.), VectorPoint::from_xy(1., 1.)],
+            None,
+        );
+        let before = line.clone();
+        line.tessellate();
+        assert_eq!(line, before);
+    }
+
+    #[test]
+    fn tessellate_multipolygon() {
+        let mut geometry: VectorGeometry = VectorGeometry::new_multipolygon(
+            vec![
+                vec![vec![
+                    VectorPoint::from_xy(0., 0.),
+                    VectorPoint::from_xy(2., 0.),
+                    VectorPoint::from_xy(2., 2.),
+                    VectorPoint::from_xy(0., 2.),
+                ]],
+                // degenerate
+                vec![vec![VectorPoint::from_xy(5., 5.), VectorPoint::from_xy(6., 6.)]],
+                vec![vec![
+                    VectorPoint::from_xy(10., 0.),
+                    VectorPoint::from_xy(13., 0.),
+                    VectorPoint::from_xy(10., 3.),
+                ]],
+            ],
+            None,
+        );
+        geometry.tessellate();
+        let VectorGeometry::MultiPolygon(multi) = &geometry else { panic!("expected a multi") };
+        let tessellation = multi.tessellation.as_ref().unwrap();
+        assert_eq!(tessellation.len(), 9 * 2);
+        let indices: Vec<usize> =
+            multi.indices.as_ref().unwrap().iter().map(|&i| i as usize).collect();
+        assert_eq!(indices.len(), 3 * 3);
+        // the last triangle points at the last polygon
+        let mut last = indices[6..].to_vec();
+        last.sort();
+        assert_eq!(last, vec![6, 7, 8]);
+        assert_eq!(triangles_area(tessellation, &indices, 2), 4. + 4.5);
+    }
+
+    #[test]
+    fn tessellate_3d() {
+        let wall: Vec<VectorPoint> = [(0., 0.), (4., 0.), (4., 3.), (0., 3.)]
+            .iter()
+            .map(|&(x, z)| VectorPoint::from_xyz(x, 7., z))
+            .collect();
+        let mut geometry: VectorGeometry = VectorGeometry::new_polygon(vec![wall], None);
+        geometry.tessellate();
+        let VectorGeometry::Polygon(polygon) = &geometry else { panic!("expected a polygon") };
+        assert!(polygon.is_3d);
+        assert_eq!(
+            polygon.tessellation,
+            Some(vec![0., 7., 0., 4., 7., 0., 4., 7., 3., 0., 7., 3.])
+        );
+        assert_eq!(polygon.indices.as_ref().map(Vec::len), Some(6));
+    }
+}
@@ -0,0 +1,148 @@
+extern crate alloc;
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+    use s2json_core::*;
+
+    #[test]
+    fn wm_points() {
+        // lon-lat points are projected into the 0->1 space first
+        let mut london = VectorPoint::from_xy(-0.1276, 51.5072);
+        london.project(None);
+        let mut sydney = VectorPoint::from_xy(151.2093, -33.8688);
+        sydney.project(None);
+        let mut tiler: Tiler = Tiler::default();
+        for point in [&london, &sydney] {
+            let geometry = VectorGeometry::new_point(point.clone(), None);
+            tiler.insert(VectorFeature::new_wm(None, Properties::new(), geometry, None));
+        }
+
+        let root = tiler.get_tile(0.into(), 0, 0, 0).unwrap();
+        assert_eq!(root.features.len(), 2);
+        assert_eq!(root.bbox(), BBox::new(0., 0., 1., 1.));
+        // london is just west of the meridian in the northern hemisphere
+        let tile = tiler.get_tile(0.into(), 1, 0, 0).unwrap();
+        assert_eq!(tile.features.len(), 1);
+        assert_eq!(tile.features[0].geometry.point(), Some(&london));
+        assert_eq!((tile.face, tile.zoom, tile.x, tile.y), (0.into(), 1, 0, 0));
+        assert_eq!(tiler.get_tile(0.into(), 1, 1, 1).unwrap().features.len(), 1);
+        // london is close enough to the meridian to fall in the buffer of the eastern tile
+        assert_eq!(tiler.get_tile(0.into(), 1, 1, 0).unwrap().features.len(), 1);
+        assert!(tiler.get_tile(0.into(), 1, 0, 1).is_none());
+        // deeper tiles are cut from the nearest cut tile
+        let (x, y) = ((london.x * 1024.) as u32, (london.y * 1024.) as u32);
+        assert_eq!(tiler.get_tile(0.into(), 10, x, y).unwrap().features.len(), 1);
+        assert!(tiler.get_tile(0.into(), 10, x + 2, y).is_none());
+        // no S2 faces were given
+        assert!(tiler.get_tile(1.into(), 0, 0, 0).is_none());
+    }
+
+    #[test]
+    fn limits_and_buffer() {
+        // just right of the z1 tile boundary, inside the buffer of the tile on its left
+        let geometry = VectorGeometry::new_point(VectorPoint::from_xy(0.505, 0.25), None);
+        let feature: VectorFeature = VectorFeature::new_wm(None, Properties::new(), geometry, None);
+        let mut tiler: Tiler = Tiler::new(TilerOptions { max_zoom: 4, ..Default::default() });
+        tiler.insert(feature.clone());
+        assert!(tiler.get_tile(0.into(), 1, 0, 0).is_some());
+        assert!(tiler.get_tile(0.into(), 1, 1, 0).is_some());
+        assert!(tiler.get_tile(0.into(), 2, 1, 0).is_none());
+        // out of range
+        assert!(tiler.get_tile(0.into(), 5, 16, 8).is_none());
+        assert!(tiler.get_tile(0.into(), 1, 2, 0).is_none());
+        assert!(tiler.get_tile(0.into(), 4, 8, 4).is_some());
+
+        let mut tiler: Tiler = Tiler::new(TilerOptions { buffer: 0., ..Default::default() });
+        tiler.insert(feature);
+        assert!(tiler.get_tile(0.into(), 1, 0, 0).is_none());
+    }
+
+    #[test]
+    fn lines_are_clipped_and_simplified() {
+        // a zigzag across the world, 1/2500 from peak to trough
+        let line = (0..=100)
+            .map(|i| {
+                let y = 0.5 + if i % 2 == 0 { 0.0002 } else { -0.0002 };
+                VectorPoint::from_xy(0.005 + i as f64 * 0.0099, y)
+            })
+            .collect();
+        let geometry = VectorGeometry::new_linestring(line, None);
+        let feature: VectorFeature = VectorFeature::new_wm(None, Properties::new(), geometry, None);
+        let mut tiler: Tiler = Tiler::default();
+        tiler.insert(feature.clone());
+
+        // too small to see at zoom 0
+        let root = tiler.get_tile(0.into(), 0, 0, 0).unwrap();
+        assert_eq!(root.features[0].geometry.linestring().unwrap().len(), 2);
+        // but kept when zoomed in
+        let tile = tiler.get_tile(0.into(), 4, 8, 7).unwrap();
+        let VectorGeometry::LineString(line) = &tile.features[0].geometry else {
+            panic!("expected a line")
+        };
+        assert_eq!(line.coordinates.len(), 9);
+        let bbox = tile.bbox();
+        let buffer = 64. / 4096. / 16.;
+        for point in &line.coordinates {
+            assert!(point.x >= bbox.left - buffer && point.x <= bbox.right + buffer);
+        }
+        // the offset tracks where the clipped piece starts
+        assert!(line.offset.unwrap() > 0.49);
+
+        let mut tiler: Tiler = Tiler::new(TilerOptions { tolerance: 0., ..Default::default() });
+        tiler.insert(feature);
+        let root = tiler.get_tile(0.into(), 0, 0, 0).unwrap();
+        assert_eq!(root.features[0].geometry.linestring().unwrap().len(), 101);
+    }
+
+    #[test]
+    fn s2_faces() {
+        let feature: Feature = serde_json::from_str(
+            r#"{"type":"Feature","properties":{"name":"a"},"geometry":{"type":"Point","coordinates":[0.5,0.5]}}"#,
+        )
+        .unwrap();
+        let s2_features = feature.to_s2_features();
+        let mut tiler: Tiler = Tiler::default();
+        for feature in s2_features.iter() {
+            tiler.insert(feature.clone());
+        }
+        let s2_feature = &s2_features[0];
+        let point = s2_feature.geometry.point().unwrap();
+        let (x, y) = ((point.x * 32.) as u32, (point.y * 32.) as u32);
+        let tile = tiler.get_tile(s2_feature.face, 5, x, y).unwrap();
+        assert_eq!(tile.features.len(), 1);
+        assert_eq!(tile.features[0]._type, VectorFeatureType::S2Feature);
+        assert_eq!(tile.features[0].properties, s2_feature.properties);
+        let other_face = if s2_feature.face == 0.into() { 1.into() } else { 0.into() };
+        assert!(tiler.get_tile(other_face, 0, 0, 0).is_none());
+    }
+
+    #[test]
+    fn inserting_recuts_tiles() {
+        let mut tiler: Tiler = Tiler::default();
+        let geometry = VectorGeometry::new_point(VectorPoint::from_xy(0.25, 0.25), None);
+        tiler.insert(VectorFeature::new_wm(None, Properties::new(), geometry, None));
+        assert_eq!(tiler.get_tile(0.into(), 2, 1, 1).unwrap().features.len(), 1);
+        let geometry = VectorGeometry::new_point(VectorPoint::from_xy(0.26, 0.26), None);
+        tiler.insert(VectorFeature::new_wm(None, Properties::new(), geometry, None));
+        assert_eq!(tiler.get_tile(0.into(), 2, 1, 1).unwrap().features.len(), 2);
+
+        let polygon = vec![vec![
+            VectorPoint::from_xy(0.1, 0.1),
+            VectorPoint::from_xy(0.9, 0.1),
+            VectorPoint::from_xy(0.9, 0.9),
+            VectorPoint::from_xy(0.1, 0.1),
+        ]];
+        tiler.insert(VectorFeature::new_wm(
+            Some(7),
+            Properties::new(),
+            VectorGeometry::new_polygon(polygon, None),
+            None,
+        ));
+        let tile = tiler.get_tile(0.into(), 1, 1, 0).unwrap();
+        assert_eq!(tile.features.len(), 1);
+        assert_eq!(tile.features[0].id, Some(7));
+        let ring = &tile.features[0].geometry.polygon().unwrap()[0];
+        assert_eq!((ring[0].x, ring[0].y), (ring[ring.len() - 1].x, ring[ring.len() - 1].y));
+    }
+}
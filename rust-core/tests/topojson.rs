@@ -0,0 +1,172 @@
+extern crate alloc;
+
+#[cfg(test)]
+mod tests {
+    use alloc::{string::String, vec, vec::Vec};
+    use s2json_core::*;
+
+    fn geometries(collection: &FeatureCollection) -> Vec<Geometry> {
+        collection
+            .features
+            .iter()
+            .map(|feature| match feature {
+                Features::Feature(feature) => feature.geometry.clone(),
+                _ => panic!("expected a feature"),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn shared_arcs() {
+        // two squares sharing the edge x = 1, the second walks it in reverse
+        let json = r#"{
+            "type": "Topology",
+            "bbox": [0, 0, 2, 1],
+            "objects": {
+                "squares": {
+                    "type": "GeometryCollection",
+                    "geometries": [
+                        { "type": "Polygon", "id": 1, "arcs": [[0, 1]], "properties": { "name": "left" } },
+                        { "type": "Polygon", "id": "2", "arcs": [[2, -1]] },
+                        { "type": null }
+                    ]
+                },
+                "edge": { "type": "LineString", "arcs": [-2] }
+            },
+            "arcs": [
+                [[1, 0], [1, 1]],
+                [[1, 1], [0, 1], [0, 0], [1, 0]],
+                [[1, 0], [2, 0], [2, 1], [1, 1]]
+            ]
+        }"#;
+        let collections: Map<String, FeatureCollection> = read_topojson(json).unwrap();
+        assert_eq!(collections.keys().collect::<Vec<_>>(), vec!["edge", "squares"]);
+
+        let squares = &collections["squares"];
+        assert_eq!(squares.bbox, Some(BBox::new(0., 0., 2., 1.)));
+        assert_eq!(
+            geometries(squares),
+            vec![
+                Geometry::Polygon(PolygonGeometry {
+                    _type: GeometryType::Polygon,
+                    coordinates: vec![vec![
+                        Point(1., 0.),
+                        Point(1., 1.),
+                        Point(0., 1.),
+                        Point(0., 0.),
+                        Point(1., 0.),
+                    ]],
+                    ..Default::default()
+                }),
+                Geometry::Polygon(PolygonGeometry {
+                    _type: GeometryType::Polygon,
+                    coordinates: vec![vec![
+                        Point(1., 0.),
+                        Point(2., 0.),
+                        Point(2., 1.),
+                        Point(1., 1.),
+                        Point(1., 0.),
+                    ]],
+                    ..Default::default()
+                }),
+            ]
+        );
+        let Features::Feature(left) = &squares.features[0] else { panic!() };
+        assert_eq!(left.id, Some(1));
+        assert_eq!(left.properties, Properties::from([("name".into(), "left".into())]));
+        let Features::Feature(right) = &squares.features[1] else { panic!() };
+        assert_eq!(right.id, Some(2));
+        assert_eq!(right.properties, Properties::default());
+
+        assert_eq!(
+            geometries(&collections["edge"]),
+            vec![Geometry::LineString(LineStringGeometry {
+                _type: GeometryType::LineString,
+                coordinates: vec![Point(1., 0.), Point(0., 0.), Point(0., 1.), Point(1., 1.)],
+                ..Default::default()
+            })]
+        );
+    }
+
+    #[test]
+    fn quantized() {
+        // arcs are delta encoded, points are only transformed
+        let json = r#"{
+            "type": "Topology",
+            "transform": { "scale": [0.5, 2], "translate": [10, -10] },
+            "objects": {
+                "shapes": {
+                    "type": "GeometryCollection",
+                    "geometries": [
+                        { "type": "MultiPolygon", "arcs": [[[0]], [[1]]] },
+                        { "type": "MultiLineString", "arcs": [[0], [-2]] },
+                        { "type": "MultiPoint", "coordinates": [[2, 2], [4, 4]] },
+                        { "type": "GeometryCollection", "geometries": [
+                            { "type": "Point", "coordinates": [0, 0] }
+                        ] }
+                    ]
+                }
+            },
+            "arcs": [
+                [[0, 0], [2, 0], [0, 1], [-2, -1]],
+                [[4, 4], [2, 0], [-2, 0]]
+            ]
+        }"#;
+        let collections: Map<String, FeatureCollection> = read_topojson(json).unwrap();
+        let triangle = vec![Point(10., -10.), Point(11., -10.), Point(11., -8.), Point(10., -10.)];
+        let flat = vec![Point(12., -2.), Point(13., -2.), Point(12., -2.)];
+        assert_eq!(
+            geometries(&collections["shapes"]),
+            vec![
+                Geometry::MultiPolygon(MultiPolygonGeometry {
+                    _type: GeometryType::MultiPolygon,
+                    coordinates: vec![vec![triangle.clone()], vec![flat.clone()]],
+                    ..Default::default()
+                }),
+                Geometry::MultiLineString(MultiLineStringGeometry {
+                    _type: GeometryType::MultiLineString,
+                    coordinates: vec![triangle, flat.into_iter().rev().collect()],
+                    ..Default::default()
+                }),
+                Geometry::MultiPoint(MultiPointGeometry {
+                    _type: GeometryType::MultiPoint,
+                    coordinates: vec![Point(11., -6.), Point(12., -2.)],
+                    ..Default::default()
+                }),
+                Geometry::Point(PointGeometry {
+                    _type: GeometryType::Point,
+                    coordinates: Point(10., -10.),
+                    ..Default::default()
+                }),
+            ]
+        );
+    }
+
+    #[test]
+    fn errors() {
+        let read = |json: &str| read_topojson::<Properties>(json).map(|_| ());
+        assert_eq!(
+            read(r#"{ "type": "FeatureCollection", "features": [] }"#),
+            Err(S2JsonError::InvalidTopoJson("expected a Topology".into()))
+        );
+        assert_eq!(
+            read(r#"{ "type": "Topology", "objects": {}, "arcs": [[[0]]] }"#),
+            Err(S2JsonError::InvalidTopoJson("a position needs at least two numbers".into()))
+        );
+        assert_eq!(
+            read(
+                r#"{ "type": "Topology", "arcs": [],
+                "objects": { "a": { "type": "LineString", "arcs": [-1] } } }"#
+            ),
+            Err(S2JsonError::InvalidTopoJson("arc -1 does not exist".into()))
+        );
+        assert_eq!(
+            read(
+                r#"{ "type": "Topology", "arcs": [],
+                "objects": { "a": { "type": "Sphere" } } }"#
+            ),
+            Err(S2JsonError::InvalidTopoJson("unknown geometry type \"Sphere\"".into()))
+        );
+        assert!(matches!(read("{"), Err(S2JsonError::InvalidTopoJson(_))));
+    }
+}
@@ -0,0 +1,121 @@
+extern crate alloc;
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+    use s2json_core::*;
+
+    fn hex(hex: &str) -> Vec<u8> {
+        let hex: Vec<u8> = hex.bytes().filter(|b| !b.is_ascii_whitespace()).collect();
+        hex.as_chunks::<2>()
+            .0
+            .iter()
+            .map(|pair| u8::from_str_radix(core::str::from_utf8(pair).unwrap(), 16).unwrap())
+            .collect()
+    }
+
+    fn wkt(wkt: &str) -> Geometry {
+        Geometry::from_wkt(wkt).unwrap()
+    }
+
+    #[test]
+    fn spec_bytes() {
+        let point = wkt("POINT (1 2)");
+        let little = hex("01 01000000 000000000000F03F 0000000000000040");
+        let big = hex("00 00000001 3FF0000000000000 4000000000000000");
+        assert_eq!(point.to_wkb(WkbByteOrder::LittleEndian), little);
+        assert_eq!(point.to_wkb(WkbByteOrder::BigEndian), big);
+        assert_eq!(Geometry::from_wkb(&little), Ok(point.clone()));
+        assert_eq!(Geometry::from_wkb(&big), Ok(point.clone()));
+
+        // EWKB flags Z and the SRID in the high bits, ISO adds 1000 for Z
+        let point = wkt("POINT Z (1 2 3)");
+        let ewkb = hex("00 A0000001 000010E6 3FF0000000000000 4000000000000000 4008000000000000");
+        let iso = hex("01 E9030000 000000000000F03F 0000000000000040 0000000000000840");
+        assert_eq!(point.to_ewkb(WkbByteOrder::BigEndian, Some(4326)), ewkb);
+        assert_eq!(point.to_wkb(WkbByteOrder::LittleEndian), iso);
+        assert_eq!(Geometry::from_ewkb(&ewkb), Ok((Some(4326), point.clone())));
+        assert_eq!(Geometry::from_ewkb(&iso), Ok((None, point.clone())));
+        assert_eq!(Geometry::from_wkb(&ewkb), Ok(point));
+
+        // members of a multi geometry carry their own byte order
+        let mixed = hex("00 00000004 00000002
+             01 01000000 000000000000F03F 0000000000000040
+             00 00000001 4008000000000000 4010000000000000");
+        assert_eq!(Geometry::from_wkb(&mixed), Ok(wkt("MULTIPOINT (1 2, 3 4)")));
+    }
+
+    #[test]
+    fn round_trip() {
+        let cases = [
+            "POINT (1.5 -2)",
+            "POINT EMPTY",
+            "POINT M (1 2 3)",
+            "POINT ZM (1 2 3 4)",
+            "MULTIPOINT (0 0, 1 1)",
+            "LINESTRING EMPTY",
+            "LINESTRING Z (0 0 0, 1 1 1)",
+            "MULTILINESTRING M ((0 0 1, 1 1 2), (2 2 3, 3 3 4, 4 4 5))",
+            "POLYGON ((0 0, 4 0, 4 4, 0 0), (1 1, 2 1, 2 2, 1 1))",
+            "POLYGON ZM ((0 0 0 0, 4 0 0 1, 4 4 0 2, 0 0 0 3))",
+            "MULTIPOLYGON (((0 0, 1 0, 1 1, 0 0)), ((5 5, 6 5, 6 6, 5 5)))",
+            "MULTIPOLYGON Z (((0 0 1, 1 0 1, 1 1 1, 0 0 1)), EMPTY)",
+        ];
+        for case in cases {
+            let geometry = wkt(case);
+            for order in [WkbByteOrder::BigEndian, WkbByteOrder::LittleEndian] {
+                let back = Geometry::<MValue>::from_wkb(&geometry.to_wkb(order)).unwrap();
+                assert_eq!(back.to_wkt(), case);
+                let (srid, back) =
+                    Geometry::<MValue>::from_ewkb(&geometry.to_ewkb(order, Some(3857))).unwrap();
+                assert_eq!((srid, back.to_wkt().as_str()), (Some(3857), case));
+            }
+        }
+
+        let geometry: VectorGeometry =
+            VectorGeometry::from_wkt("LINESTRING ZM (0 1 2 3, 4 5 6 7)").unwrap();
+        let data = geometry.to_ewkb(WkbByteOrder::LittleEndian, None);
+        assert_eq!(VectorGeometry::from_ewkb(&data), Ok((None, geometry)));
+    }
+
+    #[test]
+    fn errors() {
+        let point = wkt("POINT (1 2)").to_wkb(WkbByteOrder::LittleEndian);
+        assert_eq!(Geometry::<MValue>::from_wkb(&[]), Err(S2JsonError::UnexpectedEof));
+        assert_eq!(
+            Geometry::<MValue>::from_wkb(&point[..point.len() - 1]),
+            Err(S2JsonError::UnexpectedEof)
+        );
+        assert_eq!(
+            Geometry::<MValue>::from_wkb(&hex("02 01000000")),
+            Err(S2JsonError::invalid_wkb(0, "unknown byte order 2"))
+        );
+        assert_eq!(
+            Geometry::<MValue>::from_wkb(&hex("01 07000000 00000000")),
+            Err(S2JsonError::invalid_wkb(1, "unsupported geometry type code 7"))
+        );
+        assert_eq!(
+            Geometry::<MValue>::from_wkb(&hex("01 A00F0000")),
+            Err(S2JsonError::invalid_wkb(1, "unsupported geometry type code 4000"))
+        );
+        let mut trailing = point.clone();
+        trailing.push(0);
+        assert_eq!(
+            Geometry::<MValue>::from_wkb(&trailing),
+            Err(S2JsonError::invalid_wkb(21, "unexpected trailing data"))
+        );
+        // a multi point member must be a point of the same dimension
+        let line = wkt("LINESTRING (0 0, 1 1)").to_wkb(WkbByteOrder::LittleEndian);
+        let mut multi = hex("01 04000000 01000000");
+        multi.extend(line);
+        assert_eq!(
+            Geometry::<MValue>::from_wkb(&multi),
+            Err(S2JsonError::invalid_wkb(9, "expected a Point member, found LineString"))
+        );
+        // a huge count on truncated data fails without allocating
+        assert_eq!(
+            Geometry::<MValue>::from_wkb(&hex("01 02000000 FFFFFFFF")),
+            Err(S2JsonError::UnexpectedEof)
+        );
+    }
+}
@@ -0,0 +1,171 @@
+extern crate alloc;
+
+#[cfg(test)]
+mod tests {
+    use alloc::{string::String, vec};
+    use s2json_core::*;
+
+    fn m(m: f64) -> MValue {
+        MValue::from([(WKT_M_KEY.into(), m.into())])
+    }
+
+    #[test]
+    fn parse_dimensions() {
+        let geometry: Geometry = Geometry::from_wkt("POINT (1 2)").unwrap();
+        assert_eq!(
+            geometry,
+            Geometry::Point(PointGeometry {
+                _type: GeometryType::Point,
+                coordinates: Point(1., 2.),
+                ..Default::default()
+            })
+        );
+        let geometry: Geometry = Geometry::from_wkt("point z(1 2 3)").unwrap();
+        assert_eq!(
+            geometry,
+            Geometry::Point3D(Point3DGeometry {
+                _type: GeometryType::Point3D,
+                coordinates: Point3D(1., 2., 3.),
+                ..Default::default()
+            })
+        );
+        let geometry: Geometry = Geometry::from_wkt("LINESTRING M (0 0 1.5, 1 1 -2)").unwrap();
+        assert_eq!(
+            geometry,
+            Geometry::LineString(LineStringGeometry {
+                _type: GeometryType::LineString,
+                coordinates: vec![Point(0., 0.), Point(1., 1.)],
+                m_values: Some(vec![m(1.5), m(-2.)]),
+                ..Default::default()
+            })
+        );
+        // untagged positions take their dimension from the ordinate count
+        let geometry: Geometry = Geometry::from_wkt("MULTIPOINT ((0 0 5 6), (1 1 7 8))").unwrap();
+        assert_eq!(
+            geometry,
+            Geometry::MultiPoint3D(MultiPoint3DGeometry {
+                _type: GeometryType::MultiPoint3D,
+                coordinates: vec![Point3D(0., 0., 5.), Point3D(1., 1., 7.)],
+                m_values: Some(vec![m(6.), m(8.)]),
+                ..Default::default()
+            })
+        );
+        let geometry: Geometry =
+            Geometry::from_wkt("MULTIPOLYGON ZM (((0 0 1 NaN, 4 0 1 2, 4 4 1 3, 0 0 1 4)), EMPTY)")
+                .unwrap();
+        assert_eq!(
+            geometry,
+            Geometry::MultiPolygon3D(MultiPolygon3DGeometry {
+                _type: GeometryType::MultiPolygon3D,
+                coordinates: vec![
+                    vec![vec![
+                        Point3D(0., 0., 1.),
+                        Point3D(4., 0., 1.),
+                        Point3D(4., 4., 1.),
+                        Point3D(0., 0., 1.),
+                    ]],
+                    vec![],
+                ],
+                // a NaN measure is a missing m-value
+                m_values: Some(vec![vec![vec![MValue::new(), m(2.), m(3.), m(4.)]], vec![]]),
+                ..Default::default()
+            })
+        );
+    }
+
+    #[test]
+    fn round_trip() {
+        let cases = [
+            "POINT (1.5 -2)",
+            "POINT EMPTY",
+            "POINT M (1 2 3)",
+            "MULTIPOINT (0 0, 1 1)",
+            "LINESTRING EMPTY",
+            "LINESTRING Z (0 0 0, 1 1 1)",
+            "MULTILINESTRING ((0 0, 1 1), (2 2, 3 3, 4 4))",
+            "POLYGON ((0 0, 4 0, 4 4, 0 0), (1 1, 2 1, 2 2, 1 1))",
+            "POLYGON ZM ((0 0 0 0, 4 0 0 1, 4 4 0 2, 0 0 0 3))",
+            "MULTIPOLYGON (((0 0, 1 0, 1 1, 0 0)), ((5 5, 6 5, 6 6, 5 5)))",
+            "MULTIPOLYGON Z EMPTY",
+        ];
+        for wkt in cases {
+            let geometry: Geometry = Geometry::from_wkt(wkt).unwrap();
+            assert_eq!(geometry.to_wkt(), wkt);
+        }
+        let geometry: Geometry = Geometry::from_wkt("POINT EMPTY").unwrap();
+        let Geometry::Point(point) = geometry else { panic!("expected a point") };
+        assert!(point.coordinates.0.is_nan() && point.coordinates.1.is_nan());
+    }
+
+    #[test]
+    fn ewkt() {
+        let (srid, geometry) =
+            Geometry::<MValue>::from_ewkt("SRID=4326;LINESTRINGM(0 0 1,1 1 2)").unwrap();
+        assert_eq!(srid, Some(4326));
+        assert_eq!(geometry.to_wkt(), "LINESTRING M (0 0 1, 1 1 2)");
+        assert_eq!(geometry.to_ewkt(Some(4326)), "SRID=4326;LINESTRINGM(0 0 1, 1 1 2)");
+        let (srid, geometry) = Geometry::<MValue>::from_ewkt("POINT(1 2 3)").unwrap();
+        assert_eq!(srid, None);
+        assert_eq!(geometry.to_ewkt(None), "POINT(1 2 3)");
+        assert_eq!(geometry.to_wkt(), "POINT Z (1 2 3)");
+        let geometry = Geometry::<MValue>::from_wkt("POLYGON EMPTY").unwrap();
+        assert_eq!(geometry.to_ewkt(Some(3857)), "SRID=3857;POLYGON EMPTY");
+        // plain WKT does not take an SRID
+        assert_eq!(
+            Geometry::<MValue>::from_wkt("SRID=4326;POINT(1 2)"),
+            Err(S2JsonError::invalid_wkt(0, "unsupported geometry \"SRID\""))
+        );
+    }
+
+    #[test]
+    fn vector_geometry() {
+        let geometry: VectorGeometry =
+            VectorGeometry::from_wkt("LINESTRING ZM (0 1 2 3, 4 5 6 7)").unwrap();
+        assert_eq!(
+            geometry,
+            VectorGeometry::LineString(VectorLineStringGeometry {
+                _type: VectorGeometryType::LineString,
+                is_3d: true,
+                coordinates: vec![
+                    VectorPoint::new(0., 1., Some(2.), Some(m(3.))),
+                    VectorPoint::new(4., 5., Some(6.), Some(m(7.))),
+                ],
+                ..Default::default()
+            })
+        );
+        let VectorGeometry::LineString(line) = &geometry else { panic!("expected a line") };
+        assert_eq!(line.coordinates[1].m, Some(m(7.)));
+        assert_eq!(geometry.to_wkt(), "LINESTRING ZM (0 1 2 3, 4 5 6 7)");
+
+        let point = VectorGeometry::<MValue>::new_point(VectorPoint::from_xy(1., 2.), None);
+        assert_eq!(point.to_ewkt(Some(4326)), "SRID=4326;POINT(1 2)");
+        let (srid, back) = VectorGeometry::<MValue>::from_ewkt("SRID=4326;POINT(1 2)").unwrap();
+        assert_eq!((srid, back), (Some(4326), point));
+    }
+
+    #[test]
+    fn errors() {
+        let cases: [(&str, usize, &str); 8] = [
+            ("", 0, "unsupported geometry \"\""),
+            ("GEOMETRYCOLLECTION EMPTY", 0, "unsupported geometry \"GEOMETRYCOLLECTION\""),
+            ("POINT (1 2", 10, "expected ')'"),
+            ("POINT (1 x)", 9, "expected a number, found \"x\""),
+            ("POINT Z (1 2)", 9, "expected 3 ordinates, found 2"),
+            ("LINESTRING (0 0, 1 1 1)", 17, "expected 2 ordinates, found 3"),
+            ("POINT (1)", 7, "expected 2 to 4 ordinates, found 1"),
+            ("POINT (1 2) POINT (3 4)", 12, "unexpected trailing input"),
+        ];
+        for (wkt, offset, message) in cases {
+            assert_eq!(
+                Geometry::<MValue>::from_wkt(wkt),
+                Err(S2JsonError::invalid_wkt(offset, message)),
+                "{wkt}"
+            );
+        }
+        let err = Geometry::<MValue>::from_ewkt("SRID=x;POINT(1 2)").unwrap_err();
+        assert_eq!(
+            String::from("invalid WKT at byte 5: expected an SRID, found \"x\""),
+            alloc::format!("{err}")
+        );
+    }
+}
@@ -0,0 +1,162 @@
+use crate::*;
+use alloc::{collections::BTreeMap, vec::Vec};
+
+/// The deepest zoom whose tile coordinates fit the tiler's arithmetic
+const MAX_TILE_ZOOM: u8 = 30;
+
+/// How a [`Tiler`] cuts and simplifies features
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TilerOptions {
+    /// The deepest zoom tiles are made for
+    pub max_zoom: u8,
+    /// How far features reach past each tile's edges, as a fraction of the tile's size
+    pub buffer: f64,
+    /// How much detail is dropped at each zoom, as a fraction of the tile's size. Douglas-Peucker
+    /// drops points closer than this to the simplified line, and Visvalingam drops points whose
+    /// triangles are smaller than its square. Zero keeps every point
+    pub tolerance: f64,
+    /// How the importance of each point is measured for simplification
+    pub simplify: SimplifyMethod,
+}
+impl Default for TilerOptions {
+    fn default() -> Self {
+        TilerOptions {
+            max_zoom: 14,
+            buffer: 64. / 4_096.,
+            tolerance: 3. / 4_096.,
+            simplify: SimplifyMethod::DouglasPeucker,
+        }
+    }
+}
+
+/// The features of a tile, clipped to its buffered bounds and simplified for its zoom
+#[derive(Debug, Clone, PartialEq)]
+pub struct Tile<M = (), P: Clone + Default = Properties, D: Clone + Default = MValue> {
+    /// The face of the tile. Always 0 for WM tiles
+    pub face: Face,
+    /// The zoom of the tile
+    pub zoom: u8,
+    /// The x (WM) or s (S2) position of the tile
+    pub x: u32,
+    /// The y (WM) or t (S2) position of the tile
+    pub y: u32,
+    /// The features of the tile
+    pub features: Vec<VectorFeature<M, P, D>>,
+}
+impl<M, P: Clone + Default, D: Clone + Default> Tile<M, P, D> {
+    /// The bounds of the tile in the 0->1 space of its face, without the buffer
+    pub fn bbox(&self) -> BBox {
+        BBox::from_st_zoom(self.x as f64, self.y as f64, self.zoom)
+    }
+}
+
+/// A tile's face, zoom, x and y
+type TileKey = (Face, u8, u32, u32);
+
+/// # Tiler
+///
+/// ## Description
+///
+/// Splits [`VectorFeature`]s into quad-tree tiles on demand.
+///
+/// Features must already be in the 0->1 space of a tile pyramid: WM features projected with
+/// [`VectorPoint::project`] or S2Features in the s-t space of their face. WM features all live on
+/// face 0. Both share one code path, with tile bounds given by [`BBox::from_st_zoom`].
+///
+/// Nothing is cut until a tile is asked for. The tiler then clips the features of the nearest
+/// tile it already holds down to the requested one, keeping every tile along the way so that
+/// neighbouring requests start closer. Simplification is measured once per feature when it is
+/// inserted and applied at each tile's zoom as it is handed out.
+///
+/// ## Usage
+///
+/// - [`Tiler::new`]: Create a tiler with the given options
+/// - [`Tiler::insert`]: Add a feature
+/// - [`Tiler::get_tile`]: Get the features of a tile
+#[derive(Debug, Clone)]
+pub struct Tiler<M = (), P: Clone + Default = Properties, D: Clone + Default = MValue> {
+    options: TilerOptions,
+    /// The features of every tile reached so far, clipped but not simplified
+    tiles: BTreeMap<TileKey, Vec<VectorFeature<M, P, D>>>,
+}
+impl<M: Clone, P: Clone + Default, D: Clone + Default + Interpolate> Tiler<M, P, D> {
+    /// Create a tiler with the given options
+    pub fn new(options: TilerOptions) -> Self {
+        Tiler { options, tiles: BTreeMap::new() }
+    }
+
+    /// Add a feature to the root tile of its face. Tiles of that face cut before are discarded
+    /// and cut again when next asked for
+    pub fn insert(&mut self, feature: VectorFeature<M, P, D>) {
+        let face =
+            if feature._type == VectorFeatureType::S2Feature { feature.face } else { Face::Face0 };
+        let mut geometry = feature.geometry.clone();
+        geometry.build_simplification(self.options.simplify);
+        let root = BBox::from_st_zoom(0., 0., 0);
+        let Some(geometry) = geometry.clip_bbox(&root, Some(self.options.buffer)) else { return };
+
+        self.tiles.retain(|&(tile_face, zoom, _, _), _| tile_face != face || zoom == 0);
+        self.tiles
+            .entry((face, 0, 0, 0))
+            .or_default()
+            .push(VectorFeature::from_vector_feature(&feature, Some(geometry)));
+    }
+
+    /// Get the features of a tile, clipped to its bounds grown by the buffer and simplified for
+    /// its zoom. Returns None if the tile holds no features, is deeper than the maximum zoom or
+    /// lies outside its zoom's range
+    pub fn get_tile(&mut self, face: Face, zoom: u8, x: u32, y: u32) -> Option<Tile<M, P, D>> {
+        if zoom > self.options.max_zoom.min(MAX_TILE_ZOOM) || x >> zoom != 0 || y >> zoom != 0 {
+            return None;
+        }
+        let key = |level: u8| (face, level, x >> (zoom - level), y >> (zoom - level));
+
+        // walk up to the nearest tile cut so far
+        let mut level = zoom;
+        while !self.tiles.contains_key(&key(level)) {
+            level = level.checked_sub(1)?;
+        }
+        // and cut down from it
+        while level < zoom {
+            let parent = key(level);
+            level += 1;
+            let (_, _, cx, cy) = key(level);
+            let bbox = BBox::from_st_zoom(cx as f64, cy as f64, level);
+            let buffer = self.options.buffer / (1u64 << level) as f64;
+            let features = self.tiles[&parent]
+                .iter()
+                .filter_map(|feature| {
+                    let geometry = feature.geometry.clip_bbox(&bbox, Some(buffer))?;
+                    Some(VectorFeature::from_vector_feature(feature, Some(geometry)))
+                })
+                .collect();
+            self.tiles.insert(key(level), features);
+        }
+
+        let features = &self.tiles[&key(zoom)];
+        if features.is_empty() {
+            return None;
+        }
+        let tolerance = self.options.tolerance / (1u64 << zoom) as f64;
+        let tolerance = match self.options.simplify {
+            SimplifyMethod::DouglasPeucker => tolerance,
+            SimplifyMethod::Visvalingam => tolerance * tolerance,
+        };
+        let features = features
+            .iter()
+            .map(|feature| {
+                let mut feature = feature.clone();
+                if tolerance > 0. {
+                    feature.geometry.simplify(tolerance);
+                }
+                feature
+            })
+            .collect();
+        Some(Tile { face, zoom, x, y, features })
+    }
+}
+impl<M: Clone, P: Clone + Default, D: Clone + Default + Interpolate> Default for Tiler<M, P, D> {
+    fn default() -> Self {
+        Tiler::new(TilerOptions::default())
+    }
+}
@@ -0,0 +1,246 @@
+use crate::*;
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use serde::de::DeserializeOwned;
+use serde_json::Value as JsonValue;
+
+fn invalid(message: impl Into<String>) -> S2JsonError {
+    S2JsonError::InvalidTopoJson(message.into())
+}
+
+/// The quantization transform of a topology
+struct Transform {
+    scale: (f64, f64),
+    translate: (f64, f64),
+}
+impl Transform {
+    fn parse(transform: &JsonValue) -> Result<Self, S2JsonError> {
+        let pair = |key: &str| {
+            let pair = numbers(transform.get(key), key)?;
+            match pair[..] {
+                [a, b] => Ok((a, b)),
+                _ => Err(invalid(format!("transform {key} must have two numbers"))),
+            }
+        };
+        Ok(Transform { scale: pair("scale")?, translate: pair("translate")? })
+    }
+
+    fn apply(&self, x: f64, y: f64) -> Point {
+        Point(x * self.scale.0 + self.translate.0, y * self.scale.1 + self.translate.1)
+    }
+}
+
+fn numbers(value: Option<&JsonValue>, what: &str) -> Result<Vec<f64>, S2JsonError> {
+    value
+        .and_then(JsonValue::as_array)
+        .and_then(|list| list.iter().map(JsonValue::as_f64).collect())
+        .ok_or_else(|| invalid(format!("{what} must be a list of numbers")))
+}
+
+fn list<'a>(value: Option<&'a JsonValue>, what: &str) -> Result<&'a [JsonValue], S2JsonError> {
+    value
+        .and_then(JsonValue::as_array)
+        .map(Vec::as_slice)
+        .ok_or_else(|| invalid(format!("{what} must be a list")))
+}
+
+/// Resolves arc references and positions of a topology into coordinates
+struct TopologyReader {
+    transform: Option<Transform>,
+    /// Arcs decoded into absolute, untransformed coordinates
+    arcs: Vec<Vec<Point>>,
+}
+impl TopologyReader {
+    fn new(topology: &JsonValue) -> Result<Self, S2JsonError> {
+        let transform = match topology.get("transform") {
+            Some(transform) => Some(Transform::parse(transform)?),
+            None => None,
+        };
+        let arcs = list(topology.get("arcs"), "arcs")?
+            .iter()
+            .map(|arc| {
+                let (mut x, mut y) = (0., 0.);
+                list(Some(arc), "an arc")?
+                    .iter()
+                    .map(|position| match (&transform, self::position(position)?) {
+                        // quantized arcs are delta encoded
+                        (Some(transform), (dx, dy)) => {
+                            x += dx;
+                            y += dy;
+                            Ok(transform.apply(x, y))
+                        }
+                        (None, (x, y)) => Ok(Point(x, y)),
+                    })
+                    .collect()
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(TopologyReader { transform, arcs })
+    }
+
+    /// A point of a Point or MultiPoint geometry, which is quantized but not delta encoded
+    fn point(&self, position: &JsonValue) -> Result<Point, S2JsonError> {
+        let (x, y) = self::position(position)?;
+        Ok(match &self.transform {
+            Some(transform) => transform.apply(x, y),
+            None => Point(x, y),
+        })
+    }
+
+    /// Join arcs into a line. Each arc starts where the last one ended, so the shared point is
+    /// kept once. A negative index `!i` is arc `i` reversed
+    fn line(&self, arcs: &JsonValue) -> Result<LineString, S2JsonError> {
+        let mut line: LineString = Vec::new();
+        for index in list(Some(arcs), "arc indices")? {
+            let index = index.as_i64().ok_or_else(|| invalid("arc indices must be integers"))?;
+            let (arc_index, reversed) = if index < 0 { (!index, true) } else { (index, false) };
+            let arc = usize::try_from(arc_index)
+                .ok()
+                .and_then(|i| self.arcs.get(i))
+                .ok_or_else(|| invalid(format!("arc {index} does not exist")))?;
+            let mut points = arc.clone();
+            if reversed {
+                points.reverse();
+            }
+            let skip = if line.is_empty() { 0 } else { 1 };
+            line.extend(points.into_iter().skip(skip));
+        }
+        Ok(line)
+    }
+
+    fn lines(&self, arcs: &JsonValue) -> Result<Vec<LineString>, S2JsonError> {
+        list(Some(arcs), "arc indices")?.iter().map(|arcs| self.line(arcs)).collect()
+    }
+
+    fn geometry<D: Clone + Default>(
+        &self,
+        _type: &str,
+        object: &JsonValue,
+    ) -> Result<Geometry<D>, S2JsonError> {
+        let arcs = || object.get("arcs").ok_or_else(|| invalid(format!("{_type} has no arcs")));
+        let coordinates = || {
+            object.get("coordinates").ok_or_else(|| invalid(format!("{_type} has no coordinates")))
+        };
+        Ok(match _type {
+            "Point" => Geometry::Point(PointGeometry {
+                _type: GeometryType::Point,
+                coordinates: self.point(coordinates()?)?,
+                ..Default::default()
+            }),
+            "MultiPoint" => Geometry::MultiPoint(MultiPointGeometry {
+                _type: GeometryType::MultiPoint,
+                coordinates: list(Some(coordinates()?), "coordinates")?
+                    .iter()
+                    .map(|position| self.point(position))
+                    .collect::<Result<_, _>>()?,
+                ..Default::default()
+            }),
+            "LineString" => Geometry::LineString(LineStringGeometry {
+                _type: GeometryType::LineString,
+                coordinates: self.line(arcs()?)?,
+                ..Default::default()
+            }),
+            "MultiLineString" => Geometry::MultiLineString(MultiLineStringGeometry {
+                _type: GeometryType::MultiLineString,
+                coordinates: self.lines(arcs()?)?,
+                ..Default::default()
+            }),
+            "Polygon" => Geometry::Polygon(PolygonGeometry {
+                _type: GeometryType::Polygon,
+                coordinates: self.lines(arcs()?)?,
+                ..Default::default()
+            }),
+            "MultiPolygon" => Geometry::MultiPolygon(MultiPolygonGeometry {
+                _type: GeometryType::MultiPolygon,
+                coordinates: list(Some(arcs()?), "arc indices")?
+                    .iter()
+                    .map(|polygon| self.lines(polygon))
+                    .collect::<Result<_, _>>()?,
+                ..Default::default()
+            }),
+            _ => return Err(invalid(format!("unknown geometry type \"{_type}\""))),
+        })
+    }
+
+    /// Push the features of an object, flattening geometry collections
+    fn collect<P: Clone + Default + DeserializeOwned>(
+        &self,
+        object: &JsonValue,
+        features: &mut Vec<Features<(), P>>,
+    ) -> Result<(), S2JsonError> {
+        let _type = match object.get("type") {
+            Some(JsonValue::String(_type)) => _type.as_str(),
+            // a null geometry has no feature to hold
+            Some(JsonValue::Null) => return Ok(()),
+            _ => return Err(invalid("geometry objects must have a type")),
+        };
+        if _type == "GeometryCollection" {
+            for geometry in list(object.get("geometries"), "geometries")? {
+                self.collect(geometry, features)?;
+            }
+            return Ok(());
+        }
+
+        let geometry = self.geometry(_type, object)?;
+        let id = match object.get("id") {
+            Some(JsonValue::Number(id)) => id.as_u64(),
+            Some(JsonValue::String(id)) => id.parse().ok(),
+            _ => None,
+        };
+        let properties = match object.get("properties") {
+            Some(properties) if !properties.is_null() => {
+                serde_json::from_value(properties.clone()).map_err(|e| invalid(e.to_string()))?
+            }
+            _ => P::default(),
+        };
+        features.push(Features::Feature(Feature::new(id, properties, geometry, None)));
+        Ok(())
+    }
+}
+
+fn position(position: &JsonValue) -> Result<(f64, f64), S2JsonError> {
+    let ordinates = numbers(Some(position), "a position")?;
+    match ordinates[..] {
+        [x, y, ..] => Ok((x, y)),
+        _ => Err(invalid("a position needs at least two numbers")),
+    }
+}
+
+/// Read a TopoJSON topology into a [`FeatureCollection`] per named object.
+///
+/// Arcs are decoded through the topology's quantization transform, and the arcs of each line and
+/// ring are stitched back together. Geometry collections are flattened into their object's
+/// collection, and geometries with a null type are skipped. Object properties become
+/// [`Properties`], and numeric ids become feature ids. Positions are read as 2D
+pub fn read_topojson<P: Clone + Default + DeserializeOwned>(
+    json: &str,
+) -> Result<Map<String, FeatureCollection<(), P>>, S2JsonError> {
+    let topology: JsonValue = serde_json::from_str(json).map_err(|e| invalid(e.to_string()))?;
+    if topology.get("type").and_then(JsonValue::as_str) != Some("Topology") {
+        return Err(invalid("expected a Topology"));
+    }
+    let bbox = match topology.get("bbox") {
+        Some(bbox) => match numbers(Some(bbox), "bbox")?[..] {
+            [left, bottom, right, top] => Some(BBox::new(left, bottom, right, top)),
+            [left, bottom, _, right, top, _] => Some(BBox::new(left, bottom, right, top)),
+            _ => return Err(invalid("bbox must have four or six numbers")),
+        },
+        None => None,
+    };
+    let reader = TopologyReader::new(&topology)?;
+    let objects = topology
+        .get("objects")
+        .and_then(JsonValue::as_object)
+        .ok_or_else(|| invalid("objects must be an object"))?;
+
+    let mut collections = Map::new();
+    for (name, object) in objects {
+        let mut collection = FeatureCollection::new(None);
+        collection.bbox = bbox;
+        reader.collect(object, &mut collection.features)?;
+        collections.insert(name.clone(), collection);
+    }
+    Ok(collections)
+}
@@ -122,3 +122,8 @@ impl JSONPropertiesCompatible for JSONProperties {}
 
 /// Shape of the restricted Mapbox properties object
 pub type MapboxProperties = ValuePrimitive;
+impl From<&MapboxProperties> for MapboxProperties {
+    fn from(properties: &MapboxProperties) -> MapboxProperties {
+        properties.clone()
+    }
+}
@@ -0,0 +1,325 @@
+use crate::{
+    wkt::{Nested, build_geometry, split_geometry},
+    *,
+};
+use alloc::{format, vec::Vec};
+
+/// EWKB flag for a Z ordinate
+const EWKB_Z: u32 = 0x8000_0000;
+/// EWKB flag for an M ordinate
+const EWKB_M: u32 = 0x4000_0000;
+/// EWKB flag for an SRID following the type code
+const EWKB_SRID: u32 = 0x2000_0000;
+
+/// The byte order of well-known binary
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum WkbByteOrder {
+    /// Most significant byte first (XDR)
+    BigEndian,
+    /// Least significant byte first (NDR)
+    #[default]
+    LittleEndian,
+}
+
+fn type_code(kind: VectorGeometryType) -> u32 {
+    match kind {
+        VectorGeometryType::Point => 1,
+        VectorGeometryType::LineString => 2,
+        VectorGeometryType::Polygon => 3,
+        VectorGeometryType::MultiPoint => 4,
+        VectorGeometryType::MultiLineString => 5,
+        VectorGeometryType::MultiPolygon => 6,
+    }
+}
+
+/// The geometry type of the members of a multi geometry
+fn member_kind(kind: VectorGeometryType) -> Option<VectorGeometryType> {
+    match kind {
+        VectorGeometryType::MultiPoint => Some(VectorGeometryType::Point),
+        VectorGeometryType::MultiLineString => Some(VectorGeometryType::LineString),
+        VectorGeometryType::MultiPolygon => Some(VectorGeometryType::Polygon),
+        _ => None,
+    }
+}
+
+fn list(nested: &Nested) -> &[Nested] {
+    match nested {
+        Nested::List(list) => list,
+        Nested::Position(_) => &[],
+    }
+}
+
+// Writing
+
+struct WkbWriter {
+    out: Vec<u8>,
+    order: WkbByteOrder,
+    ewkb: bool,
+}
+impl WkbWriter {
+    fn u32(&mut self, value: u32) {
+        match self.order {
+            WkbByteOrder::BigEndian => self.out.extend(value.to_be_bytes()),
+            WkbByteOrder::LittleEndian => self.out.extend(value.to_le_bytes()),
+        }
+    }
+
+    fn f64(&mut self, value: f64) {
+        match self.order {
+            WkbByteOrder::BigEndian => self.out.extend(value.to_be_bytes()),
+            WkbByteOrder::LittleEndian => self.out.extend(value.to_le_bytes()),
+        }
+    }
+
+    fn position(&mut self, nested: &Nested, dims: usize) {
+        let Nested::Position(position) = nested else { return };
+        for i in 0..dims {
+            self.f64(position.get(i).copied().unwrap_or(f64::NAN));
+        }
+    }
+
+    fn positions(&mut self, nested: &Nested, dims: usize) {
+        let positions = list(nested);
+        self.u32(positions.len() as u32);
+        for position in positions {
+            self.position(position, dims);
+        }
+    }
+
+    fn geometry(
+        &mut self,
+        kind: VectorGeometryType,
+        has_z: bool,
+        has_m: bool,
+        srid: Option<u32>,
+        nested: &Nested,
+    ) {
+        self.out.push((self.order == WkbByteOrder::LittleEndian) as u8);
+        let code = type_code(kind);
+        if self.ewkb {
+            let mut code = code;
+            if has_z {
+                code |= EWKB_Z;
+            }
+            if has_m {
+                code |= EWKB_M;
+            }
+            if srid.is_some() {
+                code |= EWKB_SRID;
+            }
+            self.u32(code);
+            if let Some(srid) = srid {
+                self.u32(srid);
+            }
+        } else {
+            self.u32(code + 1_000 * has_z as u32 + 2_000 * has_m as u32);
+        }
+
+        let dims = 2 + has_z as usize + has_m as usize;
+        match kind {
+            VectorGeometryType::Point => self.position(nested, dims),
+            VectorGeometryType::LineString => self.positions(nested, dims),
+            VectorGeometryType::Polygon => {
+                let rings = list(nested);
+                self.u32(rings.len() as u32);
+                for ring in rings {
+                    self.positions(ring, dims);
+                }
+            }
+            _ => {
+                let members = list(nested);
+                self.u32(members.len() as u32);
+                for member in members {
+                    let member_kind = member_kind(kind).unwrap_or_default();
+                    self.geometry(member_kind, has_z, has_m, None, member);
+                }
+            }
+        }
+    }
+}
+
+fn write<D: MValueCompatible>(
+    geometry: &Geometry<D>,
+    order: WkbByteOrder,
+    ewkb: bool,
+    srid: Option<u32>,
+) -> Vec<u8> {
+    let (_type, has_m, nested) = split_geometry(geometry);
+    let mut writer = WkbWriter { out: Vec::new(), order, ewkb };
+    writer.geometry(_type.into(), _type.is_3d(), has_m, srid, &nested);
+    writer.out
+}
+
+// Reading
+
+/// A geometry header: its type, dimensions and SRID
+type Header = (VectorGeometryType, bool, bool, Option<u32>);
+
+struct WkbReader<'a> {
+    data: &'a [u8],
+    pos: usize,
+    order: WkbByteOrder,
+}
+impl WkbReader<'_> {
+    fn take<const N: usize>(&mut self) -> Result<[u8; N], S2JsonError> {
+        let bytes = self.data.get(self.pos..self.pos + N).ok_or(S2JsonError::UnexpectedEof)?;
+        self.pos += N;
+        Ok(bytes.try_into().unwrap_or([0; N]))
+    }
+
+    fn u32(&mut self) -> Result<u32, S2JsonError> {
+        let bytes = self.take()?;
+        Ok(match self.order {
+            WkbByteOrder::BigEndian => u32::from_be_bytes(bytes),
+            WkbByteOrder::LittleEndian => u32::from_le_bytes(bytes),
+        })
+    }
+
+    fn f64(&mut self) -> Result<f64, S2JsonError> {
+        let bytes = self.take()?;
+        Ok(match self.order {
+            WkbByteOrder::BigEndian => f64::from_be_bytes(bytes),
+            WkbByteOrder::LittleEndian => f64::from_le_bytes(bytes),
+        })
+    }
+
+    fn header(&mut self) -> Result<Header, S2JsonError> {
+        let [order] = self.take()?;
+        self.order = match order {
+            0 => WkbByteOrder::BigEndian,
+            1 => WkbByteOrder::LittleEndian,
+            _ => {
+                return Err(S2JsonError::invalid_wkb(
+                    self.pos - 1,
+                    format!("unknown byte order {order}"),
+                ));
+            }
+        };
+        let start = self.pos;
+        let code = self.u32()?;
+        // ISO codes add 1000 for Z, 2000 for M and 3000 for ZM, EWKB sets flags in the high bits
+        let iso = code & 0x0FFF_FFFF;
+        let (iso_z, iso_m) = (matches!(iso / 1_000, 1 | 3), matches!(iso / 1_000, 2 | 3));
+        let kind = match (iso / 1_000, iso % 1_000) {
+            (0..=3, 1) => Some(VectorGeometryType::Point),
+            (0..=3, 2) => Some(VectorGeometryType::LineString),
+            (0..=3, 3) => Some(VectorGeometryType::Polygon),
+            (0..=3, 4) => Some(VectorGeometryType::MultiPoint),
+            (0..=3, 5) => Some(VectorGeometryType::MultiLineString),
+            (0..=3, 6) => Some(VectorGeometryType::MultiPolygon),
+            _ => None,
+        };
+        let Some(kind) = kind else {
+            return Err(S2JsonError::invalid_wkb(
+                start,
+                format!("unsupported geometry type code {code}"),
+            ));
+        };
+        let srid = if code & EWKB_SRID != 0 { Some(self.u32()?) } else { None };
+        Ok((kind, iso_z || code & EWKB_Z != 0, iso_m || code & EWKB_M != 0, srid))
+    }
+
+    fn position(&mut self, dims: usize) -> Result<Nested, S2JsonError> {
+        (0..dims).map(|_| self.f64()).collect::<Result<_, _>>().map(Nested::Position)
+    }
+
+    fn positions(&mut self, dims: usize) -> Result<Nested, S2JsonError> {
+        let len = self.u32()?;
+        (0..len).map(|_| self.position(dims)).collect::<Result<_, _>>().map(Nested::List)
+    }
+
+    /// Read a geometry, checking that a member of a multi geometry matches its parent
+    fn geometry(
+        &mut self,
+        parent: Option<(VectorGeometryType, bool, bool)>,
+    ) -> Result<(Header, Nested), S2JsonError> {
+        let start = self.pos;
+        let header @ (kind, has_z, has_m, _) = self.header()?;
+        if let Some(parent) = parent
+            && parent != (kind, has_z, has_m)
+        {
+            return Err(S2JsonError::invalid_wkb(
+                start,
+                format!("expected a {:?} member, found {kind:?}", parent.0),
+            ));
+        }
+
+        let dims = 2 + has_z as usize + has_m as usize;
+        let nested = match kind {
+            VectorGeometryType::Point => self.position(dims)?,
+            VectorGeometryType::LineString => self.positions(dims)?,
+            VectorGeometryType::Polygon => {
+                let len = self.u32()?;
+                Nested::List((0..len).map(|_| self.positions(dims)).collect::<Result<_, _>>()?)
+            }
+            _ => {
+                let member = (member_kind(kind).unwrap_or_default(), has_z, has_m);
+                let len = self.u32()?;
+                Nested::List(
+                    (0..len)
+                        .map(|_| self.geometry(Some(member)).map(|(_, nested)| nested))
+                        .collect::<Result<_, _>>()?,
+                )
+            }
+        };
+        Ok((header, nested))
+    }
+}
+
+fn read<D: MValueCompatible>(data: &[u8]) -> Result<(Option<u32>, Geometry<D>), S2JsonError> {
+    let mut reader = WkbReader { data, pos: 0, order: WkbByteOrder::default() };
+    let ((kind, has_z, has_m, srid), nested) = reader.geometry(None)?;
+    if reader.pos != data.len() {
+        return Err(S2JsonError::invalid_wkb(reader.pos, "unexpected trailing data"));
+    }
+    Ok((srid, build_geometry(GeometryType::from_vector_type(kind, has_z), has_m, nested)))
+}
+
+impl<D: MValueCompatible> Geometry<D> {
+    /// Decode well-known binary in either byte order, with ISO or EWKB type codes.
+    ///
+    /// Z selects the 3D geometry types, and M is stored in the m-values under [`WKT_M_KEY`].
+    /// Geometry collections are not supported, and an empty point has NaN coordinates
+    pub fn from_wkb(data: &[u8]) -> Result<Self, S2JsonError> {
+        read(data).map(|(_, geometry)| geometry)
+    }
+
+    /// Decode PostGIS extended well-known binary into its SRID and geometry
+    pub fn from_ewkb(data: &[u8]) -> Result<(Option<u32>, Self), S2JsonError> {
+        read(data)
+    }
+
+    /// Encode the geometry as well-known binary with ISO type codes
+    pub fn to_wkb(&self, order: WkbByteOrder) -> Vec<u8> {
+        write(self, order, false, None)
+    }
+
+    /// Encode the geometry as PostGIS extended well-known binary with an optional SRID
+    pub fn to_ewkb(&self, order: WkbByteOrder, srid: Option<u32>) -> Vec<u8> {
+        write(self, order, true, srid)
+    }
+}
+
+impl<M: MValueCompatible> VectorGeometry<M> {
+    /// Decode well-known binary. Z sets `is_3d` and M is stored in each [`VectorPoint::m`] under
+    /// [`WKT_M_KEY`]. See [`Geometry::from_wkb`]
+    pub fn from_wkb(data: &[u8]) -> Result<Self, S2JsonError> {
+        Geometry::<M>::from_wkb(data).map(Into::into)
+    }
+
+    /// Decode PostGIS extended well-known binary into its SRID and geometry
+    pub fn from_ewkb(data: &[u8]) -> Result<(Option<u32>, Self), S2JsonError> {
+        Geometry::<M>::from_ewkb(data).map(|(srid, geometry)| (srid, geometry.into()))
+    }
+
+    /// Encode the geometry as well-known binary with ISO type codes. M is written if any point
+    /// has an m-value
+    pub fn to_wkb(&self, order: WkbByteOrder) -> Vec<u8> {
+        Geometry::from(self).to_wkb(order)
+    }
+
+    /// Encode the geometry as PostGIS extended well-known binary with an optional SRID
+    pub fn to_ewkb(&self, order: WkbByteOrder, srid: Option<u32>) -> Vec<u8> {
+        Geometry::from(self).to_ewkb(order, srid)
+    }
+}
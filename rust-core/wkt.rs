@@ -0,0 +1,503 @@
+use crate::*;
+use alloc::{format, string::String, vec, vec::Vec};
+use core::fmt::Write;
+
+/// The m-value key that holds the M ordinate of a WKT or WKB position
+pub const WKT_M_KEY: &str = "m";
+
+/// Coordinates between their text or binary form and a geometry. A position holds x, y, then z
+/// and m when present. An empty point has NaN ordinates
+pub(crate) enum Nested {
+    Position(Vec<f64>),
+    List(Vec<Nested>),
+}
+
+/// The M ordinate as an m-value. NaN marks a missing measure
+fn m_value<D: MValueCompatible>(m: Option<f64>) -> D {
+    match m {
+        Some(m) if !m.is_nan() => D::from(MValue::from([(WKT_M_KEY.into(), m.into())])),
+        _ => D::default(),
+    }
+}
+
+/// The M ordinate of an m-value, or NaN if it has none
+fn m_ordinate<D: MValueCompatible>(m: Option<&D>) -> f64 {
+    m.and_then(|m| {
+        let m: MValue = m.clone().into();
+        m.get(WKT_M_KEY).and_then(|v| v.to_prim()).and_then(|v| v.to_f64())
+    })
+    .unwrap_or(f64::NAN)
+}
+
+/// Geometry coordinates that convert to and from [`Nested`] positions. The m-values of a geometry
+/// share the nesting of its coordinates
+trait NestedCoordinates: Sized {
+    /// The m-values that match these coordinates
+    type MValues<D>;
+    fn from_nested<D: MValueCompatible>(nested: Nested, has_z: bool) -> (Self, Self::MValues<D>);
+    fn to_nested<D: MValueCompatible>(
+        &self,
+        m_values: Option<&Self::MValues<D>>,
+        has_m: bool,
+    ) -> Nested;
+}
+
+fn position<D: MValueCompatible>(ordinates: &[f64], m: Option<&D>, has_m: bool) -> Nested {
+    let mut position = ordinates.to_vec();
+    if has_m {
+        position.push(m_ordinate(m));
+    }
+    Nested::Position(position)
+}
+
+impl NestedCoordinates for Point {
+    type MValues<D> = D;
+
+    fn from_nested<D: MValueCompatible>(nested: Nested, has_z: bool) -> (Self, D) {
+        let Nested::Position(p) = nested else { unreachable!() };
+        let ordinate = |i: usize| p.get(i).copied().unwrap_or(f64::NAN);
+        let m = p.get(if has_z { 3 } else { 2 }).copied();
+        (Point(ordinate(0), ordinate(1)), m_value(m))
+    }
+
+    fn to_nested<D: MValueCompatible>(&self, m: Option<&D>, has_m: bool) -> Nested {
+        position(&[self.0, self.1], m, has_m)
+    }
+}
+impl NestedCoordinates for Point3D {
+    type MValues<D> = D;
+
+    fn from_nested<D: MValueCompatible>(nested: Nested, has_z: bool) -> (Self, D) {
+        let Nested::Position(p) = nested else { unreachable!() };
+        let ordinate = |i: usize| p.get(i).copied().unwrap_or(f64::NAN);
+        let m = p.get(if has_z { 3 } else { 2 }).copied();
+        (Point3D(ordinate(0), ordinate(1), ordinate(2)), m_value(m))
+    }
+
+    fn to_nested<D: MValueCompatible>(&self, m: Option<&D>, has_m: bool) -> Nested {
+        position(&[self.0, self.1, self.2], m, has_m)
+    }
+}
+impl<T: NestedCoordinates> NestedCoordinates for Vec<T> {
+    type MValues<D> = Vec<T::MValues<D>>;
+
+    fn from_nested<D: MValueCompatible>(nested: Nested, has_z: bool) -> (Self, Self::MValues<D>) {
+        let Nested::List(list) = nested else { unreachable!() };
+        list.into_iter().map(|n| T::from_nested(n, has_z)).unzip()
+    }
+
+    fn to_nested<D: MValueCompatible>(
+        &self,
+        m_values: Option<&Self::MValues<D>>,
+        has_m: bool,
+    ) -> Nested {
+        Nested::List(
+            self.iter()
+                .enumerate()
+                .map(|(i, elem)| elem.to_nested(m_values.and_then(|m| m.get(i)), has_m))
+                .collect(),
+        )
+    }
+}
+
+fn build<G: NestedCoordinates, B, D: MValueCompatible>(
+    _type: GeometryType,
+    has_m: bool,
+    nested: Nested,
+) -> BaseGeometry<G::MValues<D>, G, B> {
+    let (coordinates, m_values) = G::from_nested::<D>(nested, _type.is_3d());
+    BaseGeometry { _type, coordinates, m_values: has_m.then_some(m_values), bbox: None }
+}
+
+/// Build a geometry of the given type from its positions. M is read if `has_m` is set
+pub(crate) fn build_geometry<D: MValueCompatible>(
+    _type: GeometryType,
+    has_m: bool,
+    nested: Nested,
+) -> Geometry<D> {
+    match _type {
+        GeometryType::Point => Geometry::Point(build::<Point, _, D>(_type, has_m, nested)),
+        GeometryType::MultiPoint => {
+            Geometry::MultiPoint(build::<MultiPoint, _, D>(_type, has_m, nested))
+        }
+        GeometryType::LineString => {
+            Geometry::LineString(build::<LineString, _, D>(_type, has_m, nested))
+        }
+        GeometryType::MultiLineString => {
+            Geometry::MultiLineString(build::<MultiLineString, _, D>(_type, has_m, nested))
+        }
+        GeometryType::Polygon => Geometry::Polygon(build::<Polygon, _, D>(_type, has_m, nested)),
+        GeometryType::MultiPolygon => {
+            Geometry::MultiPolygon(build::<MultiPolygon, _, D>(_type, has_m, nested))
+        }
+        GeometryType::Point3D => Geometry::Point3D(build::<Point3D, _, D>(_type, has_m, nested)),
+        GeometryType::MultiPoint3D => {
+            Geometry::MultiPoint3D(build::<MultiPoint3D, _, D>(_type, has_m, nested))
+        }
+        GeometryType::LineString3D => {
+            Geometry::LineString3D(build::<LineString3D, _, D>(_type, has_m, nested))
+        }
+        GeometryType::MultiLineString3D => {
+            Geometry::MultiLineString3D(build::<MultiLineString3D, _, D>(_type, has_m, nested))
+        }
+        GeometryType::Polygon3D => {
+            Geometry::Polygon3D(build::<Polygon3D, _, D>(_type, has_m, nested))
+        }
+        GeometryType::MultiPolygon3D => {
+            Geometry::MultiPolygon3D(build::<MultiPolygon3D, _, D>(_type, has_m, nested))
+        }
+    }
+}
+
+fn split<G: NestedCoordinates, B, D: MValueCompatible>(
+    geometry: &BaseGeometry<G::MValues<D>, G, B>,
+) -> (GeometryType, bool, Nested) {
+    let has_m = geometry.m_values.is_some();
+    (geometry._type, has_m, geometry.coordinates.to_nested(geometry.m_values.as_ref(), has_m))
+}
+
+/// Split a geometry into its type, whether it has M, and its positions
+pub(crate) fn split_geometry<D: MValueCompatible>(
+    geometry: &Geometry<D>,
+) -> (GeometryType, bool, Nested) {
+    match geometry {
+        Geometry::Point(g) => split::<Point, _, D>(g),
+        Geometry::MultiPoint(g) => split::<MultiPoint, _, D>(g),
+        Geometry::LineString(g) => split::<LineString, _, D>(g),
+        Geometry::MultiLineString(g) => split::<MultiLineString, _, D>(g),
+        Geometry::Polygon(g) => split::<Polygon, _, D>(g),
+        Geometry::MultiPolygon(g) => split::<MultiPolygon, _, D>(g),
+        Geometry::Point3D(g) => split::<Point3D, _, D>(g),
+        Geometry::MultiPoint3D(g) => split::<MultiPoint3D, _, D>(g),
+        Geometry::LineString3D(g) => split::<LineString3D, _, D>(g),
+        Geometry::MultiLineString3D(g) => split::<MultiLineString3D, _, D>(g),
+        Geometry::Polygon3D(g) => split::<Polygon3D, _, D>(g),
+        Geometry::MultiPolygon3D(g) => split::<MultiPolygon3D, _, D>(g),
+    }
+}
+
+// Parsing
+
+/// A cursor over WKT input that tracks the ordinate count of its positions
+struct WktParser<'a> {
+    input: &'a str,
+    pos: usize,
+    ordinates: Option<usize>,
+}
+impl<'a> WktParser<'a> {
+    fn error(&self, message: impl Into<String>) -> S2JsonError {
+        S2JsonError::invalid_wkt(self.pos, message)
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = &self.input[self.pos..];
+        self.pos += rest.len() - rest.trim_start().len();
+    }
+
+    fn peek(&mut self) -> Option<u8> {
+        self.skip_whitespace();
+        self.input.as_bytes().get(self.pos).copied()
+    }
+
+    fn eat(&mut self, c: u8) -> bool {
+        let found = self.peek() == Some(c);
+        if found {
+            self.pos += 1;
+        }
+        found
+    }
+
+    fn expect(&mut self, c: u8) -> Result<(), S2JsonError> {
+        if self.eat(c) { Ok(()) } else { Err(self.error(format!("expected '{}'", c as char))) }
+    }
+
+    /// The next run of letters, without consuming it
+    fn peek_word(&mut self) -> &'a str {
+        self.skip_whitespace();
+        let rest = &self.input[self.pos..];
+        let len = rest.find(|c: char| !c.is_ascii_alphabetic()).unwrap_or(rest.len());
+        &rest[..len]
+    }
+
+    /// Consume the next word if it matches the keyword, ignoring case
+    fn keyword(&mut self, keyword: &str) -> bool {
+        let found = self.peek_word().eq_ignore_ascii_case(keyword);
+        if found {
+            self.pos += keyword.len();
+        }
+        found
+    }
+
+    /// The next token up to a delimiter
+    fn token(&mut self) -> &'a str {
+        self.skip_whitespace();
+        let rest = &self.input[self.pos..];
+        let len = rest
+            .find(|c: char| c.is_whitespace() || matches!(c, '(' | ')' | ',' | ';'))
+            .unwrap_or(rest.len());
+        self.pos += len;
+        &rest[..len]
+    }
+
+    fn position(&mut self) -> Result<Nested, S2JsonError> {
+        let start = self.pos;
+        let mut ordinates = vec![];
+        while !matches!(self.peek(), None | Some(b',' | b')')) {
+            let token_start = self.pos;
+            let token = self.token();
+            let value = token.parse().map_err(|_| {
+                S2JsonError::invalid_wkt(
+                    token_start,
+                    format!("expected a number, found \"{token}\""),
+                )
+            })?;
+            ordinates.push(value);
+        }
+        match self.ordinates {
+            Some(expected) if expected != ordinates.len() => Err(S2JsonError::invalid_wkt(
+                start,
+                format!("expected {expected} ordinates, found {}", ordinates.len()),
+            )),
+            None if !(2..=4).contains(&ordinates.len()) => Err(S2JsonError::invalid_wkt(
+                start,
+                format!("expected 2 to 4 ordinates, found {}", ordinates.len()),
+            )),
+            _ => {
+                self.ordinates = Some(ordinates.len());
+                Ok(Nested::Position(ordinates))
+            }
+        }
+    }
+
+    /// Parse coordinates nested `depth` lists deep
+    fn nested(&mut self, depth: usize) -> Result<Nested, S2JsonError> {
+        if self.keyword("EMPTY") {
+            return Ok(if depth == 0 { Nested::Position(vec![]) } else { Nested::List(vec![]) });
+        }
+        if depth == 0 {
+            // MULTIPOINT members may be wrapped in parentheses
+            if self.eat(b'(') {
+                let position = self.position()?;
+                self.expect(b')')?;
+                return Ok(position);
+            }
+            return self.position();
+        }
+        self.expect(b'(')?;
+        let mut list = vec![];
+        loop {
+            list.push(self.nested(depth - 1)?);
+            if !self.eat(b',') {
+                break;
+            }
+        }
+        self.expect(b')')?;
+        Ok(Nested::List(list))
+    }
+
+    /// Parse an optional `SRID=<srid>;` prefix
+    fn srid(&mut self) -> Result<Option<u32>, S2JsonError> {
+        if !self.keyword("SRID") {
+            return Ok(None);
+        }
+        self.expect(b'=')?;
+        let start = self.pos;
+        let token = self.token();
+        let srid = token.parse().map_err(|_| {
+            S2JsonError::invalid_wkt(start, format!("expected an SRID, found \"{token}\""))
+        })?;
+        self.expect(b';')?;
+        Ok(Some(srid))
+    }
+
+    /// Parse a tagged geometry into its type, dimensions and coordinates
+    fn geometry(&mut self) -> Result<(VectorGeometryType, bool, bool, Nested), S2JsonError> {
+        const KINDS: [(&str, VectorGeometryType, usize); 6] = [
+            ("MULTILINESTRING", VectorGeometryType::MultiLineString, 2),
+            ("MULTIPOLYGON", VectorGeometryType::MultiPolygon, 3),
+            ("MULTIPOINT", VectorGeometryType::MultiPoint, 1),
+            ("LINESTRING", VectorGeometryType::LineString, 1),
+            ("POLYGON", VectorGeometryType::Polygon, 2),
+            ("POINT", VectorGeometryType::Point, 0),
+        ];
+        let word = self.peek_word().to_ascii_uppercase();
+        let Some((kind, depth, suffix)) = KINDS.iter().find_map(|(name, kind, depth)| {
+            word.strip_prefix(name).map(|suffix| (*kind, *depth, String::from(suffix)))
+        }) else {
+            return Err(self.error(format!("unsupported geometry \"{word}\"")));
+        };
+        self.pos += word.len();
+        // the dimension is either glued on EWKT style (POINTM) or a separate word (POINT ZM)
+        let tag = if !suffix.is_empty() {
+            suffix
+        } else if self.keyword("ZM") {
+            "ZM".into()
+        } else if self.keyword("Z") {
+            "Z".into()
+        } else if self.keyword("M") {
+            "M".into()
+        } else {
+            String::new()
+        };
+        let (mut has_z, mut has_m) = match tag.as_str() {
+            "" => (false, false),
+            "Z" => (true, false),
+            "M" => (false, true),
+            "ZM" => (true, true),
+            _ => return Err(self.error(format!("unsupported geometry \"{word}\""))),
+        };
+        if !tag.is_empty() {
+            self.ordinates = Some(2 + has_z as usize + has_m as usize);
+        }
+        let nested = self.nested(depth)?;
+        // untagged positions take their dimension from their ordinate count
+        if tag.is_empty() {
+            has_z = self.ordinates.unwrap_or(2) > 2;
+            has_m = self.ordinates == Some(4);
+        }
+        Ok((kind, has_z, has_m, nested))
+    }
+}
+
+/// Parse (E)WKT into its SRID, if any, and geometry
+fn parse<D: MValueCompatible>(
+    input: &str,
+    ewkt: bool,
+) -> Result<(Option<u32>, Geometry<D>), S2JsonError> {
+    let mut parser = WktParser { input, pos: 0, ordinates: None };
+    let srid = if ewkt { parser.srid()? } else { None };
+    let (kind, has_z, has_m, nested) = parser.geometry()?;
+    if parser.peek().is_some() {
+        return Err(parser.error("unexpected trailing input"));
+    }
+    Ok((srid, build_geometry(GeometryType::from_vector_type(kind, has_z), has_m, nested)))
+}
+
+// Writing
+
+/// True if the position is an empty point
+fn is_empty(position: &[f64]) -> bool {
+    position.iter().take(2).all(|o| o.is_nan())
+}
+
+fn write_nested(nested: &Nested, out: &mut String) {
+    match nested {
+        Nested::Position(p) if is_empty(p) => out.push_str("EMPTY"),
+        Nested::Position(p) => {
+            for (i, ordinate) in p.iter().enumerate() {
+                if i > 0 {
+                    out.push(' ');
+                }
+                let _ = write!(out, "{ordinate}");
+            }
+        }
+        Nested::List(list) if list.is_empty() => out.push_str("EMPTY"),
+        Nested::List(list) => {
+            out.push('(');
+            for (i, elem) in list.iter().enumerate() {
+                if i > 0 {
+                    out.push_str(", ");
+                }
+                write_nested(elem, out);
+            }
+            out.push(')');
+        }
+    }
+}
+
+fn write_geometry<D: MValueCompatible>(geometry: &Geometry<D>, ewkt: bool, out: &mut String) {
+    let (_type, has_m, nested) = split_geometry(geometry);
+    out.push_str(match VectorGeometryType::from(_type) {
+        VectorGeometryType::Point => "POINT",
+        VectorGeometryType::MultiPoint => "MULTIPOINT",
+        VectorGeometryType::LineString => "LINESTRING",
+        VectorGeometryType::MultiLineString => "MULTILINESTRING",
+        VectorGeometryType::Polygon => "POLYGON",
+        VectorGeometryType::MultiPolygon => "MULTIPOLYGON",
+    });
+    let has_z = _type.is_3d();
+    let mut body = String::new();
+    match &nested {
+        // a point is wrapped in parentheses of its own
+        Nested::Position(p) if !is_empty(p) => {
+            body.push('(');
+            write_nested(&nested, &mut body);
+            body.push(')');
+        }
+        _ => write_nested(&nested, &mut body),
+    }
+    if ewkt {
+        // EWKT only tags M, Z is implied by the ordinate count
+        if has_m && !has_z {
+            out.push('M');
+        }
+        if body == "EMPTY" {
+            out.push(' ');
+        }
+    } else {
+        out.push_str(match (has_z, has_m) {
+            (false, false) => " ",
+            (true, false) => " Z ",
+            (false, true) => " M ",
+            (true, true) => " ZM ",
+        });
+    }
+    out.push_str(&body);
+}
+
+impl<D: MValueCompatible> Geometry<D> {
+    /// Parse well-known text, e.g. `POINT ZM (1 2 3 4)`.
+    ///
+    /// Z selects the 3D geometry types, and M is stored in the m-values under [`WKT_M_KEY`].
+    /// Positions without a dimension tag take it from their ordinate count. `GEOMETRYCOLLECTION`
+    /// is not supported, and an empty point has NaN coordinates
+    pub fn from_wkt(wkt: &str) -> Result<Self, S2JsonError> {
+        parse(wkt, false).map(|(_, geometry)| geometry)
+    }
+
+    /// Parse PostGIS extended well-known text, e.g. `SRID=4326;POINTM(1 2 3)`, into its SRID and
+    /// geometry. Plain WKT is accepted too
+    pub fn from_ewkt(ewkt: &str) -> Result<(Option<u32>, Self), S2JsonError> {
+        parse(ewkt, true)
+    }
+
+    /// Write the geometry as ISO well-known text, e.g. `LINESTRING M (0 0 1, 1 1 2)`
+    pub fn to_wkt(&self) -> String {
+        let mut out = String::new();
+        write_geometry(self, false, &mut out);
+        out
+    }
+
+    /// Write the geometry as PostGIS extended well-known text with an optional SRID prefix
+    pub fn to_ewkt(&self, srid: Option<u32>) -> String {
+        let mut out = String::new();
+        if let Some(srid) = srid {
+            let _ = write!(out, "SRID={srid};");
+        }
+        write_geometry(self, true, &mut out);
+        out
+    }
+}
+
+impl<M: MValueCompatible> VectorGeometry<M> {
+    /// Parse well-known text. Z sets `is_3d` and M is stored in each [`VectorPoint::m`] under
+    /// [`WKT_M_KEY`]. See [`Geometry::from_wkt`]
+    pub fn from_wkt(wkt: &str) -> Result<Self, S2JsonError> {
+        Geometry::<M>::from_wkt(wkt).map(Into::into)
+    }
+
+    /// Parse PostGIS extended well-known text into its SRID and geometry
+    pub fn from_ewkt(ewkt: &str) -> Result<(Option<u32>, Self), S2JsonError> {
+        Geometry::<M>::from_ewkt(ewkt).map(|(srid, geometry)| (srid, geometry.into()))
+    }
+
+    /// Write the geometry as ISO well-known text. M is written if any point has an m-value
+    pub fn to_wkt(&self) -> String {
+        Geometry::from(self).to_wkt()
+    }
+
+    /// Write the geometry as PostGIS extended well-known text with an optional SRID prefix
+    pub fn to_ewkt(&self, srid: Option<u32>) -> String {
+        Geometry::from(self).to_ewkt(srid)
+    }
+}
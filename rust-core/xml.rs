@@ -0,0 +1,276 @@
+use crate::*;
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+/// An element of an XML document. Names drop their namespace prefix, so `gpxtpx:hr` is `hr`
+#[derive(Debug, Default, Clone, PartialEq)]
+pub(crate) struct XmlElement {
+    /// The local name of the element
+    pub name: String,
+    /// The byte offset of the element's start tag
+    pub offset: usize,
+    /// Attributes by local name, in document order
+    pub attributes: Vec<(String, String)>,
+    /// Child elements in document order
+    pub children: Vec<XmlElement>,
+    /// The element's own text and CDATA, unescaped and concatenated
+    pub text: String,
+}
+impl XmlElement {
+    /// Get an attribute by its local name
+    pub fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes.iter().find(|(key, _)| key == name).map(|(_, value)| value.as_str())
+    }
+
+    /// Get the first child with the given name
+    pub fn child(&self, name: &str) -> Option<&XmlElement> {
+        self.children.iter().find(|child| child.name == name)
+    }
+
+    /// Get every child with the given name
+    pub fn children_named<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a XmlElement> {
+        self.children.iter().filter(move |child| child.name == name)
+    }
+
+    /// The trimmed text of the first child with the given name
+    pub fn child_text(&self, name: &str) -> Option<&str> {
+        self.child(name).map(|child| child.text.trim())
+    }
+
+    /// Collect every descendant with the given name, not looking inside a match
+    pub fn descendants<'a>(&'a self, name: &str, found: &mut Vec<&'a XmlElement>) {
+        for child in &self.children {
+            if child.name == name {
+                found.push(child);
+            } else {
+                child.descendants(name, found);
+            }
+        }
+    }
+
+    /// True if the element has no child elements
+    pub fn is_leaf(&self) -> bool {
+        self.children.is_empty()
+    }
+}
+
+/// Strip a namespace prefix from a name
+fn local_name(name: &str) -> &str {
+    name.rsplit(':').next().unwrap_or(name)
+}
+
+/// Elements nested deeper than this are rejected instead of recursed into
+const MAX_DEPTH: usize = 128;
+
+struct XmlParser<'a> {
+    xml: &'a str,
+    pos: usize,
+    /// The number of elements open around the current position
+    depth: usize,
+}
+impl<'a> XmlParser<'a> {
+    fn rest(&self) -> &'a str {
+        &self.xml[self.pos..]
+    }
+
+    fn error(&self, message: impl Into<String>) -> S2JsonError {
+        S2JsonError::invalid_xml(self.pos, message)
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = self.rest();
+        self.pos += rest.len() - rest.trim_start().len();
+    }
+
+    fn eat(&mut self, token: &str) -> bool {
+        let found = self.rest().starts_with(token);
+        if found {
+            self.pos += token.len();
+        }
+        found
+    }
+
+    /// Move past the next `end`, returning what came before it
+    fn until(&mut self, end: &str) -> Result<&'a str, S2JsonError> {
+        let Some(len) = self.rest().find(end) else {
+            return Err(self.error(format!("expected \"{end}\"")));
+        };
+        let start = self.pos;
+        self.pos += len + end.len();
+        Ok(&self.xml[start..start + len])
+    }
+
+    /// Skip a comment, processing instruction or doctype if one is next
+    fn skip_markup(&mut self) -> Result<bool, S2JsonError> {
+        if self.eat("<!--") {
+            self.until("-->")?;
+        } else if self.eat("<?") {
+            self.until("?>")?;
+        } else if self.eat("<!DOCTYPE") {
+            let declaration = self.until(">")?;
+            // an internal subset may hold '>' of its own
+            if declaration.contains('[') && !declaration.contains(']') {
+                self.until("]")?;
+                self.until(">")?;
+            }
+        } else {
+            return Ok(false);
+        }
+        Ok(true)
+    }
+
+    fn skip_misc(&mut self) -> Result<(), S2JsonError> {
+        loop {
+            self.skip_whitespace();
+            if !self.skip_markup()? {
+                return Ok(());
+            }
+        }
+    }
+
+    fn name(&mut self) -> Result<&'a str, S2JsonError> {
+        let rest = self.rest();
+        let len = rest
+            .find(|c: char| c.is_whitespace() || matches!(c, '/' | '>' | '='))
+            .unwrap_or(rest.len());
+        if len == 0 {
+            return Err(self.error("expected a name"));
+        }
+        let start = self.pos;
+        self.pos += len;
+        Ok(&self.xml[start..self.pos])
+    }
+
+    fn unescape(&self, text: &str, offset: usize) -> Result<String, S2JsonError> {
+        let mut out = String::with_capacity(text.len());
+        let mut rest = text;
+        while let Some(amp) = rest.find('&') {
+            out.push_str(&rest[..amp]);
+            let Some(semi) = rest[amp..].find(';') else {
+                return Err(S2JsonError::invalid_xml(offset, "unterminated entity"));
+            };
+            let entity = &rest[amp + 1..amp + semi];
+            let c = match entity {
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "amp" => Some('&'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                _ => match entity.strip_prefix("#x").or_else(|| entity.strip_prefix("#X")) {
+                    Some(hex) => u32::from_str_radix(hex, 16).ok().and_then(char::from_u32),
+                    None => entity
+                        .strip_prefix('#')
+                        .and_then(|dec| dec.parse().ok())
+                        .and_then(char::from_u32),
+                },
+            };
+            let Some(c) = c else {
+                return Err(S2JsonError::invalid_xml(offset, format!("unknown entity &{entity};")));
+            };
+            out.push(c);
+            rest = &rest[amp + semi + 1..];
+        }
+        out.push_str(rest);
+        Ok(out)
+    }
+
+    fn element(&mut self) -> Result<XmlElement, S2JsonError> {
+        let offset = self.pos;
+        if !self.eat("<") {
+            return Err(self.error("expected an element"));
+        }
+        if self.depth == MAX_DEPTH {
+            return Err(S2JsonError::invalid_xml(offset, "elements are nested too deeply"));
+        }
+        let qualified = self.name()?.to_string();
+        let mut element =
+            XmlElement { name: local_name(&qualified).into(), offset, ..Default::default() };
+
+        // attributes
+        loop {
+            self.skip_whitespace();
+            if self.eat("/>") {
+                return Ok(element);
+            }
+            if self.eat(">") {
+                break;
+            }
+            let key = local_name(self.name()?).to_string();
+            self.skip_whitespace();
+            if !self.eat("=") {
+                return Err(self.error("expected '='"));
+            }
+            self.skip_whitespace();
+            let quote = match self.rest().chars().next() {
+                Some(quote @ ('"' | '\'')) => quote,
+                _ => return Err(self.error("expected a quoted attribute value")),
+            };
+            self.pos += 1;
+            let start = self.pos;
+            let value = self.until(if quote == '"' { "\"" } else { "'" })?;
+            let value = self.unescape(value, start)?;
+            element.attributes.push((key, value));
+        }
+
+        // content
+        loop {
+            let start = self.pos;
+            let text = self.until("<").map_err(|_| self.error("unexpected end of input"))?;
+            element.text.push_str(&self.unescape(text, start)?);
+            self.pos -= 1;
+            if self.eat("</") {
+                let closing = self.name()?;
+                if closing != qualified {
+                    return Err(S2JsonError::invalid_xml(
+                        self.pos - closing.len(),
+                        format!("expected </{qualified}>, found </{closing}>"),
+                    ));
+                }
+                self.skip_whitespace();
+                if !self.eat(">") {
+                    return Err(self.error("expected '>'"));
+                }
+                return Ok(element);
+            } else if self.eat("<![CDATA[") {
+                let cdata = self.until("]]>")?;
+                element.text.push_str(cdata);
+            } else if !self.skip_markup()? {
+                self.depth += 1;
+                let child = self.element();
+                self.depth -= 1;
+                element.children.push(child?);
+            }
+        }
+    }
+}
+
+/// Parse an XML document into its root element
+pub(crate) fn parse_xml(xml: &str) -> Result<XmlElement, S2JsonError> {
+    let mut parser = XmlParser { xml, pos: 0, depth: 0 };
+    // a byte order mark may lead the document
+    parser.eat("\u{feff}");
+    parser.skip_misc()?;
+    let root = parser.element()?;
+    parser.skip_misc()?;
+    if parser.pos != xml.len() {
+        return Err(parser.error("unexpected content after the root element"));
+    }
+    Ok(root)
+}
+
+/// Read a text value as the narrowest number it holds, falling back to a string
+pub(crate) fn xml_value(text: &str) -> ValueType {
+    let text = text.trim();
+    if let Ok(value) = text.parse::<u64>() {
+        value.into()
+    } else if let Ok(value) = text.parse::<i64>() {
+        value.into()
+    } else if let Some(value) = text.parse::<f64>().ok().filter(|value| value.is_finite()) {
+        value.into()
+    } else {
+        text.into()
+    }
+}
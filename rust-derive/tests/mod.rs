@@ -33,6 +33,50 @@ mod tests {
         assert_eq!(back_to_struct, test_struct);
     }
 
+    #[test]
+    fn json_schema_test() {
+        #[derive(Properties, Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+        pub struct TestStruct {
+            pub name: String,
+            pub value: u32,
+            pub ratio: f64,
+        }
+
+        let shape = Shape::from(&Properties::from(TestStruct::default()));
+        let schema = shape.to_json_schema();
+        assert_eq!(schema["required"], serde_json::json!(["name", "ratio", "value"]));
+        assert_eq!(schema["properties"]["name"], serde_json::json!({ "type": "string" }));
+        assert_eq!(schema["properties"]["value"]["format"], "uint64");
+        assert_eq!(schema["properties"]["ratio"]["format"], "double");
+        assert_eq!(Shape::try_from_json_schema(&schema), Ok(shape));
+
+        #[derive(Properties, Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+        pub struct ListStruct {
+            pub label: Option<String>,
+            pub values: Vec<u32>,
+        }
+
+        // the shape of a default value can't see the types of a `None` or an empty `Vec`
+        let shape = Shape::from(&Properties::from(ListStruct::default()));
+        let schema = shape.to_json_schema();
+        assert_eq!(schema["properties"]["label"], serde_json::json!({ "type": "null" }));
+        assert_eq!(schema["properties"]["values"]["type"], "array");
+        assert_eq!(schema["properties"]["values"]["items"], serde_json::json!({ "type": "null" }));
+
+        // filled in, with the option marked nullable, both are described fully
+        let filled = ListStruct { label: Some("a".into()), values: vec![1] };
+        let mut shape = Shape::from(&Properties::from(filled));
+        let label = shape.get_mut("label").unwrap();
+        *label = label.clone().nullable();
+        let schema = shape.to_json_schema();
+        assert_eq!(
+            schema["properties"]["label"],
+            serde_json::json!({ "type": ["string", "null"] })
+        );
+        assert_eq!(schema["properties"]["values"]["items"]["format"], "uint64");
+        assert_eq!(Shape::try_from_json_schema(&schema), Ok(shape));
+    }
+
     #[test]
     fn unsigned_test() {
         #[derive(MValueDerive, Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
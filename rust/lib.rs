@@ -110,6 +110,121 @@
 //! - [`crate::VectorGeometry`]: The vector geometry. An enum of all vector geometry types
 //! - [`crate::VectorOffsets`]: The offsets for a vector geometry (rarely used)
 //!
+//! **Conversions**
+//!
+//! [`crate::Geometry`] and [`crate::VectorGeometry`] convert losslessly with `From`. Each primitive
+//! geometry converts into its vector counterpart with `From`, and back with `TryFrom`, which fails
+//! with an [`crate::S2JsonError`] if the dimensions don't match.
+//!
+//! **Errors**
+//!
+//! - [`crate::S2JsonError`]: The crate wide error type. Every type enum (e.g. [`crate::GeometryType`])
+//!   parses from a string with `TryFrom<&str>` or `FromStr`, returning this error on an unknown tag
+//!
+//! ### Line Delimited
+//!
+//! - [`crate::LineDelimitedReader`]: Stream [`crate::Features`] one line at a time from any byte source
+//! - [`crate::LineDelimitedWriter`]: Write features one per line to any [`core::fmt::Write`] sink
+//! - [`crate::LineError`]: A per-line read or write error that includes the line number
+//!
+//! ### Protobuf
+//!
+//! [`crate::VectorFeature`], [`crate::VectorGeometry`], [`crate::VectorPoint`], [`crate::BBox3D`],
+//! [`crate::VectorOffsets`] and [`crate::Value`] implement `ProtoRead` and `ProtoWrite` from `pbf`.
+//! Geometry coordinates are zig-zag delta encoded at [`crate::impls::proto::DEFAULT_PRECISION`]
+//! decimal places, or any other precision with [`crate::VectorFeature::write_with_precision`].
+//!
+//! ### Mapbox Vector Tiles
+//!
+//! - [`crate::MapboxVectorTile`]: An MVT v2 tile of named layers, encoded and decoded with `pbf`
+//! - [`crate::MapboxVectorLayer`]: A layer of [`crate::VectorFeature`]s in 0->1 tile space with a configurable extent
+//!
+//! ### Geobuf
+//!
+//! [`crate::FeatureCollection::to_geobuf`] and [`crate::FeatureCollection::from_geobuf`] losslessly
+//! encode a collection into a compact protobuf with a shared key table and delta encoded coordinates.
+//!
+//! ### Well-Known Text
+//!
+//! [`crate::Geometry::from_wkt`] and [`crate::Geometry::to_wkt`] read and write WKT in 2D, Z, M
+//! and ZM, with [`crate::Geometry::from_ewkt`] and [`crate::Geometry::to_ewkt`] for PostGIS EWKT
+//! and its SRID. [`crate::VectorGeometry`] has the same methods.
+//!
+//! ### Well-Known Binary
+//!
+//! [`crate::Geometry::from_wkb`] decodes WKB in either byte order with ISO or EWKB type codes,
+//! while [`crate::Geometry::to_wkb`] and [`crate::Geometry::to_ewkb`] encode it with a chosen
+//! [`crate::WkbByteOrder`]. Malformed input returns an error rather than panicking.
+//!
+//! ### Encoded Polylines
+//!
+//! - [`crate::encode_polyline`] and [`crate::decode_polyline`]: The Google polyline algorithm at any precision
+//! - [`crate::encode_flexible_polyline`] and [`crate::decode_flexible_polyline`]: Flexible polylines with an optional third dimension
+//!
+//! ### TopoJSON
+//!
+//! - [`crate::read_topojson`]: Read each named object of a topology into a [`crate::FeatureCollection`]
+//!
+//! ### GPX and KML
+//!
+//! - [`crate::read_gpx`]: Read GPX waypoints, routes and tracks, with per-point time and extension values as m-values
+//! - [`crate::read_kml`]: Read KML placemarks, including `gx:Track` timestamps and arrays as m-values
+//!
+//! ### CSV and TSV
+//!
+//! - [`crate::read_csv`] and [`crate::read_csv_vector`]: Read rows into point features and infer the [`crate::Shape`] of their columns
+//! - [`crate::write_csv`]: Write point features as rows
+//!
+//! ### JSON Schema
+//!
+//! - [`crate::Shape::to_json_schema`]: Describe the properties a shape accepts as a JSON Schema document
+//! - [`crate::Shape::try_from_json_schema`]: Read a shape from a JSON Schema
+//!
+//! ### Clipping
+//!
+//! - [`crate::VectorGeometry::clip_axis`]: Clip a geometry to a band along an [`crate::Axis`], tracking line offsets and interpolating m-values
+//! - [`crate::VectorGeometry::clip_bbox`]: Clip a geometry to a bounding box with an optional buffer
+//! - [`crate::clip_line`]: Clip a single line or ring to a band, returning each [`crate::ClippedLine`] piece with its offset
+//!
+//! ### Simplification
+//!
+//! - [`crate::VectorGeometry::build_simplification`]: Store the importance of every point in its `t` using a [`crate::SimplifyMethod`]
+//! - [`crate::VectorGeometry::simplify`]: Drop the points less important than a tolerance, keeping polygon rings valid
+//!
+//! ### Tessellation
+//!
+//! - [`crate::VectorGeometry::tessellate`]: Triangulate a polygon or multi polygon into its indices and tessellation
+//! - [`crate::earcut`]: Triangulate flat polygon coordinates with holes
+//! - [`crate::earcut_3d`]: Triangulate a planar polygon in 3D
+//!
+//! ### Tiling
+//!
+//! - [`crate::Tiler`]: Lazily split projected WM or S2 vector features into quad-tree [`crate::Tile`]s with a buffer, per-zoom simplification and a maximum zoom
+//!
+//! ### Measurement
+//!
+//! - [`crate::haversine_distance`], [`crate::vincenty_distance`] and [`crate::distance`]: Distances in meters between lon-lat points of any [`crate::GetXY`] type
+//! - [`crate::line_length`], [`crate::ring_area`] and [`crate::polygon_area`]: Lengths and areas of lon-lat lines and polygons, with holes subtracted
+//! - [`crate::Geometry::length`], [`crate::Geometry::area`], [`crate::VectorGeometry::length`] and [`crate::VectorGeometry::area`]: Measure whole geometries on the sphere or the WGS84 ellipsoid ([`crate::EarthModel`])
+//!
+//! ### S2 Conversion
+//!
+//! - [`crate::Feature::to_s2_features`]: Project a WG Feature onto the S2 sphere, split by face
+//! - [`crate::VectorFeature::to_feature`]: Unproject an S2Feature back into a WG Feature
+//! - [`crate::FeatureCollection::to_s2_collection`]: Convert a WG collection into an S2 collection
+//! - [`crate::S2FeatureCollection::to_feature_collection`]: Convert an S2 collection back into WG
+//!
+//! ### S2 Projection
+//!
+//! - [`crate::lon_lat_to_xyz`] / [`crate::xyz_to_lon_lat`]: lon-lat to and from the unit sphere
+//! - [`crate::face_uv_to_xyz`] / [`crate::xyz_to_face_uv`]: face-uv to and from the unit sphere
+//! - [`crate::face_st_to_xyz`] / [`crate::xyz_to_face_st`]: face-st to and from the unit sphere
+//! - [`crate::lon_lat_to_face_st`] / [`crate::face_st_to_lon_lat`]: lon-lat to and from face-st
+//! - [`crate::quadratic_st_to_uv`] / [`crate::tan_st_to_uv`]: The quadratic and tangent st-uv transforms
+//! - [`crate::point_to_st`] / [`crate::point_from_st`]: Project any [`crate::SetXY`] point in place
+//! - [`crate::STPoint::from_lon_lat`] / [`crate::STPoint::to_lon_lat`]: STPoint to and from lon-lat
+//! - [`crate::S2CellId`]: A cell in the S2 quad-tree with hierarchy and neighbor navigation
+//!
 //! ### Traits
 //!
 //! These traits are the fundamental building box for all geometry tooling. If you need your own